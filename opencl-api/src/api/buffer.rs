@@ -19,15 +19,18 @@
 //! A buffer object stores a one-dimensional collection of elements.
 //! Elements of a buffer object can be a scalar data type (such as an int, float), vector data type, or a user-defined structure.
 //!
+use crate::errors::{ToLibraryError, ValidationError};
+use crate::ffi;
 use crate::objects::bitfields::{MapFlags, MemFlags};
 use crate::objects::enums::ParamValue;
 use crate::objects::functions::status_update;
 use crate::objects::structs::{BufferCreateType, StatusCode};
 use crate::objects::traits::GetSetGo;
-use crate::objects::types::{APIResult, ContextPtr, EventPtr, LongProperties, MemPtr, QueuePtr};
+use crate::objects::types::{
+    APIResult, ContextPtr, EventPtr, LongProperties, MemPtr, NullMutPtr, QueuePtr,
+};
 use crate::objects::wrappers::*;
 use libc::c_void;
-use opencl_heads::ffi;
 use opencl_heads::types::*;
 use std::ptr;
 
@@ -48,8 +51,7 @@ pub fn create_buffer(
             &mut status_code,
         )
     };
-    eprintln!("POINTER: {:?}", mem);
-    status_update(status_code, fn_name, MemPtr::from_ptr(mem, fn_name)?)
+    status_update(status_code, fn_name, mem).and_then(|x| MemPtr::from_ptr(x, fn_name))
 }
 // #[cfg(feature = "cl_3_0")]
 pub fn create_buffer_with_properties(
@@ -75,7 +77,7 @@ pub fn create_buffer_with_properties(
             &mut status_code,
         )
     };
-    status_update(status_code, fn_name, MemPtr::from_ptr(mem, fn_name)?)
+    status_update(status_code, fn_name, mem).and_then(|x| MemPtr::from_ptr(x, fn_name))
 }
 
 // TODO: buffer_create_info takes cl_buffer_region as input
@@ -107,7 +109,7 @@ pub fn create_sub_buffer(
             let value = status_update(status_code, fn_name, mem)?;
             Ok(ParamValue::CPtr(value as isize))
         }
-        _ => status_update(40404, fn_name, ParamValue::default()),
+        _ => Err(ValidationError::UnsupportedParam(fn_name, buffer_create_type).to_error()),
     }
 }
 
@@ -140,7 +142,7 @@ pub fn enqueue_read_buffer(
             &mut event,
         )
     };
-    status_update(status_code, fn_name, EventPtr::from_ptr(event, fn_name)?)
+    status_update(status_code, fn_name, event).and_then(|x| EventPtr::from_ptr(x, fn_name))
 }
 pub fn enqueue_write_buffer(
     command_queue: &QueuePtr,
@@ -167,7 +169,7 @@ pub fn enqueue_write_buffer(
             &mut event,
         )
     };
-    status_update(status_code, fn_name, EventPtr::from_ptr(event, fn_name)?)
+    status_update(status_code, fn_name, event).and_then(|x| EventPtr::from_ptr(x, fn_name))
 }
 
 pub fn enqueue_read_buffer_rect(
@@ -205,7 +207,7 @@ pub fn enqueue_read_buffer_rect(
             &mut event,
         )
     };
-    status_update(status_code, fn_name, EventPtr::from_ptr(event, fn_name)?)
+    status_update(status_code, fn_name, event).and_then(|x| EventPtr::from_ptr(x, fn_name))
 }
 
 pub fn enqueue_write_buffer_rect(
@@ -243,7 +245,7 @@ pub fn enqueue_write_buffer_rect(
             &mut event,
         )
     };
-    status_update(status_code, fn_name, EventPtr::from_ptr(event, fn_name)?)
+    status_update(status_code, fn_name, event).and_then(|x| EventPtr::from_ptr(x, fn_name))
 }
 
 pub fn enqueue_copy_buffer(
@@ -271,7 +273,7 @@ pub fn enqueue_copy_buffer(
             &mut event,
         )
     };
-    status_update(status_code, fn_name, EventPtr::from_ptr(event, fn_name)?)
+    status_update(status_code, fn_name, event).and_then(|x| EventPtr::from_ptr(x, fn_name))
 }
 
 pub fn enqueue_copy_buffer_rect(
@@ -307,7 +309,7 @@ pub fn enqueue_copy_buffer_rect(
             &mut event,
         )
     };
-    status_update(status_code, fn_name, EventPtr::from_ptr(event, fn_name)?)
+    status_update(status_code, fn_name, event).and_then(|x| EventPtr::from_ptr(x, fn_name))
 }
 
 pub fn enqueue_fill_buffer(
@@ -335,9 +337,11 @@ pub fn enqueue_fill_buffer(
             &mut event,
         )
     };
-    status_update(status_code, fn_name, EventPtr::from_ptr(event, fn_name)?)
+    status_update(status_code, fn_name, event).and_then(|x| EventPtr::from_ptr(x, fn_name))
 }
 
+/// Returns the mapped region of buffer, a pointer into host memory, and the
+/// event of the map command.
 pub fn enqueue_map_buffer(
     command_queue: &QueuePtr,
     buffer: &MemPtr,
@@ -347,12 +351,11 @@ pub fn enqueue_map_buffer(
     size: size_t,
     num_events_in_wait_list: cl_uint,
     event_wait_list: WrappedPointer<cl_event>,
-    region_ptr: &mut cl_mem,
-) -> APIResult<EventPtr> {
+) -> APIResult<(NullMutPtr, EventPtr)> {
     let mut event = ptr::null_mut();
     let fn_name = "clEnqueueMapBuffer";
     let mut status_code = StatusCode::INVALID_VALUE;
-    *region_ptr = unsafe {
+    let mapped = unsafe {
         ffi::clEnqueueMapBuffer(
             command_queue.unwrap(),
             buffer.unwrap(),
@@ -366,7 +369,12 @@ pub fn enqueue_map_buffer(
             &mut status_code,
         )
     };
-    status_update(status_code, fn_name, EventPtr::from_ptr(event, fn_name)?)
+    status_update(status_code, fn_name, (mapped, event)).and_then(|(mapped, event)| {
+        Ok((
+            NullMutPtr::from_ptr(mapped, fn_name)?,
+            EventPtr::from_ptr(event, fn_name)?,
+        ))
+    })
 }
 
 /************************/
@@ -427,8 +435,9 @@ mod tests {
         assert_ne!(buffer_mem.unwrap(), ptr::null_mut());
         // Start buffer test 2
         //NOTE: This OpenCL API flag is not stable as there is 21.3% chance that this will return a null pointer;
-        let flags = MemFlags::new(MemFlags::READ_WRITE).unwrap()
-            + MemFlags::new(MemFlags::USE_HOST_PTR).unwrap();
+        let flags = (MemFlags::new(MemFlags::READ_WRITE).unwrap()
+            + MemFlags::new(MemFlags::USE_HOST_PTR).unwrap())
+        .unwrap();
         let size = 1048576; // 2MB memory
         let buffer_mem = create_buffer(&context, flags, size, buffer_mem).unwrap();
         // eprintln!("{:?}", buffer_mem);
@@ -520,4 +529,126 @@ mod tests {
         release_command_queue(queue).unwrap();
         release_context(context).unwrap();
     }
+
+    #[cfg(feature = "fault")]
+    #[test]
+    fn test_injected_faults() {
+        use crate::errors::OpenCLAPIError;
+        use crate::fault;
+
+        let platform_ids = get_platform_ids().unwrap();
+        let platform_id = PlatformPtr::from_ptr(platform_ids[0], "test_fn").unwrap();
+        let device_ids =
+            get_device_ids(&platform_id, DeviceType::new(DeviceType::DEFAULT).unwrap()).unwrap();
+        let device_id = DevicePtr::from_ptr(device_ids[0], "test_fn").unwrap();
+        let properties = ContextProperties.gen(Some(&platform_id), None);
+        let context = create_context(&properties, device_ids, None, WrapMutPtr::null()).unwrap();
+        let queue = create_command_queue_with_properties(&context, &device_id, &None).unwrap();
+        let flags = MemFlags::new(MemFlags::READ_WRITE).unwrap();
+        let ones: [cl_float; 16] = [1.0; 16];
+        let size = ones.len() * std::mem::size_of::<cl_float>();
+
+        fault::fail_nth(
+            "clCreateBuffer",
+            1,
+            StatusCode::MEM_OBJECT_ALLOCATION_FAILURE,
+        );
+        match create_buffer(&context, flags.clone(), size, WrapMutPtr::null()) {
+            Err(OpenCLAPIError::StatusCodeError { int_code, func, .. }) => {
+                assert_eq!(int_code, StatusCode::MEM_OBJECT_ALLOCATION_FAILURE);
+                assert_eq!(func, "clCreateBuffer");
+            }
+            x => panic!("expected a status error, got {:?}", x),
+        }
+        let buffer_mem = create_buffer(&context, flags, size, WrapMutPtr::null()).unwrap();
+
+        fault::fail_nth("clEnqueueWriteBuffer", 2, StatusCode::OUT_OF_RESOURCES);
+        let write = || {
+            enqueue_write_buffer(
+                &queue,
+                &buffer_mem,
+                1,
+                0,
+                size,
+                WrappedPointer::from_owned(ones.as_ptr() as cl_mem),
+                0,
+                WrappedPointer::null(),
+            )
+        };
+        assert!(write().is_ok());
+        match write() {
+            Err(OpenCLAPIError::StatusCodeError { int_code, func, .. }) => {
+                assert_eq!(int_code, StatusCode::OUT_OF_RESOURCES);
+                assert_eq!(func, "clEnqueueWriteBuffer");
+            }
+            x => panic!("expected a status error, got {:?}", x),
+        }
+        assert!(write().is_ok());
+        assert_eq!(fault::injected(), 2);
+        fault::clear();
+
+        release_command_queue(queue).unwrap();
+        release_context(context).unwrap();
+    }
+
+    #[cfg(feature = "trace")]
+    #[test]
+    fn test_traced_calls() {
+        use crate::trace::{self, RingBuffer};
+
+        let platform_ids = get_platform_ids().unwrap();
+        let platform_id = PlatformPtr::from_ptr(platform_ids[0], "test_fn").unwrap();
+        let device_ids =
+            get_device_ids(&platform_id, DeviceType::new(DeviceType::DEFAULT).unwrap()).unwrap();
+        let device_id = DevicePtr::from_ptr(device_ids[0], "test_fn").unwrap();
+        let properties = ContextProperties.gen(Some(&platform_id), None);
+        let context = create_context(&properties, device_ids, None, WrapMutPtr::null()).unwrap();
+        let queue = create_command_queue_with_properties(&context, &device_id, &None).unwrap();
+        let ones: [cl_float; 16] = [1.0; 16];
+        let size = ones.len() * std::mem::size_of::<cl_float>();
+
+        let ring = RingBuffer::new(1024);
+        trace::set_sink(ring.clone());
+        let flags = MemFlags::new(MemFlags::READ_WRITE | MemFlags::ALLOC_HOST_PTR).unwrap();
+        let buffer_mem = create_buffer(&context, flags, size, WrapMutPtr::null()).unwrap();
+        enqueue_write_buffer(
+            &queue,
+            &buffer_mem,
+            1,
+            0,
+            size,
+            WrappedPointer::from_owned(ones.as_ptr() as cl_mem),
+            0,
+            WrappedPointer::null(),
+        )
+        .unwrap();
+        trace::remove_sink();
+
+        // Other tests may be calling the api meanwhile.
+        let thread = std::thread::current().id();
+        let records: Vec<_> = ring
+            .records()
+            .into_iter()
+            .filter(|x| x.thread == thread)
+            .collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].function, "clCreateBuffer");
+        assert_eq!(
+            records[0].args[1],
+            (
+                "flags",
+                "CL_MEM_READ_WRITE|CL_MEM_ALLOC_HOST_PTR".to_owned()
+            )
+        );
+        assert_eq!(records[0].args[2], ("size", size.to_string()));
+        assert_eq!(records[0].args[3], ("host_ptr", "NULL".to_owned()));
+        assert_eq!(records[0].status_name().unwrap(), "CL_SUCCESS");
+        assert_eq!(records[0].handles.len(), 1);
+        assert_ne!(records[0].handles[0], ("return", 0));
+        assert_eq!(records[1].function, "clEnqueueWriteBuffer");
+        assert_eq!(records[1].args[2], ("blocking_write", "CL_TRUE".to_owned()));
+
+        release_command_queue(queue).unwrap();
+        release_context(context).unwrap();
+    }
 }
@@ -15,6 +15,9 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use crate::errors::{ToLibraryError, ValidationError};
+use crate::ffi;
+use crate::ffi::clGetContextInfo;
 use crate::objects::bitfields::DeviceType;
 use crate::objects::enums::{ParamValue, Size};
 use crate::objects::functions::status_update;
@@ -24,8 +27,6 @@ use crate::objects::types::{APIResult, ContextPtr, DeviceList, Properties};
 use crate::objects::wrappers::WrappedMutablePointer;
 use crate::{gen_param_value, size_getter};
 use libc::c_void;
-use opencl_heads::ffi;
-use opencl_heads::ffi::clGetContextInfo;
 use opencl_heads::types::*;
 use std::ptr;
 
@@ -51,11 +52,7 @@ pub fn create_context(
             &mut status_code,
         )
     };
-    status_update(
-        status_code,
-        fn_name,
-        ContextPtr::from_ptr(context, fn_name)?,
-    )
+    status_update(status_code, fn_name, context).and_then(|x| ContextPtr::from_ptr(x, fn_name))
 }
 
 pub fn create_context_from_type(
@@ -79,11 +76,7 @@ pub fn create_context_from_type(
             &mut status_code,
         )
     };
-    status_update(
-        status_code,
-        fn_name,
-        ContextPtr::from_ptr(context, fn_name)?,
-    )
+    status_update(status_code, fn_name, context).and_then(|x| ContextPtr::from_ptr(x, fn_name))
 }
 
 pub fn retain_context(context: &ContextPtr) -> APIResult<()> {
@@ -113,7 +106,7 @@ pub fn get_context_info(
             let param_value = gen_param_value!(clGetContextInfo, isize, context, param_name, size);
             Ok(ParamValue::ArrCPtr(param_value))
         }
-        _ => status_update(40404, "clGetContextInfo", ParamValue::default()),
+        _ => Err(ValidationError::UnsupportedParam("clGetContextInfo", param_name).to_error()),
     }
 }
 
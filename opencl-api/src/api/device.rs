@@ -15,16 +15,17 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use crate::errors::{ToLibraryError, ValidationError};
+use crate::ffi;
+use crate::ffi::{clCreateSubDevices, clGetDeviceIDs, clGetDeviceInfo};
 use crate::objects::bitfields::DeviceType;
 use crate::objects::enums::{ParamValue, Size};
 use crate::objects::functions::{bytes_into_string, status_update};
 use crate::objects::structs::DeviceInfo;
 use crate::objects::traits::GetSetGo;
 use crate::objects::types::{APIResult, DeviceList, DevicePtr, PlatformPtr, Properties};
-use crate::{gen_object_list, gen_param_value, get_count, size_getter};
+use crate::{gen_details, gen_object_list, gen_param_value, get_count, size_getter};
 use libc::c_void;
-use opencl_heads::ffi;
-use opencl_heads::ffi::{clCreateSubDevices, clGetDeviceIDs, clGetDeviceInfo};
 use opencl_heads::types::*;
 use std::ptr;
 
@@ -187,7 +188,125 @@ pub fn get_device_info(device: &DevicePtr, param_name: cl_device_info) -> APIRes
             let param_value = gen_param_value!(clGetDeviceInfo, usize, device, param_name, size);
             Ok(ParamValue::ArrCSize(param_value))
         }
-        _ => status_update(40404, fn_name, ParamValue::default()),
+        _ => Err(ValidationError::UnsupportedParam(fn_name, param_name).to_error()),
+    }
+}
+
+gen_details! {
+    /// Every device info of a device, decoded to its Rust type.
+    ///
+    /// Each field holds the result of its own query: the queries a device
+    /// fails, such as those of OpenCL versions newer than the device,
+    /// leave the other fields intact.
+    DeviceDetails: Device, device {
+        device_type: Type,
+        vendor_id: VendorId,
+        max_compute_units: MaxComputeUnits,
+        max_work_item_dimensions: MaxWorkItemDimensions,
+        max_work_group_size: MaxWorkGroupSize,
+        max_work_item_sizes: MaxWorkItemSizes,
+        preferred_vector_width_char: PreferredVectorWidthChar,
+        preferred_vector_width_short: PreferredVectorWidthShort,
+        preferred_vector_width_int: PreferredVectorWidthInt,
+        preferred_vector_width_long: PreferredVectorWidthLong,
+        preferred_vector_width_float: PreferredVectorWidthFloat,
+        preferred_vector_width_double: PreferredVectorWidthDouble,
+        preferred_vector_width_half: PreferredVectorWidthHalf,
+        native_vector_width_char: NativeVectorWidthChar,
+        native_vector_width_short: NativeVectorWidthShort,
+        native_vector_width_int: NativeVectorWidthInt,
+        native_vector_width_long: NativeVectorWidthLong,
+        native_vector_width_float: NativeVectorWidthFloat,
+        native_vector_width_double: NativeVectorWidthDouble,
+        native_vector_width_half: NativeVectorWidthHalf,
+        max_clock_frequency: MaxClockFrequency,
+        address_bits: AddressBits,
+        max_mem_alloc_size: MaxMemAllocSize,
+        image_support: ImageSupport,
+        max_read_image_args: MaxReadImageArgs,
+        max_write_image_args: MaxWriteImageArgs,
+        max_read_write_image_args: MaxReadWriteImageArgs,
+        image2d_max_width: Image2DMaxWidth,
+        image2d_max_height: Image2DMaxHeight,
+        image3d_max_width: Image3DMaxWidth,
+        image3d_max_height: Image3DMaxHeight,
+        image3d_max_depth: Image3DMaxDepth,
+        image_max_buffer_size: ImageMaxBufferSize,
+        image_max_array_size: ImageMaxArraySize,
+        max_samplers: MaxSamplers,
+        image_pitch_alignment: ImagePitchAlignment,
+        image_base_address_alignment: ImageBaseAddressAlignment,
+        max_pipe_args: MaxPipeArgs,
+        pipe_max_active_reservations: PipeMaxActiveReservations,
+        pipe_max_packet_size: PipeMaxPacketSize,
+        max_parameter_size: MaxParameterSize,
+        mem_base_addr_align: MemBaseAddrAlign,
+        min_data_type_align_size: MinDataTypeAlignSize,
+        single_fp_config: SingleFPConfig,
+        double_fp_config: DoubleFPConfig,
+        global_mem_cache_type: GlobalMemCacheType,
+        global_mem_cacheline_size: GlobalMemCachelineSize,
+        global_mem_cache_size: GlobalMemCacheSize,
+        global_mem_size: GlobalMemSize,
+        max_constant_buffer_size: MaxConstantBufferSize,
+        max_constant_args: MaxConstantArgs,
+        max_global_variable_size: MaxGlobalVariableSize,
+        global_variable_preferred_total_size: GlobalVariablePreferredTotalSize,
+        local_mem_type: LocalMemType,
+        local_mem_size: LocalMemSize,
+        error_correction_support: ErrorCorrectionSupport,
+        host_unified_memory: HostUnifiedMemory,
+        profiling_timer_resolution: ProfilingTimerResolution,
+        endian_little: EndianLittle,
+        available: Available,
+        compiler_available: CompilerAvailable,
+        linker_available: LinkerAvailable,
+        execution_capabilities: ExecutionCapabilities,
+        queue_on_host_properties: QueueOnHostProperties,
+        queue_on_device_properties: QueueOnDeviceProperties,
+        queue_on_device_preferred_size: QueueOnDevicePreferredSize,
+        queue_on_device_max_size: QueueOnDeviceMaxSize,
+        max_on_device_queues: MaxOnDeviceQueues,
+        max_on_device_events: MaxOnDeviceEvents,
+        built_in_kernels: BuiltInKernels,
+        platform: Platform,
+        name: Name,
+        vendor: Vendor,
+        driver_version: DriverVersion,
+        profile: Profile,
+        version: Version,
+        opencl_c_version: OpenCLCVersion,
+        extensions: Extensions,
+        printf_buffer_size: PrintfBufferSize,
+        preferred_interop_user_sync: PreferredInteropUserSync,
+        parent_device: ParentDevice,
+        partition_max_sub_devices: PartitionMaxSubDevices,
+        partition_properties: PartitionProperties,
+        partition_affinity_domain: PartitionAffinityDomain,
+        partition_type: PartitionType,
+        reference_count: ReferenceCount,
+        svm_capabilities: SVMCapabilities,
+        preferred_platform_atomic_alignment: PreferredPlatformAtomicAlignment,
+        preferred_global_atomic_alignment: PreferredGlobalAtomicAlignment,
+        preferred_local_atomic_alignment: PreferredLocalAtomicAlignment,
+        il_version: ILVersion,
+        max_num_sub_groups: MaxNumSubGroups,
+        sub_group_independent_forward_progress: SubGroupIndependentForwardProgress,
+        numeric_version: NumericVersion,
+        extensions_with_version: ExtensionsWithVersion,
+        ils_with_version: ILsWithVersion,
+        built_in_kernels_with_version: BuiltInKernelsWithVersion,
+        atomic_memory_capabilities: AtomicMemoryCapabilities,
+        atomic_fence_capabilities: AtomicFenceCapabilities,
+        non_uniform_work_group_support: NonUniformWorkGroupSupport,
+        opencl_c_all_versions: OpenCLCAllVersions,
+        preferred_work_group_size_multiple: PreferredWorkGroupSizeMultiple,
+        work_group_collective_functions_support: WorkGroupCollectiveFunctionsSupport,
+        generic_address_space_support: GenericAddressSpaceSupport,
+        opencl_c_features: OpenCLCFeatures,
+        device_enqueue_capabilities: DeviceEnqueueCapabilities,
+        pipe_support: PipeSupport,
+        latest_conformance_version_passed: LatestConformanceVersionPassed,
     }
 }
 
@@ -269,7 +388,10 @@ pub fn release_device(device: DevicePtr) -> APIResult<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::api::info::Handle;
     use crate::api::platform;
+    use crate::objects::bitfields::DeviceFPConfig;
+    use crate::objects::enums::MemCacheType;
     use crate::objects::structs::{DevicePartitionProperty, PlatformInfo};
     use crate::objects::types::PlatformPtr;
     #[test]
@@ -302,6 +424,31 @@ mod tests {
         assert_ne!(vendor_id, 0);
     }
 
+    #[test]
+    fn test_device_details() {
+        let all_platforms = platform::get_platform_ids().unwrap();
+        let id = PlatformPtr::from_ptr(all_platforms[0], "test_fn").unwrap();
+        let device_ids = get_device_ids(&id, DeviceType::new(DeviceType::ALL).unwrap()).unwrap();
+        let device_id = DevicePtr::from_ptr(device_ids[0], "test_fn").unwrap();
+
+        let details = DeviceDetails::query(&unsafe { Handle::new(&device_id) });
+        assert_ne!(details.name.unwrap(), "");
+        assert_ne!(details.device_type.unwrap().get() & DeviceType::ALL, 0);
+        assert_eq!(details.platform.unwrap(), all_platforms[0]);
+        assert_eq!(details.parent_device.unwrap(), None);
+        assert_eq!(details.partition_type.unwrap(), None);
+        let dimensions = details.max_work_item_dimensions.unwrap();
+        assert_eq!(
+            details.max_work_item_sizes.unwrap().len(),
+            dimensions as usize
+        );
+        assert!(details.available.unwrap());
+        let fp_config = details.single_fp_config.unwrap().get();
+        assert_ne!(fp_config & DeviceFPConfig::ROUND_TO_NEAREST, 0);
+        let cache_type = details.global_mem_cache_type.unwrap();
+        assert!(!matches!(cache_type, MemCacheType::InvalidType(_)));
+    }
+
     #[test]
     #[ignore]
     fn test_create_sub_device() {
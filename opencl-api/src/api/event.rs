@@ -0,0 +1,250 @@
+/*
+ * event.rs - Event object API wrappers (Part of OpenCL Runtime Layer).
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//!
+//! Event objects can be used to refer to a kernel execution command, read,
+//! write, map and copy commands on memory objects or user events. An event
+//! object is returned by every enqueue function and can be waited on,
+//! queried for its execution status and profiled.
+//!
+use crate::errors::{ToLibraryError, ValidationError};
+use crate::ffi;
+use crate::ffi::{clGetEventInfo, clGetEventProfilingInfo};
+use crate::gen_param_value;
+use crate::objects::enums::{Command, ExecutionStatus, ParamValue, Size};
+use crate::objects::functions::status_update;
+use crate::objects::structs::{EventInfo, ProfilingInfo, StatusCode};
+use crate::objects::types::{APIResult, ContextPtr, EventList, EventPtr};
+use crate::objects::wrappers::WrappedMutablePointer;
+use libc::c_void;
+use opencl_heads::types::*;
+use std::ptr;
+
+/// Waits on the host thread for the commands identified by event_list to
+/// complete.
+pub fn wait_for_events(event_list: &EventList) -> APIResult<()> {
+    let fn_name = "clWaitForEvents";
+    let status_code =
+        unsafe { ffi::clWaitForEvents(event_list.len() as cl_uint, event_list.as_ptr()) };
+    status_update(status_code, fn_name, ())
+}
+
+pub fn get_event_info(event: &EventPtr, param_name: cl_event_info) -> APIResult<ParamValue> {
+    type E = EventInfo;
+    let fn_name = "clGetEventInfo";
+    let event = event.unwrap();
+    match param_name {
+        E::COMMAND_QUEUE | E::CONTEXT => {
+            let param_value = gen_param_value!(clGetEventInfo, isize, event, param_name);
+            Ok(ParamValue::CPtr(param_value))
+        }
+        E::REFERENCE_COUNT => {
+            let param_value = gen_param_value!(clGetEventInfo, u32, event, param_name);
+            Ok(ParamValue::UInt(param_value))
+        }
+        E::COMMAND_TYPE => {
+            let param_value = gen_param_value!(clGetEventInfo, u32, event, param_name);
+            Ok(ParamValue::Command(Command::from(param_value)))
+        }
+        E::COMMAND_EXECUTION_STATUS => {
+            let param_value = gen_param_value!(clGetEventInfo, i32, event, param_name);
+            Ok(ParamValue::ExecutionStatus(ExecutionStatus::from(
+                param_value,
+            )))
+        }
+        _ => Err(ValidationError::UnsupportedParam(fn_name, param_name).to_error()),
+    }
+}
+
+pub fn create_user_event(context: &ContextPtr) -> APIResult<EventPtr> {
+    let fn_name = "clCreateUserEvent";
+    let mut status_code = StatusCode::INVALID_VALUE;
+    let event = unsafe { ffi::clCreateUserEvent(context.unwrap(), &mut status_code) };
+    status_update(status_code, fn_name, event).and_then(|x| EventPtr::from_ptr(x, fn_name))
+}
+
+/// * execution_status is either CommandExecutionStatus::COMPLETE or a
+/// negative integer value indicating an error.
+pub fn set_user_event_status(event: &EventPtr, execution_status: cl_int) -> APIResult<()> {
+    let fn_name = "clSetUserEventStatus";
+    let status_code = unsafe { ffi::clSetUserEventStatus(event.unwrap(), execution_status) };
+    status_update(status_code, fn_name, ())
+}
+
+/// * command_exec_callback_type is the execution status for which the
+/// callback is registered, i.e. COMPLETE, RUNNING or SUBMITTED.
+/// * pfn_notify is invoked asynchronously, possibly from another thread,
+/// with the event, its execution status and user_data.
+pub fn set_event_callback(
+    event: &EventPtr,
+    command_exec_callback_type: cl_int,
+    pfn_notify: Option<extern "C" fn(cl_event, cl_int, *mut c_void)>,
+    user_data: WrappedMutablePointer<c_void>,
+) -> APIResult<()> {
+    let fn_name = "clSetEventCallback";
+    let status_code = unsafe {
+        ffi::clSetEventCallback(
+            event.unwrap(),
+            command_exec_callback_type,
+            pfn_notify,
+            user_data.unwrap(),
+        )
+    };
+    status_update(status_code, fn_name, ())
+}
+
+pub fn retain_event(event: &EventPtr) -> APIResult<()> {
+    let fn_name = "clRetainEvent";
+    let status_code = unsafe { ffi::clRetainEvent(event.unwrap()) };
+    status_update(status_code, fn_name, ())
+}
+
+pub fn release_event(event: EventPtr) -> APIResult<()> {
+    let fn_name = "clReleaseEvent";
+    let status_code = unsafe { ffi::clReleaseEvent(event.unwrap()) };
+    status_update(status_code, fn_name, ())
+}
+
+/// Profiling information is only available if the command queue was
+/// created with CommandQueueProperties::PROFILING_ENABLE. All values are
+/// device time counters in nanoseconds.
+pub fn get_event_profiling_info(
+    event: &EventPtr,
+    param_name: cl_profiling_info,
+) -> APIResult<ParamValue> {
+    type P = ProfilingInfo;
+    let fn_name = "clGetEventProfilingInfo";
+    let event = event.unwrap();
+    match param_name {
+        P::QUEUED | P::SUBMIT | P::START | P::END | P::COMPLETE => {
+            let param_value = gen_param_value!(clGetEventProfilingInfo, u64, event, param_name);
+            Ok(ParamValue::ULong(param_value))
+        }
+        _ => Err(ValidationError::UnsupportedParam(fn_name, param_name).to_error()),
+    }
+}
+
+/************************/
+/* /\ /\ /\ /\ /\ /\ /\ */
+/*|__|__|__|__|__|__|__|*/
+/*|  |  |  |  |  |  |  |*/
+/*|  |  Unit Tests  |  |*/
+/*|__|__|__|__|__|__|__|*/
+/*|__|__|__|__|__|__|__|*/
+/************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::buffer::{create_buffer, enqueue_write_buffer};
+    use crate::api::context::{create_context, release_context};
+    use crate::api::device::get_device_ids;
+    use crate::api::memory::release_mem_object;
+    use crate::api::platform::get_platform_ids;
+    use crate::api::queue::{create_command_queue_with_properties, release_command_queue};
+    use crate::objects::bitfields::{CommandQueueProperties, DeviceType, MemFlags};
+    use crate::objects::property::QueueProperties;
+    use crate::objects::structs::CommandExecutionStatus;
+    use crate::objects::traits::GetSetGo;
+    use crate::objects::types::{DevicePtr, PlatformPtr, WrapMutPtr, WrapPtr};
+    use crate::objects::wrappers::WrappedPointer;
+
+    #[test]
+    fn test_user_event() {
+        let platform_ids = get_platform_ids().unwrap();
+        // Choose the first platform
+        let platform_id = PlatformPtr::from_ptr(platform_ids[0], "test_fn").unwrap();
+
+        let device_ids =
+            get_device_ids(&platform_id, DeviceType::new(DeviceType::DEFAULT).unwrap()).unwrap();
+        assert!(0 < device_ids.len());
+
+        let context = create_context(&None, device_ids, None, WrapMutPtr::null());
+        let context = context.unwrap();
+
+        let event = create_user_event(&context).unwrap();
+        let command_type = get_event_info(&event, EventInfo::COMMAND_TYPE).unwrap();
+        assert_eq!(Some(Command::User), command_type.unwrap_command());
+        let status = get_event_info(&event, EventInfo::COMMAND_EXECUTION_STATUS).unwrap();
+        assert_eq!(
+            Some(ExecutionStatus::Submitted),
+            status.unwrap_execution_status()
+        );
+
+        set_user_event_status(&event, CommandExecutionStatus::COMPLETE as cl_int).unwrap();
+        wait_for_events(&vec![event.unwrap()]).unwrap();
+        let status = get_event_info(&event, EventInfo::COMMAND_EXECUTION_STATUS).unwrap();
+        assert_eq!(
+            Some(ExecutionStatus::Complete),
+            status.unwrap_execution_status()
+        );
+
+        release_event(event).unwrap();
+        release_context(context).unwrap();
+    }
+
+    #[test]
+    fn test_get_event_profiling_info() {
+        let platform_ids = get_platform_ids().unwrap();
+        // Choose the first platform
+        let platform_id = PlatformPtr::from_ptr(platform_ids[0], "test_fn").unwrap();
+
+        let device_ids =
+            get_device_ids(&platform_id, DeviceType::new(DeviceType::DEFAULT).unwrap()).unwrap();
+        assert!(0 < device_ids.len());
+        let device_id = DevicePtr::from_ptr(device_ids[0], "test_fn").unwrap();
+
+        let context = create_context(&None, device_ids, None, WrapMutPtr::null());
+        let context = context.unwrap();
+
+        let properties = QueueProperties.gen(
+            Some(CommandQueueProperties::new(CommandQueueProperties::PROFILING_ENABLE).unwrap()),
+            None,
+        );
+        let queue =
+            create_command_queue_with_properties(&context, &device_id, &properties).unwrap();
+
+        let data = vec![1u8; 1024];
+        let flags = MemFlags::new(MemFlags::READ_WRITE).unwrap();
+        let buffer = create_buffer(&context, flags, data.len(), WrapMutPtr::null()).unwrap();
+        let event = enqueue_write_buffer(
+            &queue,
+            &buffer,
+            1,
+            0,
+            data.len(),
+            WrapPtr::from(&data[0]),
+            0,
+            WrappedPointer::null(),
+        )
+        .unwrap();
+        wait_for_events(&vec![event.unwrap()]).unwrap();
+
+        let command_type = get_event_info(&event, EventInfo::COMMAND_TYPE).unwrap();
+        assert_eq!(Some(Command::WriteBuffer), command_type.unwrap_command());
+        let start = get_event_profiling_info(&event, ProfilingInfo::START).unwrap();
+        let end = get_event_profiling_info(&event, ProfilingInfo::END).unwrap();
+        let start = start.unwrap_ulong().unwrap();
+        let end = end.unwrap_ulong().unwrap();
+        assert!(start <= end);
+
+        release_event(event).unwrap();
+        release_mem_object(buffer).unwrap();
+        release_command_queue(queue).unwrap();
+        release_context(context).unwrap();
+    }
+}
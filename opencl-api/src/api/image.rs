@@ -20,17 +20,24 @@
 //! The elements of an image object are selected from a list of predefined image formats.
 //! The minimum number of elements in a memory object is one.
 
+use crate::api::context::get_context_info;
+use crate::api::device::get_device_info;
+use crate::errors::{ToLibraryError, ValidationError};
+use crate::ffi;
+use crate::ffi::{clGetImageInfo, clGetSupportedImageFormats};
 use crate::objects::bitfields::{MapFlags, MemFlags};
 use crate::objects::enums::{ParamValue, Size};
 use crate::objects::functions::status_update;
-use crate::objects::structs::{ImageInfo, StatusCode};
+use crate::objects::image::{ImageDesc, ImageFormat};
+use crate::objects::structs::{ContextInfo, DeviceInfo, ImageInfo, MemObjectType, StatusCode};
 use crate::objects::traits::GetSetGo;
-use crate::objects::types::{APIResult, ContextPtr, EventPtr, MemFormatList, MemPtr, QueuePtr};
+use crate::objects::types::{
+    APIResult, ContextPtr, DevicePtr, EventPtr, MemFormatList, MemPtr, NullMutPtr, QueuePtr,
+};
 use crate::objects::wrappers::{WrappedMutablePointer, WrappedPointer};
 use crate::{gen_object_list, gen_param_value, get_count, size_getter};
 use libc::c_void;
-use opencl_heads::ffi;
-use opencl_heads::ffi::{clGetImageInfo, clGetSupportedImageFormats};
+use opencl_heads::consts::CL_TRUE;
 use opencl_heads::types::*;
 use std::ptr;
 
@@ -53,7 +60,86 @@ pub fn create_image(
             &mut status_code,
         )
     };
-    status_update(status_code, fn_name, MemPtr::from_ptr(mem_ptr, fn_name)?)
+    status_update(status_code, fn_name, mem_ptr).and_then(|x| MemPtr::from_ptr(x, fn_name))
+}
+
+/// Creates the image of format and desc, validating them first.
+/// * Fails with a ValidationError, without calling clCreateImage, when desc
+///   is incomplete, when format is not among the supported image formats
+///   of context for flags, or when the image exceeds the image limits of
+///   every device of context.
+pub fn create_image_from_desc(
+    context: &ContextPtr,
+    flags: MemFlags,
+    format: &ImageFormat,
+    desc: &ImageDesc,
+    host: WrappedMutablePointer<c_void>,
+) -> APIResult<MemPtr> {
+    let fn_name = "clCreateImage";
+    let raw_desc = desc
+        .build(format, !host.unwrap().is_null())
+        .map_err(ToLibraryError::to_error)?;
+    let supported = get_supported_image_formats(context, flags.clone(), desc.image_type())?;
+    if !supported.contains(&format.get()) {
+        return Err(ValidationError::UnsupportedImageFormat(fn_name).to_error());
+    }
+    let devices = get_context_info(context, ContextInfo::DEVICES)?
+        .unwrap_arr_cptr()
+        .unwrap_or_default();
+    let mut fits = false;
+    for device in devices {
+        let device = unsafe { DevicePtr::from_raw(device) };
+        if fits_device(&device, desc)? {
+            fits = true;
+            break;
+        }
+    }
+    if !fits {
+        return Err(ValidationError::InvalidImageSize(fn_name).to_error());
+    }
+    create_image(
+        context,
+        flags,
+        WrappedPointer::from(&format.get()),
+        WrappedPointer::from(&raw_desc),
+        host,
+    )
+}
+
+// Whether the image of desc is within the image limits of device.
+fn fits_device(device: &DevicePtr, desc: &ImageDesc) -> APIResult<bool> {
+    type D = DeviceInfo;
+    type M = MemObjectType;
+    let info = |param_name| -> APIResult<size_t> {
+        Ok(get_device_info(device, param_name)?
+            .unwrap_csize()
+            .unwrap_or(0))
+    };
+    let image_support = get_device_info(device, D::IMAGE_SUPPORT)?.unwrap_uint();
+    if image_support != Some(CL_TRUE) {
+        return Ok(false);
+    }
+    let limits = match desc.image_type() {
+        M::IMAGE1D_BUFFER => [info(D::IMAGE_MAX_BUFFER_SIZE)?, 1, 1],
+        M::IMAGE1D => [info(D::IMAGE2D_MAX_WIDTH)?, 1, 1],
+        M::IMAGE1D_ARRAY => [
+            info(D::IMAGE2D_MAX_WIDTH)?,
+            info(D::IMAGE_MAX_ARRAY_SIZE)?,
+            1,
+        ],
+        M::IMAGE2D => [info(D::IMAGE2D_MAX_WIDTH)?, info(D::IMAGE2D_MAX_HEIGHT)?, 1],
+        M::IMAGE2D_ARRAY => [
+            info(D::IMAGE2D_MAX_WIDTH)?,
+            info(D::IMAGE2D_MAX_HEIGHT)?,
+            info(D::IMAGE_MAX_ARRAY_SIZE)?,
+        ],
+        _ => [
+            info(D::IMAGE3D_MAX_WIDTH)?,
+            info(D::IMAGE3D_MAX_HEIGHT)?,
+            info(D::IMAGE3D_MAX_DEPTH)?,
+        ],
+    };
+    Ok(desc.region().iter().zip(&limits).all(|(x, max)| x <= max))
 }
 
 pub fn create_image_with_properties(
@@ -77,7 +163,7 @@ pub fn create_image_with_properties(
             &mut status_code,
         )
     };
-    status_update(status_code, fn_name, MemPtr::from_ptr(mem_ptr, fn_name)?)
+    status_update(status_code, fn_name, mem_ptr).and_then(|x| MemPtr::from_ptr(x, fn_name))
 }
 
 pub fn create_image_2d(
@@ -103,7 +189,7 @@ pub fn create_image_2d(
             &mut status_code,
         )
     };
-    status_update(status_code, fn_name, MemPtr::from_ptr(mem_ptr, fn_name)?)
+    status_update(status_code, fn_name, mem_ptr).and_then(|x| MemPtr::from_ptr(x, fn_name))
 }
 
 pub fn create_image_3d(
@@ -133,7 +219,7 @@ pub fn create_image_3d(
             &mut status_code,
         )
     };
-    status_update(status_code, fn_name, MemPtr::from_ptr(mem_ptr, fn_name)?)
+    status_update(status_code, fn_name, mem_ptr).and_then(|x| MemPtr::from_ptr(x, fn_name))
 }
 
 // TODO: Check buffer types for api compatiblity
@@ -188,11 +274,7 @@ pub fn enqueue_read_image(
             &mut event_ptr,
         )
     };
-    status_update(
-        status_code,
-        fn_name,
-        EventPtr::from_ptr(event_ptr, fn_name)?,
-    )
+    status_update(status_code, fn_name, event_ptr).and_then(|x| EventPtr::from_ptr(x, fn_name))
 }
 pub fn enqueue_write_image(
     command_queue: &QueuePtr,
@@ -223,11 +305,7 @@ pub fn enqueue_write_image(
             &mut event_ptr,
         )
     };
-    status_update(
-        status_code,
-        fn_name,
-        EventPtr::from_ptr(event_ptr, fn_name)?,
-    )
+    status_update(status_code, fn_name, event_ptr).and_then(|x| EventPtr::from_ptr(x, fn_name))
 }
 
 pub fn enqueue_copy_image(
@@ -255,11 +333,7 @@ pub fn enqueue_copy_image(
             &mut event_ptr,
         )
     };
-    status_update(
-        status_code,
-        fn_name,
-        EventPtr::from_ptr(event_ptr, fn_name)?,
-    )
+    status_update(status_code, fn_name, event_ptr).and_then(|x| EventPtr::from_ptr(x, fn_name))
 }
 
 pub fn enqueue_fill_image(
@@ -285,11 +359,7 @@ pub fn enqueue_fill_image(
             &mut event_ptr,
         )
     };
-    status_update(
-        status_code,
-        fn_name,
-        EventPtr::from_ptr(event_ptr, fn_name)?,
-    )
+    status_update(status_code, fn_name, event_ptr).and_then(|x| EventPtr::from_ptr(x, fn_name))
 }
 
 pub fn enqueue_copy_image_to_buffer(
@@ -317,11 +387,7 @@ pub fn enqueue_copy_image_to_buffer(
             &mut event_ptr,
         )
     };
-    status_update(
-        status_code,
-        fn_name,
-        EventPtr::from_ptr(event_ptr, fn_name)?,
-    )
+    status_update(status_code, fn_name, event_ptr).and_then(|x| EventPtr::from_ptr(x, fn_name))
 }
 
 pub fn enqueue_copy_buffer_to_image(
@@ -349,13 +415,13 @@ pub fn enqueue_copy_buffer_to_image(
             &mut event_ptr,
         )
     };
-    status_update(
-        status_code,
-        fn_name,
-        EventPtr::from_ptr(event_ptr, fn_name)?,
-    )
+    status_update(status_code, fn_name, event_ptr).and_then(|x| EventPtr::from_ptr(x, fn_name))
 }
 
+/// Returns the mapped region of image, a pointer into host memory, and the
+/// event of the map command.
+/// * image_row_pitch and image_slice_pitch receive the pitches in bytes of
+///   the mapped region, image_slice_pitch being null for 1D and 2D images.
 pub fn enqueue_map_image(
     command_queue: &QueuePtr,
     image: &MemPtr,
@@ -367,12 +433,11 @@ pub fn enqueue_map_image(
     image_slice_pitch: WrappedMutablePointer<size_t>,
     num_events_in_wait_list: cl_uint,
     event_wait_list: WrappedPointer<cl_event>,
-    mapped_region: &mut cl_mem,
-) -> APIResult<EventPtr> {
+) -> APIResult<(NullMutPtr, EventPtr)> {
     let fn_name = "clEnqueueMapImage";
     let mut status_code = StatusCode::INVALID_VALUE;
     let mut event_ptr = ptr::null_mut();
-    *mapped_region = unsafe {
+    let mapped = unsafe {
         ffi::clEnqueueMapImage(
             command_queue.unwrap(),
             image.unwrap(),
@@ -388,11 +453,12 @@ pub fn enqueue_map_image(
             &mut status_code,
         )
     };
-    status_update(
-        status_code,
-        fn_name,
-        EventPtr::from_ptr(event_ptr, fn_name)?,
-    )
+    status_update(status_code, fn_name, (mapped, event_ptr)).and_then(|(mapped, event)| {
+        Ok((
+            NullMutPtr::from_ptr(mapped, fn_name)?,
+            EventPtr::from_ptr(event, fn_name)?,
+        ))
+    })
 }
 
 pub fn get_image_info(image: &MemPtr, param_name: cl_image_info) -> APIResult<ParamValue> {
@@ -424,8 +490,92 @@ pub fn get_image_info(image: &MemPtr, param_name: cl_image_info) -> APIResult<Pa
                 gen_param_value!(clGetImageInfo, cl_image_format, image, param_name, size);
             Ok(ParamValue::ImageFormat(param_value))
         }
-        _ => status_update(40404, "clGetImageInfo", ParamValue::default()),
+        _ => Err(ValidationError::UnsupportedParam("clGetImageInfo", param_name).to_error()),
     }
 }
 
-// TODO: Add unit tests for this file.
+/************************/
+/* /‾‾\ /‾‾\ /‾‾\ /‾‾\  */
+/*|  |  |  |  |  |  |  |*/
+/*|  |  Unit Tests  |  |*/
+/*|__|__|__|__|__|__|__|*/
+/*|__|__|__|__|__|__|__|*/
+/************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::context::{create_context, release_context};
+    use crate::api::device::get_device_ids;
+    use crate::api::memory::release_mem_object;
+    use crate::api::platform::get_platform_ids;
+    use crate::errors::OpenCLAPIError;
+    use crate::objects::bitfields::DeviceType;
+    use crate::objects::structs::{ChannelOrder, ChannelType};
+    use crate::objects::types::PlatformPtr;
+
+    fn context() -> ContextPtr {
+        let platform_ids = get_platform_ids().unwrap();
+        let platform_id = PlatformPtr::from_ptr(platform_ids[0], "test_fn").unwrap();
+        let device_ids =
+            get_device_ids(&platform_id, DeviceType::new(DeviceType::DEFAULT).unwrap()).unwrap();
+        create_context(&None, device_ids, None, WrappedMutablePointer::null()).unwrap()
+    }
+
+    #[test]
+    fn test_create_image_from_desc() {
+        let context = context();
+        let flags = MemFlags::new(MemFlags::READ_WRITE).unwrap();
+        let format = ImageFormat::new(ChannelOrder::RGBA, ChannelType::UNORM_INT8).unwrap();
+
+        let desc = ImageDesc::image2d(16, 8);
+        let image = create_image_from_desc(
+            &context,
+            flags.clone(),
+            &format,
+            &desc,
+            WrappedMutablePointer::null(),
+        )
+        .unwrap();
+        let width = get_image_info(&image, ImageInfo::WIDTH).unwrap();
+        assert_eq!(width.unwrap_csize(), Some(16));
+        let row_pitch = get_image_info(&image, ImageInfo::ROW_PITCH).unwrap();
+        assert!(row_pitch.unwrap_csize().unwrap() >= 16 * 4);
+        release_mem_object(image).unwrap();
+
+        let mut host = vec![0u8; desc.row_pitch(128).host_size(&format).unwrap()];
+        let copy = MemFlags::new(MemFlags::READ_WRITE + MemFlags::COPY_HOST_PTR).unwrap();
+        let image = create_image_from_desc(
+            &context,
+            copy,
+            &format,
+            &desc.row_pitch(128),
+            WrappedMutablePointer::from(&mut host[0]),
+        )
+        .unwrap();
+        release_mem_object(image).unwrap();
+
+        let too_wide = ImageDesc::image2d(1 << 30, 1);
+        assert_eq!(
+            create_image_from_desc(
+                &context,
+                flags.clone(),
+                &format,
+                &too_wide,
+                WrappedMutablePointer::null()
+            )
+            .unwrap_err(),
+            OpenCLAPIError::ObjectError(ValidationError::InvalidImageSize("clCreateImage"))
+        );
+        let incomplete = ImageDesc::new(MemObjectType::IMAGE3D).unwrap().width(4);
+        assert!(create_image_from_desc(
+            &context,
+            flags,
+            &format,
+            &incomplete,
+            WrappedMutablePointer::null()
+        )
+        .is_err());
+        release_context(context).unwrap();
+    }
+}
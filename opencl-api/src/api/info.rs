@@ -0,0 +1,879 @@
+/*
+ * info.rs - Typed info queries shared by every OpenCL object kind.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Every info parameter is a zero-sized type carrying the value it decodes
+//! to and the OpenCL version introducing it, so that
+//! `query::<device::Name>(&device)` returns a `String` without a match over
+//! `ParamValue`. Parameters outside of these modules simply do not exist,
+//! those newer than the object fail with `ValidationError::UnsupportedVersion`
+//! without reaching the driver, and those a driver rejects fail with
+//! `ValidationError::UnsupportedParam`.
+//! The object is a `Handle` of the kind of the parameter, so that e.g. a
+//! device parameter can not be queried on a platform.
+//!
+//! Every decoded value also has a `Plain` form made of numbers, strings and
+//! lists only, for the tools recording or comparing them.
+
+use crate::errors::{RuntimeError, ToLibraryError, ValidationError};
+use crate::ffi;
+use crate::objects::bitfields::{
+    CommandQueueProperties, DeviceAffinityDomain, DeviceAtomicCapabilities,
+    DeviceDeviceEnqueueCapabilities, DeviceExecCapabilities, DeviceFPConfig, DeviceSVMCapabilities,
+    DeviceType, MemFlags,
+};
+use crate::objects::enums::{DevicePartition, LocalMemType, MemCacheType, PartitionScheme};
+use crate::objects::functions::{bytes_into_string, status_update};
+use crate::objects::image::ImageFormat;
+use crate::objects::structs::{
+    AddressingMode, ChannelOrder, ChannelType, DeviceLocalMemType, DeviceMemCacheType,
+    DevicePartitionProperty, FilterMode, StatusCode,
+};
+use crate::objects::traits::GetSetGo;
+use crate::objects::types::{APIResult, NullMutPtr};
+use libc::c_void;
+use opencl_heads::consts::{CL_FALSE, CL_VERSION_MINOR_BITS, CL_VERSION_PATCH_BITS};
+use opencl_heads::types::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::ffi::CStr;
+use std::fmt::LowerHex;
+use std::marker::PhantomData;
+use std::mem::size_of;
+use std::ptr;
+
+type GetInfoFn = unsafe fn(*mut c_void, cl_uint, size_t, *mut c_void, *mut size_t) -> cl_int;
+
+/// An OpenCL object kind and the `clGet*Info` function querying it.
+pub trait InfoKind: Sized {
+    const FN_NAME: &'static str;
+    const GET_INFO: GetInfoFn;
+    /// The (major, minor) OpenCL version of object, None when the driver
+    /// reports it in an unknown format.
+    fn version(object: &Handle<Self>) -> VersionResult;
+}
+
+/// An info parameter of the object kind `Kind`, decoded to `Value`.
+pub trait InfoParam {
+    type Kind: InfoKind;
+    type Value: InfoValue;
+    const NAME: cl_uint;
+    /// The (major, minor) OpenCL version introducing the parameter.
+    const VERSION: (cl_uint, cl_uint);
+}
+
+/// A Rust type an info parameter decodes to from its raw bytes.
+pub trait InfoValue: Sized {
+    fn decode(bytes: Vec<u8>, fn_name: &'static str) -> APIResult<Self>;
+    /// None for the handles and the property lists holding them, which
+    /// only mean something inside the process.
+    fn plain(&self) -> Option<Plain>;
+}
+
+/// A decoded info as plain data: constants by their OpenCL names, the
+/// versions of the name-version lists as `major.minor.patch` and every empty
+/// optional value as an empty list.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(untagged))]
+pub enum Plain {
+    Bool(bool),
+    Integer(u64),
+    Text(String),
+    List(Vec<Plain>),
+    NameVersion { name: String, version: String },
+}
+
+/// A handle of an object of kind `K`, e.g. `Handle<Device>`, so that `query`
+/// only takes the parameters of its kind.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Handle<K: InfoKind> {
+    raw: *mut c_void,
+    kind: PhantomData<K>,
+}
+
+impl<K: InfoKind> Handle<K> {
+    /// Tags object as an object of kind `K`.
+    ///
+    /// # Safety
+    ///
+    /// object must be an object of kind `K`: the pointer types of opencl-api
+    /// are all `NullMutPtr`, so nothing else checks it. The owned types of
+    /// the opencl crate convert to the handle of their kind with `From`.
+    pub unsafe fn new(object: &NullMutPtr) -> Self {
+        Handle {
+            raw: object.unwrap(),
+            kind: PhantomData,
+        }
+    }
+
+    pub fn as_raw(&self) -> *mut c_void {
+        self.raw
+    }
+
+    /// Tags raw as an object of kind `K`, e.g. as returned by a query.
+    fn from_raw<R>(raw: *mut R) -> Self {
+        Handle {
+            raw: raw as *mut c_void,
+            kind: PhantomData,
+        }
+    }
+}
+
+macro_rules! gen_info_kinds {
+    ($($kind:ident => $get_info:ident, $version:ident,)*) => {
+        $(
+            #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+            pub struct $kind;
+            impl InfoKind for $kind {
+                const FN_NAME: &'static str = stringify!($get_info);
+                const GET_INFO: GetInfoFn = ffi::$get_info;
+                fn version(object: &Handle<Self>) -> VersionResult {
+                    $version(object.as_raw())
+                }
+            }
+        )*
+    };
+}
+
+gen_info_kinds! {
+    Platform => clGetPlatformInfo, platform_version,
+    Device => clGetDeviceInfo, device_version,
+    Context => clGetContextInfo, context_version,
+    CommandQueue => clGetCommandQueueInfo, queue_version,
+    Mem => clGetMemObjectInfo, mem_version,
+    Image => clGetImageInfo, mem_version,
+    Pipe => clGetPipeInfo, mem_version,
+    Sampler => clGetSamplerInfo, sampler_version,
+}
+
+// Platforms and devices report their version, queues have that of their
+// device and the other objects that of the platform of their context.
+type VersionResult = APIResult<Option<(cl_uint, cl_uint)>>;
+
+fn platform_version(platform: *mut c_void) -> VersionResult {
+    let version = query::<platform::Version>(&Handle::from_raw(platform))?;
+    Ok(parse_version(&version))
+}
+
+fn device_version(device: *mut c_void) -> VersionResult {
+    let version = query::<device::Version>(&Handle::from_raw(device))?;
+    Ok(parse_version(&version))
+}
+
+fn context_version(context: *mut c_void) -> VersionResult {
+    match query::<context::Devices>(&Handle::from_raw(context))?.first() {
+        Some(&device) => platform_version(query::<device::Platform>(&Handle::from_raw(device))?),
+        None => Ok(None),
+    }
+}
+
+fn queue_version(queue: *mut c_void) -> VersionResult {
+    device_version(query::<queue::Device>(&Handle::from_raw(queue))?)
+}
+
+fn mem_version(mem: *mut c_void) -> VersionResult {
+    context_version(query::<mem::Context>(&Handle::from_raw(mem))?)
+}
+
+fn sampler_version(sampler: *mut c_void) -> VersionResult {
+    context_version(query::<sampler::Context>(&Handle::from_raw(sampler))?)
+}
+
+/// Parses the (major, minor) version of an `OpenCL <major>.<minor> ...`
+/// version string.
+fn parse_version(version: &str) -> Option<(cl_uint, cl_uint)> {
+    let number = version.strip_prefix("OpenCL ")?.split(' ').next()?;
+    let (major, minor) = number.split_once('.')?;
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+/// Queries the info parameter `P` of `object`.
+///
+/// ```compile_fail
+/// use opencl_api::api::info::{device, query, Handle, Platform};
+/// fn name(platform: &Handle<Platform>) {
+///     let _ = query::<device::Name>(platform);
+/// }
+/// ```
+pub fn query<P: InfoParam>(object: &Handle<P::Kind>) -> APIResult<P::Value> {
+    let fn_name = P::Kind::FN_NAME;
+    // Every object supports the parameters of OpenCL 1.0.
+    if P::VERSION > (1, 0) {
+        if let Some(version) = P::Kind::version(object)? {
+            if version < P::VERSION {
+                let (major, minor) = P::VERSION;
+                let error = ValidationError::UnsupportedVersion(fn_name, P::NAME, major, minor);
+                return Err(error.to_error());
+            }
+        }
+    }
+    let object = object.as_raw();
+    let mut size = size_t::default();
+    let status_code =
+        unsafe { (P::Kind::GET_INFO)(object, P::NAME, 0, ptr::null_mut(), &mut size) };
+    check_status::<P>(status_code)?;
+    let mut bytes = vec![0u8; size];
+    if size != 0 {
+        let status_code = unsafe {
+            (P::Kind::GET_INFO)(
+                object,
+                P::NAME,
+                size,
+                bytes.as_mut_ptr() as *mut c_void,
+                ptr::null_mut(),
+            )
+        };
+        check_status::<P>(status_code)?;
+    }
+    P::Value::decode(bytes, fn_name)
+}
+
+/// CL_INVALID_VALUE from a `clGet*Info` call means the parameter is not
+/// supported by the object, every other failure is passed on as is.
+fn check_status<P: InfoParam>(status_code: cl_int) -> APIResult<()> {
+    let fn_name = P::Kind::FN_NAME;
+    match status_code {
+        StatusCode::INVALID_VALUE => {
+            Err(ValidationError::UnsupportedParam(fn_name, P::NAME).to_error())
+        }
+        _ => status_update(status_code, fn_name, ()),
+    }
+}
+
+// Decoders of the raw bytes, only ever instantiated with plain data types.
+
+fn unexpected<T>(fn_name: &'static str) -> APIResult<T> {
+    Err(RuntimeError::UnexpectedParamValue(fn_name).to_error())
+}
+
+fn scalar<T: Copy>(bytes: &[u8], fn_name: &'static str) -> APIResult<T> {
+    if bytes.len() != size_of::<T>() {
+        return unexpected(fn_name);
+    }
+    Ok(unsafe { ptr::read_unaligned(bytes.as_ptr() as *const T) })
+}
+
+fn array<T: Copy>(bytes: &[u8], fn_name: &'static str) -> APIResult<Vec<T>> {
+    let size = size_of::<T>();
+    if !bytes.len().is_multiple_of(size) {
+        return unexpected(fn_name);
+    }
+    Ok(bytes
+        .chunks_exact(size)
+        .map(|x| unsafe { ptr::read_unaligned(x.as_ptr() as *const T) })
+        .collect())
+}
+
+macro_rules! impl_plain_values {
+    ($($typ:ty => $plain:expr,)*) => {
+        $(
+            impl InfoValue for $typ {
+                fn decode(bytes: Vec<u8>, fn_name: &'static str) -> APIResult<Self> {
+                    scalar(&bytes, fn_name)
+                }
+                fn plain(&self) -> Option<Plain> {
+                    $plain(self)
+                }
+            }
+            impl InfoValue for Vec<$typ> {
+                fn decode(bytes: Vec<u8>, fn_name: &'static str) -> APIResult<Self> {
+                    array(&bytes, fn_name)
+                }
+                fn plain(&self) -> Option<Plain> {
+                    self.iter().map($plain).collect::<Option<_>>().map(Plain::List)
+                }
+            }
+        )*
+    };
+}
+
+impl_plain_values! {
+    cl_uint => |&x: &cl_uint| Some(Plain::Integer(x.into())),
+    cl_ulong => |&x: &cl_ulong| Some(Plain::Integer(x)),
+    size_t => |&x: &size_t| Some(Plain::Integer(x as u64)),
+    isize => |_: &isize| None,
+    *mut c_void => |_: &*mut c_void| None,
+    cl_name_version => |x: &cl_name_version| Some(name_version(x)),
+}
+
+/// Renders a `cl_version` as major.minor.patch.
+pub fn version_string(version: cl_version) -> String {
+    let major = version >> (CL_VERSION_MINOR_BITS + CL_VERSION_PATCH_BITS);
+    let minor = (version >> CL_VERSION_PATCH_BITS) & ((1 << CL_VERSION_MINOR_BITS) - 1);
+    let patch = version & ((1 << CL_VERSION_PATCH_BITS) - 1);
+    format!("{}.{}.{}", major, minor, patch)
+}
+
+/// The name of a `cl_name_version`, up to its null byte.
+pub fn name_string(value: &cl_name_version) -> String {
+    let bytes: Vec<u8> = value.name.iter().map(|&x| x as u8).collect();
+    match CStr::from_bytes_until_nul(&bytes) {
+        Ok(x) => x.to_string_lossy().into_owned(),
+        Err(_) => String::from_utf8_lossy(&bytes).into_owned(),
+    }
+}
+
+fn name_version(value: &cl_name_version) -> Plain {
+    Plain::NameVersion {
+        name: name_string(value),
+        version: version_string(value.version),
+    }
+}
+
+/// The OpenCL name of a constant, its value in hex when it has none.
+fn constant<T: LowerHex>(name: Option<&str>, value: T) -> Plain {
+    Plain::Text(name.map_or_else(|| format!("{:#x}", value), str::to_owned))
+}
+
+impl InfoValue for String {
+    fn decode(bytes: Vec<u8>, _fn_name: &'static str) -> APIResult<Self> {
+        bytes_into_string(bytes)
+    }
+    fn plain(&self) -> Option<Plain> {
+        Some(Plain::Text(self.clone()))
+    }
+}
+
+impl InfoValue for bool {
+    fn decode(bytes: Vec<u8>, fn_name: &'static str) -> APIResult<Self> {
+        scalar::<cl_bool>(&bytes, fn_name).map(|x| x != CL_FALSE)
+    }
+    fn plain(&self) -> Option<Plain> {
+        Some(Plain::Bool(*self))
+    }
+}
+
+/// None for the null handles, such as the parent of a root device.
+impl InfoValue for Option<*mut c_void> {
+    fn decode(bytes: Vec<u8>, fn_name: &'static str) -> APIResult<Self> {
+        let handle: *mut c_void = scalar(&bytes, fn_name)?;
+        Ok(if handle.is_null() { None } else { Some(handle) })
+    }
+    fn plain(&self) -> Option<Plain> {
+        None
+    }
+}
+
+macro_rules! impl_bitfield_values {
+    ($($bitfield:ident,)*) => {
+        $(
+            impl InfoValue for $bitfield {
+                fn decode(bytes: Vec<u8>, fn_name: &'static str) -> APIResult<Self> {
+                    $bitfield::new(scalar(&bytes, fn_name)?).map_err(ToLibraryError::to_error)
+                }
+                fn plain(&self) -> Option<Plain> {
+                    Some(Plain::List(self.names().into_iter().map(Plain::Text).collect()))
+                }
+            }
+            /// None for the bitfields left empty by the objects without the feature.
+            impl InfoValue for Option<$bitfield> {
+                fn decode(bytes: Vec<u8>, fn_name: &'static str) -> APIResult<Self> {
+                    match scalar(&bytes, fn_name)? {
+                        0 => Ok(None),
+                        x => $bitfield::new(x).map(Some).map_err(ToLibraryError::to_error),
+                    }
+                }
+                fn plain(&self) -> Option<Plain> {
+                    match self {
+                        Some(x) => x.plain(),
+                        None => Some(Plain::List(Vec::new())),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_bitfield_values! {
+    DeviceType,
+    DeviceFPConfig,
+    DeviceExecCapabilities,
+    CommandQueueProperties,
+    DeviceAffinityDomain,
+    DeviceSVMCapabilities,
+    DeviceAtomicCapabilities,
+    DeviceDeviceEnqueueCapabilities,
+    MemFlags,
+}
+
+impl InfoValue for MemCacheType {
+    fn decode(bytes: Vec<u8>, fn_name: &'static str) -> APIResult<Self> {
+        scalar::<cl_uint>(&bytes, fn_name).map(MemCacheType::from)
+    }
+    fn plain(&self) -> Option<Plain> {
+        type T = DeviceMemCacheType;
+        let value = match *self {
+            MemCacheType::None => T::NONE,
+            MemCacheType::ReadOnly => T::READ_ONLY_CACHE,
+            MemCacheType::ReadWrite => T::READ_WRITE_CACHE,
+            MemCacheType::InvalidType(x) => x,
+        };
+        Some(constant(T::name_of(value), value))
+    }
+}
+
+impl InfoValue for LocalMemType {
+    fn decode(bytes: Vec<u8>, fn_name: &'static str) -> APIResult<Self> {
+        scalar::<cl_uint>(&bytes, fn_name).map(LocalMemType::from)
+    }
+    fn plain(&self) -> Option<Plain> {
+        type T = DeviceLocalMemType;
+        let value = match *self {
+            LocalMemType::Local => T::LOCAL,
+            LocalMemType::Global => T::GLOBAL,
+            LocalMemType::InvalidType(x) => x,
+        };
+        Some(constant(T::name_of(value), value))
+    }
+}
+
+impl InfoValue for Vec<PartitionScheme> {
+    fn decode(bytes: Vec<u8>, fn_name: &'static str) -> APIResult<Self> {
+        Ok(array::<isize>(&bytes, fn_name)?
+            .into_iter()
+            .take_while(|&x| x != 0)
+            .map(PartitionScheme::from)
+            .collect())
+    }
+    fn plain(&self) -> Option<Plain> {
+        type P = DevicePartitionProperty;
+        let schemes = self.iter().map(|x| {
+            let value = match *x {
+                PartitionScheme::Equally => P::EQUALLY,
+                PartitionScheme::ByCounts => P::BY_COUNTS,
+                PartitionScheme::ByAffinityDomain => P::BY_AFFINITY_DOMAIN,
+                PartitionScheme::InvalidScheme(x) => x,
+            };
+            constant(P::name_of(value), value)
+        });
+        Some(Plain::List(schemes.collect()))
+    }
+}
+
+impl InfoValue for Option<DevicePartition> {
+    fn decode(bytes: Vec<u8>, fn_name: &'static str) -> APIResult<Self> {
+        Ok(DevicePartition::from_properties(&array(&bytes, fn_name)?))
+    }
+    /// The scheme by its OpenCL name followed by its arguments.
+    fn plain(&self) -> Option<Plain> {
+        type P = DevicePartitionProperty;
+        let scheme = |x| constant(P::name_of(x), x);
+        let partition = match self {
+            None => Vec::new(),
+            Some(DevicePartition::Equally(x)) => {
+                vec![scheme(P::EQUALLY), Plain::Integer((*x).into())]
+            }
+            Some(DevicePartition::ByCounts(x)) => std::iter::once(scheme(P::BY_COUNTS))
+                .chain(x.iter().map(|&x| Plain::Integer(x.into())))
+                .collect(),
+            Some(DevicePartition::ByAffinityDomain(x)) => {
+                let domain = DeviceAffinityDomain::name_of(*x);
+                vec![scheme(P::BY_AFFINITY_DOMAIN), constant(domain, *x)]
+            }
+            Some(DevicePartition::InvalidPartition(x)) => {
+                x.iter().map(|&x| constant(None, x)).collect()
+            }
+        };
+        Some(Plain::List(partition))
+    }
+}
+
+impl InfoValue for ImageFormat {
+    fn decode(bytes: Vec<u8>, fn_name: &'static str) -> APIResult<Self> {
+        ImageFormat::from_raw(&scalar(&bytes, fn_name)?).map_err(ToLibraryError::to_error)
+    }
+    fn plain(&self) -> Option<Plain> {
+        let order = constant(ChannelOrder::name_of(self.order()), self.order());
+        let data_type = constant(ChannelType::name_of(self.data_type()), self.data_type());
+        Some(Plain::List(vec![order, data_type]))
+    }
+}
+
+impl InfoValue for AddressingMode {
+    fn decode(bytes: Vec<u8>, fn_name: &'static str) -> APIResult<Self> {
+        AddressingMode::new(scalar(&bytes, fn_name)?).map_err(ToLibraryError::to_error)
+    }
+    fn plain(&self) -> Option<Plain> {
+        Some(constant(AddressingMode::name_of(self.get()), self.get()))
+    }
+}
+
+impl InfoValue for FilterMode {
+    fn decode(bytes: Vec<u8>, fn_name: &'static str) -> APIResult<Self> {
+        FilterMode::new(scalar(&bytes, fn_name)?).map_err(ToLibraryError::to_error)
+    }
+    fn plain(&self) -> Option<Plain> {
+        Some(constant(FilterMode::name_of(self.get()), self.get()))
+    }
+}
+
+macro_rules! gen_info_params {
+    ($kind:ident: $consts:ident {
+        $($(#[$attr:meta])* $param:ident: $typ:ty = $name:ident @ ($major:literal, $minor:literal),)*
+    }) => {
+        $(
+            $(#[$attr])*
+            #[derive(Clone, Copy, Debug)]
+            pub struct $param;
+            $(#[$attr])*
+            impl InfoParam for $param {
+                type Kind = super::$kind;
+                type Value = $typ;
+                const NAME: cl_uint = $consts::$name;
+                const VERSION: (cl_uint, cl_uint) = ($major, $minor);
+            }
+        )*
+    };
+}
+
+pub mod platform {
+    use super::*;
+    use crate::objects::structs::PlatformInfo;
+    gen_info_params! {
+        Platform: PlatformInfo {
+            Profile: String = PROFILE @ (1, 0),
+            Version: String = VERSION @ (1, 0),
+            Name: String = NAME @ (1, 0),
+            Vendor: String = VENDOR @ (1, 0),
+            Extensions: String = EXTENSIONS @ (1, 0),
+            HostTimerResolution: cl_ulong = HOST_TIMER_RESOLUTION @ (2, 1),
+            NumericVersion: cl_version = NUMERIC_VERSION @ (3, 0),
+            ExtensionsWithVersion: Vec<cl_name_version> = EXTENSIONS_WITH_VERSION @ (3, 0),
+        }
+    }
+}
+
+pub mod device {
+    use super::*;
+    use crate::objects::enums;
+    use crate::objects::structs::DeviceInfo;
+    gen_info_params! {
+        Device: DeviceInfo {
+            Type: DeviceType = TYPE @ (1, 0),
+            VendorId: cl_uint = VENDOR_ID @ (1, 0),
+            MaxComputeUnits: cl_uint = MAX_COMPUTE_UNITS @ (1, 0),
+            MaxWorkItemDimensions: cl_uint = MAX_WORK_ITEM_DIMENSIONS @ (1, 0),
+            MaxWorkGroupSize: size_t = MAX_WORK_GROUP_SIZE @ (1, 0),
+            MaxWorkItemSizes: Vec<size_t> = MAX_WORK_ITEM_SIZES @ (1, 0),
+            PreferredVectorWidthChar: cl_uint = PREFERRED_VECTOR_WIDTH_CHAR @ (1, 0),
+            PreferredVectorWidthShort: cl_uint = PREFERRED_VECTOR_WIDTH_SHORT @ (1, 0),
+            PreferredVectorWidthInt: cl_uint = PREFERRED_VECTOR_WIDTH_INT @ (1, 0),
+            PreferredVectorWidthLong: cl_uint = PREFERRED_VECTOR_WIDTH_LONG @ (1, 0),
+            PreferredVectorWidthFloat: cl_uint = PREFERRED_VECTOR_WIDTH_FLOAT @ (1, 0),
+            PreferredVectorWidthDouble: cl_uint = PREFERRED_VECTOR_WIDTH_DOUBLE @ (1, 0),
+            PreferredVectorWidthHalf: cl_uint = PREFERRED_VECTOR_WIDTH_HALF @ (1, 1),
+            NativeVectorWidthChar: cl_uint = NATIVE_VECTOR_WIDTH_CHAR @ (1, 1),
+            NativeVectorWidthShort: cl_uint = NATIVE_VECTOR_WIDTH_SHORT @ (1, 1),
+            NativeVectorWidthInt: cl_uint = NATIVE_VECTOR_WIDTH_INT @ (1, 1),
+            NativeVectorWidthLong: cl_uint = NATIVE_VECTOR_WIDTH_LONG @ (1, 1),
+            NativeVectorWidthFloat: cl_uint = NATIVE_VECTOR_WIDTH_FLOAT @ (1, 1),
+            NativeVectorWidthDouble: cl_uint = NATIVE_VECTOR_WIDTH_DOUBLE @ (1, 1),
+            NativeVectorWidthHalf: cl_uint = NATIVE_VECTOR_WIDTH_HALF @ (1, 1),
+            MaxClockFrequency: cl_uint = MAX_CLOCK_FREQUENCY @ (1, 0),
+            AddressBits: cl_uint = ADDRESS_BITS @ (1, 0),
+            MaxMemAllocSize: cl_ulong = MAX_MEM_ALLOC_SIZE @ (1, 0),
+            ImageSupport: bool = IMAGE_SUPPORT @ (1, 0),
+            MaxReadImageArgs: cl_uint = MAX_READ_IMAGE_ARGS @ (1, 0),
+            MaxWriteImageArgs: cl_uint = MAX_WRITE_IMAGE_ARGS @ (1, 0),
+            MaxReadWriteImageArgs: cl_uint = MAX_READ_WRITE_IMAGE_ARGS @ (2, 0),
+            Image2DMaxWidth: size_t = IMAGE2D_MAX_WIDTH @ (1, 0),
+            Image2DMaxHeight: size_t = IMAGE2D_MAX_HEIGHT @ (1, 0),
+            Image3DMaxWidth: size_t = IMAGE3D_MAX_WIDTH @ (1, 0),
+            Image3DMaxHeight: size_t = IMAGE3D_MAX_HEIGHT @ (1, 0),
+            Image3DMaxDepth: size_t = IMAGE3D_MAX_DEPTH @ (1, 0),
+            ImageMaxBufferSize: size_t = IMAGE_MAX_BUFFER_SIZE @ (1, 2),
+            ImageMaxArraySize: size_t = IMAGE_MAX_ARRAY_SIZE @ (1, 2),
+            MaxSamplers: cl_uint = MAX_SAMPLERS @ (1, 0),
+            ImagePitchAlignment: cl_uint = IMAGE_PITCH_ALIGNMENT @ (2, 0),
+            ImageBaseAddressAlignment: cl_uint = IMAGE_BASE_ADDRESS_ALIGNMENT @ (2, 0),
+            MaxPipeArgs: cl_uint = MAX_PIPE_ARGS @ (2, 0),
+            PipeMaxActiveReservations: cl_uint = PIPE_MAX_ACTIVE_RESERVATIONS @ (2, 0),
+            PipeMaxPacketSize: cl_uint = PIPE_MAX_PACKET_SIZE @ (2, 0),
+            MaxParameterSize: size_t = MAX_PARAMETER_SIZE @ (1, 0),
+            MemBaseAddrAlign: cl_uint = MEM_BASE_ADDR_ALIGN @ (1, 0),
+            MinDataTypeAlignSize: cl_uint = MIN_DATA_TYPE_ALIGN_SIZE @ (1, 0),
+            SingleFPConfig: DeviceFPConfig = SINGLE_FP_CONFIG @ (1, 0),
+            DoubleFPConfig: Option<DeviceFPConfig> = DOUBLE_FP_CONFIG @ (1, 2),
+            GlobalMemCacheType: MemCacheType = GLOBAL_MEM_CACHE_TYPE @ (1, 0),
+            GlobalMemCachelineSize: cl_uint = GLOBAL_MEM_CACHELINE_SIZE @ (1, 0),
+            GlobalMemCacheSize: cl_ulong = GLOBAL_MEM_CACHE_SIZE @ (1, 0),
+            GlobalMemSize: cl_ulong = GLOBAL_MEM_SIZE @ (1, 0),
+            MaxConstantBufferSize: cl_ulong = MAX_CONSTANT_BUFFER_SIZE @ (1, 0),
+            MaxConstantArgs: cl_uint = MAX_CONSTANT_ARGS @ (1, 0),
+            MaxGlobalVariableSize: size_t = MAX_GLOBAL_VARIABLE_SIZE @ (2, 0),
+            GlobalVariablePreferredTotalSize: size_t = GLOBAL_VARIABLE_PREFERRED_TOTAL_SIZE @ (2, 0),
+            LocalMemType: enums::LocalMemType = LOCAL_MEM_TYPE @ (1, 0),
+            LocalMemSize: cl_ulong = LOCAL_MEM_SIZE @ (1, 0),
+            ErrorCorrectionSupport: bool = ERROR_CORRECTION_SUPPORT @ (1, 0),
+            HostUnifiedMemory: bool = HOST_UNIFIED_MEMORY @ (1, 1),
+            ProfilingTimerResolution: size_t = PROFILING_TIMER_RESOLUTION @ (1, 0),
+            EndianLittle: bool = ENDIAN_LITTLE @ (1, 0),
+            Available: bool = AVAILABLE @ (1, 0),
+            CompilerAvailable: bool = COMPILER_AVAILABLE @ (1, 0),
+            LinkerAvailable: bool = LINKER_AVAILABLE @ (1, 2),
+            ExecutionCapabilities: DeviceExecCapabilities = EXECUTION_CAPABILITIES @ (1, 0),
+            QueueOnHostProperties: CommandQueueProperties = QUEUE_ON_HOST_PROPERTIES @ (1, 0),
+            QueueOnDeviceProperties: Option<CommandQueueProperties> = QUEUE_ON_DEVICE_PROPERTIES @ (2, 0),
+            QueueOnDevicePreferredSize: cl_uint = QUEUE_ON_DEVICE_PREFERRED_SIZE @ (2, 0),
+            QueueOnDeviceMaxSize: cl_uint = QUEUE_ON_DEVICE_MAX_SIZE @ (2, 0),
+            MaxOnDeviceQueues: cl_uint = MAX_ON_DEVICE_QUEUES @ (2, 0),
+            MaxOnDeviceEvents: cl_uint = MAX_ON_DEVICE_EVENTS @ (2, 0),
+            BuiltInKernels: String = BUILT_IN_KERNELS @ (1, 2),
+            Platform: cl_platform_id = PLATFORM @ (1, 0),
+            Name: String = NAME @ (1, 0),
+            Vendor: String = VENDOR @ (1, 0),
+            DriverVersion: String = DRIVER_VERSION @ (1, 0),
+            Profile: String = PROFILE @ (1, 0),
+            Version: String = VERSION @ (1, 0),
+            OpenCLCVersion: String = OPENCL_C_VERSION @ (1, 1),
+            Extensions: String = EXTENSIONS @ (1, 0),
+            PrintfBufferSize: size_t = PRINTF_BUFFER_SIZE @ (1, 2),
+            PreferredInteropUserSync: bool = PREFERRED_INTEROP_USER_SYNC @ (1, 2),
+            ParentDevice: Option<cl_device_id> = PARENT_DEVICE @ (1, 2),
+            PartitionMaxSubDevices: cl_uint = PARTITION_MAX_SUB_DEVICES @ (1, 2),
+            PartitionProperties: Vec<PartitionScheme> = PARTITION_PROPERTIES @ (1, 2),
+            PartitionAffinityDomain: Option<DeviceAffinityDomain> = PARTITION_AFFINITY_DOMAIN @ (1, 2),
+            PartitionType: Option<DevicePartition> = PARTITION_TYPE @ (1, 2),
+            ReferenceCount: cl_uint = REFERENCE_COUNT @ (1, 2),
+            SVMCapabilities: Option<DeviceSVMCapabilities> = SVM_CAPABILITIES @ (2, 0),
+            PreferredPlatformAtomicAlignment: cl_uint = PREFERRED_PLATFORM_ATOMIC_ALIGNMENT @ (2, 0),
+            PreferredGlobalAtomicAlignment: cl_uint = PREFERRED_GLOBAL_ATOMIC_ALIGNMENT @ (2, 0),
+            PreferredLocalAtomicAlignment: cl_uint = PREFERRED_LOCAL_ATOMIC_ALIGNMENT @ (2, 0),
+            ILVersion: String = IL_VERSION @ (2, 1),
+            MaxNumSubGroups: cl_uint = MAX_NUM_SUB_GROUPS @ (2, 1),
+            SubGroupIndependentForwardProgress: bool = SUB_GROUP_INDEPENDENT_FORWARD_PROGRESS @ (2, 1),
+            NumericVersion: cl_version = NUMERIC_VERSION @ (3, 0),
+            ExtensionsWithVersion: Vec<cl_name_version> = EXTENSIONS_WITH_VERSION @ (3, 0),
+            ILsWithVersion: Vec<cl_name_version> = ILS_WITH_VERSION @ (3, 0),
+            BuiltInKernelsWithVersion: Vec<cl_name_version> = BUILT_IN_KERNELS_WITH_VERSION @ (3, 0),
+            AtomicMemoryCapabilities: Option<DeviceAtomicCapabilities> = ATOMIC_MEMORY_CAPABILITIES @ (3, 0),
+            AtomicFenceCapabilities: Option<DeviceAtomicCapabilities> = ATOMIC_FENCE_CAPABILITIES @ (3, 0),
+            NonUniformWorkGroupSupport: bool = NON_UNIFORM_WORK_GROUP_SUPPORT @ (3, 0),
+            OpenCLCAllVersions: Vec<cl_name_version> = OPENCL_C_ALL_VERSIONS @ (3, 0),
+            PreferredWorkGroupSizeMultiple: size_t = PREFERRED_WORK_GROUP_SIZE_MULTIPLE @ (3, 0),
+            WorkGroupCollectiveFunctionsSupport: bool = WORK_GROUP_COLLECTIVE_FUNCTIONS_SUPPORT @ (3, 0),
+            GenericAddressSpaceSupport: bool = GENERIC_ADDRESS_SPACE_SUPPORT @ (3, 0),
+            OpenCLCFeatures: Vec<cl_name_version> = OPENCL_C_FEATURES @ (3, 0),
+            DeviceEnqueueCapabilities: Option<DeviceDeviceEnqueueCapabilities> = DEVICE_ENQUEUE_CAPABILITIES @ (3, 0),
+            PipeSupport: bool = PIPE_SUPPORT @ (3, 0),
+            LatestConformanceVersionPassed: String = LATEST_CONFORMANCE_VERSION_PASSED @ (3, 0),
+        }
+    }
+}
+
+pub mod context {
+    use super::*;
+    use crate::objects::structs::ContextInfo;
+    gen_info_params! {
+        Context: ContextInfo {
+            ReferenceCount: cl_uint = REFERENCE_COUNT @ (1, 0),
+            Devices: Vec<cl_device_id> = DEVICES @ (1, 0),
+            Properties: Vec<cl_context_properties> = PROPERTIES @ (1, 0),
+            NumDevices: cl_uint = NUM_DEVICES @ (1, 1),
+        }
+    }
+}
+
+pub mod queue {
+    use super::*;
+    use crate::objects::structs::CommandQueueInfo;
+    gen_info_params! {
+        CommandQueue: CommandQueueInfo {
+            Context: cl_context = CONTEXT @ (1, 0),
+            Device: cl_device_id = DEVICE @ (1, 0),
+            ReferenceCount: cl_uint = REFERENCE_COUNT @ (1, 0),
+            Properties: Option<CommandQueueProperties> = PROPERTIES @ (1, 0),
+            #[cfg(feature = "cl_2_0")]
+            Size: cl_uint = SIZE @ (2, 0),
+            #[cfg(feature = "cl_2_1")]
+            DeviceDefault: Option<cl_command_queue> = DEVICE_DEFAULT @ (2, 1),
+            PropertiesArray: Vec<cl_queue_properties> = PROPERTIES_ARRAY @ (3, 0),
+        }
+    }
+}
+
+pub mod mem {
+    use super::*;
+    use crate::objects::structs::MemInfo;
+    gen_info_params! {
+        Mem: MemInfo {
+            Type: cl_mem_object_type = TYPE @ (1, 0),
+            Flags: MemFlags = FLAGS @ (1, 0),
+            Size: size_t = SIZE @ (1, 0),
+            HostPtr: Option<*mut c_void> = HOST_PTR @ (1, 0),
+            MapCount: cl_uint = MAP_COUNT @ (1, 0),
+            ReferenceCount: cl_uint = REFERENCE_COUNT @ (1, 0),
+            Context: cl_context = CONTEXT @ (1, 0),
+            AssociatedMemobject: Option<cl_mem> = ASSOCIATED_MEMOBJECT @ (1, 1),
+            Offset: size_t = OFFSET @ (1, 1),
+            UsesSVMPointer: bool = USES_SVM_POINTER @ (2, 0),
+            Properties: Vec<cl_mem_properties> = PROPERTIES @ (3, 0),
+        }
+    }
+}
+
+pub mod image {
+    use super::*;
+    use crate::objects::structs::ImageInfo;
+    gen_info_params! {
+        Image: ImageInfo {
+            Format: ImageFormat = FORMAT @ (1, 0),
+            ElementSize: size_t = ELEMENT_SIZE @ (1, 0),
+            RowPitch: size_t = ROW_PITCH @ (1, 0),
+            SlicePitch: size_t = SLICE_PITCH @ (1, 0),
+            Width: size_t = WIDTH @ (1, 0),
+            Height: size_t = HEIGHT @ (1, 0),
+            Depth: size_t = DEPTH @ (1, 0),
+            ArraySize: size_t = ARRAY_SIZE @ (1, 2),
+            Buffer: Option<cl_mem> = BUFFER @ (1, 2),
+            NumMipLevels: cl_uint = NUM_MIP_LEVELS @ (1, 2),
+            NumSamples: cl_uint = NUM_SAMPLES @ (1, 2),
+        }
+    }
+}
+
+pub mod pipe {
+    use super::*;
+    use crate::objects::structs::PipeInfo;
+    gen_info_params! {
+        Pipe: PipeInfo {
+            PacketSize: cl_uint = PACKET_SIZE @ (2, 0),
+            MaxPackets: cl_uint = MAX_PACKETS @ (2, 0),
+            Properties: Vec<cl_pipe_properties> = PROPERTIES @ (3, 0),
+        }
+    }
+}
+
+pub mod sampler {
+    use super::*;
+    use crate::objects::structs::SamplerInfo;
+    gen_info_params! {
+        Sampler: SamplerInfo {
+            ReferenceCount: cl_uint = REFERENCE_COUNT @ (1, 0),
+            Context: cl_context = CONTEXT @ (1, 0),
+            NormalizedCoords: bool = NORMALIZED_COORDS @ (1, 0),
+            AddressingMode: super::AddressingMode = ADDRESSING_MODE @ (1, 0),
+            FilterMode: super::FilterMode = FILTER_MODE @ (1, 0),
+            Properties: Vec<cl_sampler_properties> = PROPERTIES @ (3, 0),
+        }
+    }
+}
+
+/************************/
+/* /\ /\ /\ /\ /\ /\ /\ */
+/*|__|__|__|__|__|__|__|*/
+/*|  |  |  |  |  |  |  |*/
+/*|  |  Unit Tests  |  |*/
+/*|__|__|__|__|__|__|__|*/
+/*|__|__|__|__|__|__|__|*/
+/************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::buffer::create_buffer;
+    use crate::api::context::{create_context, release_context};
+    use crate::api::device::get_device_ids;
+    use crate::api::memory::release_mem_object;
+    use crate::api::platform::get_platform_ids;
+    use crate::objects::types::{DevicePtr, PlatformPtr, WrapMutPtr};
+
+    struct Unknown;
+    impl InfoParam for Unknown {
+        type Kind = Device;
+        type Value = cl_uint;
+        const NAME: cl_uint = 0xdead;
+        const VERSION: (cl_uint, cl_uint) = (1, 0);
+    }
+
+    struct Future;
+    impl InfoParam for Future {
+        type Kind = Device;
+        type Value = cl_uint;
+        const NAME: cl_uint = 0xdead;
+        const VERSION: (cl_uint, cl_uint) = (9, 1);
+    }
+
+    #[test]
+    fn test_query() {
+        let platform_ids = get_platform_ids().unwrap();
+        let platform_id = PlatformPtr::from_ptr(platform_ids[0], "test_fn").unwrap();
+        let platform = unsafe { Handle::new(&platform_id) };
+        let platform_name = query::<platform::Name>(&platform).unwrap();
+        assert_ne!(platform_name, "");
+
+        let device_ids =
+            get_device_ids(&platform_id, DeviceType::new(DeviceType::ALL).unwrap()).unwrap();
+        let device_id = DevicePtr::from_ptr(device_ids[0], "test_fn").unwrap();
+        let device_id = unsafe { Handle::new(&device_id) };
+        let dimensions = query::<device::MaxWorkItemDimensions>(&device_id).unwrap();
+        let sizes = query::<device::MaxWorkItemSizes>(&device_id).unwrap();
+        assert_eq!(sizes.len(), dimensions as usize);
+        assert_eq!(
+            query::<device::Platform>(&device_id).unwrap(),
+            platform_ids[0]
+        );
+        assert_eq!(query::<device::ParentDevice>(&device_id).unwrap(), None);
+        assert_eq!(<device::OpenCLCFeatures as InfoParam>::VERSION, (3, 0));
+
+        let context = create_context(&None, device_ids.clone(), None, WrapMutPtr::null());
+        let context = context.unwrap();
+        let handle = unsafe { Handle::new(&context) };
+        let devices = query::<context::Devices>(&handle).unwrap();
+        assert_eq!(devices, device_ids);
+        let num_devices = query::<context::NumDevices>(&handle).unwrap();
+        assert_eq!(num_devices as usize, device_ids.len());
+
+        let flags = MemFlags::new(MemFlags::READ_WRITE).unwrap();
+        let buffer = create_buffer(&context, flags, 64, WrapMutPtr::null()).unwrap();
+        let handle = unsafe { Handle::new(&buffer) };
+        assert_eq!(query::<mem::Size>(&handle).unwrap(), 64);
+        let flags = query::<mem::Flags>(&handle).unwrap();
+        assert_eq!(flags.get(), MemFlags::READ_WRITE);
+        assert_eq!(query::<mem::HostPtr>(&handle).unwrap(), None);
+        let buffer_context = query::<mem::Context>(&handle).unwrap();
+        assert_eq!(buffer_context, context.unwrap());
+
+        release_mem_object(buffer).unwrap();
+        release_context(context).unwrap();
+    }
+
+    #[test]
+    fn test_query_unsupported_param() {
+        let platform_ids = get_platform_ids().unwrap();
+        let platform_id = PlatformPtr::from_ptr(platform_ids[0], "test_fn").unwrap();
+        let device_ids =
+            get_device_ids(&platform_id, DeviceType::new(DeviceType::ALL).unwrap()).unwrap();
+        let device_id = DevicePtr::from_ptr(device_ids[0], "test_fn").unwrap();
+        assert_eq!(
+            query::<Unknown>(&unsafe { Handle::new(&device_id) }).unwrap_err(),
+            ValidationError::UnsupportedParam("clGetDeviceInfo", 0xdead).to_error()
+        );
+        assert_eq!(
+            query::<Future>(&unsafe { Handle::new(&device_id) }).unwrap_err(),
+            ValidationError::UnsupportedVersion("clGetDeviceInfo", 0xdead, 9, 1).to_error()
+        );
+    }
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("OpenCL 3.0 Mock"), Some((3, 0)));
+        assert_eq!(parse_version("OpenCL 1.2"), Some((1, 2)));
+        assert_eq!(parse_version("OpenCL C 1.2"), None);
+        assert_eq!(parse_version("3.0"), None);
+    }
+}
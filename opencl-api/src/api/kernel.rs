@@ -0,0 +1,472 @@
+/*
+ * kernel.rs - Kernel object API wrappers (Part of OpenCL Runtime Layer).
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//!
+//! A kernel is a function declared in a program. A kernel is identified by
+//! the __kernel qualifier applied to any function in a program. A kernel
+//! object encapsulates the specific __kernel function declared in a program
+//! and the argument values to be used when executing this __kernel function.
+//!
+use crate::errors::{ToLibraryError, ValidationError};
+use crate::ffi;
+use crate::ffi::{
+    clGetKernelArgInfo, clGetKernelInfo, clGetKernelSubGroupInfo, clGetKernelWorkGroupInfo,
+};
+use crate::objects::bitfields::KernelArgTypeQualifier;
+use crate::objects::enums::{AccessQualifier, AddressQualifier, ParamValue, Size};
+use crate::objects::functions::{bytes_into_string, status_update, str_into_cstring};
+use crate::objects::structs::{
+    KernelArgInfo, KernelInfo, KernelSubGroupInfo, KernelWorkGroupInfo, StatusCode,
+};
+use crate::objects::traits::{GetSetGo, Pod};
+use crate::objects::types::{
+    APIResult, DevicePtr, KernelList, KernelPtr, MemPtr, ProgramPtr, SVMPtr, SamplerPtr,
+};
+use crate::{gen_param_value, size_getter};
+use libc::c_void;
+use opencl_heads::types::*;
+use std::mem;
+use std::ptr;
+
+pub fn create_kernel(program: &ProgramPtr, kernel_name: &str) -> APIResult<KernelPtr> {
+    let fn_name = "clCreateKernel";
+    let mut status_code = StatusCode::INVALID_VALUE;
+    let kernel_name = str_into_cstring(kernel_name, fn_name)?;
+    let kernel = unsafe {
+        ffi::clCreateKernel(
+            program.unwrap(),
+            kernel_name.as_ptr() as *const c_char,
+            &mut status_code,
+        )
+    };
+    status_update(status_code, fn_name, kernel).and_then(|x| KernelPtr::from_ptr(x, fn_name))
+}
+
+/// Creates kernel objects for all kernel functions in a program object.
+pub fn create_kernels_in_program(program: &ProgramPtr) -> APIResult<KernelList> {
+    let fn_name = "clCreateKernelsInProgram";
+    let program = program.unwrap();
+    let mut num_kernels: cl_uint = 0;
+    let status_code =
+        unsafe { ffi::clCreateKernelsInProgram(program, 0, ptr::null_mut(), &mut num_kernels) };
+    status_update(status_code, fn_name, ())?;
+    let mut kernels: KernelList = std::vec::from_elem(ptr::null_mut(), num_kernels as usize);
+    let status_code = unsafe {
+        ffi::clCreateKernelsInProgram(program, num_kernels, kernels.as_mut_ptr(), ptr::null_mut())
+    };
+    status_update(status_code, fn_name, kernels)
+}
+
+/// Makes a shallow copy of the kernel object, its arguments and any
+/// information passed to the kernel object using set_kernel_exec_info.
+pub fn clone_kernel(source_kernel: &KernelPtr) -> APIResult<KernelPtr> {
+    let fn_name = "clCloneKernel";
+    let mut status_code = StatusCode::INVALID_VALUE;
+    let kernel = unsafe { ffi::clCloneKernel(source_kernel.unwrap(), &mut status_code) };
+    status_update(status_code, fn_name, kernel).and_then(|x| KernelPtr::from_ptr(x, fn_name))
+}
+
+pub fn retain_kernel(kernel: &KernelPtr) -> APIResult<()> {
+    let fn_name = "clRetainKernel";
+    let status_code = unsafe { ffi::clRetainKernel(kernel.unwrap()) };
+    status_update(status_code, fn_name, ())
+}
+
+pub fn release_kernel(kernel: KernelPtr) -> APIResult<()> {
+    let fn_name = "clReleaseKernel";
+    let status_code = unsafe { ffi::clReleaseKernel(kernel.unwrap()) };
+    status_update(status_code, fn_name, ())
+}
+
+/// Sets a sized value (scalar, vector or struct declared in the kernel) as
+/// the argument at arg_index, the argument size is taken from T. Structs
+/// must implement `Pod` to be passed.
+pub fn set_kernel_arg<T: Pod>(
+    kernel: &KernelPtr,
+    arg_index: cl_uint,
+    arg_value: &T,
+) -> APIResult<()> {
+    set_kernel_arg_value(kernel, arg_index, arg_value)
+}
+
+// Handles are not Pod, but are passed by value like one.
+fn set_kernel_arg_value<T>(kernel: &KernelPtr, arg_index: cl_uint, arg_value: &T) -> APIResult<()> {
+    let fn_name = "clSetKernelArg";
+    let status_code = unsafe {
+        ffi::clSetKernelArg(
+            kernel.unwrap(),
+            arg_index,
+            mem::size_of::<T>(),
+            arg_value as *const T as *const c_void,
+        )
+    };
+    status_update(status_code, fn_name, ())
+}
+
+/// Sets a buffer, image or pipe object as the argument at arg_index.
+pub fn set_kernel_arg_mem(
+    kernel: &KernelPtr,
+    arg_index: cl_uint,
+    memobj: &MemPtr,
+) -> APIResult<()> {
+    set_kernel_arg_value::<cl_mem>(kernel, arg_index, &memobj.unwrap())
+}
+
+/// Sets a sampler object as the argument at arg_index.
+pub fn set_kernel_arg_sampler(
+    kernel: &KernelPtr,
+    arg_index: cl_uint,
+    sampler: &SamplerPtr,
+) -> APIResult<()> {
+    set_kernel_arg_value::<cl_sampler>(kernel, arg_index, &sampler.unwrap())
+}
+
+/// Allocates local_size bytes of local memory for the __local argument at
+/// arg_index.
+pub fn set_kernel_arg_local(
+    kernel: &KernelPtr,
+    arg_index: cl_uint,
+    local_size: size_t,
+) -> APIResult<()> {
+    let fn_name = "clSetKernelArg";
+    let status_code =
+        unsafe { ffi::clSetKernelArg(kernel.unwrap(), arg_index, local_size, ptr::null()) };
+    status_update(status_code, fn_name, ())
+}
+
+/// * arg_value is a pointer into (or the start of) an SVM allocation.
+pub fn set_kernel_arg_svm_pointer(
+    kernel: &KernelPtr,
+    arg_index: cl_uint,
+    arg_value: &SVMPtr,
+) -> APIResult<()> {
+    let fn_name = "clSetKernelArgSVMPointer";
+    let status_code = unsafe {
+        ffi::clSetKernelArgSVMPointer(
+            kernel.unwrap(),
+            arg_index,
+            arg_value.unwrap() as *const c_void,
+        )
+    };
+    status_update(status_code, fn_name, ())
+}
+
+/// * param_value is the list of values for param_name, i.e. a list of SVM
+/// pointers for EXEC_INFO_SVM_PTRS or a single cl_bool for
+/// EXEC_INFO_SVM_FINE_GRAIN_SYSTEM.
+pub fn set_kernel_exec_info<T>(
+    kernel: &KernelPtr,
+    param_name: cl_kernel_exec_info,
+    param_value: &[T],
+) -> APIResult<()> {
+    let fn_name = "clSetKernelExecInfo";
+    let status_code = unsafe {
+        ffi::clSetKernelExecInfo(
+            kernel.unwrap(),
+            param_name,
+            mem::size_of_val(param_value),
+            param_value.as_ptr() as *const c_void,
+        )
+    };
+    status_update(status_code, fn_name, ())
+}
+
+pub fn get_kernel_info(kernel: &KernelPtr, param_name: cl_kernel_info) -> APIResult<ParamValue> {
+    type K = KernelInfo;
+    let fn_name = "clGetKernelInfo";
+    let kernel = kernel.unwrap();
+    size_getter!(get_kernel_info_size, clGetKernelInfo);
+    match param_name {
+        K::NUM_ARGS | K::REFERENCE_COUNT => {
+            let param_value = gen_param_value!(clGetKernelInfo, u32, kernel, param_name);
+            Ok(ParamValue::UInt(param_value))
+        }
+        K::CONTEXT | K::PROGRAM => {
+            let param_value = gen_param_value!(clGetKernelInfo, isize, kernel, param_name);
+            Ok(ParamValue::CPtr(param_value))
+        }
+        K::FUNCTION_NAME | K::ATTRIBUTES => {
+            let size = get_kernel_info_size(kernel, param_name)?;
+            let param_value = gen_param_value!(clGetKernelInfo, u8, kernel, param_name, size);
+            Ok(ParamValue::String(bytes_into_string(param_value)?))
+        }
+        _ => Err(ValidationError::UnsupportedParam(fn_name, param_name).to_error()),
+    }
+}
+
+/// Argument information is only available if the program was built with
+/// the `-cl-kernel-arg-info` option or created with a binary containing it.
+pub fn get_kernel_arg_info(
+    kernel: &KernelPtr,
+    arg_index: cl_uint,
+    param_name: cl_kernel_arg_info,
+) -> APIResult<ParamValue> {
+    type K = KernelArgInfo;
+    let fn_name = "clGetKernelArgInfo";
+    let kernel = kernel.unwrap();
+    size_getter!(get_kernel_arg_info_size, clGetKernelArgInfo, arg_index);
+    match param_name {
+        K::ADDRESS_QUALIFIER => {
+            let param_value =
+                gen_param_value!(clGetKernelArgInfo, u32, [kernel, arg_index], param_name);
+            Ok(ParamValue::AddressQualifier(AddressQualifier::from(
+                param_value,
+            )))
+        }
+        K::ACCESS_QUALIFIER => {
+            let param_value =
+                gen_param_value!(clGetKernelArgInfo, u32, [kernel, arg_index], param_name);
+            Ok(ParamValue::AccessQualifier(AccessQualifier::from(
+                param_value,
+            )))
+        }
+        K::TYPE_QUALIFIER => {
+            let param_value =
+                gen_param_value!(clGetKernelArgInfo, u64, [kernel, arg_index], param_name);
+            match KernelArgTypeQualifier::new(param_value) {
+                Ok(x) => Ok(ParamValue::TypeQualifier(x)),
+                Err(x) => Err(x.to_error()),
+            }
+        }
+        K::TYPE_NAME | K::NAME => {
+            let size = get_kernel_arg_info_size(kernel, param_name)?;
+            let param_value = gen_param_value!(
+                clGetKernelArgInfo,
+                u8,
+                [kernel, arg_index],
+                param_name,
+                size
+            );
+            Ok(ParamValue::String(bytes_into_string(param_value)?))
+        }
+        _ => Err(ValidationError::UnsupportedParam(fn_name, param_name).to_error()),
+    }
+}
+
+pub fn get_kernel_work_group_info(
+    kernel: &KernelPtr,
+    device: &DevicePtr,
+    param_name: cl_kernel_work_group_info,
+) -> APIResult<ParamValue> {
+    type K = KernelWorkGroupInfo;
+    let fn_name = "clGetKernelWorkGroupInfo";
+    let kernel = kernel.unwrap();
+    let device = device.unwrap();
+    size_getter!(
+        get_kernel_work_group_info_size,
+        clGetKernelWorkGroupInfo,
+        device
+    );
+    match param_name {
+        K::WORK_GROUP_SIZE | K::PREFERRED_WORK_GROUP_SIZE_MULTIPLE => {
+            let param_value = gen_param_value!(
+                clGetKernelWorkGroupInfo,
+                usize,
+                [kernel, device],
+                param_name
+            );
+            Ok(ParamValue::CSize(param_value))
+        }
+        K::LOCAL_MEM_SIZE | K::PRIVATE_MEM_SIZE => {
+            let param_value =
+                gen_param_value!(clGetKernelWorkGroupInfo, u64, [kernel, device], param_name);
+            Ok(ParamValue::ULong(param_value))
+        }
+        K::COMPILE_WORK_GROUP_SIZE | K::GLOBAL_WORK_SIZE => {
+            let size = get_kernel_work_group_info_size(kernel, param_name)?;
+            let param_value = gen_param_value!(
+                clGetKernelWorkGroupInfo,
+                usize,
+                [kernel, device],
+                param_name,
+                size
+            );
+            Ok(ParamValue::ArrCSize(param_value))
+        }
+        _ => Err(ValidationError::UnsupportedParam(fn_name, param_name).to_error()),
+    }
+}
+
+/// * input_value is the local work size for MAX_SUB_GROUP_SIZE_FOR_NDRANGE
+/// and SUB_GROUP_COUNT_FOR_NDRANGE, or the sub-group count (a single value)
+/// for LOCAL_SIZE_FOR_SUB_GROUP_COUNT. It is ignored for the other queries.
+pub fn get_kernel_sub_group_info(
+    kernel: &KernelPtr,
+    device: &DevicePtr,
+    param_name: cl_kernel_sub_group_info,
+    input_value: &[size_t],
+) -> APIResult<ParamValue> {
+    type K = KernelSubGroupInfo;
+    let fn_name = "clGetKernelSubGroupInfo";
+    let kernel = kernel.unwrap();
+    let device = device.unwrap();
+    let input_value_size = input_value.len() * Size::usize.get();
+    let input_value = match input_value.len() {
+        0 => ptr::null(),
+        _ => input_value.as_ptr() as *const c_void,
+    };
+    let get_sub_group_info = |size: size_t, param_value: *mut c_void| -> APIResult<size_t> {
+        let mut size_ret: size_t = 0;
+        let status_code = unsafe {
+            clGetKernelSubGroupInfo(
+                kernel,
+                device,
+                param_name,
+                input_value_size,
+                input_value,
+                size,
+                param_value,
+                &mut size_ret,
+            )
+        };
+        status_update(status_code, fn_name, size_ret)
+    };
+    match param_name {
+        K::MAX_SUB_GROUP_SIZE_FOR_NDRANGE
+        | K::SUB_GROUP_COUNT_FOR_NDRANGE
+        | K::MAX_NUM_SUB_GROUPS
+        | K::COMPILE_NUM_SUB_GROUPS => {
+            let mut param_value: size_t = 0;
+            get_sub_group_info(
+                Size::usize.get(),
+                &mut param_value as *mut size_t as *mut c_void,
+            )?;
+            Ok(ParamValue::CSize(param_value))
+        }
+        K::LOCAL_SIZE_FOR_SUB_GROUP_COUNT => {
+            let size = get_sub_group_info(0, ptr::null_mut())?;
+            let mut param_value: Vec<size_t> = vec![0; size / Size::usize.get()];
+            get_sub_group_info(size, param_value.as_mut_ptr() as *mut c_void)?;
+            Ok(ParamValue::ArrCSize(param_value))
+        }
+        _ => Err(ValidationError::UnsupportedParam(fn_name, param_name).to_error()),
+    }
+}
+
+/************************/
+/* /\ /\ /\ /\ /\ /\ /\ */
+/*|__|__|__|__|__|__|__|*/
+/*|  |  |  |  |  |  |  |*/
+/*|  |  Unit Tests  |  |*/
+/*|__|__|__|__|__|__|__|*/
+/*|__|__|__|__|__|__|__|*/
+/************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::buffer::create_buffer;
+    use crate::api::context::{create_context, release_context};
+    use crate::api::device::get_device_ids;
+    use crate::api::memory::release_mem_object;
+    use crate::api::platform::get_platform_ids;
+    use crate::api::program::{build_program, create_program_with_source, release_program};
+    use crate::objects::bitfields::{DeviceType, MemFlags};
+    use crate::objects::types::{PlatformPtr, WrapMutPtr};
+
+    const SOURCE: &str = "__kernel void scale(__global float* a, const float b, __local float* c) {
+        int i = get_global_id(0);
+        a[i] *= b;
+    }";
+
+    #[test]
+    fn test_set_kernel_arg() {
+        let platform_ids = get_platform_ids().unwrap();
+        // Choose the first platform
+        let platform_id = PlatformPtr::from_ptr(platform_ids[0], "test_fn").unwrap();
+
+        let device_ids =
+            get_device_ids(&platform_id, DeviceType::new(DeviceType::DEFAULT).unwrap()).unwrap();
+        assert!(0 < device_ids.len());
+
+        let context = create_context(&None, device_ids.clone(), None, WrapMutPtr::null());
+        let context = context.unwrap();
+
+        let program = create_program_with_source(&context, &[SOURCE]).unwrap();
+        build_program(&program, device_ids, None, None, WrapMutPtr::null()).unwrap();
+        let kernel = create_kernel(&program, "scale").unwrap();
+
+        let flags = MemFlags::new(MemFlags::READ_WRITE).unwrap();
+        let buffer = create_buffer(&context, flags, 1024 * 4, WrapMutPtr::null()).unwrap();
+        set_kernel_arg_mem(&kernel, 0, &buffer).unwrap();
+        set_kernel_arg(&kernel, 1, &2.0f32).unwrap();
+        set_kernel_arg_local(&kernel, 2, 64 * 4).unwrap();
+
+        release_mem_object(buffer).unwrap();
+        release_kernel(kernel).unwrap();
+        release_program(program).unwrap();
+        release_context(context).unwrap();
+    }
+
+    #[test]
+    fn test_get_kernel_info() {
+        let platform_ids = get_platform_ids().unwrap();
+        // Choose the first platform
+        let platform_id = PlatformPtr::from_ptr(platform_ids[0], "test_fn").unwrap();
+
+        let device_ids =
+            get_device_ids(&platform_id, DeviceType::new(DeviceType::DEFAULT).unwrap()).unwrap();
+        assert!(0 < device_ids.len());
+        let device_id = DevicePtr::from_ptr(device_ids[0], "test_fn").unwrap();
+
+        let context = create_context(&None, device_ids.clone(), None, WrapMutPtr::null());
+        let context = context.unwrap();
+
+        let program = create_program_with_source(&context, &[SOURCE]).unwrap();
+        let options = Some("-cl-kernel-arg-info");
+        build_program(&program, device_ids, options, None, WrapMutPtr::null()).unwrap();
+        let kernels = create_kernels_in_program(&program).unwrap();
+        assert_eq!(1, kernels.len());
+        let kernel = KernelPtr::from_ptr(kernels[0], "test_fn").unwrap();
+
+        let function_name = get_kernel_info(&kernel, KernelInfo::FUNCTION_NAME).unwrap();
+        assert_eq!(Some(String::from("scale")), function_name.unwrap_string());
+        let num_args = get_kernel_info(&kernel, KernelInfo::NUM_ARGS).unwrap();
+        assert_eq!(Some(3), num_args.unwrap_uint());
+
+        let address = get_kernel_arg_info(&kernel, 0, KernelArgInfo::ADDRESS_QUALIFIER).unwrap();
+        assert_eq!(
+            Some(AddressQualifier::Global),
+            address.unwrap_address_qualifier()
+        );
+        let address = get_kernel_arg_info(&kernel, 2, KernelArgInfo::ADDRESS_QUALIFIER).unwrap();
+        assert_eq!(
+            Some(AddressQualifier::Local),
+            address.unwrap_address_qualifier()
+        );
+        let access = get_kernel_arg_info(&kernel, 0, KernelArgInfo::ACCESS_QUALIFIER).unwrap();
+        assert_eq!(
+            Some(AccessQualifier::None),
+            access.unwrap_access_qualifier()
+        );
+        let type_qualifier =
+            get_kernel_arg_info(&kernel, 1, KernelArgInfo::TYPE_QUALIFIER).unwrap();
+        let type_qualifier = type_qualifier.unwrap_type_qualifier().unwrap();
+        assert_eq!(KernelArgTypeQualifier::CONST, type_qualifier.get());
+        let arg_name = get_kernel_arg_info(&kernel, 1, KernelArgInfo::NAME).unwrap();
+        assert_eq!(Some(String::from("b")), arg_name.unwrap_string());
+
+        let work_group_size =
+            get_kernel_work_group_info(&kernel, &device_id, KernelWorkGroupInfo::WORK_GROUP_SIZE)
+                .unwrap();
+        assert!(0 < work_group_size.unwrap_csize().unwrap());
+
+        release_kernel(kernel).unwrap();
+        release_program(program).unwrap();
+        release_context(context).unwrap();
+    }
+}
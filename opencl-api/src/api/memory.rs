@@ -20,6 +20,9 @@
 //! It also eliminates the need to marshal data between the host and devices.
 //! As a result, SVM substantially simplifies OpenCL programming and may improve performance.
 //!
+use crate::errors::{ToLibraryError, ValidationError};
+use crate::ffi;
+use crate::ffi::clGetMemObjectInfo;
 use crate::objects::bitfields::{MapFlags, MemFlags, MemMigrationFlags};
 use crate::objects::enums::{ParamValue, Size};
 use crate::objects::functions::status_update;
@@ -29,8 +32,6 @@ use crate::objects::types::{APIResult, ContextPtr, EventPtr, MemPtr, QueuePtr, S
 use crate::objects::wrappers::{WrappedMutablePointer, WrappedPointer};
 use crate::{gen_param_value, size_getter};
 use libc::c_void;
-use opencl_heads::ffi;
-use opencl_heads::ffi::clGetMemObjectInfo;
 use opencl_heads::types::*;
 use std::ptr;
 
@@ -74,11 +75,7 @@ pub fn enqueue_unmap_mem_object(
             &mut event_ptr,
         )
     };
-    status_update(
-        status_code,
-        fn_name,
-        EventPtr::from_ptr(event_ptr, fn_name)?,
-    )
+    status_update(status_code, fn_name, event_ptr).and_then(|x| EventPtr::from_ptr(x, fn_name))
 }
 
 pub fn enqueue_migrate_mem_objects(
@@ -102,11 +99,7 @@ pub fn enqueue_migrate_mem_objects(
             &mut event_ptr,
         )
     };
-    status_update(
-        status_code,
-        fn_name,
-        EventPtr::from_ptr(event_ptr, fn_name)?,
-    )
+    status_update(status_code, fn_name, event_ptr).and_then(|x| EventPtr::from_ptr(x, fn_name))
 }
 
 pub fn get_mem_object_info(memobj: MemPtr, param_name: cl_mem_info) -> APIResult<ParamValue> {
@@ -137,7 +130,7 @@ pub fn get_mem_object_info(memobj: MemPtr, param_name: cl_mem_info) -> APIResult
             let param_value = gen_param_value!(clGetMemObjectInfo, u64, memobj, param_name, size);
             Ok(ParamValue::ArrULong(param_value))
         }
-        _ => status_update(40404, "clGetMemObjectInfo", ParamValue::default()),
+        _ => Err(ValidationError::UnsupportedParam("clGetMemObjectInfo", param_name).to_error()),
     }
 }
 
@@ -193,11 +186,7 @@ pub fn enqueue_svm_free(
             &mut event_ptr,
         )
     };
-    status_update(
-        status_code,
-        fn_name,
-        EventPtr::from_ptr(event_ptr, fn_name)?,
-    )
+    status_update(status_code, fn_name, event_ptr).and_then(|x| EventPtr::from_ptr(x, fn_name))
 }
 
 pub fn enqueue_svm_memcpy(
@@ -223,11 +212,7 @@ pub fn enqueue_svm_memcpy(
             &mut event_ptr,
         )
     };
-    status_update(
-        status_code,
-        fn_name,
-        EventPtr::from_ptr(event_ptr, fn_name)?,
-    )
+    status_update(status_code, fn_name, event_ptr).and_then(|x| EventPtr::from_ptr(x, fn_name))
 }
 
 pub fn enqueue_svm_memfill(
@@ -253,11 +238,7 @@ pub fn enqueue_svm_memfill(
             &mut event_ptr,
         )
     };
-    status_update(
-        status_code,
-        fn_name,
-        EventPtr::from_ptr(event_ptr, fn_name)?,
-    )
+    status_update(status_code, fn_name, event_ptr).and_then(|x| EventPtr::from_ptr(x, fn_name))
 }
 
 pub fn enqueue_svm_map(
@@ -283,11 +264,7 @@ pub fn enqueue_svm_map(
             &mut event_ptr,
         )
     };
-    status_update(
-        status_code,
-        fn_name,
-        EventPtr::from_ptr(event_ptr, fn_name)?,
-    )
+    status_update(status_code, fn_name, event_ptr).and_then(|x| EventPtr::from_ptr(x, fn_name))
 }
 
 pub fn enqueue_svm_unmap(
@@ -307,11 +284,7 @@ pub fn enqueue_svm_unmap(
             &mut event_ptr,
         )
     };
-    status_update(
-        status_code,
-        fn_name,
-        EventPtr::from_ptr(event_ptr, fn_name)?,
-    )
+    status_update(status_code, fn_name, event_ptr).and_then(|x| EventPtr::from_ptr(x, fn_name))
 }
 
 pub fn enqueue_svm_migrate_mem(
@@ -337,11 +310,7 @@ pub fn enqueue_svm_migrate_mem(
             &mut event_ptr,
         )
     };
-    status_update(
-        status_code,
-        fn_name,
-        EventPtr::from_ptr(event_ptr, fn_name)?,
-    )
+    status_update(status_code, fn_name, event_ptr).and_then(|x| EventPtr::from_ptr(x, fn_name))
 }
 
 // TODO: Add unit tests for this file.
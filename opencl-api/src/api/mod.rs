@@ -18,9 +18,13 @@
 pub mod buffer;
 pub mod context;
 pub mod device;
+pub mod event;
 pub mod image;
+pub mod info;
+pub mod kernel;
 pub mod memory;
 pub mod pipe;
 pub mod platform;
+pub mod program;
 pub mod queue;
 pub mod sampler;
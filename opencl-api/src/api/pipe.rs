@@ -25,6 +25,9 @@
 //! - Information about the number of packets currently in the pipe
 //! - Data packets
 //!
+use crate::errors::{ToLibraryError, ValidationError};
+use crate::ffi;
+use crate::ffi::clGetPipeInfo;
 use crate::objects::bitfields::MemFlags;
 use crate::objects::enums::{ParamValue, Size};
 use crate::objects::functions::status_update;
@@ -33,8 +36,6 @@ use crate::objects::traits::GetSetGo;
 use crate::objects::types::{APIResult, ContextPtr, MemPtr};
 use crate::{gen_param_value, size_getter};
 use libc::c_void;
-use opencl_heads::ffi;
-use opencl_heads::ffi::clGetPipeInfo;
 use opencl_heads::types::*;
 use std::ptr;
 
@@ -57,7 +58,7 @@ pub fn create_pipe(
             &mut status_code,
         )
     };
-    status_update(status_code, fn_name, MemPtr::from_ptr(mem_ptr, fn_name)?)
+    status_update(status_code, fn_name, mem_ptr).and_then(|x| MemPtr::from_ptr(x, fn_name))
 }
 
 pub fn get_pipe_info(pipe: &MemPtr, param_name: cl_pipe_info) -> APIResult<ParamValue> {
@@ -73,7 +74,7 @@ pub fn get_pipe_info(pipe: &MemPtr, param_name: cl_pipe_info) -> APIResult<Param
             let param_value = gen_param_value!(clGetPipeInfo, isize, pipe, param_name, size);
             Ok(ParamValue::ArrCPtr(param_value))
         }
-        _ => status_update(40404, "clGetPipeInfo", ParamValue::default()),
+        _ => Err(ValidationError::UnsupportedParam("clGetPipeInfo", param_name).to_error()),
     }
 }
 
@@ -18,13 +18,15 @@
 //! The platform layer allows the host program to discover OpenCL devices
 //! and their capabilities and to create contexts.
 //!
+use crate::errors::{ToLibraryError, ValidationError};
+use crate::ffi::*;
 use crate::objects::enums::{ParamValue, Size};
 use crate::objects::functions::{bytes_into_string, status_update};
 use crate::objects::structs::PlatformInfo;
 use crate::objects::types::{APIResult, PlatformList, PlatformPtr};
-use crate::{gen_object_list, gen_param_value, get_count, size_getter};
+use crate::{gen_details, gen_object_list, gen_param_value, get_count, size_getter};
 use libc::c_void;
-use opencl_heads::ffi::*;
+use opencl_heads::types::*;
 use std::ptr;
 
 /// Returns the list of all platforms available
@@ -84,7 +86,21 @@ pub fn get_platform_info(
             );
             Ok(ParamValue::NameVersion(param_value))
         }
-        _ => status_update(40404, fn_name, ParamValue::default()),
+        _ => Err(ValidationError::UnsupportedParam(fn_name, param_name).to_error()),
+    }
+}
+
+gen_details! {
+    /// Every platform info of a platform, decoded to its Rust type.
+    PlatformDetails: Platform, platform {
+        profile: Profile,
+        version: Version,
+        name: Name,
+        vendor: Vendor,
+        extensions: Extensions,
+        host_timer_resolution: HostTimerResolution,
+        numeric_version: NumericVersion,
+        extensions_with_version: ExtensionsWithVersion,
     }
 }
 
@@ -100,6 +116,7 @@ pub fn get_platform_info(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::api::info::Handle;
     #[test]
     // #[ignore]
     fn test_get_platform_ids() {
@@ -134,6 +151,18 @@ mod tests {
         assert_ne!(extensions.unwrap_string().unwrap(), "");
     }
 
+    #[test]
+    fn test_platform_details() {
+        let all_platforms = get_platform_ids().unwrap();
+        let id = PlatformPtr::from_ptr(all_platforms[0], "test_fn").unwrap();
+
+        let details = PlatformDetails::query(&unsafe { Handle::new(&id) });
+        assert_ne!(details.name.unwrap(), "");
+        let version = details.version.unwrap();
+        assert!(version.starts_with("OpenCL "));
+        assert_ne!(details.extensions.unwrap(), "");
+    }
+
     #[test]
     #[ignore]
     fn test_get_platform_info_fail() {
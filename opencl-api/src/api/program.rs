@@ -0,0 +1,488 @@
+/*
+ * program.rs - Program object API wrappers (Part of OpenCL Runtime Layer).
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//!
+//! An OpenCL program consists of a set of kernels that are identified as
+//! functions declared with the __kernel qualifier in the program source.
+//! OpenCL programs may also contain auxiliary functions and constant data
+//! that can be used by __kernel functions. The program executable can be
+//! generated online or offline by the OpenCL compiler for the appropriate
+//! target device(s).
+//!
+use crate::errors::{ToLibraryError, ValidationError};
+use crate::ffi;
+use crate::ffi::{clGetProgramBuildInfo, clGetProgramInfo};
+use crate::objects::enums::{ParamValue, Size};
+use crate::objects::functions::{bytes_into_string, status_update, str_into_cstring};
+use crate::objects::structs::{ProgramBuildInfo, ProgramInfo, StatusCode};
+use crate::objects::types::{
+    APIResult, ContextPtr, DeviceList, DevicePtr, ProgramList, ProgramPtr,
+};
+use crate::objects::wrappers::WrappedMutablePointer;
+use crate::{gen_param_value, size_getter};
+use libc::c_void;
+use opencl_heads::types::*;
+use std::ptr;
+
+/// * context must be a valid OpenCL context.
+/// * strings is a list of source code fragments which together make up the
+/// program source. The strings need not be null terminated, their lengths
+/// are passed along to the native api.
+pub fn create_program_with_source(context: &ContextPtr, strings: &[&str]) -> APIResult<ProgramPtr> {
+    let fn_name = "clCreateProgramWithSource";
+    let mut status_code = StatusCode::INVALID_VALUE;
+    let lengths: Vec<size_t> = strings.iter().map(|x| x.len()).collect();
+    let strings: Vec<*const c_char> = strings
+        .iter()
+        .map(|x| x.as_ptr() as *const c_char)
+        .collect();
+    let program = unsafe {
+        ffi::clCreateProgramWithSource(
+            context.unwrap(),
+            strings.len() as cl_uint,
+            strings.as_ptr(),
+            lengths.as_ptr(),
+            &mut status_code,
+        )
+    };
+    status_update(status_code, fn_name, program).and_then(|x| ProgramPtr::from_ptr(x, fn_name))
+}
+
+/// * devices and binaries must be of the same length, binaries[i] is loaded
+/// for devices[i].
+///
+/// Returns the program together with the per device binary load status.
+pub fn create_program_with_binary(
+    context: &ContextPtr,
+    devices: DeviceList,
+    binaries: &[&[u8]],
+) -> APIResult<(ProgramPtr, Vec<cl_int>)> {
+    let fn_name = "clCreateProgramWithBinary";
+    if devices.len() != binaries.len() {
+        return status_update(
+            StatusCode::INVALID_VALUE,
+            fn_name,
+            (ProgramPtr::null(), Vec::default()),
+        );
+    }
+    let mut status_code = StatusCode::INVALID_VALUE;
+    let mut binary_status: Vec<cl_int> = vec![StatusCode::SUCCESS; devices.len()];
+    let lengths: Vec<size_t> = binaries.iter().map(|x| x.len()).collect();
+    let binaries: Vec<*const c_uchar> = binaries.iter().map(|x| x.as_ptr()).collect();
+    let program = unsafe {
+        ffi::clCreateProgramWithBinary(
+            context.unwrap(),
+            devices.len() as cl_uint,
+            devices.as_ptr(),
+            lengths.as_ptr(),
+            binaries.as_ptr(),
+            binary_status.as_mut_ptr(),
+            &mut status_code,
+        )
+    };
+    let program = status_update(status_code, fn_name, program)?;
+    Ok((ProgramPtr::from_ptr(program, fn_name)?, binary_status))
+}
+
+/// * kernel_names is a semi-colon separated list of built-in kernel names.
+pub fn create_program_with_built_in_kernels(
+    context: &ContextPtr,
+    devices: DeviceList,
+    kernel_names: &str,
+) -> APIResult<ProgramPtr> {
+    let fn_name = "clCreateProgramWithBuiltInKernels";
+    let mut status_code = StatusCode::INVALID_VALUE;
+    let kernel_names = str_into_cstring(kernel_names, fn_name)?;
+    let program = unsafe {
+        ffi::clCreateProgramWithBuiltInKernels(
+            context.unwrap(),
+            devices.len() as cl_uint,
+            devices.as_ptr(),
+            kernel_names.as_ptr() as *const c_char,
+            &mut status_code,
+        )
+    };
+    status_update(status_code, fn_name, program).and_then(|x| ProgramPtr::from_ptr(x, fn_name))
+}
+
+/// * il is the intermediate language program, e.g. a SPIR-V module.
+pub fn create_program_with_il(context: &ContextPtr, il: &[u8]) -> APIResult<ProgramPtr> {
+    let fn_name = "clCreateProgramWithIL";
+    let mut status_code = StatusCode::INVALID_VALUE;
+    let program = unsafe {
+        ffi::clCreateProgramWithIL(
+            context.unwrap(),
+            il.as_ptr() as *const c_void,
+            il.len(),
+            &mut status_code,
+        )
+    };
+    status_update(status_code, fn_name, program).and_then(|x| ProgramPtr::from_ptr(x, fn_name))
+}
+
+pub fn retain_program(program: &ProgramPtr) -> APIResult<()> {
+    let fn_name = "clRetainProgram";
+    let status_code = unsafe { ffi::clRetainProgram(program.unwrap()) };
+    status_update(status_code, fn_name, ())
+}
+
+pub fn release_program(program: ProgramPtr) -> APIResult<()> {
+    let fn_name = "clReleaseProgram";
+    let status_code = unsafe { ffi::clReleaseProgram(program.unwrap()) };
+    status_update(status_code, fn_name, ())
+}
+
+/// * devices may be empty, in which case the program is built for all the
+/// devices associated with the program.
+/// * options is the build options string, None uses the default options.
+pub fn build_program(
+    program: &ProgramPtr,
+    devices: DeviceList,
+    options: Option<&str>,
+    pfn_notify: Option<extern "C" fn(cl_program, *mut c_void)>,
+    user_data: WrappedMutablePointer<c_void>,
+) -> APIResult<()> {
+    let fn_name = "clBuildProgram";
+    let options = match options {
+        Some(x) => Some(str_into_cstring(x, fn_name)?),
+        None => None,
+    };
+    let options = match &options {
+        Some(x) => x.as_ptr() as *const c_char,
+        None => ptr::null(),
+    };
+    let device_list = match devices.len() {
+        0 => ptr::null(),
+        _ => devices.as_ptr(),
+    };
+    let status_code = unsafe {
+        ffi::clBuildProgram(
+            program.unwrap(),
+            devices.len() as cl_uint,
+            device_list,
+            options,
+            pfn_notify,
+            user_data.unwrap(),
+        )
+    };
+    status_update(status_code, fn_name, ())
+}
+
+/// * input_headers and header_include_names must be of the same length,
+/// header_include_names[i] is the include name of input_headers[i].
+pub fn compile_program(
+    program: &ProgramPtr,
+    devices: DeviceList,
+    options: Option<&str>,
+    input_headers: ProgramList,
+    header_include_names: &[&str],
+    pfn_notify: Option<extern "C" fn(cl_program, *mut c_void)>,
+    user_data: WrappedMutablePointer<c_void>,
+) -> APIResult<()> {
+    let fn_name = "clCompileProgram";
+    if input_headers.len() != header_include_names.len() {
+        return status_update(StatusCode::INVALID_VALUE, fn_name, ());
+    }
+    let options = match options {
+        Some(x) => Some(str_into_cstring(x, fn_name)?),
+        None => None,
+    };
+    let options = match &options {
+        Some(x) => x.as_ptr() as *const c_char,
+        None => ptr::null(),
+    };
+    let device_list = match devices.len() {
+        0 => ptr::null(),
+        _ => devices.as_ptr(),
+    };
+    let mut include_names = Vec::with_capacity(header_include_names.len());
+    for name in header_include_names {
+        include_names.push(str_into_cstring(name, fn_name)?);
+    }
+    let include_name_ptrs: Vec<*const c_char> = include_names
+        .iter()
+        .map(|x| x.as_ptr() as *const c_char)
+        .collect();
+    let (input_headers_ptr, include_names_ptr) = match input_headers.len() {
+        0 => (ptr::null(), ptr::null()),
+        _ => (input_headers.as_ptr(), include_name_ptrs.as_ptr()),
+    };
+    let status_code = unsafe {
+        ffi::clCompileProgram(
+            program.unwrap(),
+            devices.len() as cl_uint,
+            device_list,
+            options,
+            input_headers.len() as cl_uint,
+            input_headers_ptr,
+            include_names_ptr,
+            pfn_notify,
+            user_data.unwrap(),
+        )
+    };
+    status_update(status_code, fn_name, ())
+}
+
+/// * input_programs is the list of compiled programs and libraries to be
+/// linked into a new program.
+pub fn link_program(
+    context: &ContextPtr,
+    devices: DeviceList,
+    options: Option<&str>,
+    input_programs: ProgramList,
+    pfn_notify: Option<extern "C" fn(cl_program, *mut c_void)>,
+    user_data: WrappedMutablePointer<c_void>,
+) -> APIResult<ProgramPtr> {
+    let fn_name = "clLinkProgram";
+    let mut status_code = StatusCode::INVALID_VALUE;
+    let options = match options {
+        Some(x) => Some(str_into_cstring(x, fn_name)?),
+        None => None,
+    };
+    let options = match &options {
+        Some(x) => x.as_ptr() as *const c_char,
+        None => ptr::null(),
+    };
+    let device_list = match devices.len() {
+        0 => ptr::null(),
+        _ => devices.as_ptr(),
+    };
+    let program = unsafe {
+        ffi::clLinkProgram(
+            context.unwrap(),
+            devices.len() as cl_uint,
+            device_list,
+            options,
+            input_programs.len() as cl_uint,
+            input_programs.as_ptr(),
+            pfn_notify,
+            user_data.unwrap(),
+            &mut status_code,
+        )
+    };
+    status_update(status_code, fn_name, program).and_then(|x| ProgramPtr::from_ptr(x, fn_name))
+}
+
+pub fn get_program_info(
+    program: &ProgramPtr,
+    param_name: cl_program_info,
+) -> APIResult<ParamValue> {
+    type P = ProgramInfo;
+    let fn_name = "clGetProgramInfo";
+    let program = program.unwrap();
+    size_getter!(get_program_info_size, clGetProgramInfo);
+    match param_name {
+        P::REFERENCE_COUNT
+        | P::NUM_DEVICES
+        | P::SCOPE_GLOBAL_CTORS_PRESENT
+        | P::SCOPE_GLOBAL_DTORS_PRESENT => {
+            let param_value = gen_param_value!(clGetProgramInfo, u32, program, param_name);
+            Ok(ParamValue::UInt(param_value))
+        }
+        P::CONTEXT => {
+            let param_value = gen_param_value!(clGetProgramInfo, isize, program, param_name);
+            Ok(ParamValue::CPtr(param_value))
+        }
+        P::NUM_KERNELS => {
+            let param_value = gen_param_value!(clGetProgramInfo, usize, program, param_name);
+            Ok(ParamValue::CSize(param_value))
+        }
+        P::DEVICES => {
+            let size = get_program_info_size(program, param_name)?;
+            let param_value = gen_param_value!(clGetProgramInfo, isize, program, param_name, size);
+            Ok(ParamValue::ArrCPtr(param_value))
+        }
+        P::BINARY_SIZES => {
+            let size = get_program_info_size(program, param_name)?;
+            let param_value = gen_param_value!(clGetProgramInfo, usize, program, param_name, size);
+            Ok(ParamValue::ArrCSize(param_value))
+        }
+        P::SOURCE | P::KERNEL_NAMES => {
+            let size = get_program_info_size(program, param_name)?;
+            let param_value = gen_param_value!(clGetProgramInfo, u8, program, param_name, size);
+            Ok(ParamValue::String(bytes_into_string(param_value)?))
+        }
+        P::IL => {
+            let size = get_program_info_size(program, param_name)?;
+            let param_value = gen_param_value!(clGetProgramInfo, u8, program, param_name, size);
+            Ok(ParamValue::ArrUChar(param_value))
+        }
+        P::BINARIES => {
+            // One binary per device, each sized as per BINARY_SIZES
+            let binary_sizes = P::BINARY_SIZES;
+            let size = get_program_info_size(program, binary_sizes)?;
+            let binary_sizes =
+                gen_param_value!(clGetProgramInfo, usize, program, binary_sizes, size);
+            let mut binaries: Vec<Vec<cl_uchar>> =
+                binary_sizes.iter().map(|&x| vec![0; x]).collect();
+            let mut binary_ptrs: Vec<*mut cl_uchar> = binaries
+                .iter_mut()
+                .map(|x| match x.len() {
+                    0 => ptr::null_mut(),
+                    _ => x.as_mut_ptr(),
+                })
+                .collect();
+            let status_code = unsafe {
+                clGetProgramInfo(
+                    program,
+                    param_name,
+                    binary_ptrs.len() * Size::usize.get(),
+                    binary_ptrs.as_mut_ptr() as *mut c_void,
+                    ptr::null_mut(),
+                )
+            };
+            Ok(ParamValue::Binaries(status_update(
+                status_code,
+                fn_name,
+                binaries,
+            )?))
+        }
+        _ => Err(ValidationError::UnsupportedParam(fn_name, param_name).to_error()),
+    }
+}
+
+pub fn get_program_build_info(
+    program: &ProgramPtr,
+    device: &DevicePtr,
+    param_name: cl_program_build_info,
+) -> APIResult<ParamValue> {
+    type P = ProgramBuildInfo;
+    let fn_name = "clGetProgramBuildInfo";
+    let program = program.unwrap();
+    let device = device.unwrap();
+    size_getter!(get_program_build_info_size, clGetProgramBuildInfo, device);
+    match param_name {
+        P::BUILD_STATUS => {
+            let param_value =
+                gen_param_value!(clGetProgramBuildInfo, i32, [program, device], param_name);
+            Ok(ParamValue::Int(param_value))
+        }
+        P::BINARY_TYPE => {
+            let param_value =
+                gen_param_value!(clGetProgramBuildInfo, u32, [program, device], param_name);
+            Ok(ParamValue::UInt(param_value))
+        }
+        P::BUILD_GLOBAL_VARIABLE_TOTAL_SIZE => {
+            let param_value =
+                gen_param_value!(clGetProgramBuildInfo, usize, [program, device], param_name);
+            Ok(ParamValue::CSize(param_value))
+        }
+        P::BUILD_OPTIONS | P::BUILD_LOG => {
+            let size = get_program_build_info_size(program, param_name)?;
+            let param_value = gen_param_value!(
+                clGetProgramBuildInfo,
+                u8,
+                [program, device],
+                param_name,
+                size
+            );
+            Ok(ParamValue::String(bytes_into_string(param_value)?))
+        }
+        _ => Err(ValidationError::UnsupportedParam(fn_name, param_name).to_error()),
+    }
+}
+
+/************************/
+/* /\ /\ /\ /\ /\ /\ /\ */
+/*|__|__|__|__|__|__|__|*/
+/*|  |  |  |  |  |  |  |*/
+/*|  |  Unit Tests  |  |*/
+/*|__|__|__|__|__|__|__|*/
+/*|__|__|__|__|__|__|__|*/
+/************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::context::{create_context, release_context};
+    use crate::api::device::get_device_ids;
+    use crate::api::platform::get_platform_ids;
+    use crate::objects::bitfields::DeviceType;
+    use crate::objects::structs::BuildStatus;
+    use crate::objects::traits::GetSetGo;
+    use crate::objects::types::{PlatformPtr, WrapMutPtr};
+
+    const SOURCE: &str = "__kernel void add(__global int* a, __global int* b) {
+        int i = get_global_id(0);
+        a[i] += b[i];
+    }";
+
+    #[test]
+    fn test_build_program() {
+        let platform_ids = get_platform_ids().unwrap();
+        // Choose the first platform
+        let platform_id = PlatformPtr::from_ptr(platform_ids[0], "test_fn").unwrap();
+
+        let device_ids =
+            get_device_ids(&platform_id, DeviceType::new(DeviceType::DEFAULT).unwrap()).unwrap();
+        assert!(0 < device_ids.len());
+        let device_id = DevicePtr::from_ptr(device_ids[0], "test_fn").unwrap();
+
+        let context = create_context(&None, device_ids.clone(), None, WrapMutPtr::null());
+        let context = context.unwrap();
+
+        let program = create_program_with_source(&context, &[SOURCE]).unwrap();
+        build_program(&program, device_ids, None, None, WrapMutPtr::null()).unwrap();
+
+        let build_status =
+            get_program_build_info(&program, &device_id, ProgramBuildInfo::BUILD_STATUS).unwrap();
+        assert_eq!(Some(BuildStatus::SUCCESS), build_status.unwrap_int());
+        let build_log =
+            get_program_build_info(&program, &device_id, ProgramBuildInfo::BUILD_LOG).unwrap();
+        assert!(build_log.unwrap_string().is_some());
+
+        release_program(program).unwrap();
+        release_context(context).unwrap();
+    }
+
+    #[test]
+    fn test_get_program_info() {
+        let platform_ids = get_platform_ids().unwrap();
+        // Choose the first platform
+        let platform_id = PlatformPtr::from_ptr(platform_ids[0], "test_fn").unwrap();
+
+        let device_ids =
+            get_device_ids(&platform_id, DeviceType::new(DeviceType::DEFAULT).unwrap()).unwrap();
+        assert!(0 < device_ids.len());
+
+        let context = create_context(&None, device_ids.clone(), None, WrapMutPtr::null());
+        let context = context.unwrap();
+
+        let program = create_program_with_source(&context, &[SOURCE]).unwrap();
+        let source = get_program_info(&program, ProgramInfo::SOURCE).unwrap();
+        assert_eq!(Some(String::from(SOURCE)), source.unwrap_string());
+
+        build_program(&program, device_ids.clone(), None, None, WrapMutPtr::null()).unwrap();
+        let num_devices = get_program_info(&program, ProgramInfo::NUM_DEVICES).unwrap();
+        assert_eq!(Some(device_ids.len() as cl_uint), num_devices.unwrap_uint());
+        let kernel_names = get_program_info(&program, ProgramInfo::KERNEL_NAMES).unwrap();
+        assert_eq!(Some(String::from("add")), kernel_names.unwrap_string());
+
+        let binary_sizes = get_program_info(&program, ProgramInfo::BINARY_SIZES).unwrap();
+        let binary_sizes = binary_sizes.unwrap_arr_csize().unwrap();
+        assert_eq!(device_ids.len(), binary_sizes.len());
+        let binaries = get_program_info(&program, ProgramInfo::BINARIES).unwrap();
+        let binaries = binaries.unwrap_binaries().unwrap();
+        assert_eq!(binary_sizes.len(), binaries.len());
+        for (size, binary) in binary_sizes.iter().zip(binaries.iter()) {
+            assert_eq!(*size, binary.len());
+        }
+
+        release_program(program).unwrap();
+        release_context(context).unwrap();
+    }
+}
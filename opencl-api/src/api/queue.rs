@@ -18,16 +18,20 @@
 //! Considering OpenCL 1.x as base.
 
 // use crate::enums::Status;
+use crate::errors::{ToLibraryError, ValidationError};
+use crate::ffi;
+use crate::ffi::clGetCommandQueueInfo;
 use crate::objects::bitfields::CommandQueueProperties;
 use crate::objects::enums::{ParamValue, Size};
 use crate::objects::functions::status_update;
 use crate::objects::structs::{CommandQueueInfo, StatusCode};
 use crate::objects::traits::GetSetGo;
-use crate::objects::types::{APIResult, ContextPtr, DevicePtr, LongProperties, QueuePtr};
+use crate::objects::types::{
+    APIResult, ContextPtr, DevicePtr, EventPtr, KernelPtr, LongProperties, QueuePtr,
+};
+use crate::objects::wrappers::{WrappedMutablePointer, WrappedPointer};
 use crate::{gen_param_value, size_getter};
 use libc::c_void;
-use opencl_heads::ffi;
-use opencl_heads::ffi::clGetCommandQueueInfo;
 use opencl_heads::types::*;
 use std::ptr;
 
@@ -47,11 +51,7 @@ pub fn create_command_queue(
             &mut status_code,
         )
     };
-    status_update(
-        status_code,
-        fn_name,
-        QueuePtr::from_ptr(queue_ptr, fn_name)?,
-    )
+    status_update(status_code, fn_name, queue_ptr).and_then(|x| QueuePtr::from_ptr(x, fn_name))
 }
 
 pub fn create_command_queue_with_properties(
@@ -73,11 +73,7 @@ pub fn create_command_queue_with_properties(
             &mut status_code,
         )
     };
-    status_update(
-        status_code,
-        fn_name,
-        QueuePtr::from_ptr(queue_ptr, fn_name)?,
-    )
+    status_update(status_code, fn_name, queue_ptr).and_then(|x| QueuePtr::from_ptr(x, fn_name))
 }
 
 pub fn set_default_device_command_queue(
@@ -134,7 +130,7 @@ pub fn get_command_queue_info(
                 gen_param_value!(clGetCommandQueueInfo, u64, command_queue, param_name, size);
             Ok(ParamValue::ArrULong(param_value))
         }
-        _ => status_update(40404, "clGetContextInfo", ParamValue::default()),
+        _ => Err(ValidationError::UnsupportedParam("clGetCommandQueueInfo", param_name).to_error()),
     }
 }
 
@@ -160,6 +156,176 @@ pub fn finish(command_queue: &QueuePtr) -> APIResult<()> {
     status_update(status_code, "clFinish", ())
 }
 
+/// * work_dim is the number of dimensions used to specify the global work-items
+/// and work-items in the work-group, global_work_size must hold exactly
+/// work_dim values.
+/// * global_work_offset and local_work_size are optional, when given they
+/// must also hold exactly work_dim values.
+pub fn enqueue_nd_range_kernel(
+    command_queue: &QueuePtr,
+    kernel: &KernelPtr,
+    work_dim: cl_uint,
+    global_work_offset: Option<&[size_t]>,
+    global_work_size: &[size_t],
+    local_work_size: Option<&[size_t]>,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: WrappedPointer<cl_event>,
+) -> APIResult<EventPtr> {
+    let fn_name = "clEnqueueNDRangeKernel";
+    let dims = work_dim as usize;
+    if dims == 0 {
+        return status_update(
+            StatusCode::INVALID_WORK_DIMENSION,
+            fn_name,
+            EventPtr::null(),
+        );
+    }
+    if global_work_size.len() != dims {
+        return status_update(
+            StatusCode::INVALID_GLOBAL_WORK_SIZE,
+            fn_name,
+            EventPtr::null(),
+        );
+    }
+    let global_work_offset = match global_work_offset {
+        Some(x) if x.len() != dims => {
+            return status_update(StatusCode::INVALID_GLOBAL_OFFSET, fn_name, EventPtr::null())
+        }
+        Some(x) => x.as_ptr(),
+        None => ptr::null(),
+    };
+    let local_work_size = match local_work_size {
+        Some(x) if x.len() != dims => {
+            return status_update(
+                StatusCode::INVALID_WORK_GROUP_SIZE,
+                fn_name,
+                EventPtr::null(),
+            )
+        }
+        Some(x) => x.as_ptr(),
+        None => ptr::null(),
+    };
+    let mut event = ptr::null_mut();
+    let status_code = unsafe {
+        ffi::clEnqueueNDRangeKernel(
+            command_queue.unwrap(),
+            kernel.unwrap(),
+            work_dim,
+            global_work_offset,
+            global_work_size.as_ptr(),
+            local_work_size,
+            num_events_in_wait_list,
+            event_wait_list.unwrap(),
+            &mut event,
+        )
+    };
+    status_update(status_code, fn_name, event).and_then(|x| EventPtr::from_ptr(x, fn_name))
+}
+
+/// Equivalent to enqueue_nd_range_kernel with work_dim 1, a global size of 1
+/// and a local size of 1.
+#[cfg(feature = "depr_2_0")]
+pub fn enqueue_task(
+    command_queue: &QueuePtr,
+    kernel: &KernelPtr,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: WrappedPointer<cl_event>,
+) -> APIResult<EventPtr> {
+    let fn_name = "clEnqueueTask";
+    let mut event = ptr::null_mut();
+    let status_code = unsafe {
+        ffi::clEnqueueTask(
+            command_queue.unwrap(),
+            kernel.unwrap(),
+            num_events_in_wait_list,
+            event_wait_list.unwrap(),
+            &mut event,
+        )
+    };
+    status_update(status_code, fn_name, event).and_then(|x| EventPtr::from_ptr(x, fn_name))
+}
+
+/// * args is the argument block of cb_args bytes passed to user_func, the
+/// native api copies it before returning.
+/// * mem_list and args_mem_loc must be of the same length, each memory object
+/// in mem_list replaces the pointer at args_mem_loc[i] within args by its
+/// global memory pointer on the device.
+pub fn enqueue_native_kernel(
+    command_queue: &QueuePtr,
+    user_func: Option<extern "C" fn(*mut c_void)>,
+    args: WrappedMutablePointer<c_void>,
+    cb_args: size_t,
+    mem_list: &[cl_mem],
+    args_mem_loc: &[*const c_void],
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: WrappedPointer<cl_event>,
+) -> APIResult<EventPtr> {
+    let fn_name = "clEnqueueNativeKernel";
+    if mem_list.len() != args_mem_loc.len() {
+        return status_update(StatusCode::INVALID_VALUE, fn_name, EventPtr::null());
+    }
+    let (mem_list_ptr, args_mem_loc_ptr) = match mem_list.len() {
+        0 => (ptr::null(), ptr::null()),
+        _ => (mem_list.as_ptr(), args_mem_loc.as_ptr()),
+    };
+    let mut event = ptr::null_mut();
+    let status_code = unsafe {
+        ffi::clEnqueueNativeKernel(
+            command_queue.unwrap(),
+            user_func,
+            args.unwrap(),
+            cb_args,
+            mem_list.len() as cl_uint,
+            mem_list_ptr,
+            args_mem_loc_ptr,
+            num_events_in_wait_list,
+            event_wait_list.unwrap(),
+            &mut event,
+        )
+    };
+    status_update(status_code, fn_name, event).and_then(|x| EventPtr::from_ptr(x, fn_name))
+}
+
+/// Enqueues a marker which waits on event_wait_list, or on all previously
+/// enqueued commands if the list is empty.
+pub fn enqueue_marker_with_wait_list(
+    command_queue: &QueuePtr,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: WrappedPointer<cl_event>,
+) -> APIResult<EventPtr> {
+    let fn_name = "clEnqueueMarkerWithWaitList";
+    let mut event = ptr::null_mut();
+    let status_code = unsafe {
+        ffi::clEnqueueMarkerWithWaitList(
+            command_queue.unwrap(),
+            num_events_in_wait_list,
+            event_wait_list.unwrap(),
+            &mut event,
+        )
+    };
+    status_update(status_code, fn_name, event).and_then(|x| EventPtr::from_ptr(x, fn_name))
+}
+
+/// Same as enqueue_marker_with_wait_list, but additionally blocks every
+/// command enqueued after it until the barrier completes.
+pub fn enqueue_barrier_with_wait_list(
+    command_queue: &QueuePtr,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: WrappedPointer<cl_event>,
+) -> APIResult<EventPtr> {
+    let fn_name = "clEnqueueBarrierWithWaitList";
+    let mut event = ptr::null_mut();
+    let status_code = unsafe {
+        ffi::clEnqueueBarrierWithWaitList(
+            command_queue.unwrap(),
+            num_events_in_wait_list,
+            event_wait_list.unwrap(),
+            &mut event,
+        )
+    };
+    status_update(status_code, fn_name, event).and_then(|x| EventPtr::from_ptr(x, fn_name))
+}
+
 /************************/
 /* /\ /\ /\ /\ /\ /\ /\ */
 /*|__|__|__|__|__|__|__|*/
@@ -172,10 +338,16 @@ pub fn finish(command_queue: &QueuePtr) -> APIResult<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::api::buffer::{create_buffer, enqueue_read_buffer};
     use crate::api::context::{create_context, release_context};
     use crate::api::device::get_device_ids;
+    use crate::api::event::{release_event, wait_for_events};
+    use crate::api::kernel::{create_kernel, release_kernel, set_kernel_arg, set_kernel_arg_mem};
+    use crate::api::memory::release_mem_object;
     use crate::api::platform::get_platform_ids;
-    use crate::objects::bitfields::{CommandQueueProperties, DeviceType};
+    use crate::api::program::{build_program, create_program_with_source, release_program};
+    use crate::errors::OpenCLAPIError;
+    use crate::objects::bitfields::{CommandQueueProperties, DeviceType, MemFlags};
     use crate::objects::property::{ContextProperties, QueueProperties};
     use crate::objects::structs::{CommandQueueInfo};
     use crate::objects::traits::GetSetGo;
@@ -290,4 +462,138 @@ mod tests {
 
         release_context(context).unwrap();
     }
+
+    #[test]
+    fn test_enqueue_nd_range_kernel() {
+        let platform_ids = get_platform_ids().unwrap();
+        // Choose the first platform
+        let platform_id = PlatformPtr::from_ptr(platform_ids[0], "test_fn").unwrap();
+
+        let device_ids =
+            get_device_ids(&platform_id, DeviceType::new(DeviceType::DEFAULT).unwrap()).unwrap();
+        assert!(0 < device_ids.len());
+        let device_id = DevicePtr::from_ptr(device_ids[0], "test_fn").unwrap();
+
+        let context = create_context(&None, device_ids.clone(), None, WrapMutPtr::null());
+        let context = context.unwrap();
+        let queue = create_command_queue_with_properties(&context, &device_id, &None).unwrap();
+
+        let source = "__kernel void fill(__global int* a, const int b) {
+            a[get_global_id(0)] = b;
+        }";
+        // The mock platform runs a Rust body in place of the source.
+        #[cfg(feature = "mock")]
+        opencl_heads::mock::register_kernel("fill", |call| {
+            let a = unsafe { call.buffer::<i32>(0) }.unwrap();
+            let b = call.scalar::<i32>(1).unwrap();
+            for id in call.global_ids() {
+                a[id[0]] = b;
+            }
+        });
+        let program = create_program_with_source(&context, &[source]).unwrap();
+        build_program(&program, device_ids, None, None, WrapMutPtr::null()).unwrap();
+        let kernel = create_kernel(&program, "fill").unwrap();
+
+        let mut data = vec![0i32; 64];
+        let size = data.len() * std::mem::size_of::<i32>();
+        let flags = MemFlags::new(MemFlags::READ_WRITE).unwrap();
+        let buffer = create_buffer(&context, flags, size, WrapMutPtr::null()).unwrap();
+        set_kernel_arg_mem(&kernel, 0, &buffer).unwrap();
+        set_kernel_arg(&kernel, 1, &7i32).unwrap();
+
+        let global_work_size = [data.len()];
+        let kernel_event = enqueue_nd_range_kernel(
+            &queue,
+            &kernel,
+            1,
+            None,
+            &global_work_size,
+            None,
+            0,
+            WrappedPointer::null(),
+        )
+        .unwrap();
+        let barrier_event =
+            enqueue_barrier_with_wait_list(&queue, 0, WrappedPointer::null()).unwrap();
+        let read_event = enqueue_read_buffer(
+            &queue,
+            &buffer,
+            1,
+            0,
+            size,
+            WrapMutPtr::from(&mut data[0]),
+            0,
+            WrappedPointer::null(),
+        )
+        .unwrap();
+        let marker_event =
+            enqueue_marker_with_wait_list(&queue, 0, WrappedPointer::null()).unwrap();
+        wait_for_events(&vec![marker_event.unwrap()]).unwrap();
+        assert!(data.iter().all(|&x| x == 7));
+
+        for event in vec![kernel_event, barrier_event, read_event, marker_event] {
+            release_event(event).unwrap();
+        }
+        release_mem_object(buffer).unwrap();
+        release_kernel(kernel).unwrap();
+        release_program(program).unwrap();
+        release_command_queue(queue).unwrap();
+        release_context(context).unwrap();
+    }
+
+    #[test]
+    fn test_enqueue_nd_range_kernel_work_dim() {
+        let queue = QueuePtr::null();
+        let kernel = KernelPtr::null();
+        let global_work_size = [64, 64];
+        let result = enqueue_nd_range_kernel(
+            &queue,
+            &kernel,
+            1,
+            None,
+            &global_work_size,
+            None,
+            0,
+            WrappedPointer::null(),
+        );
+        assert_eq!(
+            StatusCode::INVALID_GLOBAL_WORK_SIZE,
+            status_code_of(result.unwrap_err())
+        );
+        let result = enqueue_nd_range_kernel(
+            &queue,
+            &kernel,
+            2,
+            Some(&[0]),
+            &global_work_size,
+            None,
+            0,
+            WrappedPointer::null(),
+        );
+        assert_eq!(
+            StatusCode::INVALID_GLOBAL_OFFSET,
+            status_code_of(result.unwrap_err())
+        );
+        let result = enqueue_nd_range_kernel(
+            &queue,
+            &kernel,
+            2,
+            None,
+            &global_work_size,
+            Some(&[8, 8, 1]),
+            0,
+            WrappedPointer::null(),
+        );
+        assert_eq!(
+            StatusCode::INVALID_WORK_GROUP_SIZE,
+            status_code_of(result.unwrap_err())
+        );
+    }
+
+    fn status_code_of(error: OpenCLAPIError) -> cl_int {
+        match error {
+            OpenCLAPIError::StatusCodeError { int_code, .. } => int_code,
+            _ => 0,
+        }
+    }
 }
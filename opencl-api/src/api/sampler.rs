@@ -22,14 +22,15 @@
 //! OpenCL functions and passed as argument values to the kernel or can be samplers declared
 //! inside a kernel.
 //!
+use crate::errors::{ToLibraryError, ValidationError};
+use crate::ffi;
+use crate::ffi::clGetSamplerInfo;
 use crate::objects::enums::{ParamValue, Size};
 use crate::objects::functions::status_update;
 use crate::objects::structs::{SamplerInfo, StatusCode};
 use crate::objects::types::{APIResult, ContextPtr, LongProperties, SamplerPtr};
 use crate::{gen_param_value, size_getter};
 use libc::c_void;
-use opencl_heads::ffi;
-use opencl_heads::ffi::clGetSamplerInfo;
 use opencl_heads::types::*;
 use std::ptr;
 
@@ -52,11 +53,7 @@ pub fn create_sampler(
             &mut status_code,
         )
     };
-    status_update(
-        status_code,
-        fn_name,
-        SamplerPtr::from_ptr(sampler_ptr, fn_name)?,
-    )
+    status_update(status_code, fn_name, sampler_ptr).and_then(|x| SamplerPtr::from_ptr(x, fn_name))
 }
 
 /// * context must be a valid OpenCL context.
@@ -81,11 +78,7 @@ pub fn create_sampler_with_properties(
     let sampler_ptr = unsafe {
         ffi::clCreateSamplerWithProperties(context.unwrap(), sampler_properties, &mut status_code)
     };
-    status_update(
-        status_code,
-        fn_name,
-        SamplerPtr::from_ptr(sampler_ptr, fn_name)?,
-    )
+    status_update(status_code, fn_name, sampler_ptr).and_then(|x| SamplerPtr::from_ptr(x, fn_name))
 }
 
 pub fn retain_sampler(sampler: &SamplerPtr) -> APIResult<()> {
@@ -122,7 +115,7 @@ pub fn get_sampler_info(
             let param_value = gen_param_value!(clGetSamplerInfo, isize, sampler, param_name, size);
             Ok(ParamValue::ArrCPtr(param_value))
         }
-        _ => status_update(40404, fn_name, ParamValue::default()),
+        _ => Err(ValidationError::UnsupportedParam(fn_name, param_name).to_error()),
     }
 }
 
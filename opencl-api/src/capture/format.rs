@@ -0,0 +1,424 @@
+/*
+ * format.rs - Binary format of the captured traces.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+//!
+//! A trace starts with the magic `CLTRACE\0` and the version of the format
+//! as a u32, followed by the calls until the end of the file. Integers are
+//! little endian, strings and lists are prefixed by their length as a u32.
+//!
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// Version of the format written by this crate, the only one it reads.
+pub const VERSION: u32 = 1;
+const MAGIC: &[u8; 8] = b"CLTRACE\0";
+
+/// Argument of a captured call, as needed to pass it again.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Arg {
+    /// Integer, flags or enum, passed as is.
+    Scalar(u64),
+    /// Null pointer.
+    Null,
+    /// Object handle, remapped to the object created by the replay.
+    Handle(u64),
+    /// Array of object handles, remapped one by one.
+    Handles(Vec<u64>),
+    /// Data read by the call, e.g. the host memory of a write.
+    Bytes(Vec<u8>),
+    /// Array of pointer sized words read by the call, e.g. properties. The
+    /// words equal to a known handle are remapped.
+    Words(Vec<u64>),
+    /// Array of strings or binaries, without their NUL terminators.
+    Strings(Vec<Vec<u8>>),
+    /// Memory of len bytes written by the call, its contents are part of the
+    /// results if keep, i.e. once the call returns they are final.
+    Out { len: u64, keep: bool },
+    /// Array of count handles created by the call.
+    OutHandles(u64),
+    /// Callback, whether one was given.
+    Callback(bool),
+    /// Pointer opaque to the platform, e.g. `user_data`, replayed as null.
+    Opaque,
+    /// Pointer to memory of unknown size, the call can't be replayed.
+    Unsupported,
+}
+
+/// Result written by a call through one of its arguments.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Output {
+    Bytes(Vec<u8>),
+    Handles(Vec<u64>),
+}
+
+/// Call of an entry point and its results.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Call {
+    /// Name of the entry point, e.g. `clCreateBuffer`.
+    pub function: String,
+    /// Number of the thread the call was made from, see `current_thread`.
+    pub thread: u64,
+    pub args: Vec<(String, Arg)>,
+    /// Status reported by the call, if the entry point reports one.
+    pub status: Option<i32>,
+    /// Handle or pointer returned by the call, if any.
+    pub returned: Option<u64>,
+    /// Results written through the arguments, by argument name.
+    pub outputs: Vec<(String, Output)>,
+}
+
+impl Call {
+    /// Returns the argument named name.
+    pub fn arg(&self, name: &str) -> Option<&Arg> {
+        self.args.iter().find(|(x, _)| x == name).map(|(_, x)| x)
+    }
+
+    /// Returns the output written through the argument named name.
+    pub fn output(&self, name: &str) -> Option<&Output> {
+        self.outputs.iter().find(|(x, _)| x == name).map(|(_, x)| x)
+    }
+
+    pub(crate) fn write<W: Write + ?Sized>(&self, w: &mut W) -> io::Result<()> {
+        write_str(w, &self.function)?;
+        w.write_all(&self.thread.to_le_bytes())?;
+        write_len(w, self.args.len())?;
+        for (name, arg) in &self.args {
+            write_str(w, name)?;
+            write_arg(w, arg)?;
+        }
+        match self.status {
+            Some(status) => {
+                w.write_all(&[1])?;
+                w.write_all(&status.to_le_bytes())?;
+            }
+            None => w.write_all(&[0])?,
+        }
+        match self.returned {
+            Some(returned) => {
+                w.write_all(&[1])?;
+                w.write_all(&returned.to_le_bytes())?;
+            }
+            None => w.write_all(&[0])?,
+        }
+        write_len(w, self.outputs.len())?;
+        for (name, output) in &self.outputs {
+            write_str(w, name)?;
+            match output {
+                Output::Bytes(x) => {
+                    w.write_all(&[0])?;
+                    write_bytes(w, x)?;
+                }
+                Output::Handles(x) => {
+                    w.write_all(&[1])?;
+                    write_words(w, x)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the next call, None at the end of the trace.
+    fn read<R: Read>(r: &mut R) -> io::Result<Option<Call>> {
+        let mut first = [0u8; 1];
+        if r.read(&mut first)? == 0 {
+            return Ok(None);
+        }
+        let mut rest = [0u8; 3];
+        r.read_exact(&mut rest)?;
+        let len = u32::from_le_bytes([first[0], rest[0], rest[1], rest[2]]);
+        let function = into_string(read_exact(r, len as usize)?)?;
+        let thread = read_u64(r)?;
+        let mut args = Vec::new();
+        for _ in 0..read_u32(r)? {
+            args.push((read_str(r)?, read_arg(r)?));
+        }
+        let status = match read_u8(r)? {
+            0 => None,
+            _ => Some(read_u32(r)? as i32),
+        };
+        let returned = match read_u8(r)? {
+            0 => None,
+            _ => Some(read_u64(r)?),
+        };
+        let mut outputs = Vec::new();
+        for _ in 0..read_u32(r)? {
+            let name = read_str(r)?;
+            let output = match read_u8(r)? {
+                0 => Output::Bytes(read_bytes(r)?),
+                1 => Output::Handles(read_words(r)?),
+                x => return Err(invalid(format!("unknown output kind {}", x))),
+            };
+            outputs.push((name, output));
+        }
+        Ok(Some(Call {
+            function,
+            thread,
+            args,
+            status,
+            returned,
+            outputs,
+        }))
+    }
+}
+
+/// Calls read back from a trace.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trace {
+    pub calls: Vec<Call>,
+}
+
+impl Trace {
+    pub fn read<R: Read>(reader: R) -> io::Result<Trace> {
+        let mut reader = BufReader::new(reader);
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid("not an OpenCL trace".to_owned()));
+        }
+        let version = read_u32(&mut reader)?;
+        if version != VERSION {
+            return Err(invalid(format!("unsupported trace version {}", version)));
+        }
+        let mut calls = Vec::new();
+        while let Some(call) = Call::read(&mut reader)? {
+            calls.push(call);
+        }
+        Ok(Trace { calls })
+    }
+
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Trace> {
+        Trace::read(File::open(path)?)
+    }
+
+    pub fn write<W: Write>(&self, writer: W) -> io::Result<()> {
+        let mut writer = BufWriter::new(writer);
+        write_header(&mut writer)?;
+        for call in &self.calls {
+            call.write(&mut writer)?;
+        }
+        writer.flush()
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.write(File::create(path)?)
+    }
+
+    /// Returns the calls made from thread, in order.
+    pub fn thread(&self, thread: u64) -> Vec<Call> {
+        self.calls
+            .iter()
+            .filter(|x| x.thread == thread)
+            .cloned()
+            .collect()
+    }
+}
+
+pub(crate) fn write_header<W: Write + ?Sized>(w: &mut W) -> io::Result<()> {
+    w.write_all(MAGIC)?;
+    w.write_all(&VERSION.to_le_bytes())
+}
+
+fn write_arg<W: Write + ?Sized>(w: &mut W, arg: &Arg) -> io::Result<()> {
+    match arg {
+        Arg::Scalar(x) => {
+            w.write_all(&[0])?;
+            w.write_all(&x.to_le_bytes())
+        }
+        Arg::Null => w.write_all(&[1]),
+        Arg::Handle(x) => {
+            w.write_all(&[2])?;
+            w.write_all(&x.to_le_bytes())
+        }
+        Arg::Handles(x) => {
+            w.write_all(&[3])?;
+            write_words(w, x)
+        }
+        Arg::Bytes(x) => {
+            w.write_all(&[4])?;
+            write_bytes(w, x)
+        }
+        Arg::Words(x) => {
+            w.write_all(&[5])?;
+            write_words(w, x)
+        }
+        Arg::Strings(x) => {
+            w.write_all(&[6])?;
+            write_len(w, x.len())?;
+            x.iter().try_for_each(|x| write_bytes(w, x))
+        }
+        Arg::Out { len, keep } => {
+            w.write_all(&[7])?;
+            w.write_all(&len.to_le_bytes())?;
+            w.write_all(&[*keep as u8])
+        }
+        Arg::OutHandles(x) => {
+            w.write_all(&[8])?;
+            w.write_all(&x.to_le_bytes())
+        }
+        Arg::Callback(x) => w.write_all(&[9, *x as u8]),
+        Arg::Opaque => w.write_all(&[10]),
+        Arg::Unsupported => w.write_all(&[11]),
+    }
+}
+
+fn read_arg<R: Read>(r: &mut R) -> io::Result<Arg> {
+    Ok(match read_u8(r)? {
+        0 => Arg::Scalar(read_u64(r)?),
+        1 => Arg::Null,
+        2 => Arg::Handle(read_u64(r)?),
+        3 => Arg::Handles(read_words(r)?),
+        4 => Arg::Bytes(read_bytes(r)?),
+        5 => Arg::Words(read_words(r)?),
+        6 => {
+            let mut strings = Vec::new();
+            for _ in 0..read_u32(r)? {
+                strings.push(read_bytes(r)?);
+            }
+            Arg::Strings(strings)
+        }
+        7 => Arg::Out {
+            len: read_u64(r)?,
+            keep: read_u8(r)? != 0,
+        },
+        8 => Arg::OutHandles(read_u64(r)?),
+        9 => Arg::Callback(read_u8(r)? != 0),
+        10 => Arg::Opaque,
+        11 => Arg::Unsupported,
+        x => return Err(invalid(format!("unknown argument kind {}", x))),
+    })
+}
+
+fn write_len<W: Write + ?Sized>(w: &mut W, len: usize) -> io::Result<()> {
+    w.write_all(&(len as u32).to_le_bytes())
+}
+
+fn write_str<W: Write + ?Sized>(w: &mut W, s: &str) -> io::Result<()> {
+    write_bytes(w, s.as_bytes())
+}
+
+fn write_bytes<W: Write + ?Sized>(w: &mut W, bytes: &[u8]) -> io::Result<()> {
+    write_len(w, bytes.len())?;
+    w.write_all(bytes)
+}
+
+fn write_words<W: Write + ?Sized>(w: &mut W, words: &[u64]) -> io::Result<()> {
+    write_len(w, words.len())?;
+    words.iter().try_for_each(|x| w.write_all(&x.to_le_bytes()))
+}
+
+fn read_exact<R: Read>(r: &mut R, len: usize) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    r.take(len as u64).read_to_end(&mut bytes)?;
+    if bytes.len() != len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(bytes)
+}
+
+fn read_u8<R: Read>(r: &mut R) -> io::Result<u8> {
+    let mut x = [0u8; 1];
+    r.read_exact(&mut x)?;
+    Ok(x[0])
+}
+
+fn read_u32<R: Read>(r: &mut R) -> io::Result<u32> {
+    let mut x = [0u8; 4];
+    r.read_exact(&mut x)?;
+    Ok(u32::from_le_bytes(x))
+}
+
+fn read_u64<R: Read>(r: &mut R) -> io::Result<u64> {
+    let mut x = [0u8; 8];
+    r.read_exact(&mut x)?;
+    Ok(u64::from_le_bytes(x))
+}
+
+fn read_bytes<R: Read>(r: &mut R) -> io::Result<Vec<u8>> {
+    let len = read_u32(r)?;
+    read_exact(r, len as usize)
+}
+
+fn read_words<R: Read>(r: &mut R) -> io::Result<Vec<u64>> {
+    (0..read_u32(r)?).map(|_| read_u64(r)).collect()
+}
+
+fn read_str<R: Read>(r: &mut R) -> io::Result<String> {
+    into_string(read_bytes(r)?)
+}
+
+fn into_string(bytes: Vec<u8>) -> io::Result<String> {
+    String::from_utf8(bytes).map_err(|x| invalid(x.to_string()))
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/************************/
+/* /‾‾\ /‾‾\ /‾‾\ /‾‾\  */
+/*|  |  |  |  |  |  |  |*/
+/*|  |  Unit Tests  |  |*/
+/*|__|__|__|__|__|__|__|*/
+/*|__|__|__|__|__|__|__|*/
+/************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let trace = Trace {
+            calls: vec![Call {
+                function: "clCreateProgramWithSource".to_owned(),
+                thread: 3,
+                args: vec![
+                    ("context".to_owned(), Arg::Handle(0x1000)),
+                    ("count".to_owned(), Arg::Scalar(1)),
+                    ("strings".to_owned(), Arg::Strings(vec![b"kernel".to_vec()])),
+                    ("lengths".to_owned(), Arg::Null),
+                    ("properties".to_owned(), Arg::Words(vec![0x1084, 0x2000, 0])),
+                    ("data".to_owned(), Arg::Bytes(vec![1, 2, 3])),
+                    ("event".to_owned(), Arg::OutHandles(1)),
+                    ("ptr".to_owned(), Arg::Out { len: 8, keep: true }),
+                    ("list".to_owned(), Arg::Handles(vec![1, 2])),
+                    ("pfn_notify".to_owned(), Arg::Callback(false)),
+                    ("user_data".to_owned(), Arg::Opaque),
+                    ("args".to_owned(), Arg::Unsupported),
+                    ("host_ptr".to_owned(), Arg::Null),
+                ],
+                status: Some(-5),
+                returned: Some(0),
+                outputs: vec![
+                    ("ptr".to_owned(), Output::Bytes(vec![0; 8])),
+                    ("event".to_owned(), Output::Handles(vec![0x3000])),
+                ],
+            }],
+        };
+        let mut bytes = Vec::new();
+        trace.write(&mut bytes).unwrap();
+        assert_eq!(&bytes[..8], MAGIC);
+        assert_eq!(Trace::read(&bytes[..]).unwrap(), trace);
+
+        // Truncated traces and other versions are rejected.
+        assert!(Trace::read(&bytes[..bytes.len() - 1]).is_err());
+        bytes[8] = 2;
+        let error = Trace::read(&bytes[..]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,527 @@
+/*
+ * capture.rs - Capture and replay of the OpenCL calls.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+//!
+//! With the `capture` feature, the ffi calls of the api can be written to
+//! a trace: every call with its arguments, the host memory it reads (e.g.
+//! the data of a write or the sources of a program) and its results (the
+//! status, the handles it created, the memory it wrote). A `Replayer`
+//! makes the calls of a trace again, on any platform, and reports where
+//! the results diverge from the captured ones.
+//!
+//! Handles differ from one run to the other, so the replayer maps every
+//! handle of the trace to the one its replay created. Callbacks and the
+//! memory written through mapped pointers are not captured. The calls
+//! passing memory of a size the api doesn't know, e.g. the host memory of
+//! images, are captured but skipped by the replayer.
+//!
+//! ```no_run
+//! use opencl_api::capture::{self, Replayer, Trace};
+//!
+//! capture::create("app.cltrace").unwrap();
+//! // ...
+//! capture::stop().unwrap();
+//!
+//! let trace = Trace::open("app.cltrace").unwrap();
+//! let report = Replayer::new().replay(&trace.calls);
+//! for divergence in &report.divergences {
+//!     println!("{}", divergence);
+//! }
+//! ```
+//!
+mod format;
+mod replay;
+
+pub use format::{Arg, Call, Output, Trace, VERSION};
+pub(crate) use replay::Frame;
+pub use replay::{Divergence, Mismatch, Replayer, Report};
+
+use crate::ffi::Value;
+use libc::{c_char, strlen};
+use opencl_heads::consts::{CL_SUCCESS, CL_TRUE};
+use opencl_heads::types::*;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::mem::size_of;
+use std::path::Path;
+use std::slice;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+
+struct Capture {
+    writer: Box<dyn Write + Send>,
+    error: Option<io::Error>,
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static CAPTURE: Mutex<Option<Capture>> = Mutex::new(None);
+
+/// Starts capturing the calls of all the threads to writer.
+pub fn start<W: Write + Send + 'static>(writer: W) -> io::Result<()> {
+    let mut capture = CAPTURE.lock().unwrap();
+    if capture.is_some() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            "a capture is already running",
+        ));
+    }
+    let mut writer: Box<dyn Write + Send> = Box::new(writer);
+    format::write_header(&mut writer)?;
+    *capture = Some(Capture {
+        writer,
+        error: None,
+    });
+    ENABLED.store(true, Ordering::Release);
+    Ok(())
+}
+
+/// Starts capturing the calls of all the threads to the file at path.
+pub fn create<P: AsRef<Path>>(path: P) -> io::Result<()> {
+    start(BufWriter::new(File::create(path)?))
+}
+
+/// Stops capturing, returning the first error met writing the trace.
+pub fn stop() -> io::Result<()> {
+    let capture = CAPTURE.lock().unwrap().take();
+    ENABLED.store(false, Ordering::Release);
+    match capture {
+        Some(Capture {
+            error: Some(error), ..
+        }) => Err(error),
+        Some(mut capture) => capture.writer.flush(),
+        None => Ok(()),
+    }
+}
+
+static NEXT_THREAD: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    static THREAD: u64 = NEXT_THREAD.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Number of the current thread in the traces, unique to the process.
+pub fn current_thread() -> u64 {
+    THREAD.with(|x| *x)
+}
+
+/// Memory written by a call in progress.
+enum Out {
+    Bytes { ptr: usize, len: usize, keep: bool },
+    Handles { ptr: usize, count: usize },
+}
+
+/// Call in progress, written to the trace once it returns.
+pub(crate) struct Pending {
+    call: Call,
+    outs: Vec<(&'static str, Out)>,
+}
+
+impl Pending {
+    /// Captures the arguments of a call, unless no capture is running.
+    ///
+    /// # Safety
+    ///
+    /// The pointers among args must be valid for the call being made.
+    pub(crate) unsafe fn begin(
+        function: &'static str,
+        args: &[(&'static str, Value)],
+    ) -> Option<Pending> {
+        if !ENABLED.load(Ordering::Acquire) {
+            return None;
+        }
+        let mut outs = Vec::new();
+        let args = args
+            .iter()
+            .map(|(name, value)| {
+                let arg = classify(function, name, *value, args);
+                match arg {
+                    Arg::Out { len, keep } => outs.push((
+                        *name,
+                        Out::Bytes {
+                            ptr: value.word() as usize,
+                            len: len as usize,
+                            keep,
+                        },
+                    )),
+                    Arg::OutHandles(count) => outs.push((
+                        *name,
+                        Out::Handles {
+                            ptr: value.word() as usize,
+                            count: count as usize,
+                        },
+                    )),
+                    _ => (),
+                }
+                ((*name).to_owned(), arg)
+            })
+            .collect();
+        Some(Pending {
+            call: Call {
+                function: function.to_owned(),
+                thread: current_thread(),
+                args,
+                status: None,
+                returned: None,
+                outputs: Vec::new(),
+            },
+            outs,
+        })
+    }
+
+    /// Captures the results of the call and writes it to the trace.
+    ///
+    /// # Safety
+    ///
+    /// The out arguments of the call must still be valid.
+    pub(crate) unsafe fn end(mut self, status: Option<cl_int>, returned: Option<usize>) {
+        self.call.status = status;
+        self.call.returned = returned.map(|x| x as u64);
+        if status.is_none() || status == Some(CL_SUCCESS) {
+            for (name, out) in self.outs {
+                let output = match out {
+                    Out::Bytes { keep: false, .. } => continue,
+                    Out::Bytes { ptr, len, .. } => {
+                        Output::Bytes(slice::from_raw_parts(ptr as *const u8, len).to_vec())
+                    }
+                    Out::Handles { ptr, count } => Output::Handles(
+                        slice::from_raw_parts(ptr as *const usize, count)
+                            .iter()
+                            .map(|x| *x as u64)
+                            .collect(),
+                    ),
+                };
+                self.call.outputs.push((name.to_owned(), output));
+            }
+        }
+        if let Some(capture) = CAPTURE.lock().unwrap().as_mut() {
+            if capture.error.is_none() {
+                if let Err(error) = self.call.write(&mut capture.writer) {
+                    capture.error = Some(error);
+                }
+            }
+        }
+    }
+}
+
+/// Object handles passed to the entry points.
+const HANDLES: &[&str] = &[
+    "platform",
+    "device",
+    "in_device",
+    "context",
+    "command_queue",
+    "memobj",
+    "buffer",
+    "src_buffer",
+    "dst_buffer",
+    "image",
+    "src_image",
+    "dst_image",
+    "pipe",
+    "program",
+    "kernel",
+    "source_kernel",
+    "sampler",
+    "mapped_ptr",
+    "svm_pointer",
+    "svm_ptr",
+    "src_ptr",
+    "dst_ptr",
+];
+
+/// Arrays of object handles passed to the entry points, with the argument
+/// giving their length.
+const HANDLE_LISTS: &[(&str, &str)] = &[
+    ("device_list", "num_devices"),
+    ("event_wait_list", "num_events_in_wait_list"),
+    ("event_list", "num_events"),
+    ("mem_objects", "num_mem_objects"),
+    ("input_programs", "num_input_programs"),
+    ("input_headers", "num_input_headers"),
+    ("svm_pointers", "num_svm_pointers"),
+];
+
+/// Entry points taking an event, rather than creating one.
+const EVENT_USERS: &[&str] = &[
+    "clGetEventInfo",
+    "clGetEventProfilingInfo",
+    "clRetainEvent",
+    "clReleaseEvent",
+    "clSetUserEventStatus",
+    "clSetEventCallback",
+];
+
+/// Captures the argument name of function.
+///
+/// # Safety
+///
+/// The pointers among args must be valid for the call being made.
+unsafe fn classify(function: &str, name: &str, value: Value, args: &[(&str, Value)]) -> Arg {
+    let ptr = match value {
+        Value::Int(_) | Value::Uint(_) => return Arg::Scalar(value.word()),
+        Value::Callback(false) => return Arg::Callback(false),
+        Value::Callback(true) => {
+            return match function {
+                // The callback is the point of these calls.
+                "clSetEventCallback"
+                | "clSetMemObjectDestructorCallback"
+                | "clSetContextDestructorCallback"
+                | "clEnqueueNativeKernel" => Arg::Unsupported,
+                _ => Arg::Callback(true),
+            };
+        }
+        Value::Pointer(0) => return Arg::Null,
+        Value::Pointer(ptr) => ptr,
+    };
+    let count = |name: &str| {
+        args.iter()
+            .find(|(x, _)| *x == name)
+            .map_or(0, |(_, x)| x.word() as usize)
+    };
+    let bytes = |len: usize| Arg::Bytes(slice::from_raw_parts(ptr as *const u8, len).to_vec());
+    let words = |len: usize| {
+        Arg::Words(
+            slice::from_raw_parts(ptr as *const usize, len)
+                .iter()
+                .map(|x| *x as u64)
+                .collect(),
+        )
+    };
+    let handles = |len: usize| match words(len) {
+        Arg::Words(x) => Arg::Handles(x),
+        _ => unreachable!(),
+    };
+    let out = |len: usize| Arg::Out {
+        len: len as u64,
+        keep: true,
+    };
+
+    if HANDLES.contains(&name) || (name == "event" && EVENT_USERS.contains(&function)) {
+        return Arg::Handle(ptr as u64);
+    }
+    if let Some((_, len)) = HANDLE_LISTS.iter().find(|(x, _)| *x == name) {
+        return handles(count(len));
+    }
+    match (function, name) {
+        // Handles created by the call.
+        ("clGetPlatformIDs", "platforms") | ("clGetDeviceIDs", "devices") => {
+            Arg::OutHandles(count("num_entries") as u64)
+        }
+        ("clCreateSubDevices", "out_devices") => Arg::OutHandles(count("num_devices") as u64),
+        ("clCreateKernelsInProgram", "kernels") => Arg::OutHandles(count("num_kernels") as u64),
+        (_, "event") => Arg::OutHandles(1),
+        ("clCreateContext", "devices") => handles(count("num_devices")),
+
+        // Memory written by the call.
+        ("clSetKernelExecInfo", "param_value") => bytes(count("param_value_size")),
+        (_, "param_value") => out(count("param_value_size")),
+        (_, "num_platforms")
+        | (_, "num_devices")
+        | (_, "num_devices_ret")
+        | (_, "num_kernels_ret")
+        | (_, "num_image_formats") => out(size_of::<cl_uint>()),
+        (_, "param_value_size_ret")
+        | (_, "device_timestamp")
+        | (_, "host_timestamp")
+        | (_, "old_properties")
+        | (_, "image_row_pitch")
+        | (_, "image_slice_pitch") => out(size_of::<u64>()),
+        (_, "binary_status") => out(count("num_devices") * size_of::<cl_int>()),
+        (_, "image_formats") => out(count("num_entries") * size_of::<cl_image_format>()),
+        ("clEnqueueReadBuffer", "ptr") => Arg::Out {
+            len: count("size") as u64,
+            // A non blocking read completes after the call returns.
+            keep: count("blocking_read") == CL_TRUE as usize,
+        },
+
+        // Memory read by the call.
+        ("clEnqueueWriteBuffer", "ptr") => bytes(count("size")),
+        ("clCreateBuffer", "host_ptr") | ("clCreateBufferWithProperties", "host_ptr") => {
+            bytes(count("size"))
+        }
+        (_, "pattern") => bytes(count("pattern_size")),
+        (_, "buffer_create_info") => bytes(size_of::<cl_buffer_region>()),
+        (_, "global_work_offset") | (_, "global_work_size") | (_, "local_work_size") => {
+            bytes(count("work_dim") * size_of::<size_t>())
+        }
+        (_, "origin")
+        | (_, "region")
+        | (_, "buffer_origin")
+        | (_, "host_origin")
+        | (_, "src_origin")
+        | (_, "dst_origin") => bytes(3 * size_of::<size_t>()),
+        (_, "sizes") => bytes(count("num_svm_pointers") * size_of::<size_t>()),
+        ("clCreateProgramWithSource", "lengths") => bytes(count("count") * size_of::<size_t>()),
+        ("clCreateProgramWithBinary", "lengths") => {
+            bytes(count("num_devices") * size_of::<size_t>())
+        }
+        (_, "il") => bytes(count("length")),
+        (_, "image_format") => bytes(size_of::<cl_image_format>()),
+        // The descriptor may hold the handle of a buffer.
+        (_, "image_desc") => words(size_of::<cl_image_desc>() / size_of::<usize>()),
+        (_, "input_value") => bytes(count("input_value_size")),
+        (_, "fill_color") => bytes(4 * size_of::<cl_uint>()),
+        ("clSetKernelArg", "arg_value") => match count("arg_size") {
+            // The value may be the handle of a memory object or sampler.
+            x if x % size_of::<usize>() == 0 => words(x / size_of::<usize>()),
+            x => bytes(x),
+        },
+        (_, "kernel_name") | (_, "kernel_names") | (_, "options") => {
+            bytes(strlen(ptr as *const c_char) + 1)
+        }
+        ("clCreateSubDevices", "properties") => Arg::Unsupported,
+        (_, "properties") | (_, "sampler_properties") => {
+            // Pairs of a name and a value, ending with a zero name.
+            let properties = ptr as *const usize;
+            let mut len = 0;
+            while *properties.add(len) != 0 {
+                len += 2;
+            }
+            words(len + 1)
+        }
+        ("clCreateProgramWithSource", "strings") => {
+            let lengths = args.iter().find(|(x, _)| *x == "lengths").map(|(_, x)| x);
+            strings(ptr, count("count"), lengths.map(|x| x.word() as usize))
+        }
+        ("clCreateProgramWithBinary", "binaries") => {
+            let lengths = args.iter().find(|(x, _)| *x == "lengths").map(|(_, x)| x);
+            strings(
+                ptr,
+                count("num_devices"),
+                lengths.map(|x| x.word() as usize),
+            )
+        }
+        (_, "header_include_names") => strings(ptr, count("num_input_headers"), None),
+        (_, "user_data") => Arg::Opaque,
+        _ => Arg::Unsupported,
+    }
+}
+
+/// Captures an array of count strings, of the given lengths if any, else
+/// NUL terminated.
+unsafe fn strings(ptr: usize, count: usize, lengths: Option<usize>) -> Arg {
+    let strings = slice::from_raw_parts(ptr as *const *const c_char, count);
+    let lengths = match lengths {
+        Some(lengths) if lengths != 0 => slice::from_raw_parts(lengths as *const size_t, count),
+        _ => &[],
+    };
+    Arg::Strings(
+        strings
+            .iter()
+            .enumerate()
+            .map(|(i, x)| {
+                let len = match lengths.get(i) {
+                    Some(len) if *len != 0 => *len,
+                    _ => strlen(*x),
+                };
+                slice::from_raw_parts(*x as *const u8, len).to_vec()
+            })
+            .collect(),
+    )
+}
+
+/************************/
+/* /‾‾\ /‾‾\ /‾‾\ /‾‾\  */
+/*|  |  |  |  |  |  |  |*/
+/*|  |  Unit Tests  |  |*/
+/*|__|__|__|__|__|__|__|*/
+/*|__|__|__|__|__|__|__|*/
+/************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::buffer::{create_buffer, enqueue_read_buffer, enqueue_write_buffer};
+    use crate::api::context::{create_context, release_context};
+    use crate::api::device::get_device_ids;
+    use crate::api::memory::release_mem_object;
+    use crate::api::platform::get_platform_ids;
+    use crate::api::queue::{create_command_queue_with_properties, release_command_queue};
+    use crate::objects::bitfields::{DeviceType, MemFlags};
+    use crate::objects::property::ContextProperties;
+    use crate::objects::traits::GetSetGo;
+    use crate::objects::types::{DevicePtr, PlatformPtr, WrapMutPtr, WrapPtr};
+    use libc::intptr_t;
+
+    #[test]
+    fn test_capture_and_replay() {
+        let path = std::env::temp_dir().join(format!("opencl-api-{}.cltrace", std::process::id()));
+        create(&path).unwrap();
+
+        let platform_ids = get_platform_ids().unwrap();
+        let platform_id = PlatformPtr::from_ptr(platform_ids[0], "test_fn").unwrap();
+        let device_ids =
+            get_device_ids(&platform_id, DeviceType::new(DeviceType::DEFAULT).unwrap()).unwrap();
+        let device_id = DevicePtr::from_ptr(device_ids[0], "test_fn").unwrap();
+        let properties = ContextProperties.gen(Some(&platform_id), None);
+        let context = create_context(&properties, device_ids, None, WrapMutPtr::null()).unwrap();
+        let queue = create_command_queue_with_properties(&context, &device_id, &None).unwrap();
+        let data: Vec<cl_uint> = (0..16).collect();
+        let size = data.len() * size_of::<cl_uint>();
+        let flags = MemFlags::new(MemFlags::READ_WRITE).unwrap();
+        let buffer = create_buffer(&context, flags, size, WrapMutPtr::null()).unwrap();
+        unsafe {
+            let ptr = WrapPtr::from_raw(data.as_ptr() as intptr_t);
+            enqueue_write_buffer(&queue, &buffer, 1, 0, size, ptr, 0, WrapPtr::null()).unwrap();
+            let mut read = vec![0 as cl_uint; data.len()];
+            let ptr = WrapMutPtr::from_raw(read.as_mut_ptr() as intptr_t);
+            enqueue_read_buffer(&queue, &buffer, 1, 0, size, ptr, 0, WrapPtr::null()).unwrap();
+            assert_eq!(read, data);
+        }
+        release_mem_object(buffer).unwrap();
+        release_command_queue(queue).unwrap();
+        release_context(context).unwrap();
+        stop().unwrap();
+
+        let trace = Trace::open(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        // Other tests may be calling the api meanwhile.
+        let mut calls = trace.thread(current_thread());
+        let bytes: Vec<u8> = data.iter().flat_map(|x| x.to_ne_bytes().to_vec()).collect();
+        let write = calls
+            .iter()
+            .position(|x| x.function == "clEnqueueWriteBuffer")
+            .unwrap();
+        assert_eq!(calls[write].arg("ptr"), Some(&Arg::Bytes(bytes.clone())));
+        let read = calls
+            .iter()
+            .position(|x| x.function == "clEnqueueReadBuffer")
+            .unwrap();
+        assert_eq!(calls[read].output("ptr"), Some(&Output::Bytes(bytes)));
+
+        let report = Replayer::new().replay(&calls);
+        assert!(report.is_faithful(), "{:?}", report);
+        assert_eq!(report.replayed, calls.len());
+
+        // Writing other data makes the read diverge.
+        if let Some((_, Arg::Bytes(x))) = calls[write].args.iter_mut().find(|x| x.0 == "ptr") {
+            x[0] ^= 1;
+        }
+        let report = Replayer::new().replay(&calls);
+        assert!(report.skipped.is_empty());
+        assert_eq!(
+            report.divergences,
+            vec![Divergence {
+                index: read,
+                function: "clEnqueueReadBuffer".to_owned(),
+                mismatch: Mismatch::Output {
+                    arg: "ptr".to_owned()
+                },
+            }]
+        );
+    }
+}
@@ -0,0 +1,358 @@
+/*
+ * replay.rs - Replay of the captured traces.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+use super::format::{Arg, Call, Output};
+use crate::ffi;
+use libc::c_void;
+use opencl_heads::consts::CL_SUCCESS;
+use opencl_heads::types::*;
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::fmt;
+use std::mem::size_of;
+use std::slice;
+
+/// Difference between the results of a call and the captured ones.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Mismatch {
+    Status {
+        captured: Option<i32>,
+        replayed: Option<i32>,
+    },
+    /// A handle was created by only one of the calls.
+    Handle { arg: String },
+    /// The memory written through arg differs.
+    Output { arg: String },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Divergence {
+    /// Index of the call in the replayed calls.
+    pub index: usize,
+    pub function: String,
+    pub mismatch: Mismatch,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{} {}: ", self.index, self.function)?;
+        match &self.mismatch {
+            Mismatch::Status { captured, replayed } => write!(
+                f,
+                "status {:?} instead of {:?}",
+                replayed.unwrap_or_default(),
+                captured.unwrap_or_default()
+            ),
+            Mismatch::Handle { arg } => write!(f, "handle {} created by only one run", arg),
+            Mismatch::Output { arg } => write!(f, "{} differs", arg),
+        }
+    }
+}
+
+/// Outcome of a replay.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Report {
+    /// Number of calls made again.
+    pub replayed: usize,
+    /// Calls that could not be made again, by index, with the reason.
+    pub skipped: Vec<(usize, String)>,
+    pub divergences: Vec<Divergence>,
+}
+
+impl Report {
+    /// Whether every call was replayed with the captured results.
+    pub fn is_faithful(&self) -> bool {
+        self.skipped.is_empty() && self.divergences.is_empty()
+    }
+}
+
+/// Arguments of a call being replayed, as passed to the platform.
+pub(crate) struct Frame {
+    words: Vec<(String, u64)>,
+}
+
+impl Frame {
+    /// Returns the argument named name as a value of type T.
+    pub(crate) fn arg<T: FromWord>(&self, name: &str) -> T {
+        let word = self
+            .words
+            .iter()
+            .find(|(x, _)| x == name)
+            .map_or(0, |(_, x)| *x);
+        T::from_word(word)
+    }
+}
+
+/// Argument type of an entry point, rebuilt from the word it is passed in.
+pub(crate) trait FromWord {
+    fn from_word(word: u64) -> Self;
+}
+
+macro_rules! from_word {
+    ($($typ:ty),*) => {
+        $(
+            impl FromWord for $typ {
+                fn from_word(word: u64) -> Self {
+                    word as $typ
+                }
+            }
+        )*
+    };
+}
+
+from_word!(i32, u32, isize, usize, u64);
+
+impl<T> FromWord for *const T {
+    fn from_word(word: u64) -> Self {
+        word as usize as *const T
+    }
+}
+
+impl<T> FromWord for *mut T {
+    fn from_word(word: u64) -> Self {
+        word as usize as *mut T
+    }
+}
+
+/// Callbacks are not replayed.
+impl<F> FromWord for Option<F> {
+    fn from_word(_: u64) -> Self {
+        None
+    }
+}
+
+extern "C" fn ignore_context(_: cl_context, _: *mut c_void) {}
+
+impl FromWord for extern "C" fn(cl_context, *mut c_void) {
+    fn from_word(_: u64) -> Self {
+        ignore_context
+    }
+}
+
+/// Memory written by a call being replayed.
+enum Out {
+    Bytes { index: usize, len: usize },
+    Handles { index: usize, count: usize },
+}
+
+/// Makes the calls of a trace again.
+///
+/// The replayer owns the memory passed to the calls, e.g. the host memory of
+/// buffers created with `CL_MEM_USE_HOST_PTR`, until it is dropped.
+#[derive(Default)]
+pub struct Replayer {
+    handles: HashMap<u64, u64>,
+    memory: Vec<Vec<u64>>,
+}
+
+impl Replayer {
+    pub fn new() -> Self {
+        Replayer::default()
+    }
+
+    /// Returns the handle created by the replay for the captured handle.
+    pub fn handle(&self, captured: u64) -> Option<u64> {
+        self.handles.get(&captured).copied()
+    }
+
+    /// Replays calls in order, reporting where they diverge.
+    pub fn replay(&mut self, calls: &[Call]) -> Report {
+        let mut report = Report::default();
+        for (index, call) in calls.iter().enumerate() {
+            match self.replay_call(call) {
+                Ok(mismatches) => {
+                    report.replayed += 1;
+                    report
+                        .divergences
+                        .extend(mismatches.into_iter().map(|mismatch| Divergence {
+                            index,
+                            function: call.function.clone(),
+                            mismatch,
+                        }));
+                }
+                Err(reason) => report.skipped.push((index, reason)),
+            }
+        }
+        report
+    }
+
+    /// Replays call, returning how its results differ from the captured ones.
+    pub fn replay_call(&mut self, call: &Call) -> Result<Vec<Mismatch>, String> {
+        let mut words = Vec::new();
+        let mut outs = Vec::new();
+        for (name, arg) in &call.args {
+            let word = match arg {
+                Arg::Scalar(x) => *x,
+                Arg::Null | Arg::Opaque | Arg::Callback(_) => 0,
+                Arg::Handle(x) => self.remap(*x).ok_or_else(|| unknown(name, *x))?,
+                Arg::Handles(x) => {
+                    let handles = x
+                        .iter()
+                        .map(|x| self.remap(*x).ok_or_else(|| unknown(name, *x)))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    self.keep(handles)
+                }
+                Arg::Bytes(x) => self.keep_bytes(x),
+                Arg::Words(x) => {
+                    let words = x.iter().map(|x| self.remap(*x).unwrap_or(*x)).collect();
+                    self.keep(words)
+                }
+                Arg::Strings(x) => {
+                    let strings = x
+                        .iter()
+                        .map(|x| {
+                            let mut x = x.clone();
+                            x.push(0);
+                            self.keep_bytes(&x)
+                        })
+                        .collect();
+                    self.keep(strings)
+                }
+                Arg::Out { len, keep } => {
+                    let len = *len as usize;
+                    let word = self.keep(vec![0; len.div_ceil(8)]);
+                    if *keep {
+                        outs.push((
+                            name,
+                            Out::Bytes {
+                                index: self.memory.len() - 1,
+                                len,
+                            },
+                        ));
+                    }
+                    word
+                }
+                Arg::OutHandles(x) => {
+                    let count = *x as usize;
+                    let word = self.keep(vec![0; count]);
+                    outs.push((
+                        name,
+                        Out::Handles {
+                            index: self.memory.len() - 1,
+                            count,
+                        },
+                    ));
+                    word
+                }
+                Arg::Unsupported => return Err(format!("{} was not captured", name)),
+            };
+            words.push((name.clone(), word));
+        }
+
+        let frame = Frame { words };
+        let (status, returned) = unsafe { ffi::replay(&call.function, &frame) }
+            .ok_or_else(|| format!("{} is not replayable", call.function))?;
+        let returned = returned.map(|x| x as u64);
+
+        let mut mismatches = Vec::new();
+        if status != call.status {
+            mismatches.push(Mismatch::Status {
+                captured: call.status,
+                replayed: status,
+            });
+        }
+        if let (Some(captured), Some(replayed)) = (call.returned, returned) {
+            self.map("return", captured, replayed, &mut mismatches);
+        }
+        if status.is_some() && status != Some(CL_SUCCESS) {
+            return Ok(mismatches);
+        }
+        for (name, out) in outs {
+            match (out, call.output(name)) {
+                (Out::Bytes { index, len }, Some(Output::Bytes(captured))) => {
+                    let replayed = unsafe {
+                        slice::from_raw_parts(self.memory[index].as_ptr() as *const u8, len)
+                    };
+                    if !self.same_bytes(captured, replayed) {
+                        mismatches.push(Mismatch::Output { arg: name.clone() });
+                    }
+                }
+                (Out::Handles { index, count }, Some(Output::Handles(captured))) => {
+                    let replayed = self.memory[index][..count].to_vec();
+                    for (captured, replayed) in captured.iter().zip(replayed) {
+                        self.map(name, *captured, replayed, &mut mismatches);
+                    }
+                }
+                // Failed captured calls have no outputs.
+                _ => (),
+            }
+        }
+        Ok(mismatches)
+    }
+
+    fn remap(&self, captured: u64) -> Option<u64> {
+        match captured {
+            0 => Some(0),
+            x => self.handles.get(&x).copied(),
+        }
+    }
+
+    /// Maps the captured handle to the replayed one.
+    fn map(&mut self, arg: &str, captured: u64, replayed: u64, mismatches: &mut Vec<Mismatch>) {
+        if (captured == 0) != (replayed == 0) {
+            mismatches.push(Mismatch::Handle {
+                arg: arg.to_owned(),
+            });
+        } else if captured != 0 {
+            self.handles.insert(captured, replayed);
+        }
+    }
+
+    /// Compares memory written by the calls, the pointer sized words of
+    /// handles mapped to each other being the same.
+    fn same_bytes(&self, captured: &[u8], replayed: &[u8]) -> bool {
+        if captured.len() != replayed.len() {
+            return false;
+        }
+        if !captured.len().is_multiple_of(size_of::<u64>()) {
+            return captured == replayed;
+        }
+        captured
+            .chunks(size_of::<u64>())
+            .zip(replayed.chunks(size_of::<u64>()))
+            .all(|(x, y)| {
+                let x = u64::from_ne_bytes(x.try_into().unwrap());
+                let y = u64::from_ne_bytes(y.try_into().unwrap());
+                x == y || self.handles.get(&x) == Some(&y)
+            })
+    }
+
+    /// Keeps words alive for the replay, returning their address.
+    fn keep(&mut self, words: Vec<u64>) -> u64 {
+        let ptr = words.as_ptr() as u64;
+        self.memory.push(words);
+        ptr
+    }
+
+    /// Keeps a word aligned copy of bytes alive for the replay, returning
+    /// its address.
+    fn keep_bytes(&mut self, bytes: &[u8]) -> u64 {
+        let mut words = vec![0u64; bytes.len().div_ceil(8)];
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                bytes.as_ptr(),
+                words.as_mut_ptr() as *mut u8,
+                bytes.len(),
+            );
+        }
+        self.keep(words)
+    }
+}
+
+fn unknown(name: &str, handle: u64) -> String {
+    format!("{} is the unknown handle {:#x}", name, handle)
+}
@@ -46,6 +46,18 @@ pub enum ValidationError {
     InvalidBitfield(&'static str),
     #[error("invalid property configuration found at api function `{0}`")]
     InvalidProperty(&'static str),
+    #[error("string with an interior null byte passed at api function `{0}`")]
+    InvalidString(&'static str),
+    #[error("invalid image descriptor at api function `{0}`, reason: {1}")]
+    InvalidImageDesc(&'static str, &'static str),
+    #[error("image format not supported by the context at api function `{0}`")]
+    UnsupportedImageFormat(&'static str),
+    #[error("image size exceeds the limits of every device at api function `{0}`")]
+    InvalidImageSize(&'static str),
+    #[error("parameter {1:#x} not supported by the object at api function `{0}`")]
+    UnsupportedParam(&'static str, u32),
+    #[error("parameter {1:#x} requires OpenCL {2}.{3} at api function `{0}`")]
+    UnsupportedVersion(&'static str, u32, u32, u32),
 }
 
 impl ToLibraryError for ValidationError {
@@ -60,6 +72,8 @@ pub enum RuntimeError {
     CorruptedByteArray,
     #[error("null pointer has been returned to rust! this exception occured at function `{0}`")]
     NullPointer(&'static str),
+    #[error("parameter value of an unexpected type returned at function `{0}`")]
+    UnexpectedParamValue(&'static str),
 }
 
 impl ToLibraryError for RuntimeError {
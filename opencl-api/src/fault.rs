@@ -0,0 +1,226 @@
+/*
+ * fault.rs - Fault injection for the OpenCL entry points.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+//!
+//! With the `fault` feature, tests can make the ffi calls of the api fail
+//! with a status of their choice, e.g. CL_OUT_OF_RESOURCES, which real
+//! drivers never return on demand.
+//!
+//! A failing call does not reach the platform. Entry points returning a
+//! status return the injected one, entry points creating an object write it
+//! to errcode_ret and return a null handle. Either way the error comes back
+//! through `status_update` as `OpenCLAPIError::StatusCodeError`, exactly as
+//! if the driver had reported it.
+//!
+//! Faults are scripted per thread, so that tests running in parallel don't
+//! see each other's faults. Calls are counted from the moment a fault is
+//! scripted.
+//!
+//! ```no_run
+//! use opencl_api::fault;
+//! use opencl_heads::consts::{CL_MEM_OBJECT_ALLOCATION_FAILURE, CL_OUT_OF_RESOURCES};
+//!
+//! // The third write from now fails.
+//! fault::fail_nth("clEnqueueWriteBuffer", 3, CL_OUT_OF_RESOURCES);
+//! // One buffer creation in ten fails.
+//! fault::seed(42);
+//! fault::fail_with_probability("clCreateBuffer", 0.1, CL_MEM_OBJECT_ALLOCATION_FAILURE);
+//! // ...
+//! fault::clear();
+//! ```
+//!
+use opencl_heads::types::cl_int;
+use std::cell::RefCell;
+
+enum Trigger {
+    Nth(usize),
+    Probability(f64),
+}
+
+struct Fault {
+    function: String,
+    trigger: Trigger,
+    status: cl_int,
+    calls: usize,
+}
+
+struct Faults {
+    faults: Vec<Fault>,
+    injected: usize,
+    state: u64,
+}
+
+impl Faults {
+    const SEED: u64 = 0x5eed;
+
+    /// Next number of the SplitMix64 sequence.
+    fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform number in [0, 1).
+    fn uniform(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+thread_local! {
+    static FAULTS: RefCell<Faults> = const {
+        RefCell::new(Faults {
+            faults: Vec::new(),
+            injected: 0,
+            state: Faults::SEED,
+        })
+    };
+}
+
+fn push(function: &str, trigger: Trigger, status: cl_int) {
+    FAULTS.with(|x| {
+        x.borrow_mut().faults.push(Fault {
+            function: function.to_owned(),
+            trigger,
+            status,
+            calls: 0,
+        })
+    });
+}
+
+/// Fails the nth call to function, counting from 1, with status.
+pub fn fail_nth(function: &str, n: usize, status: cl_int) {
+    assert!(n > 0, "calls are counted from 1");
+    push(function, Trigger::Nth(n), status);
+}
+
+/// Fails every call to function with status at the given probability, in
+/// the range [0, 1].
+pub fn fail_with_probability(function: &str, probability: f64, status: cl_int) {
+    assert!(
+        (0.0..=1.0).contains(&probability),
+        "probability out of range [0, 1]"
+    );
+    push(function, Trigger::Probability(probability), status);
+}
+
+/// Seeds the generator deciding the probabilistic faults of this thread, a
+/// given seed always fails the same calls.
+pub fn seed(seed: u64) {
+    FAULTS.with(|x| x.borrow_mut().state = seed);
+}
+
+/// Removes the faults of this thread and resets its counters and seed.
+pub fn clear() {
+    FAULTS.with(|x| {
+        let mut faults = x.borrow_mut();
+        faults.faults.clear();
+        faults.injected = 0;
+        faults.state = Faults::SEED;
+    });
+}
+
+/// Returns the number of calls failed on this thread since the last clear.
+pub fn injected() -> usize {
+    FAULTS.with(|x| x.borrow().injected)
+}
+
+/// Returns the status a call to function must fail with, if any.
+///
+/// Every fault scripted for function counts the call, the first one to
+/// trigger decides the status.
+pub(crate) fn intercept(function: &str) -> Option<cl_int> {
+    FAULTS.with(|x| {
+        let mut faults = x.borrow_mut();
+        let mut status = None;
+        for i in 0..faults.faults.len() {
+            if faults.faults[i].function != function {
+                continue;
+            }
+            faults.faults[i].calls += 1;
+            let triggered = match faults.faults[i].trigger {
+                Trigger::Nth(n) => faults.faults[i].calls == n,
+                Trigger::Probability(p) => faults.uniform() < p,
+            };
+            if triggered && status.is_none() {
+                status = Some(faults.faults[i].status);
+            }
+        }
+        if status.is_some() {
+            faults.injected += 1;
+        }
+        status
+    })
+}
+
+/************************/
+/* /‾‾\ /‾‾\ /‾‾\ /‾‾\  */
+/*|  |  |  |  |  |  |  |*/
+/*|  |  Unit Tests  |  |*/
+/*|__|__|__|__|__|__|__|*/
+/*|__|__|__|__|__|__|__|*/
+/************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opencl_heads::consts::{CL_DEVICE_NOT_AVAILABLE, CL_OUT_OF_RESOURCES};
+
+    #[test]
+    fn test_fail_nth() {
+        fail_nth("clFinish", 2, CL_OUT_OF_RESOURCES);
+        assert_eq!(intercept("clFlush"), None);
+        assert_eq!(intercept("clFinish"), None);
+        assert_eq!(intercept("clFinish"), Some(CL_OUT_OF_RESOURCES));
+        assert_eq!(intercept("clFinish"), None);
+        assert_eq!(injected(), 1);
+        clear();
+        assert_eq!(injected(), 0);
+    }
+
+    #[test]
+    fn test_fail_with_probability() {
+        fail_with_probability("clFlush", 0.0, CL_OUT_OF_RESOURCES);
+        fail_with_probability("clFinish", 1.0, CL_DEVICE_NOT_AVAILABLE);
+        for _ in 0..100 {
+            assert_eq!(intercept("clFlush"), None);
+            assert_eq!(intercept("clFinish"), Some(CL_DEVICE_NOT_AVAILABLE));
+        }
+        clear();
+
+        let run = || {
+            seed(7);
+            fail_with_probability("clFinish", 0.5, CL_OUT_OF_RESOURCES);
+            let failed: Vec<bool> = (0..64).map(|_| intercept("clFinish").is_some()).collect();
+            clear();
+            failed
+        };
+        let failed = run();
+        assert_eq!(failed, run());
+        assert!(failed.iter().any(|x| *x) && failed.iter().any(|x| !*x));
+    }
+
+    #[test]
+    fn test_faults_per_thread() {
+        fail_with_probability("clFinish", 1.0, CL_OUT_OF_RESOURCES);
+        let other = std::thread::spawn(|| intercept("clFinish")).join().unwrap();
+        assert_eq!(other, None);
+        assert_eq!(intercept("clFinish"), Some(CL_OUT_OF_RESOURCES));
+        clear();
+    }
+}
@@ -0,0 +1,989 @@
+/*
+ * ffi.rs - Hooked OpenCL entry points used by the api.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+//!
+//! Every call of the api into `opencl_heads::ffi` goes through the wrappers
+//! of this module, which give the optional layers (`fault`, `trace`) a single
+//! place to observe or intercept it. Without those layers a wrapper only
+//! forwards its arguments.
+//!
+use libc::c_void;
+use opencl_heads::types::*;
+
+/// Returns the status the call to function must fail with, if a fault was
+/// scripted for it.
+#[inline]
+fn intercept(_function: &str) -> Option<cl_int> {
+    #[cfg(feature = "fault")]
+    return crate::fault::intercept(_function);
+    #[cfg(not(feature = "fault"))]
+    None
+}
+
+/// Raw value of an argument, as passed to the platform.
+#[cfg(any(feature = "trace", feature = "capture"))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Value {
+    Int(i64),
+    Uint(u64),
+    Pointer(usize),
+    Callback(bool),
+}
+
+#[cfg(feature = "capture")]
+impl Value {
+    /// Returns the value as the 64 bits word it was passed in.
+    pub(crate) fn word(self) -> u64 {
+        match self {
+            Value::Int(x) => x as u64,
+            Value::Uint(x) => x,
+            Value::Pointer(x) => x as u64,
+            Value::Callback(x) => x as u64,
+        }
+    }
+}
+
+#[cfg(any(feature = "trace", feature = "capture"))]
+macro_rules! value_from {
+    ($($variant:ident($target:ty): $($typ:ty),*;)*) => {
+        $($(
+            impl From<$typ> for Value {
+                fn from(x: $typ) -> Self {
+                    Value::$variant(x as $target)
+                }
+            }
+        )*)*
+    };
+}
+
+#[cfg(any(feature = "trace", feature = "capture"))]
+value_from! {
+    Int(i64): i32, isize;
+    Uint(u64): u32, u64, usize;
+}
+
+#[cfg(any(feature = "trace", feature = "capture"))]
+impl<T> From<*const T> for Value {
+    fn from(x: *const T) -> Self {
+        Value::Pointer(x as usize)
+    }
+}
+
+#[cfg(any(feature = "trace", feature = "capture"))]
+impl<T> From<*mut T> for Value {
+    fn from(x: *mut T) -> Self {
+        Value::Pointer(x as usize)
+    }
+}
+
+#[cfg(any(feature = "trace", feature = "capture"))]
+impl<F> From<Option<F>> for Value {
+    fn from(x: Option<F>) -> Self {
+        Value::Callback(x.is_some())
+    }
+}
+
+#[cfg(any(feature = "trace", feature = "capture"))]
+impl From<extern "C" fn(cl_context, *mut c_void)> for Value {
+    fn from(_: extern "C" fn(cl_context, *mut c_void)) -> Self {
+        Value::Callback(true)
+    }
+}
+
+#[cfg(any(feature = "trace", feature = "capture"))]
+/// Returned value of an entry point, a handle or nothing.
+pub(crate) trait Returned {
+    fn handle(&self) -> Option<usize>;
+}
+
+#[cfg(any(feature = "trace", feature = "capture"))]
+impl Returned for () {
+    fn handle(&self) -> Option<usize> {
+        None
+    }
+}
+
+#[cfg(any(feature = "trace", feature = "capture"))]
+impl<T> Returned for *mut T {
+    fn handle(&self) -> Option<usize> {
+        Some(*self as usize)
+    }
+}
+
+/// Declares the wrappers of the entry points.
+///
+/// Entry points under `status` return their status code, those under
+/// `errcode` report it through an `errcode_ret` argument appended to the
+/// listed ones, and those under `other` have no status to report so are
+/// never intercepted. Attributes of an entry point, such as the `cfg` of
+/// a deprecated version, apply to its wrapper.
+///
+/// With the `capture` feature, it also declares `replay`, which calls an
+/// entry point by name with arguments rebuilt from a trace.
+macro_rules! api_functions {
+    (
+        status {
+            $($(#[$s_attr:meta])* fn $s_name:ident($($s_arg:ident: $s_ty:ty),* $(,)?);)*
+        }
+        errcode {
+            $($(#[$e_attr:meta])* fn $e_name:ident($($e_arg:ident: $e_ty:ty),* $(,)?) -> $e_ret:ty;)*
+        }
+        other {
+            $($(#[$o_attr:meta])* fn $o_name:ident($($o_arg:ident: $o_ty:ty),* $(,)?) $(-> $o_ret:ty)?;)*
+        }
+    ) => {
+        $(
+            $(#[$s_attr])*
+            #[allow(non_snake_case, clippy::too_many_arguments)]
+            pub unsafe fn $s_name($($s_arg: $s_ty),*) -> cl_int {
+                #[cfg(any(feature = "trace", feature = "capture"))]
+                let args = [$((stringify!($s_arg), Value::from($s_arg))),*];
+                #[cfg(feature = "trace")]
+                let call = crate::trace::Call::begin(stringify!($s_name), &args);
+                #[cfg(feature = "capture")]
+                let captured = crate::capture::Pending::begin(stringify!($s_name), &args);
+                let status = match intercept(stringify!($s_name)) {
+                    Some(status) => status,
+                    None => opencl_heads::ffi::$s_name($($s_arg),*),
+                };
+                #[cfg(feature = "trace")]
+                if let Some(call) = call {
+                    call.end(Some(status), None);
+                }
+                #[cfg(feature = "capture")]
+                if let Some(captured) = captured {
+                    captured.end(Some(status), None);
+                }
+                status
+            }
+        )*
+
+        $(
+            $(#[$e_attr])*
+            #[allow(non_snake_case, clippy::too_many_arguments)]
+            pub unsafe fn $e_name($($e_arg: $e_ty,)* errcode_ret: *mut cl_int) -> $e_ret {
+                #[cfg(any(feature = "trace", feature = "capture"))]
+                let args = [$((stringify!($e_arg), Value::from($e_arg))),*];
+                #[cfg(feature = "trace")]
+                let call = crate::trace::Call::begin(stringify!($e_name), &args);
+                #[cfg(feature = "capture")]
+                let captured = crate::capture::Pending::begin(stringify!($e_name), &args);
+                let handle = match intercept(stringify!($e_name)) {
+                    Some(status) => {
+                        if !errcode_ret.is_null() {
+                            *errcode_ret = status;
+                        }
+                        std::ptr::null_mut()
+                    }
+                    None => opencl_heads::ffi::$e_name($($e_arg,)* errcode_ret),
+                };
+                #[cfg(any(feature = "trace", feature = "capture"))]
+                let status = if errcode_ret.is_null() { None } else { Some(*errcode_ret) };
+                #[cfg(feature = "trace")]
+                if let Some(call) = call {
+                    call.end(status, Some(handle as usize));
+                }
+                #[cfg(feature = "capture")]
+                if let Some(captured) = captured {
+                    captured.end(status, Some(handle as usize));
+                }
+                handle
+            }
+        )*
+
+        $(
+            $(#[$o_attr])*
+            #[allow(non_snake_case)]
+            pub unsafe fn $o_name($($o_arg: $o_ty),*) $(-> $o_ret)? {
+                #[cfg(any(feature = "trace", feature = "capture"))]
+                let args = [$((stringify!($o_arg), Value::from($o_arg))),*];
+                #[cfg(feature = "trace")]
+                let call = crate::trace::Call::begin(stringify!($o_name), &args);
+                #[cfg(feature = "capture")]
+                let captured = crate::capture::Pending::begin(stringify!($o_name), &args);
+                let returned = opencl_heads::ffi::$o_name($($o_arg),*);
+                #[cfg(feature = "trace")]
+                if let Some(call) = call {
+                    call.end(None, returned.handle());
+                }
+                #[cfg(feature = "capture")]
+                if let Some(captured) = captured {
+                    captured.end(None, returned.handle());
+                }
+                returned
+            }
+        )*
+
+        /// Calls function with the arguments of frame, returning its status
+        /// and returned handle, or None if the api does not call function.
+        #[cfg(feature = "capture")]
+        pub(crate) unsafe fn replay(
+            function: &str,
+            frame: &crate::capture::Frame,
+        ) -> Option<(Option<cl_int>, Option<usize>)> {
+            match function {
+                $(
+                    $(#[$s_attr])*
+                    stringify!($s_name) => {
+                        let status = opencl_heads::ffi::$s_name(
+                            $(frame.arg::<$s_ty>(stringify!($s_arg))),*
+                        );
+                        Some((Some(status), None))
+                    }
+                )*
+                $(
+                    $(#[$e_attr])*
+                    stringify!($e_name) => {
+                        let mut status = 0;
+                        let handle = opencl_heads::ffi::$e_name(
+                            $(frame.arg::<$e_ty>(stringify!($e_arg)),)*
+                            &mut status,
+                        );
+                        Some((Some(status), Some(handle as usize)))
+                    }
+                )*
+                $(
+                    $(#[$o_attr])*
+                    stringify!($o_name) => {
+                        let returned = opencl_heads::ffi::$o_name(
+                            $(frame.arg::<$o_ty>(stringify!($o_arg))),*
+                        );
+                        Some((None, returned.handle()))
+                    }
+                )*
+                _ => None,
+            }
+        }
+    };
+}
+
+api_functions! {
+    status {
+        fn clGetPlatformIDs(
+            num_entries: cl_uint,
+            platforms: *mut cl_platform_id,
+            num_platforms: *mut cl_uint,
+        );
+        fn clGetPlatformInfo(
+            platform: cl_platform_id,
+            param_name: cl_platform_info,
+            param_value_size: size_t,
+            param_value: *mut c_void,
+            param_value_size_ret: *mut size_t,
+        );
+        fn clGetDeviceIDs(
+            platform: cl_platform_id,
+            device_type: cl_device_type,
+            num_entries: cl_uint,
+            devices: *mut cl_device_id,
+            num_devices: *mut cl_uint,
+        );
+        fn clGetDeviceInfo(
+            device: cl_device_id,
+            param_name: cl_device_info,
+            param_value_size: size_t,
+            param_value: *mut c_void,
+            param_value_size_ret: *mut size_t,
+        );
+        fn clCreateSubDevices(
+            in_device: cl_device_id,
+            properties: *const cl_device_partition_property,
+            num_devices: cl_uint,
+            out_devices: *mut cl_device_id,
+            num_devices_ret: *mut cl_uint,
+        );
+        fn clRetainDevice(device: cl_device_id);
+        fn clReleaseDevice(device: cl_device_id);
+        fn clSetDefaultDeviceCommandQueue(
+            context: cl_context,
+            device: cl_device_id,
+            command_queue: cl_command_queue,
+        );
+        fn clGetDeviceAndHostTimer(
+            device: cl_device_id,
+            device_timestamp: *mut cl_ulong,
+            host_timestamp: *mut cl_ulong,
+        );
+        fn clGetHostTimer(device: cl_device_id, host_timestamp: *mut cl_ulong);
+        fn clRetainContext(context: cl_context);
+        fn clReleaseContext(context: cl_context);
+        fn clGetContextInfo(
+            context: cl_context,
+            param_name: cl_context_info,
+            param_value_size: size_t,
+            param_value: *mut c_void,
+            param_value_size_ret: *mut size_t,
+        );
+        fn clSetContextDestructorCallback(
+            context: cl_context,
+            pfn_notify: extern "C" fn(context: cl_context, user_data: *mut c_void),
+            user_data: *mut c_void,
+        );
+        fn clRetainCommandQueue(command_queue: cl_command_queue);
+        fn clReleaseCommandQueue(command_queue: cl_command_queue);
+        fn clGetCommandQueueInfo(
+            command_queue: cl_command_queue,
+            param_name: cl_command_queue_info,
+            param_value_size: size_t,
+            param_value: *mut c_void,
+            param_value_size_ret: *mut size_t,
+        );
+        fn clRetainMemObject(memobj: cl_mem);
+        fn clReleaseMemObject(memobj: cl_mem);
+        fn clGetSupportedImageFormats(
+            context: cl_context,
+            flags: cl_mem_flags,
+            image_type: cl_mem_object_type,
+            num_entries: cl_uint,
+            image_formats: *mut cl_image_format,
+            num_image_formats: *mut cl_uint,
+        );
+        fn clGetMemObjectInfo(
+            memobj: cl_mem,
+            param_name: cl_mem_info,
+            param_value_size: size_t,
+            param_value: *mut c_void,
+            param_value_size_ret: *mut size_t,
+        );
+        fn clGetImageInfo(
+            image: cl_mem,
+            param_name: cl_image_info,
+            param_value_size: size_t,
+            param_value: *mut c_void,
+            param_value_size_ret: *mut size_t,
+        );
+        fn clGetPipeInfo(
+            pipe: cl_mem,
+            param_name: cl_pipe_info,
+            param_value_size: size_t,
+            param_value: *mut c_void,
+            param_value_size_ret: *mut size_t,
+        );
+        fn clSetMemObjectDestructorCallback(
+            memobj: cl_mem,
+            pfn_notify: Option<extern "C" fn(memobj: cl_mem, user_data: *mut c_void)>,
+            user_data: *mut c_void,
+        );
+        fn clRetainSampler(sampler: cl_sampler);
+        fn clReleaseSampler(sampler: cl_sampler);
+        fn clGetSamplerInfo(
+            sampler: cl_sampler,
+            param_name: cl_sampler_info,
+            param_value_size: size_t,
+            param_value: *mut c_void,
+            param_value_size_ret: *mut size_t,
+        );
+        fn clRetainProgram(program: cl_program);
+        fn clReleaseProgram(program: cl_program);
+        fn clBuildProgram(
+            program: cl_program,
+            num_devices: cl_uint,
+            device_list: *const cl_device_id,
+            options: *const c_char,
+            pfn_notify: Option<extern "C" fn(program: cl_program, user_data: *mut c_void)>,
+            user_data: *mut c_void,
+        );
+        fn clCompileProgram(
+            program: cl_program,
+            num_devices: cl_uint,
+            device_list: *const cl_device_id,
+            options: *const c_char,
+            num_input_headers: cl_uint,
+            input_headers: *const cl_program,
+            header_include_names: *const *const c_char,
+            pfn_notify: Option<extern "C" fn(program: cl_program, user_data: *mut c_void)>,
+            user_data: *mut c_void,
+        );
+        fn clGetProgramInfo(
+            program: cl_program,
+            param_name: cl_program_info,
+            param_value_size: size_t,
+            param_value: *mut c_void,
+            param_value_size_ret: *mut size_t,
+        );
+        fn clGetProgramBuildInfo(
+            program: cl_program,
+            device: cl_device_id,
+            param_name: cl_program_build_info,
+            param_value_size: size_t,
+            param_value: *mut c_void,
+            param_value_size_ret: *mut size_t,
+        );
+        fn clCreateKernelsInProgram(
+            program: cl_program,
+            num_kernels: cl_uint,
+            kernels: *mut cl_kernel,
+            num_kernels_ret: *mut cl_uint,
+        );
+        fn clRetainKernel(kernel: cl_kernel);
+        fn clReleaseKernel(kernel: cl_kernel);
+        fn clSetKernelArg(
+            kernel: cl_kernel,
+            arg_index: cl_uint,
+            arg_size: size_t,
+            arg_value: *const c_void,
+        );
+        fn clSetKernelArgSVMPointer(
+            kernel: cl_kernel,
+            arg_index: cl_uint,
+            arg_value: *const c_void,
+        );
+        fn clSetKernelExecInfo(
+            kernel: cl_kernel,
+            param_name: cl_kernel_exec_info,
+            param_value_size: size_t,
+            param_value: *const c_void,
+        );
+        fn clGetKernelInfo(
+            kernel: cl_kernel,
+            param_name: cl_kernel_info,
+            param_value_size: size_t,
+            param_value: *mut c_void,
+            param_value_size_ret: *mut size_t,
+        );
+        fn clGetKernelArgInfo(
+            kernel: cl_kernel,
+            arg_indx: cl_uint,
+            param_name: cl_kernel_arg_info,
+            param_value_size: size_t,
+            param_value: *mut c_void,
+            param_value_size_ret: *mut size_t,
+        );
+        fn clGetKernelWorkGroupInfo(
+            kernel: cl_kernel,
+            device: cl_device_id,
+            param_name: cl_kernel_work_group_info,
+            param_value_size: size_t,
+            param_value: *mut c_void,
+            param_value_size_ret: *mut size_t,
+        );
+        fn clGetKernelSubGroupInfo(
+            kernel: cl_kernel,
+            device: cl_device_id,
+            param_name: cl_kernel_sub_group_info,
+            input_value_size: size_t,
+            input_value: *const c_void,
+            param_value_size: size_t,
+            param_value: *mut c_void,
+            param_value_size_ret: *mut size_t,
+        );
+        fn clWaitForEvents(num_events: cl_uint, event_list: *const cl_event);
+        fn clGetEventInfo(
+            event: cl_event,
+            param_name: cl_event_info,
+            param_value_size: size_t,
+            param_value: *mut c_void,
+            param_value_size_ret: *mut size_t,
+        );
+        fn clRetainEvent(event: cl_event);
+        fn clReleaseEvent(event: cl_event);
+        fn clSetUserEventStatus(event: cl_event, execution_status: cl_int);
+        fn clSetEventCallback(
+            event: cl_event,
+            command_exec_callback_type: cl_int,
+            pfn_notify: Option<
+                extern "C" fn(
+                    event: cl_event,
+                    event_command_status: cl_int,
+                    user_data: *mut c_void,
+                ),
+            >,
+            user_data: *mut c_void,
+        );
+        fn clGetEventProfilingInfo(
+            event: cl_event,
+            param_name: cl_profiling_info,
+            param_value_size: size_t,
+            param_value: *mut c_void,
+            param_value_size_ret: *mut size_t,
+        );
+        fn clFlush(command_queue: cl_command_queue);
+        fn clFinish(command_queue: cl_command_queue);
+        fn clEnqueueReadBuffer(
+            command_queue: cl_command_queue,
+            buffer: cl_mem,
+            blocking_read: cl_bool,
+            offset: size_t,
+            size: size_t,
+            ptr: *mut c_void,
+            num_events_in_wait_list: cl_uint,
+            event_wait_list: *const cl_event,
+            event: *mut cl_event,
+        );
+        fn clEnqueueReadBufferRect(
+            command_queue: cl_command_queue,
+            buffer: cl_mem,
+            blocking_read: cl_bool,
+            buffer_origin: *const size_t,
+            host_origin: *const size_t,
+            region: *const size_t,
+            buffer_row_pitch: size_t,
+            buffer_slice_pitch: size_t,
+            host_row_pitch: size_t,
+            host_slice_pitch: size_t,
+            ptr: *mut c_void,
+            num_events_in_wait_list: cl_uint,
+            event_wait_list: *const cl_event,
+            event: *mut cl_event,
+        );
+        fn clEnqueueWriteBuffer(
+            command_queue: cl_command_queue,
+            buffer: cl_mem,
+            blocking_write: cl_bool,
+            offset: size_t,
+            size: size_t,
+            ptr: *const c_void,
+            num_events_in_wait_list: cl_uint,
+            event_wait_list: *const cl_event,
+            event: *mut cl_event,
+        );
+        fn clEnqueueWriteBufferRect(
+            command_queue: cl_command_queue,
+            buffer: cl_mem,
+            blocking_write: cl_bool,
+            buffer_origin: *const size_t,
+            host_origin: *const size_t,
+            region: *const size_t,
+            buffer_row_pitch: size_t,
+            buffer_slice_pitch: size_t,
+            host_row_pitch: size_t,
+            host_slice_pitch: size_t,
+            ptr: *const c_void,
+            num_events_in_wait_list: cl_uint,
+            event_wait_list: *const cl_event,
+            event: *mut cl_event,
+        );
+        fn clEnqueueFillBuffer(
+            command_queue: cl_command_queue,
+            buffer: cl_mem,
+            pattern: *const c_void,
+            pattern_size: size_t,
+            offset: size_t,
+            size: size_t,
+            num_events_in_wait_list: cl_uint,
+            event_wait_list: *const cl_event,
+            event: *mut cl_event,
+        );
+        fn clEnqueueCopyBuffer(
+            command_queue: cl_command_queue,
+            src_buffer: cl_mem,
+            dst_buffer: cl_mem,
+            src_offset: size_t,
+            dst_offset: size_t,
+            size: size_t,
+            num_events_in_wait_list: cl_uint,
+            event_wait_list: *const cl_event,
+            event: *mut cl_event,
+        );
+        fn clEnqueueCopyBufferRect(
+            command_queue: cl_command_queue,
+            src_buffer: cl_mem,
+            dst_buffer: cl_mem,
+            src_origin: *const size_t,
+            dst_origin: *const size_t,
+            region: *const size_t,
+            src_row_pitch: size_t,
+            src_slice_pitch: size_t,
+            dst_row_pitch: size_t,
+            dst_slice_pitch: size_t,
+            num_events_in_wait_list: cl_uint,
+            event_wait_list: *const cl_event,
+            event: *mut cl_event,
+        );
+        fn clEnqueueReadImage(
+            command_queue: cl_command_queue,
+            image: cl_mem,
+            blocking_read: cl_bool,
+            origin: *const size_t,
+            region: *const size_t,
+            row_pitch: size_t,
+            slice_pitch: size_t,
+            ptr: *mut c_void,
+            num_events_in_wait_list: cl_uint,
+            event_wait_list: *const cl_event,
+            event: *mut cl_event,
+        );
+        fn clEnqueueWriteImage(
+            command_queue: cl_command_queue,
+            image: cl_mem,
+            blocking_write: cl_bool,
+            origin: *const size_t,
+            region: *const size_t,
+            input_row_pitch: size_t,
+            input_slice_pitch: size_t,
+            ptr: *const c_void,
+            num_events_in_wait_list: cl_uint,
+            event_wait_list: *const cl_event,
+            event: *mut cl_event,
+        );
+        fn clEnqueueFillImage(
+            command_queue: cl_command_queue,
+            image: cl_mem,
+            fill_color: *const c_void,
+            origin: *const size_t,
+            region: *const size_t,
+            num_events_in_wait_list: cl_uint,
+            event_wait_list: *const cl_event,
+            event: *mut cl_event,
+        );
+        fn clEnqueueCopyImage(
+            command_queue: cl_command_queue,
+            src_image: cl_mem,
+            dst_image: cl_mem,
+            src_origin: *const size_t,
+            dst_origin: *const size_t,
+            region: *const size_t,
+            num_events_in_wait_list: cl_uint,
+            event_wait_list: *const cl_event,
+            event: *mut cl_event,
+        );
+        fn clEnqueueCopyImageToBuffer(
+            command_queue: cl_command_queue,
+            src_image: cl_mem,
+            dst_buffer: cl_mem,
+            src_origin: *const size_t,
+            region: *const size_t,
+            dst_offset: size_t,
+            num_events_in_wait_list: cl_uint,
+            event_wait_list: *const cl_event,
+            event: *mut cl_event,
+        );
+        fn clEnqueueCopyBufferToImage(
+            command_queue: cl_command_queue,
+            src_buffer: cl_mem,
+            dst_image: cl_mem,
+            src_offset: size_t,
+            dst_origin: *const size_t,
+            region: *const size_t,
+            num_events_in_wait_list: cl_uint,
+            event_wait_list: *const cl_event,
+            event: *mut cl_event,
+        );
+        fn clEnqueueUnmapMemObject(
+            command_queue: cl_command_queue,
+            memobj: cl_mem,
+            mapped_ptr: *mut c_void,
+            num_events_in_wait_list: cl_uint,
+            event_wait_list: *const cl_event,
+            event: *mut cl_event,
+        );
+        fn clEnqueueMigrateMemObjects(
+            command_queue: cl_command_queue,
+            num_mem_objects: cl_uint,
+            mem_objects: *const cl_mem,
+            flags: cl_mem_migration_flags,
+            num_events_in_wait_list: cl_uint,
+            event_wait_list: *const cl_event,
+            event: *mut cl_event,
+        );
+        fn clEnqueueNDRangeKernel(
+            command_queue: cl_command_queue,
+            kernel: cl_kernel,
+            work_dim: cl_uint,
+            global_work_offset: *const size_t,
+            global_work_size: *const size_t,
+            local_work_size: *const size_t,
+            num_events_in_wait_list: cl_uint,
+            event_wait_list: *const cl_event,
+            event: *mut cl_event,
+        );
+        fn clEnqueueNativeKernel(
+            command_queue: cl_command_queue,
+            user_func: Option<extern "C" fn(*mut c_void)>,
+            args: *mut c_void,
+            cb_args: size_t,
+            num_mem_objects: cl_uint,
+            mem_list: *const cl_mem,
+            args_mem_loc: *const *const c_void,
+            num_events_in_wait_list: cl_uint,
+            event_wait_list: *const cl_event,
+            event: *mut cl_event,
+        );
+        fn clEnqueueMarkerWithWaitList(
+            command_queue: cl_command_queue,
+            num_events_in_wait_list: cl_uint,
+            event_wait_list: *const cl_event,
+            event: *mut cl_event,
+        );
+        fn clEnqueueBarrierWithWaitList(
+            command_queue: cl_command_queue,
+            num_events_in_wait_list: cl_uint,
+            event_wait_list: *const cl_event,
+            event: *mut cl_event,
+        );
+        fn clEnqueueSVMFree(
+            command_queue: cl_command_queue,
+            num_svm_pointers: cl_uint,
+            svm_pointers: *const *const c_void,
+            pfn_free_func: Option<
+                extern "C" fn(
+                    queue: cl_command_queue,
+                    num_svm_pointers: cl_uint,
+                    svm_pointers: *const *const c_void,
+                    user_data: *mut c_void,
+                ),
+            >,
+            user_data: *mut c_void,
+            num_events_in_wait_list: cl_uint,
+            event_wait_list: *const cl_event,
+            event: *mut cl_event,
+        );
+        fn clEnqueueSVMMemcpy(
+            command_queue: cl_command_queue,
+            blocking_copy: cl_bool,
+            dst_ptr: *mut c_void,
+            src_ptr: *const c_void,
+            size: size_t,
+            num_events_in_wait_list: cl_uint,
+            event_wait_list: *const cl_event,
+            event: *mut cl_event,
+        );
+        fn clEnqueueSVMMemFill(
+            command_queue: cl_command_queue,
+            svm_ptr: *mut c_void,
+            pattern: *const c_void,
+            pattern_size: size_t,
+            size: size_t,
+            num_events_in_wait_list: cl_uint,
+            event_wait_list: *const cl_event,
+            event: *mut cl_event,
+        );
+        fn clEnqueueSVMMap(
+            command_queue: cl_command_queue,
+            blocking_map: cl_bool,
+            flags: cl_map_flags,
+            svm_ptr: *mut c_void,
+            size: size_t,
+            num_events_in_wait_list: cl_uint,
+            event_wait_list: *const cl_event,
+            event: *mut cl_event,
+        );
+        fn clEnqueueSVMUnmap(
+            command_queue: cl_command_queue,
+            svm_ptr: *mut c_void,
+            num_events_in_wait_list: cl_uint,
+            event_wait_list: *const cl_event,
+            event: *mut cl_event,
+        );
+        fn clEnqueueSVMMigrateMem(
+            command_queue: cl_command_queue,
+            num_svm_pointers: cl_uint,
+            svm_pointers: *const *const c_void,
+            sizes: *const size_t,
+            flags: cl_mem_migration_flags,
+            num_events_in_wait_list: cl_uint,
+            event_wait_list: *const cl_event,
+            event: *mut cl_event,
+        );
+        #[cfg(feature = "depr_1_0")]
+        fn clSetCommandQueueProperty(
+            command_queue: cl_command_queue,
+            properties: cl_command_queue_properties,
+            enable: cl_bool,
+            old_properties: *mut cl_command_queue_properties,
+        );
+        #[cfg(feature = "depr_2_0")]
+        fn clEnqueueTask(
+            command_queue: cl_command_queue,
+            kernel: cl_kernel,
+            num_events_in_wait_list: cl_uint,
+            event_wait_list: *const cl_event,
+            event: *mut cl_event,
+        );
+    }
+    errcode {
+        fn clCreateContext(
+            properties: *const cl_context_properties,
+            num_devices: cl_uint,
+            devices: *const cl_device_id,
+            pfn_notify: Option<
+                extern "C" fn(
+                    errinfo: *const c_char,
+                    private_info: *const c_void,
+                    cb: size_t,
+                    user_data: *mut c_void,
+                ),
+            >,
+            user_data: *mut c_void,
+        ) -> cl_context;
+        fn clCreateContextFromType(
+            properties: *const cl_context_properties,
+            device_type: cl_device_type,
+            pfn_notify: Option<
+                extern "C" fn(
+                    errinfo: *const c_char,
+                    private_info: *const c_void,
+                    cb: size_t,
+                    user_data: *mut c_void,
+                ),
+            >,
+            user_data: *mut c_void,
+        ) -> cl_context;
+        fn clCreateCommandQueueWithProperties(
+            context: cl_context,
+            device: cl_device_id,
+            properties: *const cl_queue_properties,
+        ) -> cl_command_queue;
+        fn clCreateBuffer(
+            context: cl_context,
+            flags: cl_mem_flags,
+            size: size_t,
+            host_ptr: *mut c_void,
+        ) -> cl_mem;
+        fn clCreateSubBuffer(
+            buffer: cl_mem,
+            flags: cl_mem_flags,
+            buffer_create_type: cl_buffer_create_type,
+            buffer_create_info: *const c_void,
+        ) -> cl_mem;
+        fn clCreateImage(
+            context: cl_context,
+            flags: cl_mem_flags,
+            image_format: *const cl_image_format,
+            image_desc: *const cl_image_desc,
+            host_ptr: *mut c_void,
+        ) -> cl_mem;
+        fn clCreatePipe(
+            context: cl_context,
+            flags: cl_mem_flags,
+            pipe_packet_size: cl_uint,
+            pipe_max_packets: cl_uint,
+            properties: *const cl_pipe_properties,
+        ) -> cl_mem;
+        fn clCreateBufferWithProperties(
+            context: cl_context,
+            properties: *const cl_mem_properties,
+            flags: cl_mem_flags,
+            size: size_t,
+            host_ptr: *mut c_void,
+        ) -> cl_mem;
+        fn clCreateImageWithProperties(
+            context: cl_context,
+            properties: *const cl_mem_properties,
+            flags: cl_mem_flags,
+            image_format: *const cl_image_format,
+            image_desc: *const cl_image_desc,
+            host_ptr: *mut c_void,
+        ) -> cl_mem;
+        fn clCreateSamplerWithProperties(
+            context: cl_context,
+            sampler_properties: *const cl_sampler_properties,
+        ) -> cl_sampler;
+        fn clCreateProgramWithSource(
+            context: cl_context,
+            count: cl_uint,
+            strings: *const *const c_char,
+            lengths: *const size_t,
+        ) -> cl_program;
+        fn clCreateProgramWithBinary(
+            context: cl_context,
+            num_devices: cl_uint,
+            device_list: *const cl_device_id,
+            lengths: *const size_t,
+            binaries: *const *const c_uchar,
+            binary_status: *mut cl_int,
+        ) -> cl_program;
+        fn clCreateProgramWithBuiltInKernels(
+            context: cl_context,
+            num_devices: cl_uint,
+            device_list: *const cl_device_id,
+            kernel_names: *const c_char,
+        ) -> cl_program;
+        fn clCreateProgramWithIL(
+            context: cl_context,
+            il: *const c_void,
+            length: size_t,
+        ) -> cl_program;
+        fn clLinkProgram(
+            context: cl_context,
+            num_devices: cl_uint,
+            device_list: *const cl_device_id,
+            options: *const c_char,
+            num_input_programs: cl_uint,
+            input_programs: *const cl_program,
+            pfn_notify: Option<extern "C" fn(program: cl_program, user_data: *mut c_void)>,
+            user_data: *mut c_void,
+        ) -> cl_program;
+        fn clCreateKernel(program: cl_program, kernel_name: *const c_char) -> cl_kernel;
+        fn clCloneKernel(source_kernel: cl_kernel) -> cl_kernel;
+        fn clCreateUserEvent(context: cl_context) -> cl_event;
+        fn clEnqueueMapBuffer(
+            command_queue: cl_command_queue,
+            buffer: cl_mem,
+            blocking_map: cl_bool,
+            map_flags: cl_map_flags,
+            offset: size_t,
+            size: size_t,
+            num_events_in_wait_list: cl_uint,
+            event_wait_list: *const cl_event,
+            event: *mut cl_event,
+        ) -> *mut c_void;
+        fn clEnqueueMapImage(
+            command_queue: cl_command_queue,
+            image: cl_mem,
+            blocking_map: cl_bool,
+            map_flags: cl_map_flags,
+            origin: *const size_t,
+            region: *const size_t,
+            image_row_pitch: *mut size_t,
+            image_slice_pitch: *mut size_t,
+            num_events_in_wait_list: cl_uint,
+            event_wait_list: *const cl_event,
+            event: *mut cl_event,
+        ) -> *mut c_void;
+        fn clCreateImage2D(
+            context: cl_context,
+            flags: cl_mem_flags,
+            image_format: *const cl_image_format,
+            image_width: size_t,
+            image_height: size_t,
+            image_row_pitch: size_t,
+            host_ptr: *mut c_void,
+        ) -> cl_mem;
+        fn clCreateImage3D(
+            context: cl_context,
+            flags: cl_mem_flags,
+            image_format: *const cl_image_format,
+            image_width: size_t,
+            image_height: size_t,
+            image_depth: size_t,
+            image_row_pitch: size_t,
+            image_slice_pitch: size_t,
+            host_ptr: *mut c_void,
+        ) -> cl_mem;
+        fn clCreateCommandQueue(
+            context: cl_context,
+            device: cl_device_id,
+            properties: cl_command_queue_properties,
+        ) -> cl_command_queue;
+        fn clCreateSampler(
+            context: cl_context,
+            normalized_coords: cl_bool,
+            addressing_mode: cl_addressing_mode,
+            filter_mode: cl_filter_mode,
+        ) -> cl_sampler;
+    }
+    other {
+        fn clSVMAlloc(
+            context: cl_context,
+            flags: cl_svm_mem_flags,
+            size: size_t,
+            alignment: cl_uint,
+        ) -> *mut c_void;
+        fn clSVMFree(context: cl_context, svm_pointer: *mut c_void);
+    }
+}
@@ -17,5 +17,12 @@
 */
 
 pub mod api;
-mod errors;
-mod objects;
+#[cfg(feature = "capture")]
+pub mod capture;
+pub mod errors;
+#[cfg(feature = "fault")]
+pub mod fault;
+mod ffi;
+pub mod objects;
+#[cfg(feature = "trace")]
+pub mod trace;
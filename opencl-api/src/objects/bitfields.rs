@@ -16,26 +16,36 @@
  * limitations under the License.
 */
 use crate::errors::ValidationError;
-use crate::gen_add_trait;
 use crate::objects::traits::GetSetGo;
 use crate::objects::types::BitfieldResult;
+use crate::{gen_bitfield_ops, gen_consts};
 use opencl_heads::consts::*;
 use opencl_heads::types::*;
 
+/// True when at most one flag of each group is set in `value`.
+fn exclusive(value: cl_bitfield, groups: &[&[cl_bitfield]]) -> bool {
+    groups
+        .iter()
+        .all(|group| group.iter().filter(|&&x| value & x != 0).count() <= 1)
+}
+
 #[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
 pub struct DeviceType(cl_device_type);
-impl DeviceType {
-    /* cl_device_type - cl_bitfield */
-    pub const DEFAULT: cl_device_type = CL_DEVICE_TYPE_DEFAULT;
-    pub const CPU: cl_device_type = CL_DEVICE_TYPE_CPU;
-    pub const GPU: cl_device_type = CL_DEVICE_TYPE_GPU;
-    pub const ACCELERATOR: cl_device_type = CL_DEVICE_TYPE_ACCELERATOR;
-    // 1.2;
-    pub const CUSTOM: cl_device_type = CL_DEVICE_TYPE_CUSTOM;
-    pub const ALL: cl_device_type = CL_DEVICE_TYPE_ALL;
+gen_consts! {
+    DeviceType: cl_device_type {
+        /* cl_device_type - cl_bitfield */
+        DEFAULT = CL_DEVICE_TYPE_DEFAULT,
+        CPU = CL_DEVICE_TYPE_CPU,
+        GPU = CL_DEVICE_TYPE_GPU,
+        ACCELERATOR = CL_DEVICE_TYPE_ACCELERATOR,
+        // 1.2;
+        CUSTOM = CL_DEVICE_TYPE_CUSTOM,
+        ALL = CL_DEVICE_TYPE_ALL,
+    }
 }
 
-gen_add_trait!(DeviceType);
+gen_bitfield_ops!(DeviceType);
 
 impl GetSetGo for DeviceType {
     fn new(value: cl_device_type) -> BitfieldResult<Self> {
@@ -65,25 +75,27 @@ impl GetSetGo for DeviceType {
 }
 
 #[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
 pub struct DeviceFPConfig(cl_device_fp_config);
-impl DeviceFPConfig {
-    /* cl_device_fp_config - cl_bitfield */
-    pub const DENORM: cl_device_fp_config = CL_FP_DENORM;
-    pub const INF_NAN: cl_device_fp_config = CL_FP_INF_NAN;
-    pub const ROUND_TO_NEAREST: cl_device_fp_config = CL_FP_ROUND_TO_NEAREST;
-    pub const ROUND_TO_ZERO: cl_device_fp_config = CL_FP_ROUND_TO_ZERO;
-    pub const ROUND_TO_INF: cl_device_fp_config = CL_FP_ROUND_TO_INF;
-    pub const FMA: cl_device_fp_config = CL_FP_FMA;
-    // #ifdef CL_VERSION_1_1;
-    pub const SOFT_FLOAT: cl_device_fp_config = CL_FP_SOFT_FLOAT;
-    // #endif;
-    // #ifdef CL_VERSION_1_2;
-    pub const CORRECTLY_ROUNDED_DIVIDE_SQRT: cl_device_fp_config =
-        CL_FP_CORRECTLY_ROUNDED_DIVIDE_SQRT;
-    // #endif;
+gen_consts! {
+    DeviceFPConfig: cl_device_fp_config {
+        /* cl_device_fp_config - cl_bitfield */
+        DENORM = CL_FP_DENORM,
+        INF_NAN = CL_FP_INF_NAN,
+        ROUND_TO_NEAREST = CL_FP_ROUND_TO_NEAREST,
+        ROUND_TO_ZERO = CL_FP_ROUND_TO_ZERO,
+        ROUND_TO_INF = CL_FP_ROUND_TO_INF,
+        FMA = CL_FP_FMA,
+        // #ifdef CL_VERSION_1_1;
+        SOFT_FLOAT = CL_FP_SOFT_FLOAT,
+        // #endif;
+        // #ifdef CL_VERSION_1_2;
+        CORRECTLY_ROUNDED_DIVIDE_SQRT = CL_FP_CORRECTLY_ROUNDED_DIVIDE_SQRT,
+        // #endif;
+    }
 }
 
-gen_add_trait!(DeviceFPConfig);
+gen_bitfield_ops!(DeviceFPConfig);
 
 impl GetSetGo for DeviceFPConfig {
     fn new(value: cl_device_fp_config) -> BitfieldResult<Self> {
@@ -127,14 +139,17 @@ impl GetSetGo for DeviceFPConfig {
 }
 
 #[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
 pub struct DeviceExecCapabilities(cl_device_exec_capabilities);
-impl DeviceExecCapabilities {
-    /* cl_device_exec_capabilities - cl_bitfield */
-    pub const KERNEL: cl_device_exec_capabilities = CL_EXEC_KERNEL;
-    pub const NATIVE_KERNEL: cl_device_exec_capabilities = CL_EXEC_NATIVE_KERNEL;
+gen_consts! {
+    DeviceExecCapabilities: cl_device_exec_capabilities {
+        /* cl_device_exec_capabilities - cl_bitfield */
+        KERNEL = CL_EXEC_KERNEL,
+        NATIVE_KERNEL = CL_EXEC_NATIVE_KERNEL,
+    }
 }
 
-gen_add_trait!(DeviceExecCapabilities);
+gen_bitfield_ops!(DeviceExecCapabilities);
 
 impl GetSetGo for DeviceExecCapabilities {
     fn new(value: cl_device_exec_capabilities) -> BitfieldResult<Self> {
@@ -164,20 +179,21 @@ impl GetSetGo for DeviceExecCapabilities {
 }
 
 #[non_exhaustive]
-#[derive(Debug)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct CommandQueueProperties(cl_command_queue_properties);
-impl CommandQueueProperties {
-    /* cl_command_queue_properties - cl_bitfield */
-    pub const OUT_OF_ORDER_EXEC_MODE_ENABLE: cl_command_queue_properties =
-        CL_QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE;
-    pub const PROFILING_ENABLE: cl_command_queue_properties = CL_QUEUE_PROFILING_ENABLE;
-    // #ifdef CL_VERSION_2_0;
-    pub const ON_DEVICE: cl_command_queue_properties = CL_QUEUE_ON_DEVICE;
-    pub const ON_DEVICE_DEFAULT: cl_command_queue_properties = CL_QUEUE_ON_DEVICE_DEFAULT;
-    // #endif;
+gen_consts! {
+    CommandQueueProperties: cl_command_queue_properties {
+        /* cl_command_queue_properties - cl_bitfield */
+        OUT_OF_ORDER_EXEC_MODE_ENABLE = CL_QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE,
+        PROFILING_ENABLE = CL_QUEUE_PROFILING_ENABLE,
+        // #ifdef CL_VERSION_2_0;
+        ON_DEVICE = CL_QUEUE_ON_DEVICE,
+        ON_DEVICE_DEFAULT = CL_QUEUE_ON_DEVICE_DEFAULT,
+        // #endif;
+    }
 }
 
-gen_add_trait!(CommandQueueProperties);
+gen_bitfield_ops!(CommandQueueProperties);
 
 impl GetSetGo for CommandQueueProperties {
     fn new(value: cl_command_queue_properties) -> BitfieldResult<Self> {
@@ -213,21 +229,23 @@ impl GetSetGo for CommandQueueProperties {
 }
 
 #[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
 pub struct DeviceAffinityDomain(cl_device_affinity_domain);
-impl DeviceAffinityDomain {
-    // #ifdef CL_VERSION_1_2;
-    /* cl_device_affinity_domain - cl_bitfield*/
-    pub const NUMA: cl_device_affinity_domain = CL_DEVICE_AFFINITY_DOMAIN_NUMA;
-    pub const L4_CACHE: cl_device_affinity_domain = CL_DEVICE_AFFINITY_DOMAIN_L4_CACHE;
-    pub const L3_CACHE: cl_device_affinity_domain = CL_DEVICE_AFFINITY_DOMAIN_L3_CACHE;
-    pub const L2_CACHE: cl_device_affinity_domain = CL_DEVICE_AFFINITY_DOMAIN_L2_CACHE;
-    pub const L1_CACHE: cl_device_affinity_domain = CL_DEVICE_AFFINITY_DOMAIN_L1_CACHE;
-    pub const NEXT_PARTITIONABLE: cl_device_affinity_domain =
-        CL_DEVICE_AFFINITY_DOMAIN_NEXT_PARTITIONABLE;
-    // #endif;
+gen_consts! {
+    DeviceAffinityDomain: cl_device_affinity_domain {
+        // #ifdef CL_VERSION_1_2;
+        /* cl_device_affinity_domain - cl_bitfield*/
+        NUMA = CL_DEVICE_AFFINITY_DOMAIN_NUMA,
+        L4_CACHE = CL_DEVICE_AFFINITY_DOMAIN_L4_CACHE,
+        L3_CACHE = CL_DEVICE_AFFINITY_DOMAIN_L3_CACHE,
+        L2_CACHE = CL_DEVICE_AFFINITY_DOMAIN_L2_CACHE,
+        L1_CACHE = CL_DEVICE_AFFINITY_DOMAIN_L1_CACHE,
+        NEXT_PARTITIONABLE = CL_DEVICE_AFFINITY_DOMAIN_NEXT_PARTITIONABLE,
+        // #endif;
+    }
 }
 
-gen_add_trait!(DeviceAffinityDomain);
+gen_bitfield_ops!(DeviceAffinityDomain);
 
 impl GetSetGo for DeviceAffinityDomain {
     fn new(value: cl_device_affinity_domain) -> BitfieldResult<Self> {
@@ -259,18 +277,21 @@ impl GetSetGo for DeviceAffinityDomain {
 }
 
 #[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
 pub struct DeviceSVMCapabilities(cl_device_svm_capabilities);
-impl DeviceSVMCapabilities {
-    // #ifdef CL_VERSION_2_0;
-    /* cl_device_svm_capabilities - cl_bitfield */
-    pub const COARSE_GRAIN_BUFFER: cl_device_svm_capabilities = CL_DEVICE_SVM_COARSE_GRAIN_BUFFER;
-    pub const FINE_GRAIN_BUFFER: cl_device_svm_capabilities = CL_DEVICE_SVM_FINE_GRAIN_BUFFER;
-    pub const FINE_GRAIN_SYSTEM: cl_device_svm_capabilities = CL_DEVICE_SVM_FINE_GRAIN_SYSTEM;
-    pub const ATOMICS: cl_device_svm_capabilities = CL_DEVICE_SVM_ATOMICS;
-    // #endif;
+gen_consts! {
+    DeviceSVMCapabilities: cl_device_svm_capabilities {
+        // #ifdef CL_VERSION_2_0;
+        /* cl_device_svm_capabilities - cl_bitfield */
+        COARSE_GRAIN_BUFFER = CL_DEVICE_SVM_COARSE_GRAIN_BUFFER,
+        FINE_GRAIN_BUFFER = CL_DEVICE_SVM_FINE_GRAIN_BUFFER,
+        FINE_GRAIN_SYSTEM = CL_DEVICE_SVM_FINE_GRAIN_SYSTEM,
+        ATOMICS = CL_DEVICE_SVM_ATOMICS,
+        // #endif;
+    }
 }
 
-gen_add_trait!(DeviceSVMCapabilities);
+gen_bitfield_ops!(DeviceSVMCapabilities);
 
 impl GetSetGo for DeviceSVMCapabilities {
     fn new(value: cl_device_svm_capabilities) -> BitfieldResult<Self> {
@@ -302,30 +323,48 @@ impl GetSetGo for DeviceSVMCapabilities {
 }
 
 #[non_exhaustive]
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct MemFlags(cl_mem_flags);
-impl MemFlags {
-    /* cl_mem_flags and cl_svm_mem_flags - cl_bitfield */
-    pub const READ_WRITE: cl_mem_flags = CL_MEM_READ_WRITE;
-    pub const WRITE_ONLY: cl_mem_flags = CL_MEM_WRITE_ONLY;
-    pub const READ_ONLY: cl_mem_flags = CL_MEM_READ_ONLY;
-    pub const USE_HOST_PTR: cl_mem_flags = CL_MEM_USE_HOST_PTR;
-    pub const ALLOC_HOST_PTR: cl_mem_flags = CL_MEM_ALLOC_HOST_PTR;
-    pub const COPY_HOST_PTR: cl_mem_flags = CL_MEM_COPY_HOST_PTR;
-    /* reserved: cl_bitfield = 1 << 6: cl_bitfield = */
-    // #ifdef CL_VERSION_1_2;
-    pub const HOST_WRITE_ONLY: cl_mem_flags = CL_MEM_HOST_WRITE_ONLY;
-    pub const HOST_READ_ONLY: cl_mem_flags = CL_MEM_HOST_READ_ONLY;
-    pub const HOST_NO_ACCESS: cl_mem_flags = CL_MEM_HOST_NO_ACCESS;
-    // #endif;
-    // #ifdef CL_VERSION_2_0;
-    pub const KERNEL_READ_AND_WRITE: cl_mem_flags = CL_MEM_KERNEL_READ_AND_WRITE;
-    pub const SVM_FINE_GRAIN_BUFFER: cl_mem_flags = CL_MEM_SVM_FINE_GRAIN_BUFFER; /* used by cl_svm_mem_flags only */
-    pub const SVM_ATOMICS: cl_mem_flags = CL_MEM_SVM_ATOMICS; /* used by cl_svm_mem_flags only */
-    // #endif;
+gen_consts! {
+    MemFlags: cl_mem_flags {
+        /* cl_mem_flags and cl_svm_mem_flags - cl_bitfield */
+        READ_WRITE = CL_MEM_READ_WRITE,
+        WRITE_ONLY = CL_MEM_WRITE_ONLY,
+        READ_ONLY = CL_MEM_READ_ONLY,
+        USE_HOST_PTR = CL_MEM_USE_HOST_PTR,
+        ALLOC_HOST_PTR = CL_MEM_ALLOC_HOST_PTR,
+        COPY_HOST_PTR = CL_MEM_COPY_HOST_PTR,
+        /* reserved: cl_bitfield = 1 << 6: cl_bitfield = */
+        // #ifdef CL_VERSION_1_2;
+        HOST_WRITE_ONLY = CL_MEM_HOST_WRITE_ONLY,
+        HOST_READ_ONLY = CL_MEM_HOST_READ_ONLY,
+        HOST_NO_ACCESS = CL_MEM_HOST_NO_ACCESS,
+        // #endif;
+        // #ifdef CL_VERSION_2_0;
+        KERNEL_READ_AND_WRITE = CL_MEM_KERNEL_READ_AND_WRITE,
+        SVM_FINE_GRAIN_BUFFER = CL_MEM_SVM_FINE_GRAIN_BUFFER, /* used by cl_svm_mem_flags only */
+        SVM_ATOMICS = CL_MEM_SVM_ATOMICS, /* used by cl_svm_mem_flags only */
+        // #endif;
+    }
 }
 
-gen_add_trait!(MemFlags);
+gen_bitfield_ops!(MemFlags);
+
+/// Groups of memory flags of which at most one may be set.
+const MEM_FLAGS_EXCLUSIVE: &[&[cl_mem_flags]] = &[
+    &[
+        MemFlags::READ_WRITE,
+        MemFlags::WRITE_ONLY,
+        MemFlags::READ_ONLY,
+    ],
+    &[MemFlags::USE_HOST_PTR, MemFlags::ALLOC_HOST_PTR],
+    &[MemFlags::USE_HOST_PTR, MemFlags::COPY_HOST_PTR],
+    &[
+        MemFlags::HOST_WRITE_ONLY,
+        MemFlags::HOST_READ_ONLY,
+        MemFlags::HOST_NO_ACCESS,
+    ],
+];
 
 impl GetSetGo for MemFlags {
     fn new(value: cl_mem_flags) -> BitfieldResult<Self> {
@@ -344,7 +383,7 @@ impl GetSetGo for MemFlags {
             + T::SVM_FINE_GRAIN_BUFFER
             + T::SVM_ATOMICS;
         match value {
-            val @ 1..=MAX => Ok(MemFlags(val)),
+            val @ 1..=MAX if exclusive(val, MEM_FLAGS_EXCLUSIVE) => Ok(MemFlags(val)),
             _ => Err(ValidationError::InvalidBitfield(fn_name)),
         }
     }
@@ -367,7 +406,7 @@ impl GetSetGo for MemFlags {
             + T::SVM_FINE_GRAIN_BUFFER
             + T::SVM_ATOMICS;
         match value {
-            val @ 1..=MAX => {
+            val @ 1..=MAX if exclusive(val, MEM_FLAGS_EXCLUSIVE) => {
                 self.0 = val;
                 Ok(())
             }
@@ -379,15 +418,17 @@ impl GetSetGo for MemFlags {
 // #ifdef CL_VERSION_1_2;
 /* cl_mem_migration_flags - cl_bitfield */
 #[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
 pub struct MemMigrationFlags(cl_mem_migration_flags);
-impl MemMigrationFlags {
-    pub const OBJECT_HOST: cl_mem_migration_flags = CL_MIGRATE_MEM_OBJECT_HOST;
-    pub const OBJECT_CONTENT_UNDEFINED: cl_mem_migration_flags =
-        CL_MIGRATE_MEM_OBJECT_CONTENT_UNDEFINED;
+gen_consts! {
+    MemMigrationFlags: cl_mem_migration_flags {
+        OBJECT_HOST = CL_MIGRATE_MEM_OBJECT_HOST,
+        OBJECT_CONTENT_UNDEFINED = CL_MIGRATE_MEM_OBJECT_CONTENT_UNDEFINED,
+    }
 }
 // #endif;
 
-gen_add_trait!(MemMigrationFlags);
+gen_bitfield_ops!(MemMigrationFlags);
 
 impl GetSetGo for MemMigrationFlags {
     fn new(value: cl_mem_migration_flags) -> BitfieldResult<Self> {
@@ -417,17 +458,26 @@ impl GetSetGo for MemMigrationFlags {
 }
 
 #[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
 pub struct MapFlags(cl_map_flags);
-impl MapFlags {
-    /* cl_map_flags - cl_bitfield */
-    pub const READ: cl_map_flags = CL_MAP_READ;
-    pub const WRITE: cl_map_flags = CL_MAP_WRITE;
-    // #ifdef CL_VERSION_1_2;
-    pub const WRITE_INVALIDATE_REGION: cl_map_flags = CL_MAP_WRITE_INVALIDATE_REGION;
-    // #endif;
+gen_consts! {
+    MapFlags: cl_map_flags {
+        /* cl_map_flags - cl_bitfield */
+        READ = CL_MAP_READ,
+        WRITE = CL_MAP_WRITE,
+        // #ifdef CL_VERSION_1_2;
+        WRITE_INVALIDATE_REGION = CL_MAP_WRITE_INVALIDATE_REGION,
+        // #endif;
+    }
 }
 
-gen_add_trait!(MapFlags);
+gen_bitfield_ops!(MapFlags);
+
+/// Groups of map flags of which at most one may be set.
+const MAP_FLAGS_EXCLUSIVE: &[&[cl_map_flags]] = &[
+    &[MapFlags::READ, MapFlags::WRITE_INVALIDATE_REGION],
+    &[MapFlags::WRITE, MapFlags::WRITE_INVALIDATE_REGION],
+];
 
 impl GetSetGo for MapFlags {
     fn new(value: cl_map_flags) -> BitfieldResult<Self> {
@@ -435,7 +485,7 @@ impl GetSetGo for MapFlags {
         let fn_name = "MapFlags";
         const MAX: cl_bitfield = T::READ + T::WRITE + T::WRITE_INVALIDATE_REGION;
         match value {
-            val @ 1..=MAX => Ok(MapFlags(val)),
+            val @ 1..=MAX if exclusive(val, MAP_FLAGS_EXCLUSIVE) => Ok(MapFlags(val)),
             _ => Err(ValidationError::InvalidBitfield(fn_name)),
         }
     }
@@ -447,7 +497,7 @@ impl GetSetGo for MapFlags {
         let fn_name = "MapFlags";
         const MAX: cl_bitfield = T::READ + T::WRITE + T::WRITE_INVALIDATE_REGION;
         match value {
-            val @ 1..=MAX => {
+            val @ 1..=MAX if exclusive(val, MAP_FLAGS_EXCLUSIVE) => {
                 self.0 = val;
                 Ok(())
             }
@@ -457,21 +507,24 @@ impl GetSetGo for MapFlags {
 }
 
 #[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
 pub struct KernelArgTypeQualifier(cl_kernel_arg_type_qualifier);
-impl KernelArgTypeQualifier {
-    // #ifdef CL_VERSION_1_2;
-    /* cl_kernel_arg_type_qualifier - cl_bitfield */
-    pub const NONE: cl_kernel_arg_type_qualifier = CL_KERNEL_ARG_TYPE_NONE;
-    pub const CONST: cl_kernel_arg_type_qualifier = CL_KERNEL_ARG_TYPE_CONST;
-    pub const RESTRICT: cl_kernel_arg_type_qualifier = CL_KERNEL_ARG_TYPE_RESTRICT;
-    pub const VOLATILE: cl_kernel_arg_type_qualifier = CL_KERNEL_ARG_TYPE_VOLATILE;
-    // #ifdef CL_VERSION_2_0;
-    pub const PIPE: cl_kernel_arg_type_qualifier = CL_KERNEL_ARG_TYPE_PIPE;
-    // #endif;
-    // #endif;
+gen_consts! {
+    KernelArgTypeQualifier: cl_kernel_arg_type_qualifier {
+        // #ifdef CL_VERSION_1_2;
+        /* cl_kernel_arg_type_qualifier - cl_bitfield */
+        NONE = CL_KERNEL_ARG_TYPE_NONE,
+        CONST = CL_KERNEL_ARG_TYPE_CONST,
+        RESTRICT = CL_KERNEL_ARG_TYPE_RESTRICT,
+        VOLATILE = CL_KERNEL_ARG_TYPE_VOLATILE,
+        // #ifdef CL_VERSION_2_0;
+        PIPE = CL_KERNEL_ARG_TYPE_PIPE,
+        // #endif;
+        // #endif;
+    }
 }
 
-gen_add_trait!(KernelArgTypeQualifier);
+gen_bitfield_ops!(KernelArgTypeQualifier);
 
 impl GetSetGo for KernelArgTypeQualifier {
     fn new(value: cl_kernel_arg_type_qualifier) -> BitfieldResult<Self> {
@@ -479,7 +532,7 @@ impl GetSetGo for KernelArgTypeQualifier {
         let fn_name = "KernelArgTypeQualifier";
         const MAX: cl_bitfield = T::NONE + T::CONST + T::RESTRICT + T::VOLATILE + T::PIPE;
         match value {
-            val @ 1..=MAX => Ok(KernelArgTypeQualifier(val)),
+            val @ 0..=MAX => Ok(KernelArgTypeQualifier(val)),
             _ => Err(ValidationError::InvalidBitfield(fn_name)),
         }
     }
@@ -491,7 +544,7 @@ impl GetSetGo for KernelArgTypeQualifier {
         let fn_name = "KernelArgTypeQualifier";
         const MAX: cl_bitfield = T::NONE + T::CONST + T::RESTRICT + T::VOLATILE + T::PIPE;
         match value {
-            val @ 1..=MAX => {
+            val @ 0..=MAX => {
                 self.0 = val;
                 Ok(())
             }
@@ -501,21 +554,24 @@ impl GetSetGo for KernelArgTypeQualifier {
 }
 
 #[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
 pub struct DeviceAtomicCapabilities(cl_device_atomic_capabilities);
-impl DeviceAtomicCapabilities {
-    /* cl_device_atomic_capabilities - cl_bitfield */
-    // #ifdef CL_VERSION_3_0;
-    pub const ORDER_RELAXED: cl_device_atomic_capabilities = CL_DEVICE_ATOMIC_ORDER_RELAXED;
-    pub const ORDER_ACQ_REL: cl_device_atomic_capabilities = CL_DEVICE_ATOMIC_ORDER_ACQ_REL;
-    pub const ORDER_SEQ_CST: cl_device_atomic_capabilities = CL_DEVICE_ATOMIC_ORDER_SEQ_CST;
-    pub const SCOPE_WORK_ITEM: cl_device_atomic_capabilities = CL_DEVICE_ATOMIC_SCOPE_WORK_ITEM;
-    pub const SCOPE_WORK_GROUP: cl_device_atomic_capabilities = CL_DEVICE_ATOMIC_SCOPE_WORK_GROUP;
-    pub const SCOPE_DEVICE: cl_device_atomic_capabilities = CL_DEVICE_ATOMIC_SCOPE_DEVICE;
-    pub const SCOPE_ALL_DEVICES: cl_device_atomic_capabilities = CL_DEVICE_ATOMIC_SCOPE_ALL_DEVICES;
-    // #endif;
+gen_consts! {
+    DeviceAtomicCapabilities: cl_device_atomic_capabilities {
+        /* cl_device_atomic_capabilities - cl_bitfield */
+        // #ifdef CL_VERSION_3_0;
+        ORDER_RELAXED = CL_DEVICE_ATOMIC_ORDER_RELAXED,
+        ORDER_ACQ_REL = CL_DEVICE_ATOMIC_ORDER_ACQ_REL,
+        ORDER_SEQ_CST = CL_DEVICE_ATOMIC_ORDER_SEQ_CST,
+        SCOPE_WORK_ITEM = CL_DEVICE_ATOMIC_SCOPE_WORK_ITEM,
+        SCOPE_WORK_GROUP = CL_DEVICE_ATOMIC_SCOPE_WORK_GROUP,
+        SCOPE_DEVICE = CL_DEVICE_ATOMIC_SCOPE_DEVICE,
+        SCOPE_ALL_DEVICES = CL_DEVICE_ATOMIC_SCOPE_ALL_DEVICES,
+        // #endif;
+    }
 }
 
-gen_add_trait!(DeviceAtomicCapabilities);
+gen_bitfield_ops!(DeviceAtomicCapabilities);
 
 impl GetSetGo for DeviceAtomicCapabilities {
     fn new(value: cl_device_atomic_capabilities) -> BitfieldResult<Self> {
@@ -557,17 +613,19 @@ impl GetSetGo for DeviceAtomicCapabilities {
 }
 
 #[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
 pub struct DeviceDeviceEnqueueCapabilities(cl_device_device_enqueue_capabilities);
-impl DeviceDeviceEnqueueCapabilities {
-    /* cl_device_device_enqueue_capabilities - cl_bitfield */
-    // #ifdef CL_VERSION_3_0;
-    pub const SUPPORTED: cl_device_device_enqueue_capabilities = CL_DEVICE_QUEUE_SUPPORTED;
-    pub const REPLACEABLE_DEFAULT: cl_device_device_enqueue_capabilities =
-        CL_DEVICE_QUEUE_REPLACEABLE_DEFAULT;
-    // #endif;
+gen_consts! {
+    DeviceDeviceEnqueueCapabilities: cl_device_device_enqueue_capabilities {
+        /* cl_device_device_enqueue_capabilities - cl_bitfield */
+        // #ifdef CL_VERSION_3_0;
+        SUPPORTED = CL_DEVICE_QUEUE_SUPPORTED,
+        REPLACEABLE_DEFAULT = CL_DEVICE_QUEUE_REPLACEABLE_DEFAULT,
+        // #endif;
+    }
 }
 
-gen_add_trait!(DeviceDeviceEnqueueCapabilities);
+gen_bitfield_ops!(DeviceDeviceEnqueueCapabilities);
 
 impl GetSetGo for DeviceDeviceEnqueueCapabilities {
     fn new(value: cl_device_device_enqueue_capabilities) -> BitfieldResult<Self> {
@@ -595,3 +653,108 @@ impl GetSetGo for DeviceDeviceEnqueueCapabilities {
         }
     }
 }
+
+/************************/
+/* /\ /\ /\ /\ /\ /\ /\ */
+/*|__|__|__|__|__|__|__|*/
+/*|  |  |  |  |  |  |  |*/
+/*|  |  Unit Tests  |  |*/
+/*|__|__|__|__|__|__|__|*/
+/*|__|__|__|__|__|__|__|*/
+/************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitfield_ops() {
+        let read = MapFlags::new(MapFlags::READ).unwrap();
+        let write = MapFlags::new(MapFlags::WRITE).unwrap();
+        let both = (read.clone() | write.clone()).unwrap();
+        assert_eq!(both.get(), MapFlags::READ | MapFlags::WRITE);
+        assert_eq!((read.clone() + write.clone()).unwrap(), both);
+        assert_eq!((both.clone() & read.clone()).get(), MapFlags::READ);
+        assert_eq!((both.clone() - read.clone()).get(), MapFlags::WRITE);
+        assert_eq!(
+            (!both.clone()).unwrap().get(),
+            MapFlags::WRITE_INVALIDATE_REGION
+        );
+        assert!(both.contains(MapFlags::READ | MapFlags::WRITE));
+        assert!(!read.contains(MapFlags::READ | MapFlags::WRITE));
+        assert!(read.intersects(MapFlags::READ | MapFlags::WRITE));
+        assert!(!read.intersects(MapFlags::WRITE));
+        assert_eq!(
+            both.iter().collect::<Vec<_>>(),
+            vec![MapFlags::READ, MapFlags::WRITE]
+        );
+
+        let mut flags = MemFlags::new(MemFlags::READ_ONLY).unwrap();
+        flags.insert(MemFlags::COPY_HOST_PTR).unwrap();
+        assert!(flags.contains(MemFlags::COPY_HOST_PTR));
+        flags.remove(MemFlags::COPY_HOST_PTR);
+        assert_eq!(flags.get(), MemFlags::READ_ONLY);
+        // Removing the last flag leaves the empty bitfield.
+        flags.remove(MemFlags::READ_ONLY);
+        assert_eq!(flags.get(), 0);
+        assert_eq!(flags.to_string(), "0");
+    }
+
+    #[test]
+    fn test_bitfield_format() {
+        let flags = MemFlags::new(MemFlags::READ_WRITE | MemFlags::COPY_HOST_PTR).unwrap();
+        assert_eq!(
+            flags.to_string(),
+            "CL_MEM_READ_WRITE | CL_MEM_COPY_HOST_PTR"
+        );
+        assert_eq!(
+            format!("{:?}", flags),
+            "MemFlags(CL_MEM_READ_WRITE | CL_MEM_COPY_HOST_PTR)"
+        );
+        let device_type = DeviceType::new(DeviceType::ALL).unwrap();
+        assert_eq!(device_type.to_string(), "CL_DEVICE_TYPE_ALL");
+        let device_type = DeviceType::new(DeviceType::CPU | DeviceType::GPU | 0x100).unwrap();
+        assert_eq!(
+            device_type.to_string(),
+            "CL_DEVICE_TYPE_CPU | CL_DEVICE_TYPE_GPU | 0x100"
+        );
+        assert_eq!(flags.names(), ["CL_MEM_READ_WRITE", "CL_MEM_COPY_HOST_PTR"]);
+        let qualifier = KernelArgTypeQualifier::new(KernelArgTypeQualifier::NONE).unwrap();
+        assert_eq!(qualifier.to_string(), "CL_KERNEL_ARG_TYPE_NONE");
+        assert_eq!(qualifier.names(), ["CL_KERNEL_ARG_TYPE_NONE"]);
+    }
+
+    #[test]
+    fn test_exclusive_flags() {
+        let fn_name = "MemFlags";
+        let invalid = [
+            MemFlags::READ_WRITE | MemFlags::READ_ONLY,
+            MemFlags::WRITE_ONLY | MemFlags::READ_ONLY,
+            MemFlags::USE_HOST_PTR | MemFlags::ALLOC_HOST_PTR,
+            MemFlags::USE_HOST_PTR | MemFlags::COPY_HOST_PTR,
+            MemFlags::HOST_READ_ONLY | MemFlags::HOST_NO_ACCESS,
+        ];
+        for value in invalid.iter() {
+            let error = ValidationError::InvalidBitfield(fn_name);
+            assert_eq!(MemFlags::new(*value).unwrap_err(), error);
+        }
+        let valid = MemFlags::READ_ONLY | MemFlags::ALLOC_HOST_PTR | MemFlags::COPY_HOST_PTR;
+        let mut flags = MemFlags::new(valid).unwrap();
+        assert!(flags.insert(MemFlags::USE_HOST_PTR).is_err());
+        assert_eq!(flags.get(), valid);
+
+        let map = MapFlags::READ | MapFlags::WRITE_INVALIDATE_REGION;
+        assert!(MapFlags::new(map).is_err());
+
+        // The operators validate their result like `new`.
+        let error = ValidationError::InvalidBitfield(fn_name);
+        let read_only = MemFlags::new(MemFlags::READ_ONLY).unwrap();
+        let read_write = MemFlags::new(MemFlags::READ_WRITE).unwrap();
+        assert_eq!((read_only.clone() | read_write.clone()).unwrap_err(), error);
+        assert_eq!((read_only.clone() + read_write.clone()).unwrap_err(), error);
+        assert_eq!((!read_only.clone()).unwrap_err(), error);
+        // Clearing flags can not fail, even when it clears all of them.
+        assert_eq!((read_only.clone() & read_write).get(), 0);
+        assert_eq!((read_only.clone() - read_only).get(), 0);
+    }
+}
@@ -17,7 +17,11 @@
 */
 
 #![allow(dead_code)]
-use crate::objects::structs::StatusCode;
+use crate::objects::bitfields::KernelArgTypeQualifier;
+use crate::objects::structs::{
+    CommandExecutionStatus, CommandType, DeviceLocalMemType, DeviceMemCacheType,
+    DevicePartitionProperty, KernelArgAccessQualifier, KernelArgAddressQualifier, StatusCode,
+};
 use crate::objects::wrappers::*;
 use opencl_heads::consts::*;
 use opencl_heads::types::*;
@@ -185,9 +189,7 @@ impl From<Status> for cl_int {
             S::BuildProgramFailure => Sc::BUILD_PROGRAM_FAILURE,
             S::MapFailure => Sc::MAP_FAILURE,
             S::MisalignedSubBufferOffset => Sc::MISALIGNED_SUB_BUFFER_OFFSET,
-            S::ExecStatusErrorForEventsInWaitList => {
-                CL_EXEC_STATUS_ERROR_FOR_EVENTS_IN_WAIT_LIST
-            }
+            S::ExecStatusErrorForEventsInWaitList => CL_EXEC_STATUS_ERROR_FOR_EVENTS_IN_WAIT_LIST,
             S::CompileProgramFailure => Sc::COMPILE_PROGRAM_FAILURE,
             S::LinkerNotAvailable => Sc::LINKER_NOT_AVAILABLE,
             S::LinkProgramFailure => Sc::LINK_PROGRAM_FAILURE,
@@ -313,6 +315,263 @@ impl Status {
     }
 }
 
+// Kernel Argument Address Qualifiers
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AddressQualifier {
+    Global,
+    Local,
+    Constant,
+    Private,
+    InvalidQualifier(cl_kernel_arg_address_qualifier),
+}
+
+/// Generates AddressQualifier from cl_kernel_arg_address_qualifier
+impl From<cl_kernel_arg_address_qualifier> for AddressQualifier {
+    fn from(qualifier: cl_kernel_arg_address_qualifier) -> Self {
+        type Q = KernelArgAddressQualifier;
+        type A = AddressQualifier;
+        match qualifier {
+            Q::GLOBAL => A::Global,
+            Q::LOCAL => A::Local,
+            Q::CONSTANT => A::Constant,
+            Q::PRIVATE => A::Private,
+            x => A::InvalidQualifier(x),
+        }
+    }
+}
+
+// Kernel Argument Access Qualifiers
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AccessQualifier {
+    ReadOnly,
+    WriteOnly,
+    ReadWrite,
+    None,
+    InvalidQualifier(cl_kernel_arg_access_qualifier),
+}
+
+/// Generates AccessQualifier from cl_kernel_arg_access_qualifier
+impl From<cl_kernel_arg_access_qualifier> for AccessQualifier {
+    fn from(qualifier: cl_kernel_arg_access_qualifier) -> Self {
+        type Q = KernelArgAccessQualifier;
+        type A = AccessQualifier;
+        match qualifier {
+            Q::READ_ONLY => A::ReadOnly,
+            Q::WRITE_ONLY => A::WriteOnly,
+            Q::READ_WRITE => A::ReadWrite,
+            Q::NONE => A::None,
+            x => A::InvalidQualifier(x),
+        }
+    }
+}
+
+// Event Command Types
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Command {
+    NDRangeKernel,
+    Task,
+    NativeKernel,
+    ReadBuffer,
+    WriteBuffer,
+    CopyBuffer,
+    ReadImage,
+    WriteImage,
+    CopyImage,
+    CopyImageToBuffer,
+    CopyBufferToImage,
+    MapBuffer,
+    MapImage,
+    UnmapMemObject,
+    Marker,
+    AcquireGLObjects,
+    ReleaseGLObjects,
+    ReadBufferRect,
+    WriteBufferRect,
+    CopyBufferRect,
+    User,
+    Barrier,
+    MigrateMemObjects,
+    FillBuffer,
+    FillImage,
+    SVMFree,
+    SVMMemcpy,
+    SVMMemfill,
+    SVMMap,
+    SVMUnmap,
+    SVMMigrateMem,
+    InvalidCommand(cl_command_type),
+}
+
+/// Generates Command from cl_command_type
+impl From<cl_command_type> for Command {
+    fn from(command_type: cl_command_type) -> Self {
+        type T = CommandType;
+        type C = Command;
+        match command_type {
+            T::NDRANGE_KERNEL => C::NDRangeKernel,
+            T::TASK => C::Task,
+            T::NATIVE_KERNEL => C::NativeKernel,
+            T::READ_BUFFER => C::ReadBuffer,
+            T::WRITE_BUFFER => C::WriteBuffer,
+            T::COPY_BUFFER => C::CopyBuffer,
+            T::READ_IMAGE => C::ReadImage,
+            T::WRITE_IMAGE => C::WriteImage,
+            T::COPY_IMAGE => C::CopyImage,
+            T::COPY_IMAGE_TO_BUFFER => C::CopyImageToBuffer,
+            T::COPY_BUFFER_TO_IMAGE => C::CopyBufferToImage,
+            T::MAP_BUFFER => C::MapBuffer,
+            T::MAP_IMAGE => C::MapImage,
+            T::UNMAP_MEM_OBJECT => C::UnmapMemObject,
+            T::MARKER => C::Marker,
+            T::ACQUIRE_GL_OBJECTS => C::AcquireGLObjects,
+            T::RELEASE_GL_OBJECTS => C::ReleaseGLObjects,
+            T::READ_BUFFER_RECT => C::ReadBufferRect,
+            T::WRITE_BUFFER_RECT => C::WriteBufferRect,
+            T::COPY_BUFFER_RECT => C::CopyBufferRect,
+            T::USER => C::User,
+            T::BARRIER => C::Barrier,
+            T::MIGRATE_MEM_OBJECTS => C::MigrateMemObjects,
+            T::FILL_BUFFER => C::FillBuffer,
+            T::FILL_IMAGE => C::FillImage,
+            T::SVM_FREE => C::SVMFree,
+            T::SVM_MEMCPY => C::SVMMemcpy,
+            T::SVM_MEMFILL => C::SVMMemfill,
+            T::SVM_MAP => C::SVMMap,
+            T::SVM_UNMAP => C::SVMUnmap,
+            T::SVM_MIGRATE_MEM => C::SVMMigrateMem,
+            x => C::InvalidCommand(x),
+        }
+    }
+}
+
+// Event Command Execution Status
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ExecutionStatus {
+    Complete,
+    Running,
+    Submitted,
+    Queued,
+    /// The command was abnormally terminated, holds the error code.
+    Error(Status),
+}
+
+/// Generates ExecutionStatus from the cl_int execution status of an event
+impl From<cl_int> for ExecutionStatus {
+    fn from(execution_status: cl_int) -> Self {
+        type S = CommandExecutionStatus;
+        type E = ExecutionStatus;
+        match execution_status {
+            x if x == S::COMPLETE as cl_int => E::Complete,
+            x if x == S::RUNNING as cl_int => E::Running,
+            x if x == S::SUBMITTED as cl_int => E::Submitted,
+            x if x == S::QUEUED as cl_int => E::Queued,
+            x => E::Error(Status::from(x)),
+        }
+    }
+}
+
+// Device Global Memory Cache Types
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MemCacheType {
+    None,
+    ReadOnly,
+    ReadWrite,
+    InvalidType(cl_device_mem_cache_type),
+}
+
+/// Generates MemCacheType from cl_device_mem_cache_type
+impl From<cl_device_mem_cache_type> for MemCacheType {
+    fn from(cache_type: cl_device_mem_cache_type) -> Self {
+        type T = DeviceMemCacheType;
+        type M = MemCacheType;
+        match cache_type {
+            T::NONE => M::None,
+            T::READ_ONLY_CACHE => M::ReadOnly,
+            T::READ_WRITE_CACHE => M::ReadWrite,
+            x => M::InvalidType(x),
+        }
+    }
+}
+
+// Device Local Memory Types
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LocalMemType {
+    Local,
+    Global,
+    InvalidType(cl_device_local_mem_type),
+}
+
+/// Generates LocalMemType from cl_device_local_mem_type
+impl From<cl_device_local_mem_type> for LocalMemType {
+    fn from(mem_type: cl_device_local_mem_type) -> Self {
+        type T = DeviceLocalMemType;
+        type L = LocalMemType;
+        match mem_type {
+            T::LOCAL => L::Local,
+            T::GLOBAL => L::Global,
+            x => L::InvalidType(x),
+        }
+    }
+}
+
+// Device Partition Schemes
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PartitionScheme {
+    Equally,
+    ByCounts,
+    ByAffinityDomain,
+    InvalidScheme(cl_device_partition_property),
+}
+
+/// Generates PartitionScheme from cl_device_partition_property
+impl From<cl_device_partition_property> for PartitionScheme {
+    fn from(property: cl_device_partition_property) -> Self {
+        type P = DevicePartitionProperty;
+        type S = PartitionScheme;
+        match property {
+            P::EQUALLY => S::Equally,
+            P::BY_COUNTS => S::ByCounts,
+            P::BY_AFFINITY_DOMAIN => S::ByAffinityDomain,
+            x => S::InvalidScheme(x),
+        }
+    }
+}
+
+// Device Partitions, as passed to create_sub_devices
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DevicePartition {
+    /// Compute units of every sub device.
+    Equally(cl_uint),
+    /// Compute units of each sub device.
+    ByCounts(Vec<cl_uint>),
+    ByAffinityDomain(cl_device_affinity_domain),
+    InvalidPartition(Vec<cl_device_partition_property>),
+}
+
+impl DevicePartition {
+    /// Parses the zero terminated partition properties of a sub device,
+    /// None for an empty list, as of a device that is not a sub device.
+    pub fn from_properties(properties: &[cl_device_partition_property]) -> Option<Self> {
+        type P = DevicePartitionProperty;
+        type D = DevicePartition;
+        let invalid = || Some(D::InvalidPartition(properties.to_vec()));
+        match properties {
+            [] | [0, ..] => None,
+            [P::EQUALLY, units, 0] => Some(D::Equally(*units as cl_uint)),
+            [P::BY_AFFINITY_DOMAIN, domain, 0] => {
+                Some(D::ByAffinityDomain(*domain as cl_device_affinity_domain))
+            }
+            [P::BY_COUNTS, counts @ .., P::BY_COUNTS_LIST_END, 0] => {
+                if counts.contains(&P::BY_COUNTS_LIST_END) {
+                    return invalid();
+                }
+                Some(D::ByCounts(counts.iter().map(|&x| x as cl_uint).collect()))
+            }
+            _ => invalid(),
+        }
+    }
+}
+
 #[allow(non_camel_case_types)]
 pub enum Size {
     i8,
@@ -354,6 +613,7 @@ impl Size {
 #[derive(Debug, PartialEq)]
 pub enum ParamValue {
     String(String),
+    Int(cl_int),
     UInt(cl_uint),
     ULong(cl_ulong),
     CSize(size_t),
@@ -361,8 +621,15 @@ pub enum ParamValue {
     ArrCSize(Vec<size_t>),
     ArrCPtr(Vec<intptr_t>),
     ArrULong(Vec<cl_ulong>),
+    ArrUChar(Vec<cl_uchar>),
+    Binaries(Vec<Vec<cl_uchar>>),
     NameVersion(Vec<cl_name_version>),
     ImageFormat(Vec<cl_image_format>),
+    AddressQualifier(AddressQualifier),
+    AccessQualifier(AccessQualifier),
+    TypeQualifier(KernelArgTypeQualifier),
+    Command(Command),
+    ExecutionStatus(ExecutionStatus),
 }
 
 impl ParamValue {
@@ -372,6 +639,12 @@ impl ParamValue {
             _ => None,
         }
     }
+    pub fn unwrap_int(self) -> Option<cl_int> {
+        match self {
+            ParamValue::Int(dat) => Some(dat),
+            _ => None,
+        }
+    }
     pub fn unwrap_uint(self) -> Option<cl_uint> {
         match self {
             ParamValue::UInt(dat) => Some(dat),
@@ -420,12 +693,54 @@ impl ParamValue {
             _ => None,
         }
     }
+    pub fn unwrap_arr_uchar(self) -> Option<Vec<cl_uchar>> {
+        match self {
+            ParamValue::ArrUChar(dat) => Some(dat),
+            _ => None,
+        }
+    }
+    pub fn unwrap_binaries(self) -> Option<Vec<Vec<cl_uchar>>> {
+        match self {
+            ParamValue::Binaries(dat) => Some(dat),
+            _ => None,
+        }
+    }
     pub fn unwrap_name_version(self) -> Option<Vec<cl_name_version>> {
         match self {
             ParamValue::NameVersion(dat) => Some(dat),
             _ => None,
         }
     }
+    pub fn unwrap_address_qualifier(self) -> Option<AddressQualifier> {
+        match self {
+            ParamValue::AddressQualifier(dat) => Some(dat),
+            _ => None,
+        }
+    }
+    pub fn unwrap_access_qualifier(self) -> Option<AccessQualifier> {
+        match self {
+            ParamValue::AccessQualifier(dat) => Some(dat),
+            _ => None,
+        }
+    }
+    pub fn unwrap_type_qualifier(self) -> Option<KernelArgTypeQualifier> {
+        match self {
+            ParamValue::TypeQualifier(dat) => Some(dat),
+            _ => None,
+        }
+    }
+    pub fn unwrap_command(self) -> Option<Command> {
+        match self {
+            ParamValue::Command(dat) => Some(dat),
+            _ => None,
+        }
+    }
+    pub fn unwrap_execution_status(self) -> Option<ExecutionStatus> {
+        match self {
+            ParamValue::ExecutionStatus(dat) => Some(dat),
+            _ => None,
+        }
+    }
 }
 
 impl Default for ParamValue {
@@ -457,4 +772,41 @@ mod tests {
         let status_code: cl_int = status.into();
         assert_eq!(status_code, 80085);
     }
+
+    #[test]
+    fn test_execution_status_from_code() {
+        let status = ExecutionStatus::from(CommandExecutionStatus::RUNNING as cl_int);
+        assert_eq!(ExecutionStatus::Running, status);
+        let status = ExecutionStatus::from(-5);
+        assert_eq!(ExecutionStatus::Error(Status::OutOfResources), status);
+    }
+
+    #[test]
+    fn test_device_partition_from_properties() {
+        type P = DevicePartitionProperty;
+        assert_eq!(DevicePartition::from_properties(&[]), None);
+        assert_eq!(DevicePartition::from_properties(&[0]), None);
+        assert_eq!(
+            DevicePartition::from_properties(&[P::EQUALLY, 4, 0]),
+            Some(DevicePartition::Equally(4))
+        );
+        assert_eq!(
+            DevicePartition::from_properties(&[P::BY_COUNTS, 2, 3, P::BY_COUNTS_LIST_END, 0]),
+            Some(DevicePartition::ByCounts(vec![2, 3]))
+        );
+        assert_eq!(
+            DevicePartition::from_properties(&[P::EQUALLY, 4]),
+            Some(DevicePartition::InvalidPartition(vec![P::EQUALLY, 4]))
+        );
+        assert_eq!(
+            PartitionScheme::from(P::BY_COUNTS),
+            PartitionScheme::ByCounts
+        );
+    }
+
+    #[test]
+    fn test_command_from_command_type() {
+        assert_eq!(Command::User, Command::from(CommandType::USER));
+        assert_eq!(Command::InvalidCommand(80085), Command::from(80085));
+    }
 }
@@ -22,6 +22,7 @@ use crate::objects::enums::Status;
 use crate::objects::structs::StatusCode;
 use crate::objects::types::{APIResult, HelperResult};
 use opencl_heads::types::*;
+use std::ffi::CString;
 
 pub fn status_update<T>(
     status_code: cl_int,
@@ -65,6 +66,15 @@ pub fn bytes_into_string(mut bytes: Vec<u8>) -> HelperResult<String> {
     }
 }
 
+/// Converts a string into a null terminated C string, failing if it contains
+/// an interior null byte.
+pub fn str_into_cstring(value: &str, function_name: &'static str) -> HelperResult<CString> {
+    match CString::new(value) {
+        Ok(x) => Ok(x),
+        Err(_) => Err(ValidationError::InvalidString(function_name).to_error()),
+    }
+}
+
 pub fn to_mut_ptr<T>(x: &mut T) -> *mut T {
     &mut *x
 }
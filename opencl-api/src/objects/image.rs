@@ -0,0 +1,470 @@
+/*
+ * image.rs - Validated image format and descriptor builders.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+//!
+//! `ImageFormat` and `ImageDesc` build the `cl_image_format` and
+//! `cl_image_desc` of `create_image`, checking what can be checked without
+//! the driver: the channel order and type against the `ChannelOrder` and
+//! `ChannelType` tables, and the fields required by each image type.
+//! The checks against the devices of a context are done by
+//! `api::image::create_image_from_desc`.
+
+use crate::errors::ValidationError;
+use crate::objects::structs::{ChannelOrder, ChannelType, MemObjectType};
+use crate::objects::types::{MemPtr, PropertyResult};
+use opencl_heads::types::*;
+use std::ptr;
+
+/// a times b, failing when the size overflows.
+fn size_mul(a: size_t, b: size_t) -> PropertyResult<size_t> {
+    a.checked_mul(b).ok_or(ValidationError::InvalidImageDesc(
+        "clCreateImage",
+        "size overflows the address space",
+    ))
+}
+
+/// Channel order and data type of the elements of an image.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ImageFormat {
+    order: cl_channel_order,
+    data_type: cl_channel_type,
+    element_size: size_t,
+}
+
+impl ImageFormat {
+    /// Fails for orders and types missing from `ChannelOrder` and
+    /// `ChannelType`, and for packed types of the wrong order, such as
+    /// `UNORM_SHORT_565` with anything but `RGB` or `RGBx`.
+    pub fn new(order: cl_channel_order, data_type: cl_channel_type) -> PropertyResult<Self> {
+        let fn_name = "ImageFormat";
+        if !ChannelOrder::NAMES.iter().any(|&(_, x)| x == order)
+            || !ChannelType::NAMES.iter().any(|&(_, x)| x == data_type)
+        {
+            return Err(ValidationError::InvalidProperty(fn_name));
+        }
+        match element_size(order, data_type) {
+            Some(element_size) => Ok(ImageFormat {
+                order,
+                data_type,
+                element_size,
+            }),
+            None => Err(ValidationError::InvalidProperty(fn_name)),
+        }
+    }
+
+    pub fn from_raw(format: &cl_image_format) -> PropertyResult<Self> {
+        Self::new(format.image_channel_order, format.image_channel_data_type)
+    }
+
+    pub fn order(&self) -> cl_channel_order {
+        self.order
+    }
+
+    pub fn data_type(&self) -> cl_channel_type {
+        self.data_type
+    }
+
+    /// Size in bytes of an element, i.e. a pixel, of the format.
+    pub fn element_size(&self) -> size_t {
+        self.element_size
+    }
+
+    pub fn get(&self) -> cl_image_format {
+        cl_image_format {
+            image_channel_order: self.order,
+            image_channel_data_type: self.data_type,
+        }
+    }
+}
+
+fn element_size(order: cl_channel_order, data_type: cl_channel_type) -> Option<size_t> {
+    type O = ChannelOrder;
+    type T = ChannelType;
+    match (order, data_type) {
+        // Packed types hold all the channels of an element.
+        (O::RGB | O::RGBx, T::UNORM_SHORT_565 | T::UNORM_SHORT_555) => Some(2),
+        (O::RGB | O::RGBx, T::UNORM_INT_101010) | (O::RGBA, T::UNORM_INT_101010_2) => Some(4),
+        (_, T::UNORM_SHORT_565 | T::UNORM_SHORT_555)
+        | (_, T::UNORM_INT_101010 | T::UNORM_INT_101010_2) => None,
+        (O::DEPTH | O::DEPTH_STENCIL, T::UNORM_INT24) => Some(4),
+        (_, T::UNORM_INT24) => None,
+        // 32 bits of depth and 8 of stencil, padded to 64 bits.
+        (O::DEPTH_STENCIL, T::FLOAT) => Some(8),
+        (O::DEPTH_STENCIL, _) => None,
+        _ => {
+            let channels = match order {
+                O::R | O::A | O::INTENSITY | O::LUMINANCE | O::DEPTH => 1,
+                O::RG | O::RA | O::Rx => 2,
+                O::RGB | O::RGx | O::SRGB => 3,
+                _ => 4,
+            };
+            let channel_size = match data_type {
+                T::SNORM_INT8 | T::UNORM_INT8 | T::SIGNED_INT8 | T::UNSIGNED_INT8 => 1,
+                T::SNORM_INT16
+                | T::UNORM_INT16
+                | T::SIGNED_INT16
+                | T::UNSIGNED_INT16
+                | T::HALF_FLOAT => 2,
+                _ => 4,
+            };
+            Some(channels * channel_size)
+        }
+    }
+}
+
+/// Builder of the `cl_image_desc` of an image.
+///
+/// Start from the constructor of the image type, all of which take the
+/// fields required by that type, then set the pitches or the buffer the
+/// image is created from. Pitches left at zero default to the tightly
+/// packed layout of the image.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ImageDesc {
+    desc: cl_image_desc,
+}
+
+impl ImageDesc {
+    /// Descriptor of type image_type with every size left unset, for
+    /// filling in with the setters.
+    pub fn new(image_type: cl_mem_object_type) -> PropertyResult<Self> {
+        type M = MemObjectType;
+        match image_type {
+            M::IMAGE1D
+            | M::IMAGE1D_BUFFER
+            | M::IMAGE1D_ARRAY
+            | M::IMAGE2D
+            | M::IMAGE2D_ARRAY
+            | M::IMAGE3D => Ok(Self::of(image_type)),
+            _ => Err(ValidationError::InvalidProperty("ImageDesc")),
+        }
+    }
+
+    pub fn image1d(width: size_t) -> Self {
+        Self::of(MemObjectType::IMAGE1D).width(width)
+    }
+
+    /// 1D image over the elements of buffer.
+    pub fn image1d_buffer(width: size_t, buffer: &MemPtr) -> Self {
+        Self::of(MemObjectType::IMAGE1D_BUFFER)
+            .width(width)
+            .buffer(buffer)
+    }
+
+    pub fn image1d_array(width: size_t, array_size: size_t) -> Self {
+        Self::of(MemObjectType::IMAGE1D_ARRAY)
+            .width(width)
+            .array_size(array_size)
+    }
+
+    pub fn image2d(width: size_t, height: size_t) -> Self {
+        Self::of(MemObjectType::IMAGE2D).width(width).height(height)
+    }
+
+    pub fn image2d_array(width: size_t, height: size_t, array_size: size_t) -> Self {
+        Self::of(MemObjectType::IMAGE2D_ARRAY)
+            .width(width)
+            .height(height)
+            .array_size(array_size)
+    }
+
+    pub fn image3d(width: size_t, height: size_t, depth: size_t) -> Self {
+        Self::of(MemObjectType::IMAGE3D)
+            .width(width)
+            .height(height)
+            .depth(depth)
+    }
+
+    fn of(image_type: cl_mem_object_type) -> Self {
+        ImageDesc {
+            desc: cl_image_desc {
+                image_type,
+                ..Default::default()
+            },
+        }
+    }
+
+    pub fn width(mut self, width: size_t) -> Self {
+        self.desc.image_width = width;
+        self
+    }
+
+    pub fn height(mut self, height: size_t) -> Self {
+        self.desc.image_height = height;
+        self
+    }
+
+    pub fn depth(mut self, depth: size_t) -> Self {
+        self.desc.image_depth = depth;
+        self
+    }
+
+    pub fn array_size(mut self, array_size: size_t) -> Self {
+        self.desc.image_array_size = array_size;
+        self
+    }
+
+    /// Bytes between the rows of the image, only for images of host
+    /// memory or of a buffer.
+    pub fn row_pitch(mut self, row_pitch: size_t) -> Self {
+        self.desc.image_row_pitch = row_pitch;
+        self
+    }
+
+    /// Bytes between the 2D slices of a 3D image, or the images of an
+    /// array, only for images of host memory.
+    pub fn slice_pitch(mut self, slice_pitch: size_t) -> Self {
+        self.desc.image_slice_pitch = slice_pitch;
+        self
+    }
+
+    /// Buffer the image is created from, for 1D buffer and 2D images.
+    pub fn buffer(mut self, buffer: &MemPtr) -> Self {
+        self.desc.mem_object = buffer.unwrap();
+        self
+    }
+
+    pub fn image_type(&self) -> cl_mem_object_type {
+        self.desc.image_type
+    }
+
+    /// Width, height and depth of the image, the array size being the
+    /// height of 1D arrays and the depth of 2D arrays, as for the regions
+    /// of the image commands.
+    pub fn region(&self) -> [size_t; 3] {
+        type M = MemObjectType;
+        let d = &self.desc;
+        match d.image_type {
+            M::IMAGE1D_ARRAY => [d.image_width, d.image_array_size, 1],
+            M::IMAGE2D => [d.image_width, d.image_height, 1],
+            M::IMAGE2D_ARRAY => [d.image_width, d.image_height, d.image_array_size],
+            M::IMAGE3D => [d.image_width, d.image_height, d.image_depth],
+            _ => [d.image_width, 1, 1],
+        }
+    }
+
+    /// Row pitch of the image in format, the packed one if left unset.
+    pub fn row_pitch_for(&self, format: &ImageFormat) -> PropertyResult<size_t> {
+        match self.desc.image_row_pitch {
+            0 => size_mul(self.desc.image_width, format.element_size()),
+            x => Ok(x),
+        }
+    }
+
+    /// Slice pitch of the image in format, the packed one if left unset,
+    /// zero for the images without slices.
+    pub fn slice_pitch_for(&self, format: &ImageFormat) -> PropertyResult<size_t> {
+        type M = MemObjectType;
+        let rows = match self.desc.image_type {
+            M::IMAGE1D_ARRAY => 1,
+            M::IMAGE2D_ARRAY | M::IMAGE3D => self.desc.image_height,
+            _ => return Ok(0),
+        };
+        match self.desc.image_slice_pitch {
+            0 => size_mul(self.row_pitch_for(format)?, rows),
+            x => Ok(x),
+        }
+    }
+
+    /// Bytes of host memory holding the image in format.
+    pub fn host_size(&self, format: &ImageFormat) -> PropertyResult<size_t> {
+        let [_, height, depth] = self.region();
+        match self.slice_pitch_for(format)? {
+            0 => size_mul(self.row_pitch_for(format)?, height),
+            slice_pitch => size_mul(slice_pitch, depth),
+        }
+    }
+
+    /// Returns the descriptor of the image in format.
+    ///
+    /// host_memory tells whether the image is created over host memory,
+    /// the pitches may only be given for images of host memory or of a
+    /// buffer, and are then filled in with their defaults.
+    pub fn build(&self, format: &ImageFormat, host_memory: bool) -> PropertyResult<cl_image_desc> {
+        type M = MemObjectType;
+        let fn_name = "clCreateImage";
+        let invalid = |reason| Err(ValidationError::InvalidImageDesc(fn_name, reason));
+        let d = &self.desc;
+        let (height, depth, array) = match d.image_type {
+            M::IMAGE1D | M::IMAGE1D_BUFFER => (false, false, false),
+            M::IMAGE1D_ARRAY => (false, false, true),
+            M::IMAGE2D => (true, false, false),
+            M::IMAGE2D_ARRAY => (true, false, true),
+            M::IMAGE3D => (true, true, false),
+            _ => return invalid("not an image type"),
+        };
+        if d.image_width == 0 {
+            return invalid("zero width");
+        }
+        if height != (d.image_height != 0) {
+            return invalid("height missing or not allowed for the image type");
+        }
+        if depth != (d.image_depth != 0) {
+            return invalid("depth missing or not allowed for the image type");
+        }
+        if array != (d.image_array_size != 0) {
+            return invalid("array size missing or not allowed for the image type");
+        }
+        let buffer = !d.mem_object.is_null();
+        match d.image_type {
+            M::IMAGE1D_BUFFER if !buffer => return invalid("1d buffer image without a buffer"),
+            M::IMAGE1D_BUFFER | M::IMAGE2D => {}
+            _ if buffer => return invalid("buffer not allowed for the image type"),
+            _ => {}
+        }
+        if buffer && host_memory {
+            return invalid("image of both a buffer and host memory");
+        }
+
+        let provided = buffer || host_memory;
+        let element_size = format.element_size();
+        if d.image_row_pitch != 0 {
+            if !provided {
+                return invalid("row pitch without host memory");
+            }
+            if d.image_row_pitch < size_mul(d.image_width, element_size)?
+                || !d.image_row_pitch.is_multiple_of(element_size)
+            {
+                return invalid(
+                    "row pitch smaller than a row or not a multiple of the element size",
+                );
+            }
+        }
+        if d.image_slice_pitch != 0 {
+            // The packed slice pitch, of the given row pitch.
+            let slice_pitch = self.slice_pitch(0).slice_pitch_for(format)?;
+            if !provided || buffer {
+                return invalid("slice pitch without host memory");
+            }
+            if slice_pitch == 0 {
+                return invalid("slice pitch not allowed for the image type");
+            }
+            let row_pitch = self.row_pitch_for(format)?;
+            if d.image_slice_pitch < slice_pitch || !d.image_slice_pitch.is_multiple_of(row_pitch) {
+                return invalid(
+                    "slice pitch smaller than a slice or not a multiple of the row pitch",
+                );
+            }
+        }
+
+        let mut desc = *d;
+        if provided {
+            desc.image_row_pitch = self.row_pitch_for(format)?;
+            desc.image_slice_pitch = self.slice_pitch_for(format)?;
+        }
+        // The mip levels and samples are reserved for extensions.
+        desc.num_mip_levels = 0;
+        desc.mem_samples = 0;
+        if !buffer {
+            desc.mem_object = ptr::null_mut();
+        }
+        Ok(desc)
+    }
+}
+
+/************************/
+/* /‾‾\ /‾‾\ /‾‾\ /‾‾\  */
+/*|  |  |  |  |  |  |  |*/
+/*|  |  Unit Tests  |  |*/
+/*|__|__|__|__|__|__|__|*/
+/*|__|__|__|__|__|__|__|*/
+/************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image_format() {
+        type O = ChannelOrder;
+        type T = ChannelType;
+        let format = ImageFormat::new(O::RGBA, T::UNSIGNED_INT8).unwrap();
+        assert_eq!(format.element_size(), 4);
+        assert_eq!(format.get().image_channel_order, O::RGBA);
+        assert_eq!(ImageFormat::new(O::RG, T::FLOAT).unwrap().element_size(), 8);
+        assert_eq!(
+            ImageFormat::new(O::RGB, T::UNORM_SHORT_565)
+                .unwrap()
+                .element_size(),
+            2
+        );
+        let invalid = Err(ValidationError::InvalidProperty("ImageFormat"));
+        assert_eq!(ImageFormat::new(O::RGBA, T::UNORM_SHORT_565), invalid);
+        assert_eq!(ImageFormat::new(O::RGBA, 0), invalid);
+        assert_eq!(ImageFormat::new(0, T::FLOAT), invalid);
+    }
+
+    #[test]
+    fn test_image_desc() {
+        type M = MemObjectType;
+        let format = ImageFormat::new(ChannelOrder::RGBA, ChannelType::FLOAT).unwrap();
+
+        let desc = ImageDesc::image3d(8, 4, 2);
+        assert_eq!(desc.region(), [8, 4, 2]);
+        assert_eq!(desc.host_size(&format), Ok(8 * 4 * 2 * 16));
+        let raw = desc.build(&format, false).unwrap();
+        assert_eq!((raw.image_row_pitch, raw.image_slice_pitch), (0, 0));
+        let raw = desc.build(&format, true).unwrap();
+        assert_eq!((raw.image_row_pitch, raw.image_slice_pitch), (128, 512));
+
+        let desc = ImageDesc::image1d_array(8, 3).row_pitch(256);
+        assert_eq!(desc.region(), [8, 3, 1]);
+        let raw = desc.build(&format, true).unwrap();
+        assert_eq!((raw.image_row_pitch, raw.image_slice_pitch), (256, 256));
+
+        let invalid = |reason| Err(ValidationError::InvalidImageDesc("clCreateImage", reason));
+        assert_eq!(
+            ImageDesc::image2d(8, 4)
+                .row_pitch(256)
+                .build(&format, false),
+            invalid("row pitch without host memory")
+        );
+        assert_eq!(
+            ImageDesc::image2d(8, 4).row_pitch(100).build(&format, true),
+            invalid("row pitch smaller than a row or not a multiple of the element size")
+        );
+        assert_eq!(
+            ImageDesc::new(M::IMAGE2D)
+                .unwrap()
+                .width(8)
+                .build(&format, false),
+            invalid("height missing or not allowed for the image type")
+        );
+        assert_eq!(
+            ImageDesc::image1d(8).depth(2).build(&format, false),
+            invalid("depth missing or not allowed for the image type")
+        );
+        assert_eq!(
+            ImageDesc::new(M::IMAGE1D_BUFFER)
+                .unwrap()
+                .width(8)
+                .build(&format, false),
+            invalid("1d buffer image without a buffer")
+        );
+        assert!(ImageDesc::new(M::BUFFER).is_err());
+
+        let overflow = || {
+            ValidationError::InvalidImageDesc("clCreateImage", "size overflows the address space")
+        };
+        let huge = ImageDesc::image2d(usize::MAX / 8, 4);
+        assert_eq!(huge.host_size(&format).unwrap_err(), overflow());
+        assert_eq!(huge.build(&format, true).unwrap_err(), overflow());
+        assert_eq!(
+            huge.row_pitch(usize::MAX).build(&format, true).unwrap_err(),
+            overflow()
+        );
+    }
+}
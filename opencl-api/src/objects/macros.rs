@@ -16,8 +16,6 @@
  * limitations under the License.
  */
 
-#![allow(dead_code)]
-
 #[macro_export]
 macro_rules! get_count {
     // 0 parameters
@@ -58,6 +56,22 @@ macro_rules! size_getter {
             status_update(status_code, stringify!($fn), size)
         };
     };
+    ($name:ident, $fn:ident, $($extra:ident),+) => {
+        let $name = |ret_dat_ptr: *mut libc::c_void, param_name: cl_uint| -> APIResult<size_t> {
+            let mut size: size_t = 0;
+            let status_code = unsafe {
+                $fn(
+                    ret_dat_ptr,
+                    $($extra,)+
+                    param_name,
+                    0,
+                    ptr::null_mut(),
+                    &mut size as *mut size_t,
+                )
+            };
+            status_update(status_code, stringify!($fn), size)
+        };
+    };
 }
 
 #[macro_export]
@@ -97,6 +111,42 @@ macro_rules! gen_param_value {
         };
         status_update(status_code, stringify!($fn), param_value)?
     }};
+
+    // return vector object, with extra leading arguments
+    ($fn:ident, $typ:tt, [$($args: ident),+], $param_name: ident, $size: ident) => {{
+        if $size == 0 {
+            Vec::default()
+        } else {
+            let arr_len = $size / Size::$typ.get();
+            let mut param_value: Vec<$typ> = std::vec::from_elem($typ::default(), arr_len);
+            let status_code = unsafe {
+                $fn(
+                    $($args,)+
+                    $param_name,
+                    $size,
+                    param_value.as_mut_ptr() as *mut c_void,
+                    ptr::null_mut(),
+                )
+            };
+            status_update(status_code, stringify!($fn), param_value)?
+        }
+    }};
+
+    // return single object, with extra leading arguments
+    ($fn:ident, $typ:tt, [$($args: ident),+], $param_name: ident) => {{
+        let size = Size::$typ.get();
+        let mut param_value = $typ::default();
+        let status_code = unsafe {
+            $fn(
+                $($args,)+
+                $param_name,
+                size,
+                $crate::objects::functions::to_mut_ptr(&mut param_value) as *mut c_void,
+                ptr::null_mut(),
+            )
+        };
+        status_update(status_code, stringify!($fn), param_value)?
+    }};
 }
 
 #[macro_export]
@@ -147,9 +197,207 @@ macro_rules! gen_object_list {
 macro_rules! gen_add_trait {
     ($name:ident) => {
         impl std::ops::Add for $name {
-            type Output = Self;
+            type Output = $crate::objects::types::BitfieldResult<Self>;
             fn add(self, other: Self) -> Self::Output {
-                Self(self.0 | other.0)
+                <Self as $crate::objects::traits::GetSetGo>::new(self.0 | other.0)
+            }
+        }
+    };
+}
+
+/// Implements the flag-set API of the bitfield $name on top of `GetSetGo`:
+/// `|`, `+` and `!` build their result through `new` and `insert` goes
+/// through `set`, so they keep its validation, while `&`, `-` and `remove`
+/// only clear flags of a valid value and can not fail, the empty value
+/// standing for no flag. `Debug` and `Display` name the value by the
+/// constant equal to it if any, else by every single-bit flag set, through
+/// `NAMES`.
+#[macro_export]
+macro_rules! gen_bitfield_ops {
+    ($name:ident) => {
+        $crate::gen_add_trait!($name);
+
+        impl $name {
+            /// Every single-bit flag declared for $name.
+            fn flags() -> impl Iterator<Item = (&'static str, cl_bitfield)> {
+                Self::NAMES
+                    .iter()
+                    .copied()
+                    .filter(|&(_, x)| x.count_ones() == 1)
+            }
+            pub fn contains(&self, flags: cl_bitfield) -> bool {
+                self.0 & flags == flags
+            }
+            pub fn intersects(&self, flags: cl_bitfield) -> bool {
+                self.0 & flags != 0
+            }
+            pub fn insert(&mut self, flags: cl_bitfield) -> BitfieldResult<()> {
+                self.set(self.0 | flags)
+            }
+            pub fn remove(&mut self, flags: cl_bitfield) {
+                self.0 &= !flags;
+            }
+            /// The single-bit flags set, in declaration order.
+            pub fn iter(&self) -> impl Iterator<Item = cl_bitfield> + '_ {
+                Self::flags()
+                    .map(|(_, x)| x)
+                    .filter(move |&x| self.contains(x))
+            }
+            /// OpenCL name of the constant equal to the value, e.g.
+            /// `CL_DEVICE_TYPE_ALL`, else names of the flags set, in
+            /// declaration order, with the undeclared bits left over in hex.
+            pub fn names(&self) -> Vec<String> {
+                if let Some(&(name, _)) = Self::NAMES.iter().find(|&&(_, x)| x == self.0) {
+                    return vec![name.to_owned()];
+                }
+                let mut unknown = self.0;
+                let mut names = Vec::new();
+                for (name, x) in Self::flags().filter(|&(_, x)| self.contains(x)) {
+                    names.push(name.to_owned());
+                    unknown &= !x;
+                }
+                if unknown != 0 {
+                    names.push(format!("{:#x}", unknown));
+                }
+                names
+            }
+        }
+
+        impl std::ops::BitOr for $name {
+            type Output = BitfieldResult<Self>;
+            fn bitor(self, other: Self) -> Self::Output {
+                Self::new(self.0 | other.0)
+            }
+        }
+
+        impl std::ops::BitAnd for $name {
+            type Output = Self;
+            fn bitand(self, other: Self) -> Self::Output {
+                Self(self.0 & other.0)
+            }
+        }
+
+        impl std::ops::Sub for $name {
+            type Output = Self;
+            fn sub(self, other: Self) -> Self::Output {
+                Self(self.0 & !other.0)
+            }
+        }
+
+        /// Complement within the flags declared for $name.
+        impl std::ops::Not for $name {
+            type Output = BitfieldResult<Self>;
+            fn not(self) -> Self::Output {
+                let known = Self::flags().fold(0, |acc, (_, x)| acc | x);
+                Self::new(!self.0 & known)
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                match self.names().as_slice() {
+                    [] => f.write_str("0"),
+                    names => f.write_str(&names.join(" | ")),
+                }
+            }
+        }
+
+        impl std::fmt::Debug for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "{}({})", stringify!($name), self)
+            }
+        }
+    };
+}
+
+/// Declares the struct $name holding the result of the info query of every
+/// listed parameter of `api::info::$module`, along with `query` running
+/// them all on an object. A failed query only fails its own field.
+/// `plain` lists the fields by their OpenCL names, for the snapshots.
+#[macro_export]
+macro_rules! gen_details {
+    (
+        $(#[$doc:meta])*
+        $name:ident: $kind:ident, $module:ident {
+            $($field:ident: $param:ident,)*
+        }
+    ) => {
+        $(#[$doc])*
+        pub struct $name {
+            $(
+                pub $field: $crate::objects::types::APIResult<
+                    <$crate::api::info::$module::$param as $crate::api::info::InfoParam>::Value,
+                >,
+            )*
+        }
+
+        impl $name {
+            pub fn query(object: &$crate::api::info::Handle<$crate::api::info::$kind>) -> Self {
+                $name {
+                    $($field: $crate::api::info::query::<$crate::api::info::$module::$param>(object),)*
+                }
+            }
+
+            /// The OpenCL name and plain form of every field, in declaration
+            /// order, leaving out the handles.
+            pub fn plain(
+                &self,
+            ) -> Vec<(cl_uint, Result<$crate::api::info::Plain, &$crate::errors::OpenCLAPIError>)>
+            {
+                use $crate::api::info::{InfoParam, InfoValue};
+                let mut fields = Vec::new();
+                $(
+                    let name = <$crate::api::info::$module::$param as InfoParam>::NAME;
+                    match &self.$field {
+                        Ok(x) => fields.extend(x.plain().map(|x| (name, Ok(x)))),
+                        Err(e) => fields.push((name, Err(e))),
+                    }
+                )*
+                fields
+            }
+        }
+    };
+}
+
+/// Declares a table of OpenCL constants as associated consts of $name,
+/// each bound to the opencl-heads constant it stands for, along with
+/// `NAMES` which pairs the OpenCL name of every constant with its value,
+/// and the `name_of` and `from_name` lookups over it.
+#[macro_export]
+macro_rules! gen_consts {
+    ($name:ident: $typ:ty { $($(#[$attr:meta])* $constant:ident = $value:ident,)* }) => {
+        impl $name {
+            $(
+                $(#[$attr])*
+                pub const $constant: $typ = $value;
+            )*
+
+            /// OpenCL names of the constants, in declaration order.
+            pub(crate) const NAMES: &'static [(&'static str, $typ)] = &[
+                $($(#[$attr])* (stringify!($value), $value),)*
+            ];
+
+            /// Returns the OpenCL name of value, the first declared one
+            /// when several constants share it.
+            pub fn name_of(value: $typ) -> Option<&'static str> {
+                Self::NAMES
+                    .iter()
+                    .find(|&&(_, x)| x == value)
+                    .map(|&(name, _)| name)
+            }
+
+            /// Parses either the OpenCL name of a constant, as in
+            /// `CL_MEM_SIZE`, or the name of its associated const, as in
+            /// `SIZE`.
+            pub fn from_name(name: &str) -> Option<$typ> {
+                const CONSTANTS: &[(&str, $typ)] = &[
+                    $($(#[$attr])* (stringify!($constant), $value),)*
+                ];
+                Self::NAMES
+                    .iter()
+                    .chain(CONSTANTS)
+                    .find(|&&(x, _)| x == name)
+                    .map(|&(_, value)| value)
             }
         }
     };
@@ -19,6 +19,7 @@
 pub mod bitfields;
 pub mod enums;
 pub mod functions;
+pub mod image;
 pub mod macros;
 pub mod structs;
 pub mod traits;
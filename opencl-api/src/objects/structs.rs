@@ -20,6 +20,7 @@
 // use crate::errors::ValidationError;
 // use crate::helpers::{LongProperties, PlatformPtr, Properties};
 use crate::errors::ValidationError;
+use crate::gen_consts;
 use crate::objects::bitfields::{DeviceAffinityDomain};
 use crate::objects::traits::GetSetGo;
 use crate::objects::types::{Properties, PropertyResult};
@@ -29,80 +30,81 @@ use opencl_heads::types::*;
 
 #[non_exhaustive]
 pub struct StatusCode;
-impl StatusCode {
-    // Error Codes
-    pub const SUCCESS: cl_int = CL_SUCCESS;
-    pub const DEVICE_NOT_FOUND: cl_int = CL_DEVICE_NOT_FOUND;
-    pub const DEVICE_NOT_AVAILABLE: cl_int = CL_DEVICE_NOT_AVAILABLE;
-    pub const COMPILER_NOT_AVAILABLE: cl_int = CL_COMPILER_NOT_AVAILABLE;
-    pub const MEM_OBJECT_ALLOCATION_FAILURE: cl_int = CL_MEM_OBJECT_ALLOCATION_FAILURE;
-    pub const OUT_OF_RESOURCES: cl_int = CL_OUT_OF_RESOURCES;
-    pub const OUT_OF_HOST_MEMORY: cl_int = CL_OUT_OF_HOST_MEMORY;
-    pub const PROFILING_INFO_NOT_AVAILABLE: cl_int = CL_PROFILING_INFO_NOT_AVAILABLE;
-    pub const MEM_COPY_OVERLAP: cl_int = CL_MEM_COPY_OVERLAP;
-    pub const IMAGE_FORMAT_MISMATCH: cl_int = CL_IMAGE_FORMAT_MISMATCH;
-    pub const IMAGE_FORMAT_NOT_SUPPORTED: cl_int = CL_IMAGE_FORMAT_NOT_SUPPORTED;
-    pub const BUILD_PROGRAM_FAILURE: cl_int = CL_BUILD_PROGRAM_FAILURE;
-    pub const MAP_FAILURE: cl_int = CL_MAP_FAILURE;
-    // #ifdef CL_VERSION_1_1
-    pub const MISALIGNED_SUB_BUFFER_OFFSET: cl_int = CL_MISALIGNED_SUB_BUFFER_OFFSET;
-    pub const EXEC_STATUS_ERROR_FOR_EVENTS_IN_WAIT_LIST: cl_int =
-        CL_EXEC_STATUS_ERROR_FOR_EVENTS_IN_WAIT_LIST;
-    // #endif
-    // #ifdef CL_VERSION_1_2
-    pub const COMPILE_PROGRAM_FAILURE: cl_int = CL_COMPILE_PROGRAM_FAILURE;
-    pub const LINKER_NOT_AVAILABLE: cl_int = CL_LINKER_NOT_AVAILABLE;
-    pub const LINK_PROGRAM_FAILURE: cl_int = CL_LINK_PROGRAM_FAILURE;
-    pub const DEVICE_PARTITION_FAILED: cl_int = CL_DEVICE_PARTITION_FAILED;
-    pub const KERNEL_ARG_INFO_NOT_AVAILABLE: cl_int = CL_KERNEL_ARG_INFO_NOT_AVAILABLE;
-    // #endif
-    pub const INVALID_VALUE: cl_int = CL_INVALID_VALUE;
-    pub const INVALID_DEVICE_TYPE: cl_int = CL_INVALID_DEVICE_TYPE;
-    pub const INVALID_PLATFORM: cl_int = CL_INVALID_PLATFORM;
-    pub const INVALID_DEVICE: cl_int = CL_INVALID_DEVICE;
-    pub const INVALID_CONTEXT: cl_int = CL_INVALID_CONTEXT;
-    pub const INVALID_QUEUE_PROPERTIES: cl_int = CL_INVALID_QUEUE_PROPERTIES;
-    pub const INVALID_COMMAND_QUEUE: cl_int = CL_INVALID_COMMAND_QUEUE;
-    pub const INVALID_HOST_PTR: cl_int = CL_INVALID_HOST_PTR;
-    pub const INVALID_MEM_OBJECT: cl_int = CL_INVALID_MEM_OBJECT;
-    pub const INVALID_IMAGE_FORMAT_DESCRIPTOR: cl_int = CL_INVALID_IMAGE_FORMAT_DESCRIPTOR;
-    pub const INVALID_IMAGE_SIZE: cl_int = CL_INVALID_IMAGE_SIZE;
-    pub const INVALID_SAMPLER: cl_int = CL_INVALID_SAMPLER;
-    pub const INVALID_BINARY: cl_int = CL_INVALID_BINARY;
-    pub const INVALID_BUILD_OPTIONS: cl_int = CL_INVALID_BUILD_OPTIONS;
-    pub const INVALID_PROGRAM: cl_int = CL_INVALID_PROGRAM;
-    pub const INVALID_PROGRAM_EXECUTABLE: cl_int = CL_INVALID_PROGRAM_EXECUTABLE;
-    pub const INVALID_KERNEL_NAME: cl_int = CL_INVALID_KERNEL_NAME;
-    pub const INVALID_KERNEL_DEFINITION: cl_int = CL_INVALID_KERNEL_DEFINITION;
-    pub const INVALID_KERNEL: cl_int = CL_INVALID_KERNEL;
-    pub const INVALID_ARG_INDEX: cl_int = CL_INVALID_ARG_INDEX;
-    pub const INVALID_ARG_VALUE: cl_int = CL_INVALID_ARG_VALUE;
-    pub const INVALID_ARG_SIZE: cl_int = CL_INVALID_ARG_SIZE;
-    pub const INVALID_KERNEL_ARGS: cl_int = CL_INVALID_KERNEL_ARGS;
-    pub const INVALID_WORK_DIMENSION: cl_int = CL_INVALID_WORK_DIMENSION;
-    pub const INVALID_WORK_GROUP_SIZE: cl_int = CL_INVALID_WORK_GROUP_SIZE;
-    pub const INVALID_WORK_ITEM_SIZE: cl_int = CL_INVALID_WORK_ITEM_SIZE;
-    pub const INVALID_GLOBAL_OFFSET: cl_int = CL_INVALID_GLOBAL_OFFSET;
-    pub const INVALID_EVENT_WAIT_LIST: cl_int = CL_INVALID_EVENT_WAIT_LIST;
-    pub const INVALID_EVENT: cl_int = CL_INVALID_EVENT;
-    pub const INVALID_OPERATION: cl_int = CL_INVALID_OPERATION;
-    pub const INVALID_GL_OBJECT: cl_int = CL_INVALID_GL_OBJECT;
-    pub const INVALID_BUFFER_SIZE: cl_int = CL_INVALID_BUFFER_SIZE;
-    pub const INVALID_MIP_LEVEL: cl_int = CL_INVALID_MIP_LEVEL;
-    pub const INVALID_GLOBAL_WORK_SIZE: cl_int = CL_INVALID_GLOBAL_WORK_SIZE;
-    // cl_version_1_1;
-    pub const INVALID_PROPERTY: cl_int = CL_INVALID_PROPERTY;
-    // cl_version_1_2;
-    pub const INVALID_IMAGE_DESCRIPTOR: cl_int = CL_INVALID_IMAGE_DESCRIPTOR;
-    pub const INVALID_COMPILER_OPTIONS: cl_int = CL_INVALID_COMPILER_OPTIONS;
-    pub const INVALID_LINKER_OPTIONS: cl_int = CL_INVALID_LINKER_OPTIONS;
-    pub const INVALID_DEVICE_PARTITION_COUNT: cl_int = CL_INVALID_DEVICE_PARTITION_COUNT;
-    // cl_version_2_0;
-    pub const INVALID_PIPE_SIZE: cl_int = CL_INVALID_PIPE_SIZE;
-    pub const INVALID_DEVICE_QUEUE: cl_int = CL_INVALID_DEVICE_QUEUE;
-    // cl_version_2_2;
-    pub const INVALID_SPEC_ID: cl_int = CL_INVALID_SPEC_ID;
-    pub const MAX_SIZE_RESTRICTION_EXCEEDED: cl_int = CL_MAX_SIZE_RESTRICTION_EXCEEDED;
+gen_consts! {
+    StatusCode: cl_int {
+        // Error Codes
+        SUCCESS = CL_SUCCESS,
+        DEVICE_NOT_FOUND = CL_DEVICE_NOT_FOUND,
+        DEVICE_NOT_AVAILABLE = CL_DEVICE_NOT_AVAILABLE,
+        COMPILER_NOT_AVAILABLE = CL_COMPILER_NOT_AVAILABLE,
+        MEM_OBJECT_ALLOCATION_FAILURE = CL_MEM_OBJECT_ALLOCATION_FAILURE,
+        OUT_OF_RESOURCES = CL_OUT_OF_RESOURCES,
+        OUT_OF_HOST_MEMORY = CL_OUT_OF_HOST_MEMORY,
+        PROFILING_INFO_NOT_AVAILABLE = CL_PROFILING_INFO_NOT_AVAILABLE,
+        MEM_COPY_OVERLAP = CL_MEM_COPY_OVERLAP,
+        IMAGE_FORMAT_MISMATCH = CL_IMAGE_FORMAT_MISMATCH,
+        IMAGE_FORMAT_NOT_SUPPORTED = CL_IMAGE_FORMAT_NOT_SUPPORTED,
+        BUILD_PROGRAM_FAILURE = CL_BUILD_PROGRAM_FAILURE,
+        MAP_FAILURE = CL_MAP_FAILURE,
+        // #ifdef CL_VERSION_1_1
+        MISALIGNED_SUB_BUFFER_OFFSET = CL_MISALIGNED_SUB_BUFFER_OFFSET,
+        EXEC_STATUS_ERROR_FOR_EVENTS_IN_WAIT_LIST = CL_EXEC_STATUS_ERROR_FOR_EVENTS_IN_WAIT_LIST,
+        // #endif
+        // #ifdef CL_VERSION_1_2
+        COMPILE_PROGRAM_FAILURE = CL_COMPILE_PROGRAM_FAILURE,
+        LINKER_NOT_AVAILABLE = CL_LINKER_NOT_AVAILABLE,
+        LINK_PROGRAM_FAILURE = CL_LINK_PROGRAM_FAILURE,
+        DEVICE_PARTITION_FAILED = CL_DEVICE_PARTITION_FAILED,
+        KERNEL_ARG_INFO_NOT_AVAILABLE = CL_KERNEL_ARG_INFO_NOT_AVAILABLE,
+        // #endif
+        INVALID_VALUE = CL_INVALID_VALUE,
+        INVALID_DEVICE_TYPE = CL_INVALID_DEVICE_TYPE,
+        INVALID_PLATFORM = CL_INVALID_PLATFORM,
+        INVALID_DEVICE = CL_INVALID_DEVICE,
+        INVALID_CONTEXT = CL_INVALID_CONTEXT,
+        INVALID_QUEUE_PROPERTIES = CL_INVALID_QUEUE_PROPERTIES,
+        INVALID_COMMAND_QUEUE = CL_INVALID_COMMAND_QUEUE,
+        INVALID_HOST_PTR = CL_INVALID_HOST_PTR,
+        INVALID_MEM_OBJECT = CL_INVALID_MEM_OBJECT,
+        INVALID_IMAGE_FORMAT_DESCRIPTOR = CL_INVALID_IMAGE_FORMAT_DESCRIPTOR,
+        INVALID_IMAGE_SIZE = CL_INVALID_IMAGE_SIZE,
+        INVALID_SAMPLER = CL_INVALID_SAMPLER,
+        INVALID_BINARY = CL_INVALID_BINARY,
+        INVALID_BUILD_OPTIONS = CL_INVALID_BUILD_OPTIONS,
+        INVALID_PROGRAM = CL_INVALID_PROGRAM,
+        INVALID_PROGRAM_EXECUTABLE = CL_INVALID_PROGRAM_EXECUTABLE,
+        INVALID_KERNEL_NAME = CL_INVALID_KERNEL_NAME,
+        INVALID_KERNEL_DEFINITION = CL_INVALID_KERNEL_DEFINITION,
+        INVALID_KERNEL = CL_INVALID_KERNEL,
+        INVALID_ARG_INDEX = CL_INVALID_ARG_INDEX,
+        INVALID_ARG_VALUE = CL_INVALID_ARG_VALUE,
+        INVALID_ARG_SIZE = CL_INVALID_ARG_SIZE,
+        INVALID_KERNEL_ARGS = CL_INVALID_KERNEL_ARGS,
+        INVALID_WORK_DIMENSION = CL_INVALID_WORK_DIMENSION,
+        INVALID_WORK_GROUP_SIZE = CL_INVALID_WORK_GROUP_SIZE,
+        INVALID_WORK_ITEM_SIZE = CL_INVALID_WORK_ITEM_SIZE,
+        INVALID_GLOBAL_OFFSET = CL_INVALID_GLOBAL_OFFSET,
+        INVALID_EVENT_WAIT_LIST = CL_INVALID_EVENT_WAIT_LIST,
+        INVALID_EVENT = CL_INVALID_EVENT,
+        INVALID_OPERATION = CL_INVALID_OPERATION,
+        INVALID_GL_OBJECT = CL_INVALID_GL_OBJECT,
+        INVALID_BUFFER_SIZE = CL_INVALID_BUFFER_SIZE,
+        INVALID_MIP_LEVEL = CL_INVALID_MIP_LEVEL,
+        INVALID_GLOBAL_WORK_SIZE = CL_INVALID_GLOBAL_WORK_SIZE,
+        // cl_version_1_1;
+        INVALID_PROPERTY = CL_INVALID_PROPERTY,
+        // cl_version_1_2;
+        INVALID_IMAGE_DESCRIPTOR = CL_INVALID_IMAGE_DESCRIPTOR,
+        INVALID_COMPILER_OPTIONS = CL_INVALID_COMPILER_OPTIONS,
+        INVALID_LINKER_OPTIONS = CL_INVALID_LINKER_OPTIONS,
+        INVALID_DEVICE_PARTITION_COUNT = CL_INVALID_DEVICE_PARTITION_COUNT,
+        // cl_version_2_0;
+        INVALID_PIPE_SIZE = CL_INVALID_PIPE_SIZE,
+        INVALID_DEVICE_QUEUE = CL_INVALID_DEVICE_QUEUE,
+        // cl_version_2_2;
+        INVALID_SPEC_ID = CL_INVALID_SPEC_ID,
+        MAX_SIZE_RESTRICTION_EXCEEDED = CL_MAX_SIZE_RESTRICTION_EXCEEDED,
+    }
 }
 
 /*************************************************************
@@ -113,204 +115,205 @@ impl StatusCode {
 
 #[non_exhaustive]
 pub struct PlatformInfo;
-impl PlatformInfo {
-    /* cl_platform_info - cl_uint */
-    pub const PROFILE: cl_platform_info = CL_PLATFORM_PROFILE;
-    pub const VERSION: cl_platform_info = CL_PLATFORM_VERSION;
-    pub const NAME: cl_platform_info = CL_PLATFORM_NAME;
-    pub const VENDOR: cl_platform_info = CL_PLATFORM_VENDOR;
-    pub const EXTENSIONS: cl_platform_info = CL_PLATFORM_EXTENSIONS;
-    // 2.1
-    pub const HOST_TIMER_RESOLUTION: cl_platform_info = CL_PLATFORM_HOST_TIMER_RESOLUTION;
-    // 3.0
-    pub const NUMERIC_VERSION: cl_platform_info = CL_PLATFORM_NUMERIC_VERSION;
-    // 3.0
-    pub const EXTENSIONS_WITH_VERSION: cl_platform_info = CL_PLATFORM_EXTENSIONS_WITH_VERSION;
+gen_consts! {
+    PlatformInfo: cl_platform_info {
+        /* cl_platform_info - cl_uint */
+        PROFILE = CL_PLATFORM_PROFILE,
+        VERSION = CL_PLATFORM_VERSION,
+        NAME = CL_PLATFORM_NAME,
+        VENDOR = CL_PLATFORM_VENDOR,
+        EXTENSIONS = CL_PLATFORM_EXTENSIONS,
+        // 2.1
+        HOST_TIMER_RESOLUTION = CL_PLATFORM_HOST_TIMER_RESOLUTION,
+        // 3.0
+        NUMERIC_VERSION = CL_PLATFORM_NUMERIC_VERSION,
+        // 3.0
+        EXTENSIONS_WITH_VERSION = CL_PLATFORM_EXTENSIONS_WITH_VERSION,
+    }
 }
 
 #[non_exhaustive]
 pub struct DeviceInfo;
-impl DeviceInfo {
-    /* cl_device_info - cl_uint */
-    pub const TYPE: cl_device_info = CL_DEVICE_TYPE;
-    pub const VENDOR_ID: cl_device_info = CL_DEVICE_VENDOR_ID;
-    pub const MAX_COMPUTE_UNITS: cl_device_info = CL_DEVICE_MAX_COMPUTE_UNITS;
-    pub const MAX_WORK_ITEM_DIMENSIONS: cl_device_info = CL_DEVICE_MAX_WORK_ITEM_DIMENSIONS;
-    pub const MAX_WORK_GROUP_SIZE: cl_device_info = CL_DEVICE_MAX_WORK_GROUP_SIZE;
-    pub const MAX_WORK_ITEM_SIZES: cl_device_info = CL_DEVICE_MAX_WORK_ITEM_SIZES;
-    pub const PREFERRED_VECTOR_WIDTH_CHAR: cl_device_info = CL_DEVICE_PREFERRED_VECTOR_WIDTH_CHAR;
-    pub const PREFERRED_VECTOR_WIDTH_SHORT: cl_device_info = CL_DEVICE_PREFERRED_VECTOR_WIDTH_SHORT;
-    pub const PREFERRED_VECTOR_WIDTH_INT: cl_device_info = CL_DEVICE_PREFERRED_VECTOR_WIDTH_INT;
-    pub const PREFERRED_VECTOR_WIDTH_LONG: cl_device_info = CL_DEVICE_PREFERRED_VECTOR_WIDTH_LONG;
-    pub const PREFERRED_VECTOR_WIDTH_FLOAT: cl_device_info = CL_DEVICE_PREFERRED_VECTOR_WIDTH_FLOAT;
-    pub const PREFERRED_VECTOR_WIDTH_DOUBLE: cl_device_info =
-        CL_DEVICE_PREFERRED_VECTOR_WIDTH_DOUBLE;
-    pub const MAX_CLOCK_FREQUENCY: cl_device_info = CL_DEVICE_MAX_CLOCK_FREQUENCY;
-    pub const ADDRESS_BITS: cl_device_info = CL_DEVICE_ADDRESS_BITS;
-    pub const MAX_READ_IMAGE_ARGS: cl_device_info = CL_DEVICE_MAX_READ_IMAGE_ARGS;
-    pub const MAX_WRITE_IMAGE_ARGS: cl_device_info = CL_DEVICE_MAX_WRITE_IMAGE_ARGS;
-    pub const MAX_MEM_ALLOC_SIZE: cl_device_info = CL_DEVICE_MAX_MEM_ALLOC_SIZE;
-    pub const IMAGE2D_MAX_WIDTH: cl_device_info = CL_DEVICE_IMAGE2D_MAX_WIDTH;
-    pub const IMAGE2D_MAX_HEIGHT: cl_device_info = CL_DEVICE_IMAGE2D_MAX_HEIGHT;
-    pub const IMAGE3D_MAX_WIDTH: cl_device_info = CL_DEVICE_IMAGE3D_MAX_WIDTH;
-    pub const IMAGE3D_MAX_HEIGHT: cl_device_info = CL_DEVICE_IMAGE3D_MAX_HEIGHT;
-    pub const IMAGE3D_MAX_DEPTH: cl_device_info = CL_DEVICE_IMAGE3D_MAX_DEPTH;
-    pub const IMAGE_SUPPORT: cl_device_info = CL_DEVICE_IMAGE_SUPPORT;
-    pub const MAX_PARAMETER_SIZE: cl_device_info = CL_DEVICE_MAX_PARAMETER_SIZE;
-    pub const MAX_SAMPLERS: cl_device_info = CL_DEVICE_MAX_SAMPLERS;
-    pub const MEM_BASE_ADDR_ALIGN: cl_device_info = CL_DEVICE_MEM_BASE_ADDR_ALIGN;
-    pub const MIN_DATA_TYPE_ALIGN_SIZE: cl_device_info = CL_DEVICE_MIN_DATA_TYPE_ALIGN_SIZE;
-    pub const SINGLE_FP_CONFIG: cl_device_info = CL_DEVICE_SINGLE_FP_CONFIG;
-    pub const GLOBAL_MEM_CACHE_TYPE: cl_device_info = CL_DEVICE_GLOBAL_MEM_CACHE_TYPE;
-    pub const GLOBAL_MEM_CACHELINE_SIZE: cl_device_info = CL_DEVICE_GLOBAL_MEM_CACHELINE_SIZE;
-    pub const GLOBAL_MEM_CACHE_SIZE: cl_device_info = CL_DEVICE_GLOBAL_MEM_CACHE_SIZE;
-    pub const GLOBAL_MEM_SIZE: cl_device_info = CL_DEVICE_GLOBAL_MEM_SIZE;
-    pub const MAX_CONSTANT_BUFFER_SIZE: cl_device_info = CL_DEVICE_MAX_CONSTANT_BUFFER_SIZE;
-    pub const MAX_CONSTANT_ARGS: cl_device_info = CL_DEVICE_MAX_CONSTANT_ARGS;
-    pub const LOCAL_MEM_TYPE: cl_device_info = CL_DEVICE_LOCAL_MEM_TYPE;
-    pub const LOCAL_MEM_SIZE: cl_device_info = CL_DEVICE_LOCAL_MEM_SIZE;
-    pub const ERROR_CORRECTION_SUPPORT: cl_device_info = CL_DEVICE_ERROR_CORRECTION_SUPPORT;
-    pub const PROFILING_TIMER_RESOLUTION: cl_device_info = CL_DEVICE_PROFILING_TIMER_RESOLUTION;
-    pub const ENDIAN_LITTLE: cl_device_info = CL_DEVICE_ENDIAN_LITTLE;
-    pub const AVAILABLE: cl_device_info = CL_DEVICE_AVAILABLE;
-    pub const COMPILER_AVAILABLE: cl_device_info = CL_DEVICE_COMPILER_AVAILABLE;
-    pub const EXECUTION_CAPABILITIES: cl_device_info = CL_DEVICE_EXECUTION_CAPABILITIES;
-    pub const QUEUE_PROPERTIES: cl_device_info = CL_DEVICE_QUEUE_PROPERTIES; /* deprecated */
-    // #ifdef CL_VERSION_2_0;
-    pub const QUEUE_ON_HOST_PROPERTIES: cl_device_info = CL_DEVICE_QUEUE_ON_HOST_PROPERTIES;
-    // #endif;
-    pub const NAME: cl_device_info = CL_DEVICE_NAME;
-    pub const VENDOR: cl_device_info = CL_DEVICE_VENDOR;
-    pub const DRIVER_VERSION: cl_device_info = CL_DRIVER_VERSION;
-    pub const PROFILE: cl_device_info = CL_DEVICE_PROFILE;
-    pub const VERSION: cl_device_info = CL_DEVICE_VERSION;
-    pub const EXTENSIONS: cl_device_info = CL_DEVICE_EXTENSIONS;
-    pub const PLATFORM: cl_device_info = CL_DEVICE_PLATFORM;
-    // #ifdef CL_VERSION_1_2;
-    pub const DOUBLE_FP_CONFIG: cl_device_info = CL_DEVICE_DOUBLE_FP_CONFIG;
-    // #endif;
-    /* 0x1033 reserved for CL_DEVICE_HALF_FP_CONFIG which is already defined in "cl_ext.h" */
-    // #ifdef CL_VERSION_1_1;
-    pub const PREFERRED_VECTOR_WIDTH_HALF: cl_device_info = CL_DEVICE_PREFERRED_VECTOR_WIDTH_HALF;
-    pub const HOST_UNIFIED_MEMORY: cl_device_info = CL_DEVICE_HOST_UNIFIED_MEMORY; /* deprecated */
-    pub const NATIVE_VECTOR_WIDTH_CHAR: cl_device_info = CL_DEVICE_NATIVE_VECTOR_WIDTH_CHAR;
-    pub const NATIVE_VECTOR_WIDTH_SHORT: cl_device_info = CL_DEVICE_NATIVE_VECTOR_WIDTH_SHORT;
-    pub const NATIVE_VECTOR_WIDTH_INT: cl_device_info = CL_DEVICE_NATIVE_VECTOR_WIDTH_INT;
-    pub const NATIVE_VECTOR_WIDTH_LONG: cl_device_info = CL_DEVICE_NATIVE_VECTOR_WIDTH_LONG;
-    pub const NATIVE_VECTOR_WIDTH_FLOAT: cl_device_info = CL_DEVICE_NATIVE_VECTOR_WIDTH_FLOAT;
-    pub const NATIVE_VECTOR_WIDTH_DOUBLE: cl_device_info = CL_DEVICE_NATIVE_VECTOR_WIDTH_DOUBLE;
-    pub const NATIVE_VECTOR_WIDTH_HALF: cl_device_info = CL_DEVICE_NATIVE_VECTOR_WIDTH_HALF;
-    pub const OPENCL_C_VERSION: cl_device_info = CL_DEVICE_OPENCL_C_VERSION;
-    // #endif;
-    // #ifdef CL_VERSION_1_2;
-    pub const LINKER_AVAILABLE: cl_device_info = CL_DEVICE_LINKER_AVAILABLE;
-    pub const BUILT_IN_KERNELS: cl_device_info = CL_DEVICE_BUILT_IN_KERNELS;
-    pub const IMAGE_MAX_BUFFER_SIZE: cl_device_info = CL_DEVICE_IMAGE_MAX_BUFFER_SIZE;
-    pub const IMAGE_MAX_ARRAY_SIZE: cl_device_info = CL_DEVICE_IMAGE_MAX_ARRAY_SIZE;
-    pub const PARENT_DEVICE: cl_device_info = CL_DEVICE_PARENT_DEVICE;
-    pub const PARTITION_MAX_SUB_DEVICES: cl_device_info = CL_DEVICE_PARTITION_MAX_SUB_DEVICES;
-    pub const PARTITION_PROPERTIES: cl_device_info = CL_DEVICE_PARTITION_PROPERTIES;
-    pub const PARTITION_AFFINITY_DOMAIN: cl_device_info = CL_DEVICE_PARTITION_AFFINITY_DOMAIN;
-    pub const PARTITION_TYPE: cl_device_info = CL_DEVICE_PARTITION_TYPE;
-    pub const REFERENCE_COUNT: cl_device_info = CL_DEVICE_REFERENCE_COUNT;
-    pub const PREFERRED_INTEROP_USER_SYNC: cl_device_info = CL_DEVICE_PREFERRED_INTEROP_USER_SYNC;
-    pub const PRINTF_BUFFER_SIZE: cl_device_info = CL_DEVICE_PRINTF_BUFFER_SIZE;
-    // #endif;
-    // #ifdef CL_VERSION_2_0;
-    pub const IMAGE_PITCH_ALIGNMENT: cl_device_info = CL_DEVICE_IMAGE_PITCH_ALIGNMENT;
-    pub const IMAGE_BASE_ADDRESS_ALIGNMENT: cl_device_info = CL_DEVICE_IMAGE_BASE_ADDRESS_ALIGNMENT;
-    pub const MAX_READ_WRITE_IMAGE_ARGS: cl_device_info = CL_DEVICE_MAX_READ_WRITE_IMAGE_ARGS;
-    pub const MAX_GLOBAL_VARIABLE_SIZE: cl_device_info = CL_DEVICE_MAX_GLOBAL_VARIABLE_SIZE;
-    pub const QUEUE_ON_DEVICE_PROPERTIES: cl_device_info = CL_DEVICE_QUEUE_ON_DEVICE_PROPERTIES;
-    pub const QUEUE_ON_DEVICE_PREFERRED_SIZE: cl_device_info =
-        CL_DEVICE_QUEUE_ON_DEVICE_PREFERRED_SIZE;
-    pub const QUEUE_ON_DEVICE_MAX_SIZE: cl_device_info = CL_DEVICE_QUEUE_ON_DEVICE_MAX_SIZE;
-    pub const MAX_ON_DEVICE_QUEUES: cl_device_info = CL_DEVICE_MAX_ON_DEVICE_QUEUES;
-    pub const MAX_ON_DEVICE_EVENTS: cl_device_info = CL_DEVICE_MAX_ON_DEVICE_EVENTS;
-    pub const SVM_CAPABILITIES: cl_device_info = CL_DEVICE_SVM_CAPABILITIES;
-    pub const GLOBAL_VARIABLE_PREFERRED_TOTAL_SIZE: cl_device_info =
-        CL_DEVICE_GLOBAL_VARIABLE_PREFERRED_TOTAL_SIZE;
-    pub const MAX_PIPE_ARGS: cl_device_info = CL_DEVICE_MAX_PIPE_ARGS;
-    pub const PIPE_MAX_ACTIVE_RESERVATIONS: cl_device_info = CL_DEVICE_PIPE_MAX_ACTIVE_RESERVATIONS;
-    pub const PIPE_MAX_PACKET_SIZE: cl_device_info = CL_DEVICE_PIPE_MAX_PACKET_SIZE;
-    pub const PREFERRED_PLATFORM_ATOMIC_ALIGNMENT: cl_device_info =
-        CL_DEVICE_PREFERRED_PLATFORM_ATOMIC_ALIGNMENT;
-    pub const PREFERRED_GLOBAL_ATOMIC_ALIGNMENT: cl_device_info =
-        CL_DEVICE_PREFERRED_GLOBAL_ATOMIC_ALIGNMENT;
-    pub const PREFERRED_LOCAL_ATOMIC_ALIGNMENT: cl_device_info =
-        CL_DEVICE_PREFERRED_LOCAL_ATOMIC_ALIGNMENT;
-    // #endif;
-    // #ifdef CL_VERSION_2_1;
-    pub const IL_VERSION: cl_device_info = CL_DEVICE_IL_VERSION;
-    pub const MAX_NUM_SUB_GROUPS: cl_device_info = CL_DEVICE_MAX_NUM_SUB_GROUPS;
-    pub const SUB_GROUP_INDEPENDENT_FORWARD_PROGRESS: cl_device_info =
-        CL_DEVICE_SUB_GROUP_INDEPENDENT_FORWARD_PROGRESS;
-    // #endif;
-    // #if;CL_VERSION_3_0;
-    pub const NUMERIC_VERSION: cl_device_info = CL_DEVICE_NUMERIC_VERSION;
-    pub const EXTENSIONS_WITH_VERSION: cl_device_info = CL_DEVICE_EXTENSIONS_WITH_VERSION;
-    pub const ILS_WITH_VERSION: cl_device_info = CL_DEVICE_ILS_WITH_VERSION;
-    pub const BUILT_IN_KERNELS_WITH_VERSION: cl_device_info =
-        CL_DEVICE_BUILT_IN_KERNELS_WITH_VERSION;
-    pub const ATOMIC_MEMORY_CAPABILITIES: cl_device_info = CL_DEVICE_ATOMIC_MEMORY_CAPABILITIES;
-    pub const ATOMIC_FENCE_CAPABILITIES: cl_device_info = CL_DEVICE_ATOMIC_FENCE_CAPABILITIES;
-    pub const NON_UNIFORM_WORK_GROUP_SUPPORT: cl_device_info =
-        CL_DEVICE_NON_UNIFORM_WORK_GROUP_SUPPORT;
-    pub const OPENCL_C_ALL_VERSIONS: cl_device_info = CL_DEVICE_OPENCL_C_ALL_VERSIONS;
-    pub const PREFERRED_WORK_GROUP_SIZE_MULTIPLE: cl_device_info =
-        CL_DEVICE_PREFERRED_WORK_GROUP_SIZE_MULTIPLE;
-    pub const WORK_GROUP_COLLECTIVE_FUNCTIONS_SUPPORT: cl_device_info =
-        CL_DEVICE_WORK_GROUP_COLLECTIVE_FUNCTIONS_SUPPORT;
-    pub const GENERIC_ADDRESS_SPACE_SUPPORT: cl_device_info =
-        CL_DEVICE_GENERIC_ADDRESS_SPACE_SUPPORT;
-    /* 0x106A to 0x106E - Reserved for upcoming KHR extension */
-    pub const OPENCL_C_FEATURES: cl_device_info = CL_DEVICE_OPENCL_C_FEATURES;
-    pub const DEVICE_ENQUEUE_CAPABILITIES: cl_device_info = CL_DEVICE_DEVICE_ENQUEUE_CAPABILITIES;
-    pub const PIPE_SUPPORT: cl_device_info = CL_DEVICE_PIPE_SUPPORT;
-    pub const LATEST_CONFORMANCE_VERSION_PASSED: cl_device_info =
-        CL_DEVICE_LATEST_CONFORMANCE_VERSION_PASSED;
-    // #endif
+gen_consts! {
+    DeviceInfo: cl_device_info {
+        /* cl_device_info - cl_uint */
+        TYPE = CL_DEVICE_TYPE,
+        VENDOR_ID = CL_DEVICE_VENDOR_ID,
+        MAX_COMPUTE_UNITS = CL_DEVICE_MAX_COMPUTE_UNITS,
+        MAX_WORK_ITEM_DIMENSIONS = CL_DEVICE_MAX_WORK_ITEM_DIMENSIONS,
+        MAX_WORK_GROUP_SIZE = CL_DEVICE_MAX_WORK_GROUP_SIZE,
+        MAX_WORK_ITEM_SIZES = CL_DEVICE_MAX_WORK_ITEM_SIZES,
+        PREFERRED_VECTOR_WIDTH_CHAR = CL_DEVICE_PREFERRED_VECTOR_WIDTH_CHAR,
+        PREFERRED_VECTOR_WIDTH_SHORT = CL_DEVICE_PREFERRED_VECTOR_WIDTH_SHORT,
+        PREFERRED_VECTOR_WIDTH_INT = CL_DEVICE_PREFERRED_VECTOR_WIDTH_INT,
+        PREFERRED_VECTOR_WIDTH_LONG = CL_DEVICE_PREFERRED_VECTOR_WIDTH_LONG,
+        PREFERRED_VECTOR_WIDTH_FLOAT = CL_DEVICE_PREFERRED_VECTOR_WIDTH_FLOAT,
+        PREFERRED_VECTOR_WIDTH_DOUBLE = CL_DEVICE_PREFERRED_VECTOR_WIDTH_DOUBLE,
+        MAX_CLOCK_FREQUENCY = CL_DEVICE_MAX_CLOCK_FREQUENCY,
+        ADDRESS_BITS = CL_DEVICE_ADDRESS_BITS,
+        MAX_READ_IMAGE_ARGS = CL_DEVICE_MAX_READ_IMAGE_ARGS,
+        MAX_WRITE_IMAGE_ARGS = CL_DEVICE_MAX_WRITE_IMAGE_ARGS,
+        MAX_MEM_ALLOC_SIZE = CL_DEVICE_MAX_MEM_ALLOC_SIZE,
+        IMAGE2D_MAX_WIDTH = CL_DEVICE_IMAGE2D_MAX_WIDTH,
+        IMAGE2D_MAX_HEIGHT = CL_DEVICE_IMAGE2D_MAX_HEIGHT,
+        IMAGE3D_MAX_WIDTH = CL_DEVICE_IMAGE3D_MAX_WIDTH,
+        IMAGE3D_MAX_HEIGHT = CL_DEVICE_IMAGE3D_MAX_HEIGHT,
+        IMAGE3D_MAX_DEPTH = CL_DEVICE_IMAGE3D_MAX_DEPTH,
+        IMAGE_SUPPORT = CL_DEVICE_IMAGE_SUPPORT,
+        MAX_PARAMETER_SIZE = CL_DEVICE_MAX_PARAMETER_SIZE,
+        MAX_SAMPLERS = CL_DEVICE_MAX_SAMPLERS,
+        MEM_BASE_ADDR_ALIGN = CL_DEVICE_MEM_BASE_ADDR_ALIGN,
+        MIN_DATA_TYPE_ALIGN_SIZE = CL_DEVICE_MIN_DATA_TYPE_ALIGN_SIZE,
+        SINGLE_FP_CONFIG = CL_DEVICE_SINGLE_FP_CONFIG,
+        GLOBAL_MEM_CACHE_TYPE = CL_DEVICE_GLOBAL_MEM_CACHE_TYPE,
+        GLOBAL_MEM_CACHELINE_SIZE = CL_DEVICE_GLOBAL_MEM_CACHELINE_SIZE,
+        GLOBAL_MEM_CACHE_SIZE = CL_DEVICE_GLOBAL_MEM_CACHE_SIZE,
+        GLOBAL_MEM_SIZE = CL_DEVICE_GLOBAL_MEM_SIZE,
+        MAX_CONSTANT_BUFFER_SIZE = CL_DEVICE_MAX_CONSTANT_BUFFER_SIZE,
+        MAX_CONSTANT_ARGS = CL_DEVICE_MAX_CONSTANT_ARGS,
+        LOCAL_MEM_TYPE = CL_DEVICE_LOCAL_MEM_TYPE,
+        LOCAL_MEM_SIZE = CL_DEVICE_LOCAL_MEM_SIZE,
+        ERROR_CORRECTION_SUPPORT = CL_DEVICE_ERROR_CORRECTION_SUPPORT,
+        PROFILING_TIMER_RESOLUTION = CL_DEVICE_PROFILING_TIMER_RESOLUTION,
+        ENDIAN_LITTLE = CL_DEVICE_ENDIAN_LITTLE,
+        AVAILABLE = CL_DEVICE_AVAILABLE,
+        COMPILER_AVAILABLE = CL_DEVICE_COMPILER_AVAILABLE,
+        EXECUTION_CAPABILITIES = CL_DEVICE_EXECUTION_CAPABILITIES,
+        QUEUE_PROPERTIES = CL_DEVICE_QUEUE_PROPERTIES, /* deprecated */
+        // #ifdef CL_VERSION_2_0;
+        QUEUE_ON_HOST_PROPERTIES = CL_DEVICE_QUEUE_ON_HOST_PROPERTIES,
+        // #endif;
+        NAME = CL_DEVICE_NAME,
+        VENDOR = CL_DEVICE_VENDOR,
+        DRIVER_VERSION = CL_DRIVER_VERSION,
+        PROFILE = CL_DEVICE_PROFILE,
+        VERSION = CL_DEVICE_VERSION,
+        EXTENSIONS = CL_DEVICE_EXTENSIONS,
+        PLATFORM = CL_DEVICE_PLATFORM,
+        // #ifdef CL_VERSION_1_2;
+        DOUBLE_FP_CONFIG = CL_DEVICE_DOUBLE_FP_CONFIG,
+        // #endif;
+        /* 0x1033 reserved for CL_DEVICE_HALF_FP_CONFIG which is already defined in "cl_ext.h" */
+        // #ifdef CL_VERSION_1_1;
+        PREFERRED_VECTOR_WIDTH_HALF = CL_DEVICE_PREFERRED_VECTOR_WIDTH_HALF,
+        HOST_UNIFIED_MEMORY = CL_DEVICE_HOST_UNIFIED_MEMORY, /* deprecated */
+        NATIVE_VECTOR_WIDTH_CHAR = CL_DEVICE_NATIVE_VECTOR_WIDTH_CHAR,
+        NATIVE_VECTOR_WIDTH_SHORT = CL_DEVICE_NATIVE_VECTOR_WIDTH_SHORT,
+        NATIVE_VECTOR_WIDTH_INT = CL_DEVICE_NATIVE_VECTOR_WIDTH_INT,
+        NATIVE_VECTOR_WIDTH_LONG = CL_DEVICE_NATIVE_VECTOR_WIDTH_LONG,
+        NATIVE_VECTOR_WIDTH_FLOAT = CL_DEVICE_NATIVE_VECTOR_WIDTH_FLOAT,
+        NATIVE_VECTOR_WIDTH_DOUBLE = CL_DEVICE_NATIVE_VECTOR_WIDTH_DOUBLE,
+        NATIVE_VECTOR_WIDTH_HALF = CL_DEVICE_NATIVE_VECTOR_WIDTH_HALF,
+        OPENCL_C_VERSION = CL_DEVICE_OPENCL_C_VERSION,
+        // #endif;
+        // #ifdef CL_VERSION_1_2;
+        LINKER_AVAILABLE = CL_DEVICE_LINKER_AVAILABLE,
+        BUILT_IN_KERNELS = CL_DEVICE_BUILT_IN_KERNELS,
+        IMAGE_MAX_BUFFER_SIZE = CL_DEVICE_IMAGE_MAX_BUFFER_SIZE,
+        IMAGE_MAX_ARRAY_SIZE = CL_DEVICE_IMAGE_MAX_ARRAY_SIZE,
+        PARENT_DEVICE = CL_DEVICE_PARENT_DEVICE,
+        PARTITION_MAX_SUB_DEVICES = CL_DEVICE_PARTITION_MAX_SUB_DEVICES,
+        PARTITION_PROPERTIES = CL_DEVICE_PARTITION_PROPERTIES,
+        PARTITION_AFFINITY_DOMAIN = CL_DEVICE_PARTITION_AFFINITY_DOMAIN,
+        PARTITION_TYPE = CL_DEVICE_PARTITION_TYPE,
+        REFERENCE_COUNT = CL_DEVICE_REFERENCE_COUNT,
+        PREFERRED_INTEROP_USER_SYNC = CL_DEVICE_PREFERRED_INTEROP_USER_SYNC,
+        PRINTF_BUFFER_SIZE = CL_DEVICE_PRINTF_BUFFER_SIZE,
+        // #endif;
+        // #ifdef CL_VERSION_2_0;
+        IMAGE_PITCH_ALIGNMENT = CL_DEVICE_IMAGE_PITCH_ALIGNMENT,
+        IMAGE_BASE_ADDRESS_ALIGNMENT = CL_DEVICE_IMAGE_BASE_ADDRESS_ALIGNMENT,
+        MAX_READ_WRITE_IMAGE_ARGS = CL_DEVICE_MAX_READ_WRITE_IMAGE_ARGS,
+        MAX_GLOBAL_VARIABLE_SIZE = CL_DEVICE_MAX_GLOBAL_VARIABLE_SIZE,
+        QUEUE_ON_DEVICE_PROPERTIES = CL_DEVICE_QUEUE_ON_DEVICE_PROPERTIES,
+        QUEUE_ON_DEVICE_PREFERRED_SIZE = CL_DEVICE_QUEUE_ON_DEVICE_PREFERRED_SIZE,
+        QUEUE_ON_DEVICE_MAX_SIZE = CL_DEVICE_QUEUE_ON_DEVICE_MAX_SIZE,
+        MAX_ON_DEVICE_QUEUES = CL_DEVICE_MAX_ON_DEVICE_QUEUES,
+        MAX_ON_DEVICE_EVENTS = CL_DEVICE_MAX_ON_DEVICE_EVENTS,
+        SVM_CAPABILITIES = CL_DEVICE_SVM_CAPABILITIES,
+        GLOBAL_VARIABLE_PREFERRED_TOTAL_SIZE = CL_DEVICE_GLOBAL_VARIABLE_PREFERRED_TOTAL_SIZE,
+        MAX_PIPE_ARGS = CL_DEVICE_MAX_PIPE_ARGS,
+        PIPE_MAX_ACTIVE_RESERVATIONS = CL_DEVICE_PIPE_MAX_ACTIVE_RESERVATIONS,
+        PIPE_MAX_PACKET_SIZE = CL_DEVICE_PIPE_MAX_PACKET_SIZE,
+        PREFERRED_PLATFORM_ATOMIC_ALIGNMENT = CL_DEVICE_PREFERRED_PLATFORM_ATOMIC_ALIGNMENT,
+        PREFERRED_GLOBAL_ATOMIC_ALIGNMENT = CL_DEVICE_PREFERRED_GLOBAL_ATOMIC_ALIGNMENT,
+        PREFERRED_LOCAL_ATOMIC_ALIGNMENT = CL_DEVICE_PREFERRED_LOCAL_ATOMIC_ALIGNMENT,
+        // #endif;
+        // #ifdef CL_VERSION_2_1;
+        IL_VERSION = CL_DEVICE_IL_VERSION,
+        MAX_NUM_SUB_GROUPS = CL_DEVICE_MAX_NUM_SUB_GROUPS,
+        SUB_GROUP_INDEPENDENT_FORWARD_PROGRESS = CL_DEVICE_SUB_GROUP_INDEPENDENT_FORWARD_PROGRESS,
+        // #endif;
+        // #if;CL_VERSION_3_0;
+        NUMERIC_VERSION = CL_DEVICE_NUMERIC_VERSION,
+        EXTENSIONS_WITH_VERSION = CL_DEVICE_EXTENSIONS_WITH_VERSION,
+        ILS_WITH_VERSION = CL_DEVICE_ILS_WITH_VERSION,
+        BUILT_IN_KERNELS_WITH_VERSION = CL_DEVICE_BUILT_IN_KERNELS_WITH_VERSION,
+        ATOMIC_MEMORY_CAPABILITIES = CL_DEVICE_ATOMIC_MEMORY_CAPABILITIES,
+        ATOMIC_FENCE_CAPABILITIES = CL_DEVICE_ATOMIC_FENCE_CAPABILITIES,
+        NON_UNIFORM_WORK_GROUP_SUPPORT = CL_DEVICE_NON_UNIFORM_WORK_GROUP_SUPPORT,
+        OPENCL_C_ALL_VERSIONS = CL_DEVICE_OPENCL_C_ALL_VERSIONS,
+        PREFERRED_WORK_GROUP_SIZE_MULTIPLE = CL_DEVICE_PREFERRED_WORK_GROUP_SIZE_MULTIPLE,
+        WORK_GROUP_COLLECTIVE_FUNCTIONS_SUPPORT = CL_DEVICE_WORK_GROUP_COLLECTIVE_FUNCTIONS_SUPPORT,
+        GENERIC_ADDRESS_SPACE_SUPPORT = CL_DEVICE_GENERIC_ADDRESS_SPACE_SUPPORT,
+        /* 0x106A to 0x106E - Reserved for upcoming KHR extension */
+        OPENCL_C_FEATURES = CL_DEVICE_OPENCL_C_FEATURES,
+        DEVICE_ENQUEUE_CAPABILITIES = CL_DEVICE_DEVICE_ENQUEUE_CAPABILITIES,
+        PIPE_SUPPORT = CL_DEVICE_PIPE_SUPPORT,
+        LATEST_CONFORMANCE_VERSION_PASSED = CL_DEVICE_LATEST_CONFORMANCE_VERSION_PASSED,
+        // #endif
+    }
 }
 
 #[non_exhaustive]
 pub struct DeviceMemCacheType;
-impl DeviceMemCacheType {
-    /* cl_device_mem_cache_type - cl_uint */
-    pub const NONE: cl_device_mem_cache_type = CL_NONE;
-    pub const READ_ONLY_CACHE: cl_device_mem_cache_type = CL_READ_ONLY_CACHE;
-    pub const READ_WRITE_CACHE: cl_device_mem_cache_type = CL_READ_WRITE_CACHE;
+gen_consts! {
+    DeviceMemCacheType: cl_device_mem_cache_type {
+        /* cl_device_mem_cache_type - cl_uint */
+        NONE = CL_NONE,
+        READ_ONLY_CACHE = CL_READ_ONLY_CACHE,
+        READ_WRITE_CACHE = CL_READ_WRITE_CACHE,
+    }
 }
 
 #[non_exhaustive]
 pub struct DeviceLocalMemType;
-impl DeviceLocalMemType {
-    /* cl_device_local_mem_type - cl_uint */
-    pub const LOCAL: cl_device_local_mem_type = CL_LOCAL;
-    pub const GLOBAL: cl_device_local_mem_type = CL_GLOBAL;
+gen_consts! {
+    DeviceLocalMemType: cl_device_local_mem_type {
+        /* cl_device_local_mem_type - cl_uint */
+        LOCAL = CL_LOCAL,
+        GLOBAL = CL_GLOBAL,
+    }
 }
 
 #[non_exhaustive]
 pub struct ContextInfo;
-impl ContextInfo {
-    /* cl_context_info - cl_uint */
-    pub const REFERENCE_COUNT: cl_context_info = CL_CONTEXT_REFERENCE_COUNT;
-    pub const DEVICES: cl_context_info = CL_CONTEXT_DEVICES;
-    pub const PROPERTIES: cl_context_info = CL_CONTEXT_PROPERTIES;
-    // #ifdef CL_VERSION_1_1;
-    pub const NUM_DEVICES: cl_context_info = CL_CONTEXT_NUM_DEVICES;
-    // #endif;
+gen_consts! {
+    ContextInfo: cl_context_info {
+        /* cl_context_info - cl_uint */
+        REFERENCE_COUNT = CL_CONTEXT_REFERENCE_COUNT,
+        DEVICES = CL_CONTEXT_DEVICES,
+        PROPERTIES = CL_CONTEXT_PROPERTIES,
+        // #ifdef CL_VERSION_1_1;
+        NUM_DEVICES = CL_CONTEXT_NUM_DEVICES,
+        // #endif;
+    }
 }
 
 #[non_exhaustive]
 pub struct DevicePartitionProperty;
+gen_consts! {
+    DevicePartitionProperty: cl_device_partition_property {
+        // #ifdef CL_VERSION_1_2;
+        /* cl_device_partition_property - cl_uint */
+        EQUALLY = CL_DEVICE_PARTITION_EQUALLY,
+        BY_COUNTS = CL_DEVICE_PARTITION_BY_COUNTS,
+        BY_COUNTS_LIST_END = CL_DEVICE_PARTITION_BY_COUNTS_LIST_END,
+        BY_AFFINITY_DOMAIN = CL_DEVICE_PARTITION_BY_AFFINITY_DOMAIN,
+        // #endif;
+    }
+}
 impl DevicePartitionProperty {
-    // #ifdef CL_VERSION_1_2;
-    /* cl_device_partition_property - cl_uint */
-    const EQUALLY: cl_device_partition_property = CL_DEVICE_PARTITION_EQUALLY;
-    const BY_COUNTS: cl_device_partition_property = CL_DEVICE_PARTITION_BY_COUNTS;
-    const BY_COUNTS_LIST_END: cl_device_partition_property = CL_DEVICE_PARTITION_BY_COUNTS_LIST_END;
-    const BY_AFFINITY_DOMAIN: cl_device_partition_property = CL_DEVICE_PARTITION_BY_AFFINITY_DOMAIN;
-    // #endif;
     pub fn equally(&self, compute_units: cl_uint) -> Properties {
         Some(vec![Self::EQUALLY, compute_units as isize, 0])
     }
@@ -330,192 +333,210 @@ impl DevicePartitionProperty {
 
 #[non_exhaustive]
 pub struct CommandQueueInfo;
-impl CommandQueueInfo {
-    /* cl_command_queue_info - cl_uint */
-    pub const CONTEXT: cl_command_queue_info = CL_QUEUE_CONTEXT;
-    pub const DEVICE: cl_command_queue_info = CL_QUEUE_DEVICE;
-    pub const REFERENCE_COUNT: cl_command_queue_info = CL_QUEUE_REFERENCE_COUNT;
-    pub const PROPERTIES: cl_command_queue_info = CL_QUEUE_PROPERTIES;
-    // #ifdef CL_VERSION_2_0;
-    #[cfg(feature = "cl_2_0")]
-    pub const SIZE: cl_command_queue_info = CL_QUEUE_SIZE;
-    // #endif;
-    // #ifdef CL_VERSION_2_1;
-    #[cfg(feature = "cl_2_1")]
-    pub const DEVICE_DEFAULT: cl_command_queue_info = CL_QUEUE_DEVICE_DEFAULT;
-    // #endif;
-    // #ifdef CL_VERSION_3_0;
-    // #[cfg(feature = "cl_3_0")]
-    pub const PROPERTIES_ARRAY: cl_command_queue_info = CL_QUEUE_PROPERTIES_ARRAY;
-    // #endif;
-    // fn properties_tmp(
-    //     &self,
-    //     properties: cl_command_queue_info,
-    //     queue_prop: CommandQueueProperties,
-    // ) -> LongProperties {
-    //     Some(vec![
-    //         properties as cl_properties,
-    //         queue_prop.get() as cl_properties,
-    //         0,
-    //     ])
-    // }
-    // #[cfg(feature = "cl_1_2")]
-    // pub fn properties(&self, queue_prop: CommandQueueProperties) -> LongProperties {
-    //     self.properties_tmp(Self::PROPERTIES, queue_prop)
-    // }
-    // #[cfg(feature = "cl_3_0")]
-    // pub fn properties(&self, queue_prop: CommandQueueProperties) -> LongProperties {
-    //     self.properties_tmp(Self::PROPERTIES_ARRAY, queue_prop)
-    // }
-    // #[cfg(feature = "cl_2_0")]
-    // pub fn size(&self, size: cl_uint) -> LongProperties {
-    //     Some(vec![Self::SIZE as cl_properties, size as cl_properties, 0])
-    // }
+gen_consts! {
+    CommandQueueInfo: cl_command_queue_info {
+        /* cl_command_queue_info - cl_uint */
+        CONTEXT = CL_QUEUE_CONTEXT,
+        DEVICE = CL_QUEUE_DEVICE,
+        REFERENCE_COUNT = CL_QUEUE_REFERENCE_COUNT,
+        PROPERTIES = CL_QUEUE_PROPERTIES,
+        // #ifdef CL_VERSION_2_0;
+        #[cfg(feature = "cl_2_0")]
+        SIZE = CL_QUEUE_SIZE,
+        // #endif;
+        // #ifdef CL_VERSION_2_1;
+        #[cfg(feature = "cl_2_1")]
+        DEVICE_DEFAULT = CL_QUEUE_DEVICE_DEFAULT,
+        // #endif;
+        // #ifdef CL_VERSION_3_0;
+        // #[cfg(feature = "cl_3_0")]
+        PROPERTIES_ARRAY = CL_QUEUE_PROPERTIES_ARRAY,
+        // #endif;
+        // fn properties_tmp(
+        //     &self,
+        //     properties: cl_command_queue_info,
+        //     queue_prop: CommandQueueProperties,
+        // ) -> LongProperties {
+        //     Some(vec![
+        //         properties as cl_properties,
+        //         queue_prop.get() as cl_properties,
+        //         0,
+        //     ])
+        // }
+        // #[cfg(feature = "cl_1_2")]
+        // pub fn properties(&self, queue_prop: CommandQueueProperties) -> LongProperties {
+        //     self.properties_tmp(Self::PROPERTIES, queue_prop)
+        // }
+        // #[cfg(feature = "cl_3_0")]
+        // pub fn properties(&self, queue_prop: CommandQueueProperties) -> LongProperties {
+        //     self.properties_tmp(Self::PROPERTIES_ARRAY, queue_prop)
+        // }
+        // #[cfg(feature = "cl_2_0")]
+        // pub fn size(&self, size: cl_uint) -> LongProperties {
+        //     Some(vec![Self::SIZE as cl_properties, size as cl_properties, 0])
+        // }
+    }
 }
 
 #[non_exhaustive]
 pub struct ChannelOrder;
-impl ChannelOrder {
-    /* cl_channel_order - cl_uint */
-    pub const R: cl_channel_order = CL_R;
-    pub const A: cl_channel_order = CL_A;
-    pub const RG: cl_channel_order = CL_RG;
-    pub const RA: cl_channel_order = CL_RA;
-    pub const RGB: cl_channel_order = CL_RGB;
-    pub const RGBA: cl_channel_order = CL_RGBA;
-    pub const BGRA: cl_channel_order = CL_BGRA;
-    pub const ARGB: cl_channel_order = CL_ARGB;
-    pub const INTENSITY: cl_channel_order = CL_INTENSITY;
-    pub const LUMINANCE: cl_channel_order = CL_LUMINANCE;
-    // #ifdef CL_VERSION_1_1;
-    pub const Rx: cl_channel_order = CL_Rx;
-    pub const RGx: cl_channel_order = CL_RGx;
-    pub const RGBx: cl_channel_order = CL_RGBx;
-    // #endif;
-    // #ifdef CL_VERSION_1_2;
-    pub const DEPTH: cl_channel_order = CL_DEPTH;
-    pub const DEPTH_STENCIL: cl_channel_order = CL_DEPTH_STENCIL;
-    // #endif;
-    // #ifdef CL_VERSION_2_0;
-    pub const SRGB: cl_channel_order = CL_sRGB;
-    pub const SRGBx: cl_channel_order = CL_sRGBx;
-    pub const SRGBA: cl_channel_order = CL_sRGBA;
-    pub const SBGRA: cl_channel_order = CL_sBGRA;
-    pub const ABGR: cl_channel_order = CL_ABGR;
-    // #endif
+gen_consts! {
+    ChannelOrder: cl_channel_order {
+        /* cl_channel_order - cl_uint */
+        R = CL_R,
+        A = CL_A,
+        RG = CL_RG,
+        RA = CL_RA,
+        RGB = CL_RGB,
+        RGBA = CL_RGBA,
+        BGRA = CL_BGRA,
+        ARGB = CL_ARGB,
+        INTENSITY = CL_INTENSITY,
+        LUMINANCE = CL_LUMINANCE,
+        // #ifdef CL_VERSION_1_1;
+        Rx = CL_Rx,
+        RGx = CL_RGx,
+        RGBx = CL_RGBx,
+        // #endif;
+        // #ifdef CL_VERSION_1_2;
+        DEPTH = CL_DEPTH,
+        DEPTH_STENCIL = CL_DEPTH_STENCIL,
+        // #endif;
+        // #ifdef CL_VERSION_2_0;
+        SRGB = CL_sRGB,
+        SRGBx = CL_sRGBx,
+        SRGBA = CL_sRGBA,
+        SBGRA = CL_sBGRA,
+        ABGR = CL_ABGR,
+        // #endif
+    }
 }
 
 #[non_exhaustive]
 pub struct ChannelType;
-impl ChannelType {
-    /* cl_channel_type - cl_uint */
-    pub const SNORM_INT8: cl_channel_type = CL_SNORM_INT8;
-    pub const SNORM_INT16: cl_channel_type = CL_SNORM_INT16;
-    pub const UNORM_INT8: cl_channel_type = CL_UNORM_INT8;
-    pub const UNORM_INT16: cl_channel_type = CL_UNORM_INT16;
-    pub const UNORM_SHORT_565: cl_channel_type = CL_UNORM_SHORT_565;
-    pub const UNORM_SHORT_555: cl_channel_type = CL_UNORM_SHORT_555;
-    pub const UNORM_INT_101010: cl_channel_type = CL_UNORM_INT_101010;
-    pub const SIGNED_INT8: cl_channel_type = CL_SIGNED_INT8;
-    pub const SIGNED_INT16: cl_channel_type = CL_SIGNED_INT16;
-    pub const SIGNED_INT32: cl_channel_type = CL_SIGNED_INT32;
-    pub const UNSIGNED_INT8: cl_channel_type = CL_UNSIGNED_INT8;
-    pub const UNSIGNED_INT16: cl_channel_type = CL_UNSIGNED_INT16;
-    pub const UNSIGNED_INT32: cl_channel_type = CL_UNSIGNED_INT32;
-    pub const HALF_FLOAT: cl_channel_type = CL_HALF_FLOAT;
-    pub const FLOAT: cl_channel_type = CL_FLOAT;
-    // #ifdef CL_VERSION_1_2;
-    pub const UNORM_INT24: cl_channel_type = CL_UNORM_INT24;
-    // #endif;
-    // #ifdef CL_VERSION_2_1;
-    pub const UNORM_INT_101010_2: cl_channel_type = CL_UNORM_INT_101010_2;
-    // #endif;
+gen_consts! {
+    ChannelType: cl_channel_type {
+        /* cl_channel_type - cl_uint */
+        SNORM_INT8 = CL_SNORM_INT8,
+        SNORM_INT16 = CL_SNORM_INT16,
+        UNORM_INT8 = CL_UNORM_INT8,
+        UNORM_INT16 = CL_UNORM_INT16,
+        UNORM_SHORT_565 = CL_UNORM_SHORT_565,
+        UNORM_SHORT_555 = CL_UNORM_SHORT_555,
+        UNORM_INT_101010 = CL_UNORM_INT_101010,
+        SIGNED_INT8 = CL_SIGNED_INT8,
+        SIGNED_INT16 = CL_SIGNED_INT16,
+        SIGNED_INT32 = CL_SIGNED_INT32,
+        UNSIGNED_INT8 = CL_UNSIGNED_INT8,
+        UNSIGNED_INT16 = CL_UNSIGNED_INT16,
+        UNSIGNED_INT32 = CL_UNSIGNED_INT32,
+        HALF_FLOAT = CL_HALF_FLOAT,
+        FLOAT = CL_FLOAT,
+        // #ifdef CL_VERSION_1_2;
+        UNORM_INT24 = CL_UNORM_INT24,
+        // #endif;
+        // #ifdef CL_VERSION_2_1;
+        UNORM_INT_101010_2 = CL_UNORM_INT_101010_2,
+        // #endif;
+    }
 }
 
 #[non_exhaustive]
 pub struct MemObjectType;
-impl MemObjectType {
-    /* cl_mem_object_type - cl_uint */
-    pub const BUFFER: cl_mem_object_type = CL_MEM_OBJECT_BUFFER;
-    pub const IMAGE2D: cl_mem_object_type = CL_MEM_OBJECT_IMAGE2D;
-    pub const IMAGE3D: cl_mem_object_type = CL_MEM_OBJECT_IMAGE3D;
-    // #ifdef CL_VERSION_1_2;
-    pub const IMAGE2D_ARRAY: cl_mem_object_type = CL_MEM_OBJECT_IMAGE2D_ARRAY;
-    pub const IMAGE1D: cl_mem_object_type = CL_MEM_OBJECT_IMAGE1D;
-    pub const IMAGE1D_ARRAY: cl_mem_object_type = CL_MEM_OBJECT_IMAGE1D_ARRAY;
-    pub const IMAGE1D_BUFFER: cl_mem_object_type = CL_MEM_OBJECT_IMAGE1D_BUFFER;
-    // #endif;
-    // #ifdef CL_VERSION_2_0;
-    pub const PIPE: cl_mem_object_type = CL_MEM_OBJECT_PIPE;
-    // #endif;
+gen_consts! {
+    MemObjectType: cl_mem_object_type {
+        /* cl_mem_object_type - cl_uint */
+        BUFFER = CL_MEM_OBJECT_BUFFER,
+        IMAGE2D = CL_MEM_OBJECT_IMAGE2D,
+        IMAGE3D = CL_MEM_OBJECT_IMAGE3D,
+        // #ifdef CL_VERSION_1_2;
+        IMAGE2D_ARRAY = CL_MEM_OBJECT_IMAGE2D_ARRAY,
+        IMAGE1D = CL_MEM_OBJECT_IMAGE1D,
+        IMAGE1D_ARRAY = CL_MEM_OBJECT_IMAGE1D_ARRAY,
+        IMAGE1D_BUFFER = CL_MEM_OBJECT_IMAGE1D_BUFFER,
+        // #endif;
+        // #ifdef CL_VERSION_2_0;
+        PIPE = CL_MEM_OBJECT_PIPE,
+        // #endif;
+    }
 }
 
 #[non_exhaustive]
 pub struct MemInfo;
-impl MemInfo {
-    /* cl_mem_info - cl_uint */
-    pub const TYPE: cl_mem_info = CL_MEM_TYPE;
-    pub const FLAGS: cl_mem_info = CL_MEM_FLAGS;
-    pub const SIZE: cl_mem_info = CL_MEM_SIZE;
-    pub const HOST_PTR: cl_mem_info = CL_MEM_HOST_PTR;
-    pub const MAP_COUNT: cl_mem_info = CL_MEM_MAP_COUNT;
-    pub const REFERENCE_COUNT: cl_mem_info = CL_MEM_REFERENCE_COUNT;
-    pub const CONTEXT: cl_mem_info = CL_MEM_CONTEXT;
-    // #ifdef CL_VERSION_1_1;
-    pub const ASSOCIATED_MEMOBJECT: cl_mem_info = CL_MEM_ASSOCIATED_MEMOBJECT;
-    pub const OFFSET: cl_mem_info = CL_MEM_OFFSET;
-    // #endif;
-    // #ifdef CL_VERSION_2_0;
-    pub const USES_SVM_POINTER: cl_mem_info = CL_MEM_USES_SVM_POINTER;
-    // #endif;
-    // #ifdef CL_VERSION_3_0;
-    pub const PROPERTIES: cl_mem_info = CL_MEM_PROPERTIES;
-    // #endif;
+gen_consts! {
+    MemInfo: cl_mem_info {
+        /* cl_mem_info - cl_uint */
+        TYPE = CL_MEM_TYPE,
+        FLAGS = CL_MEM_FLAGS,
+        SIZE = CL_MEM_SIZE,
+        HOST_PTR = CL_MEM_HOST_PTR,
+        MAP_COUNT = CL_MEM_MAP_COUNT,
+        REFERENCE_COUNT = CL_MEM_REFERENCE_COUNT,
+        CONTEXT = CL_MEM_CONTEXT,
+        // #ifdef CL_VERSION_1_1;
+        ASSOCIATED_MEMOBJECT = CL_MEM_ASSOCIATED_MEMOBJECT,
+        OFFSET = CL_MEM_OFFSET,
+        // #endif;
+        // #ifdef CL_VERSION_2_0;
+        USES_SVM_POINTER = CL_MEM_USES_SVM_POINTER,
+        // #endif;
+        // #ifdef CL_VERSION_3_0;
+        PROPERTIES = CL_MEM_PROPERTIES,
+        // #endif;
+    }
 }
 
 #[non_exhaustive]
 pub struct ImageInfo;
-impl ImageInfo {
-    /* cl_image_info - cl_uint */
-    pub const FORMAT: cl_image_info = CL_IMAGE_FORMAT;
-    pub const ELEMENT_SIZE: cl_image_info = CL_IMAGE_ELEMENT_SIZE;
-    pub const ROW_PITCH: cl_image_info = CL_IMAGE_ROW_PITCH;
-    pub const SLICE_PITCH: cl_image_info = CL_IMAGE_SLICE_PITCH;
-    pub const WIDTH: cl_image_info = CL_IMAGE_WIDTH;
-    pub const HEIGHT: cl_image_info = CL_IMAGE_HEIGHT;
-    pub const DEPTH: cl_image_info = CL_IMAGE_DEPTH;
-    // #ifdef CL_VERSION_1_2;
-    pub const ARRAY_SIZE: cl_image_info = CL_IMAGE_ARRAY_SIZE;
-    pub const BUFFER: cl_image_info = CL_IMAGE_BUFFER;
-    pub const NUM_MIP_LEVELS: cl_image_info = CL_IMAGE_NUM_MIP_LEVELS;
-    pub const NUM_SAMPLES: cl_image_info = CL_IMAGE_NUM_SAMPLES;
-    // #endif;
+gen_consts! {
+    ImageInfo: cl_image_info {
+        /* cl_image_info - cl_uint */
+        FORMAT = CL_IMAGE_FORMAT,
+        ELEMENT_SIZE = CL_IMAGE_ELEMENT_SIZE,
+        ROW_PITCH = CL_IMAGE_ROW_PITCH,
+        SLICE_PITCH = CL_IMAGE_SLICE_PITCH,
+        WIDTH = CL_IMAGE_WIDTH,
+        HEIGHT = CL_IMAGE_HEIGHT,
+        DEPTH = CL_IMAGE_DEPTH,
+        // #ifdef CL_VERSION_1_2;
+        ARRAY_SIZE = CL_IMAGE_ARRAY_SIZE,
+        BUFFER = CL_IMAGE_BUFFER,
+        NUM_MIP_LEVELS = CL_IMAGE_NUM_MIP_LEVELS,
+        NUM_SAMPLES = CL_IMAGE_NUM_SAMPLES,
+        // #endif;
+    }
 }
 
 #[non_exhaustive]
 pub struct PipeInfo;
-impl PipeInfo {
-    /* cl_pipe_info - cl_uint */
-    // #ifdef CL_VERSION_2_0;
-    pub const PACKET_SIZE: cl_pipe_info = CL_PIPE_PACKET_SIZE;
-    pub const MAX_PACKETS: cl_pipe_info = CL_PIPE_MAX_PACKETS;
-    // #endif;
-    // #ifdef CL_VERSION_3_0;
-    pub const PROPERTIES: cl_pipe_info = CL_PIPE_PROPERTIES;
-    // #endif;
+gen_consts! {
+    PipeInfo: cl_pipe_info {
+        /* cl_pipe_info - cl_uint */
+        // #ifdef CL_VERSION_2_0;
+        PACKET_SIZE = CL_PIPE_PACKET_SIZE,
+        MAX_PACKETS = CL_PIPE_MAX_PACKETS,
+        // #endif;
+        // #ifdef CL_VERSION_3_0;
+        PROPERTIES = CL_PIPE_PROPERTIES,
+        // #endif;
+    }
 }
 
 #[non_exhaustive]
 pub struct AddressingMode(cl_addressing_mode);
+gen_consts! {
+    AddressingMode: cl_addressing_mode {
+        /* cl_addressing_mode - cl_uint */
+        NONE = CL_ADDRESS_NONE,
+        CLAMP_TO_EDGE = CL_ADDRESS_CLAMP_TO_EDGE,
+        CLAMP = CL_ADDRESS_CLAMP,
+        REPEAT = CL_ADDRESS_REPEAT,
+        // #ifdef CL_VERSION_1_1;
+        MIRRORED_REPEAT = CL_ADDRESS_MIRRORED_REPEAT,
+        // #endif;
+    }
+}
 impl AddressingMode {
-    /* cl_addressing_mode - cl_uint */
-    pub const NONE: cl_addressing_mode = CL_ADDRESS_NONE;
-    pub const CLAMP_TO_EDGE: cl_addressing_mode = CL_ADDRESS_CLAMP_TO_EDGE;
-    pub const CLAMP: cl_addressing_mode = CL_ADDRESS_CLAMP;
-    pub const REPEAT: cl_addressing_mode = CL_ADDRESS_REPEAT;
-    // #ifdef CL_VERSION_1_1;
-    pub const MIRRORED_REPEAT: cl_addressing_mode = CL_ADDRESS_MIRRORED_REPEAT;
-    // #endif;
     // pub fn get_property(&self, addressing_mode: cl_addressing_mode) -> Option<Vec<cl_uint>> {
     // Some(vec![SamplerInfo::ADDRESSING_MODE, addressing_mode, 0])
     // }
@@ -536,10 +557,14 @@ impl AddressingMode {
 
 #[non_exhaustive]
 pub struct FilterMode(cl_filter_mode);
+gen_consts! {
+    FilterMode: cl_filter_mode {
+        /* cl_filter_mode - cl_uint */
+        NEAREST = CL_FILTER_NEAREST,
+        LINEAR = CL_FILTER_LINEAR,
+    }
+}
 impl FilterMode {
-    /* cl_filter_mode - cl_uint */
-    pub const NEAREST: cl_filter_mode = CL_FILTER_NEAREST;
-    pub const LINEAR: cl_filter_mode = CL_FILTER_LINEAR;
     // pub fn get_property(&self, filter_mode: cl_filter_mode) -> Option<Vec<cl_uint>> {
     //     Some(vec![SamplerInfo::FILTER_MODE, filter_mode, 0])
     // }
@@ -558,277 +583,306 @@ impl FilterMode {
 
 #[non_exhaustive]
 pub struct SamplerInfo;
-impl SamplerInfo {
-    /* cl_sampler_info - cl_uint */
-    pub const REFERENCE_COUNT: cl_sampler_info = CL_SAMPLER_REFERENCE_COUNT;
-    pub const CONTEXT: cl_sampler_info = CL_SAMPLER_CONTEXT;
-    pub const NORMALIZED_COORDS: cl_sampler_info = CL_SAMPLER_NORMALIZED_COORDS;
-    pub const ADDRESSING_MODE: cl_sampler_info = CL_SAMPLER_ADDRESSING_MODE;
-    pub const FILTER_MODE: cl_sampler_info = CL_SAMPLER_FILTER_MODE;
-    // #ifdef CL_VERSION_2_0;
-    /* These enumerants are for the cl_khr_mipmap_image extension.;
-    They have since been added to cl_ext.h with an appropriate;
-    KHR suffix, but are left here for backwards compatibility. */
-    pub const MIP_FILTER_MODE: cl_sampler_info = CL_SAMPLER_MIP_FILTER_MODE;
-    pub const LOD_MIN: cl_sampler_info = CL_SAMPLER_LOD_MIN;
-    pub const LOD_MAX: cl_sampler_info = CL_SAMPLER_LOD_MAX;
-    // #endif;
-    // #ifdef CL_VERSION_3_0;
-    pub const PROPERTIES: cl_sampler_info = CL_SAMPLER_PROPERTIES;
-    // #endif;
+gen_consts! {
+    SamplerInfo: cl_sampler_info {
+        /* cl_sampler_info - cl_uint */
+        REFERENCE_COUNT = CL_SAMPLER_REFERENCE_COUNT,
+        CONTEXT = CL_SAMPLER_CONTEXT,
+        NORMALIZED_COORDS = CL_SAMPLER_NORMALIZED_COORDS,
+        ADDRESSING_MODE = CL_SAMPLER_ADDRESSING_MODE,
+        FILTER_MODE = CL_SAMPLER_FILTER_MODE,
+        // #ifdef CL_VERSION_2_0;
+        /* These enumerants are for the cl_khr_mipmap_image extension.;
+        They have since been added to cl_ext.h with an appropriate;
+        KHR suffix, but are left here for backwards compatibility. */
+        MIP_FILTER_MODE = CL_SAMPLER_MIP_FILTER_MODE,
+        LOD_MIN = CL_SAMPLER_LOD_MIN,
+        LOD_MAX = CL_SAMPLER_LOD_MAX,
+        // #endif;
+        // #ifdef CL_VERSION_3_0;
+        PROPERTIES = CL_SAMPLER_PROPERTIES,
+        // #endif;
+    }
 }
 
 #[non_exhaustive]
 pub struct ProgramInfo;
-impl ProgramInfo {
-    /* cl_program_info - cl_uint */
-    pub const REFERENCE_COUNT: cl_program_info = CL_PROGRAM_REFERENCE_COUNT;
-    pub const CONTEXT: cl_program_info = CL_PROGRAM_CONTEXT;
-    pub const NUM_DEVICES: cl_program_info = CL_PROGRAM_NUM_DEVICES;
-    pub const DEVICES: cl_program_info = CL_PROGRAM_DEVICES;
-    pub const SOURCE: cl_program_info = CL_PROGRAM_SOURCE;
-    pub const BINARY_SIZES: cl_program_info = CL_PROGRAM_BINARY_SIZES;
-    pub const BINARIES: cl_program_info = CL_PROGRAM_BINARIES;
-    // #ifdef CL_VERSION_1_2;
-    pub const NUM_KERNELS: cl_program_info = CL_PROGRAM_NUM_KERNELS;
-    pub const KERNEL_NAMES: cl_program_info = CL_PROGRAM_KERNEL_NAMES;
-    // #endif;
-    // #ifdef CL_VERSION_2_1;
-    pub const IL: cl_program_info = CL_PROGRAM_IL;
-    // #endif;
-    // #ifdef CL_VERSION_2_2;
-    pub const SCOPE_GLOBAL_CTORS_PRESENT: cl_program_info = CL_PROGRAM_SCOPE_GLOBAL_CTORS_PRESENT;
-    pub const SCOPE_GLOBAL_DTORS_PRESENT: cl_program_info = CL_PROGRAM_SCOPE_GLOBAL_DTORS_PRESENT;
-    // #endif;
+gen_consts! {
+    ProgramInfo: cl_program_info {
+        /* cl_program_info - cl_uint */
+        REFERENCE_COUNT = CL_PROGRAM_REFERENCE_COUNT,
+        CONTEXT = CL_PROGRAM_CONTEXT,
+        NUM_DEVICES = CL_PROGRAM_NUM_DEVICES,
+        DEVICES = CL_PROGRAM_DEVICES,
+        SOURCE = CL_PROGRAM_SOURCE,
+        BINARY_SIZES = CL_PROGRAM_BINARY_SIZES,
+        BINARIES = CL_PROGRAM_BINARIES,
+        // #ifdef CL_VERSION_1_2;
+        NUM_KERNELS = CL_PROGRAM_NUM_KERNELS,
+        KERNEL_NAMES = CL_PROGRAM_KERNEL_NAMES,
+        // #endif;
+        // #ifdef CL_VERSION_2_1;
+        IL = CL_PROGRAM_IL,
+        // #endif;
+        // #ifdef CL_VERSION_2_2;
+        SCOPE_GLOBAL_CTORS_PRESENT = CL_PROGRAM_SCOPE_GLOBAL_CTORS_PRESENT,
+        SCOPE_GLOBAL_DTORS_PRESENT = CL_PROGRAM_SCOPE_GLOBAL_DTORS_PRESENT,
+        // #endif;
+    }
 }
 
 #[non_exhaustive]
 pub struct ProgramBuildInfo;
-impl ProgramBuildInfo {
-    /* cl_program_build_info - cl_uint */
-    pub const BUILD_STATUS: cl_program_build_info = CL_PROGRAM_BUILD_STATUS;
-    pub const BUILD_OPTIONS: cl_program_build_info = CL_PROGRAM_BUILD_OPTIONS;
-    pub const BUILD_LOG: cl_program_build_info = CL_PROGRAM_BUILD_LOG;
-    // #ifdef CL_VERSION_1_2;
-    pub const BINARY_TYPE: cl_program_build_info = CL_PROGRAM_BINARY_TYPE;
-    // #endif;
-    // #ifdef CL_VERSION_2_0;
-    pub const BUILD_GLOBAL_VARIABLE_TOTAL_SIZE: cl_program_build_info =
-        CL_PROGRAM_BUILD_GLOBAL_VARIABLE_TOTAL_SIZE;
-    // #endif;
+gen_consts! {
+    ProgramBuildInfo: cl_program_build_info {
+        /* cl_program_build_info - cl_uint */
+        BUILD_STATUS = CL_PROGRAM_BUILD_STATUS,
+        BUILD_OPTIONS = CL_PROGRAM_BUILD_OPTIONS,
+        BUILD_LOG = CL_PROGRAM_BUILD_LOG,
+        // #ifdef CL_VERSION_1_2;
+        BINARY_TYPE = CL_PROGRAM_BINARY_TYPE,
+        // #endif;
+        // #ifdef CL_VERSION_2_0;
+        BUILD_GLOBAL_VARIABLE_TOTAL_SIZE = CL_PROGRAM_BUILD_GLOBAL_VARIABLE_TOTAL_SIZE,
+        // #endif;
+    }
 }
 
 #[non_exhaustive]
 pub struct ProgramBinaryType;
-impl ProgramBinaryType {
-    // #ifdef CL_VERSION_1_2;
-    /* cl_program_binary_type - cl_uint */
-    pub const NONE: cl_program_binary_type = CL_PROGRAM_BINARY_TYPE_NONE;
-    pub const COMPILED_OBJECT: cl_program_binary_type = CL_PROGRAM_BINARY_TYPE_COMPILED_OBJECT;
-    pub const LIBRARY: cl_program_binary_type = CL_PROGRAM_BINARY_TYPE_LIBRARY;
-    pub const EXECUTABLE: cl_program_binary_type = CL_PROGRAM_BINARY_TYPE_EXECUTABLE;
-    // #endif;
+gen_consts! {
+    ProgramBinaryType: cl_program_binary_type {
+        // #ifdef CL_VERSION_1_2;
+        /* cl_program_binary_type - cl_uint */
+        NONE = CL_PROGRAM_BINARY_TYPE_NONE,
+        COMPILED_OBJECT = CL_PROGRAM_BINARY_TYPE_COMPILED_OBJECT,
+        LIBRARY = CL_PROGRAM_BINARY_TYPE_LIBRARY,
+        EXECUTABLE = CL_PROGRAM_BINARY_TYPE_EXECUTABLE,
+        // #endif;
+    }
 }
 
 #[non_exhaustive]
 pub struct BuildStatus;
-impl BuildStatus {
-    /* cl_build_status - cl_int */
-    pub const SUCCESS: cl_build_status = CL_BUILD_SUCCESS;
-    pub const NONE: cl_build_status = CL_BUILD_NONE;
-    pub const ERROR: cl_build_status = CL_BUILD_ERROR;
-    pub const IN_PROGRESS: cl_build_status = CL_BUILD_IN_PROGRESS;
+gen_consts! {
+    BuildStatus: cl_build_status {
+        /* cl_build_status - cl_int */
+        SUCCESS = CL_BUILD_SUCCESS,
+        NONE = CL_BUILD_NONE,
+        ERROR = CL_BUILD_ERROR,
+        IN_PROGRESS = CL_BUILD_IN_PROGRESS,
+    }
 }
 
 #[non_exhaustive]
 pub struct KernelInfo;
-impl KernelInfo {
-    /* cl_kernel_info - cl_uint */
-    pub const FUNCTION_NAME: cl_kernel_info = CL_KERNEL_FUNCTION_NAME;
-    pub const NUM_ARGS: cl_kernel_info = CL_KERNEL_NUM_ARGS;
-    pub const REFERENCE_COUNT: cl_kernel_info = CL_KERNEL_REFERENCE_COUNT;
-    pub const CONTEXT: cl_kernel_info = CL_KERNEL_CONTEXT;
-    pub const PROGRAM: cl_kernel_info = CL_KERNEL_PROGRAM;
-    // #ifdef CL_VERSION_1_2;
-    pub const ATTRIBUTES: cl_kernel_info = CL_KERNEL_ATTRIBUTES;
-    // #endif;
+gen_consts! {
+    KernelInfo: cl_kernel_info {
+        /* cl_kernel_info - cl_uint */
+        FUNCTION_NAME = CL_KERNEL_FUNCTION_NAME,
+        NUM_ARGS = CL_KERNEL_NUM_ARGS,
+        REFERENCE_COUNT = CL_KERNEL_REFERENCE_COUNT,
+        CONTEXT = CL_KERNEL_CONTEXT,
+        PROGRAM = CL_KERNEL_PROGRAM,
+        // #ifdef CL_VERSION_1_2;
+        ATTRIBUTES = CL_KERNEL_ATTRIBUTES,
+        // #endif;
+    }
 }
 
 #[non_exhaustive]
 pub struct KernelArgInfo;
-impl KernelArgInfo {
-    // #ifdef CL_VERSION_1_2;
-    /* cl_kernel_arg_info - cl_uint */
-    pub const ADDRESS_QUALIFIER: cl_kernel_arg_info = CL_KERNEL_ARG_ADDRESS_QUALIFIER;
-    pub const ACCESS_QUALIFIER: cl_kernel_arg_info = CL_KERNEL_ARG_ACCESS_QUALIFIER;
-    pub const TYPE_NAME: cl_kernel_arg_info = CL_KERNEL_ARG_TYPE_NAME;
-    pub const TYPE_QUALIFIER: cl_kernel_arg_info = CL_KERNEL_ARG_TYPE_QUALIFIER;
-    pub const NAME: cl_kernel_arg_info = CL_KERNEL_ARG_NAME;
-    // #endif;
+gen_consts! {
+    KernelArgInfo: cl_kernel_arg_info {
+        // #ifdef CL_VERSION_1_2;
+        /* cl_kernel_arg_info - cl_uint */
+        ADDRESS_QUALIFIER = CL_KERNEL_ARG_ADDRESS_QUALIFIER,
+        ACCESS_QUALIFIER = CL_KERNEL_ARG_ACCESS_QUALIFIER,
+        TYPE_NAME = CL_KERNEL_ARG_TYPE_NAME,
+        TYPE_QUALIFIER = CL_KERNEL_ARG_TYPE_QUALIFIER,
+        NAME = CL_KERNEL_ARG_NAME,
+        // #endif;
+    }
 }
 
 #[non_exhaustive]
 pub struct KernelArgAddressQualifier;
-impl KernelArgAddressQualifier {
-    // #ifdef CL_VERSION_1_2;
-    /* cl_kernel_arg_address_qualifier - cl_uint */
-    pub const GLOBAL: cl_kernel_arg_address_qualifier = CL_KERNEL_ARG_ADDRESS_GLOBAL;
-    pub const LOCAL: cl_kernel_arg_address_qualifier = CL_KERNEL_ARG_ADDRESS_LOCAL;
-    pub const CONSTANT: cl_kernel_arg_address_qualifier = CL_KERNEL_ARG_ADDRESS_CONSTANT;
-    pub const PRIVATE: cl_kernel_arg_address_qualifier = CL_KERNEL_ARG_ADDRESS_PRIVATE;
-    // #endif;
+gen_consts! {
+    KernelArgAddressQualifier: cl_kernel_arg_address_qualifier {
+        // #ifdef CL_VERSION_1_2;
+        /* cl_kernel_arg_address_qualifier - cl_uint */
+        GLOBAL = CL_KERNEL_ARG_ADDRESS_GLOBAL,
+        LOCAL = CL_KERNEL_ARG_ADDRESS_LOCAL,
+        CONSTANT = CL_KERNEL_ARG_ADDRESS_CONSTANT,
+        PRIVATE = CL_KERNEL_ARG_ADDRESS_PRIVATE,
+        // #endif;
+    }
 }
 
 #[non_exhaustive]
 pub struct KernelArgAccessQualifier;
-impl KernelArgAccessQualifier {
-    // #ifdef CL_VERSION_1_2;
-    /* cl_kernel_arg_access_qualifier */
-    pub const READ_ONLY: cl_kernel_arg_access_qualifier = CL_KERNEL_ARG_ACCESS_READ_ONLY;
-    pub const WRITE_ONLY: cl_kernel_arg_access_qualifier = CL_KERNEL_ARG_ACCESS_WRITE_ONLY;
-    pub const READ_WRITE: cl_kernel_arg_access_qualifier = CL_KERNEL_ARG_ACCESS_READ_WRITE;
-    pub const NONE: cl_kernel_arg_access_qualifier = CL_KERNEL_ARG_ACCESS_NONE;
-    // #endif;
+gen_consts! {
+    KernelArgAccessQualifier: cl_kernel_arg_access_qualifier {
+        // #ifdef CL_VERSION_1_2;
+        /* cl_kernel_arg_access_qualifier */
+        READ_ONLY = CL_KERNEL_ARG_ACCESS_READ_ONLY,
+        WRITE_ONLY = CL_KERNEL_ARG_ACCESS_WRITE_ONLY,
+        READ_WRITE = CL_KERNEL_ARG_ACCESS_READ_WRITE,
+        NONE = CL_KERNEL_ARG_ACCESS_NONE,
+        // #endif;
+    }
 }
 
 #[non_exhaustive]
 pub struct KernelWorkGroupInfo;
-impl KernelWorkGroupInfo {
-    /* cl_kernel_work_group_info - cl_uint */
-    pub const WORK_GROUP_SIZE: cl_kernel_work_group_info = CL_KERNEL_WORK_GROUP_SIZE;
-    pub const COMPILE_WORK_GROUP_SIZE: cl_kernel_work_group_info =
-        CL_KERNEL_COMPILE_WORK_GROUP_SIZE;
-    pub const LOCAL_MEM_SIZE: cl_kernel_work_group_info = CL_KERNEL_LOCAL_MEM_SIZE;
-    pub const PREFERRED_WORK_GROUP_SIZE_MULTIPLE: cl_kernel_work_group_info =
-        CL_KERNEL_PREFERRED_WORK_GROUP_SIZE_MULTIPLE;
-    pub const PRIVATE_MEM_SIZE: cl_kernel_work_group_info = CL_KERNEL_PRIVATE_MEM_SIZE;
-    // #ifdef CL_VERSION_1_2;
-    pub const GLOBAL_WORK_SIZE: cl_kernel_work_group_info = CL_KERNEL_GLOBAL_WORK_SIZE;
-    // #endif;
+gen_consts! {
+    KernelWorkGroupInfo: cl_kernel_work_group_info {
+        /* cl_kernel_work_group_info - cl_uint */
+        WORK_GROUP_SIZE = CL_KERNEL_WORK_GROUP_SIZE,
+        COMPILE_WORK_GROUP_SIZE = CL_KERNEL_COMPILE_WORK_GROUP_SIZE,
+        LOCAL_MEM_SIZE = CL_KERNEL_LOCAL_MEM_SIZE,
+        PREFERRED_WORK_GROUP_SIZE_MULTIPLE = CL_KERNEL_PREFERRED_WORK_GROUP_SIZE_MULTIPLE,
+        PRIVATE_MEM_SIZE = CL_KERNEL_PRIVATE_MEM_SIZE,
+        // #ifdef CL_VERSION_1_2;
+        GLOBAL_WORK_SIZE = CL_KERNEL_GLOBAL_WORK_SIZE,
+        // #endif;
+    }
 }
 
 #[non_exhaustive]
 pub struct KernelSubGroupInfo;
-impl KernelSubGroupInfo {
-    // #ifdef CL_VERSION_2_1;
-    /* cl_kernel_sub_group_info - cl_uint */
-    pub const MAX_SUB_GROUP_SIZE_FOR_NDRANGE: cl_kernel_sub_group_info =
-        CL_KERNEL_MAX_SUB_GROUP_SIZE_FOR_NDRANGE;
-    pub const SUB_GROUP_COUNT_FOR_NDRANGE: cl_kernel_sub_group_info =
-        CL_KERNEL_SUB_GROUP_COUNT_FOR_NDRANGE;
-    pub const LOCAL_SIZE_FOR_SUB_GROUP_COUNT: cl_kernel_sub_group_info =
-        CL_KERNEL_LOCAL_SIZE_FOR_SUB_GROUP_COUNT;
-    pub const MAX_NUM_SUB_GROUPS: cl_kernel_sub_group_info = CL_KERNEL_MAX_NUM_SUB_GROUPS;
-    pub const COMPILE_NUM_SUB_GROUPS: cl_kernel_sub_group_info = CL_KERNEL_COMPILE_NUM_SUB_GROUPS;
-    // #endif;
+gen_consts! {
+    KernelSubGroupInfo: cl_kernel_sub_group_info {
+        // #ifdef CL_VERSION_2_1;
+        /* cl_kernel_sub_group_info - cl_uint */
+        MAX_SUB_GROUP_SIZE_FOR_NDRANGE = CL_KERNEL_MAX_SUB_GROUP_SIZE_FOR_NDRANGE,
+        SUB_GROUP_COUNT_FOR_NDRANGE = CL_KERNEL_SUB_GROUP_COUNT_FOR_NDRANGE,
+        LOCAL_SIZE_FOR_SUB_GROUP_COUNT = CL_KERNEL_LOCAL_SIZE_FOR_SUB_GROUP_COUNT,
+        MAX_NUM_SUB_GROUPS = CL_KERNEL_MAX_NUM_SUB_GROUPS,
+        COMPILE_NUM_SUB_GROUPS = CL_KERNEL_COMPILE_NUM_SUB_GROUPS,
+        // #endif;
+    }
 }
 
 #[non_exhaustive]
 pub struct KernelExecInfo;
-impl KernelExecInfo {
-    // #ifdef CL_VERSION_2_0;
-    /* cl_kernel_exec_info - cl_uint */
-    pub const EXEC_INFO_SVM_PTRS: cl_kernel_exec_info = CL_KERNEL_EXEC_INFO_SVM_PTRS;
-    pub const EXEC_INFO_SVM_FINE_GRAIN_SYSTEM: cl_kernel_exec_info =
-        CL_KERNEL_EXEC_INFO_SVM_FINE_GRAIN_SYSTEM;
-    // #endif;
+gen_consts! {
+    KernelExecInfo: cl_kernel_exec_info {
+        // #ifdef CL_VERSION_2_0;
+        /* cl_kernel_exec_info - cl_uint */
+        EXEC_INFO_SVM_PTRS = CL_KERNEL_EXEC_INFO_SVM_PTRS,
+        EXEC_INFO_SVM_FINE_GRAIN_SYSTEM = CL_KERNEL_EXEC_INFO_SVM_FINE_GRAIN_SYSTEM,
+        // #endif;
+    }
 }
 
 #[non_exhaustive]
 pub struct EventInfo;
-impl EventInfo {
-    /* cl_event_info - cl_uint */
-    pub const COMMAND_QUEUE: cl_event_info = CL_EVENT_COMMAND_QUEUE;
-    pub const COMMAND_TYPE: cl_event_info = CL_EVENT_COMMAND_TYPE;
-    pub const REFERENCE_COUNT: cl_event_info = CL_EVENT_REFERENCE_COUNT;
-    pub const COMMAND_EXECUTION_STATUS: cl_event_info = CL_EVENT_COMMAND_EXECUTION_STATUS;
-    // #ifdef CL_VERSION_1_1;
-    pub const CONTEXT: cl_event_info = CL_EVENT_CONTEXT;
-    // #endif;
+gen_consts! {
+    EventInfo: cl_event_info {
+        /* cl_event_info - cl_uint */
+        COMMAND_QUEUE = CL_EVENT_COMMAND_QUEUE,
+        COMMAND_TYPE = CL_EVENT_COMMAND_TYPE,
+        REFERENCE_COUNT = CL_EVENT_REFERENCE_COUNT,
+        COMMAND_EXECUTION_STATUS = CL_EVENT_COMMAND_EXECUTION_STATUS,
+        // #ifdef CL_VERSION_1_1;
+        CONTEXT = CL_EVENT_CONTEXT,
+        // #endif;
+    }
 }
 
 #[non_exhaustive]
 pub struct CommandType;
-impl CommandType {
-    /* cl_command_type - cl_uint */
-    pub const NDRANGE_KERNEL: cl_command_type = CL_COMMAND_NDRANGE_KERNEL;
-    pub const TASK: cl_command_type = CL_COMMAND_TASK;
-    pub const NATIVE_KERNEL: cl_command_type = CL_COMMAND_NATIVE_KERNEL;
-    pub const READ_BUFFER: cl_command_type = CL_COMMAND_READ_BUFFER;
-    pub const WRITE_BUFFER: cl_command_type = CL_COMMAND_WRITE_BUFFER;
-    pub const COPY_BUFFER: cl_command_type = CL_COMMAND_COPY_BUFFER;
-    pub const READ_IMAGE: cl_command_type = CL_COMMAND_READ_IMAGE;
-    pub const WRITE_IMAGE: cl_command_type = CL_COMMAND_WRITE_IMAGE;
-    pub const COPY_IMAGE: cl_command_type = CL_COMMAND_COPY_IMAGE;
-    pub const COPY_IMAGE_TO_BUFFER: cl_command_type = CL_COMMAND_COPY_IMAGE_TO_BUFFER;
-    pub const COPY_BUFFER_TO_IMAGE: cl_command_type = CL_COMMAND_COPY_BUFFER_TO_IMAGE;
-    pub const MAP_BUFFER: cl_command_type = CL_COMMAND_MAP_BUFFER;
-    pub const MAP_IMAGE: cl_command_type = CL_COMMAND_MAP_IMAGE;
-    pub const UNMAP_MEM_OBJECT: cl_command_type = CL_COMMAND_UNMAP_MEM_OBJECT;
-    pub const MARKER: cl_command_type = CL_COMMAND_MARKER;
-    pub const ACQUIRE_GL_OBJECTS: cl_command_type = CL_COMMAND_ACQUIRE_GL_OBJECTS;
-    pub const RELEASE_GL_OBJECTS: cl_command_type = CL_COMMAND_RELEASE_GL_OBJECTS;
-    // #ifdef CL_VERSION_1_1;
-    pub const READ_BUFFER_RECT: cl_command_type = CL_COMMAND_READ_BUFFER_RECT;
-    pub const WRITE_BUFFER_RECT: cl_command_type = CL_COMMAND_WRITE_BUFFER_RECT;
-    pub const COPY_BUFFER_RECT: cl_command_type = CL_COMMAND_COPY_BUFFER_RECT;
-    pub const USER: cl_command_type = CL_COMMAND_USER;
-    // #endif;
-    // #ifdef CL_VERSION_1_2;
-    pub const BARRIER: cl_command_type = CL_COMMAND_BARRIER;
-    pub const MIGRATE_MEM_OBJECTS: cl_command_type = CL_COMMAND_MIGRATE_MEM_OBJECTS;
-    pub const FILL_BUFFER: cl_command_type = CL_COMMAND_FILL_BUFFER;
-    pub const FILL_IMAGE: cl_command_type = CL_COMMAND_FILL_IMAGE;
-    // #endif;
-    // #ifdef CL_VERSION_2_0;
-    pub const SVM_FREE: cl_command_type = CL_COMMAND_SVM_FREE;
-    pub const SVM_MEMCPY: cl_command_type = CL_COMMAND_SVM_MEMCPY;
-    pub const SVM_MEMFILL: cl_command_type = CL_COMMAND_SVM_MEMFILL;
-    pub const SVM_MAP: cl_command_type = CL_COMMAND_SVM_MAP;
-    pub const SVM_UNMAP: cl_command_type = CL_COMMAND_SVM_UNMAP;
-    // #endif;
-    // #ifdef CL_VERSION_3_0;
-    pub const SVM_MIGRATE_MEM: cl_command_type = CL_COMMAND_SVM_MIGRATE_MEM;
-    // #endif;
+gen_consts! {
+    CommandType: cl_command_type {
+        /* cl_command_type - cl_uint */
+        NDRANGE_KERNEL = CL_COMMAND_NDRANGE_KERNEL,
+        TASK = CL_COMMAND_TASK,
+        NATIVE_KERNEL = CL_COMMAND_NATIVE_KERNEL,
+        READ_BUFFER = CL_COMMAND_READ_BUFFER,
+        WRITE_BUFFER = CL_COMMAND_WRITE_BUFFER,
+        COPY_BUFFER = CL_COMMAND_COPY_BUFFER,
+        READ_IMAGE = CL_COMMAND_READ_IMAGE,
+        WRITE_IMAGE = CL_COMMAND_WRITE_IMAGE,
+        COPY_IMAGE = CL_COMMAND_COPY_IMAGE,
+        COPY_IMAGE_TO_BUFFER = CL_COMMAND_COPY_IMAGE_TO_BUFFER,
+        COPY_BUFFER_TO_IMAGE = CL_COMMAND_COPY_BUFFER_TO_IMAGE,
+        MAP_BUFFER = CL_COMMAND_MAP_BUFFER,
+        MAP_IMAGE = CL_COMMAND_MAP_IMAGE,
+        UNMAP_MEM_OBJECT = CL_COMMAND_UNMAP_MEM_OBJECT,
+        MARKER = CL_COMMAND_MARKER,
+        ACQUIRE_GL_OBJECTS = CL_COMMAND_ACQUIRE_GL_OBJECTS,
+        RELEASE_GL_OBJECTS = CL_COMMAND_RELEASE_GL_OBJECTS,
+        // #ifdef CL_VERSION_1_1;
+        READ_BUFFER_RECT = CL_COMMAND_READ_BUFFER_RECT,
+        WRITE_BUFFER_RECT = CL_COMMAND_WRITE_BUFFER_RECT,
+        COPY_BUFFER_RECT = CL_COMMAND_COPY_BUFFER_RECT,
+        USER = CL_COMMAND_USER,
+        // #endif;
+        // #ifdef CL_VERSION_1_2;
+        BARRIER = CL_COMMAND_BARRIER,
+        MIGRATE_MEM_OBJECTS = CL_COMMAND_MIGRATE_MEM_OBJECTS,
+        FILL_BUFFER = CL_COMMAND_FILL_BUFFER,
+        FILL_IMAGE = CL_COMMAND_FILL_IMAGE,
+        // #endif;
+        // #ifdef CL_VERSION_2_0;
+        SVM_FREE = CL_COMMAND_SVM_FREE,
+        SVM_MEMCPY = CL_COMMAND_SVM_MEMCPY,
+        SVM_MEMFILL = CL_COMMAND_SVM_MEMFILL,
+        SVM_MAP = CL_COMMAND_SVM_MAP,
+        SVM_UNMAP = CL_COMMAND_SVM_UNMAP,
+        // #endif;
+        // #ifdef CL_VERSION_3_0;
+        SVM_MIGRATE_MEM = CL_COMMAND_SVM_MIGRATE_MEM,
+        // #endif;
+    }
 }
 
 #[non_exhaustive]
 pub struct CommandExecutionStatus;
-impl CommandExecutionStatus {
-    /* command execution status */
-    pub const COMPLETE: cl_uint = CL_COMPLETE;
-    pub const RUNNING: cl_uint = CL_RUNNING;
-    pub const SUBMITTED: cl_uint = CL_SUBMITTED;
-    pub const QUEUED: cl_uint = CL_QUEUED;
+gen_consts! {
+    CommandExecutionStatus: cl_uint {
+        /* command execution status */
+        COMPLETE = CL_COMPLETE,
+        RUNNING = CL_RUNNING,
+        SUBMITTED = CL_SUBMITTED,
+        QUEUED = CL_QUEUED,
+    }
 }
 
 #[non_exhaustive]
 pub struct BufferCreateType;
-impl BufferCreateType {
-    /* cl_buffer_create_type */
-    // #ifdef CL_VERSION_1_1;
-    pub const REGION: cl_buffer_create_type = CL_BUFFER_CREATE_TYPE_REGION;
-    // #endif;
+gen_consts! {
+    BufferCreateType: cl_buffer_create_type {
+        /* cl_buffer_create_type */
+        // #ifdef CL_VERSION_1_1;
+        REGION = CL_BUFFER_CREATE_TYPE_REGION,
+        // #endif;
+    }
 }
 
 #[non_exhaustive]
 pub struct ProfilingInfo;
-impl ProfilingInfo {
-    /* cl_profiling_info - cl_uint */
-    pub const QUEUED: cl_profiling_info = CL_PROFILING_COMMAND_QUEUED;
-    pub const SUBMIT: cl_profiling_info = CL_PROFILING_COMMAND_SUBMIT;
-    pub const START: cl_profiling_info = CL_PROFILING_COMMAND_START;
-    pub const END: cl_profiling_info = CL_PROFILING_COMMAND_END;
-    // #ifdef CL_VERSION_2_0;
-    pub const COMPLETE: cl_profiling_info = CL_PROFILING_COMMAND_COMPLETE;
-    // #endif;
+gen_consts! {
+    ProfilingInfo: cl_profiling_info {
+        /* cl_profiling_info - cl_uint */
+        QUEUED = CL_PROFILING_COMMAND_QUEUED,
+        SUBMIT = CL_PROFILING_COMMAND_SUBMIT,
+        START = CL_PROFILING_COMMAND_START,
+        END = CL_PROFILING_COMMAND_END,
+        // #ifdef CL_VERSION_2_0;
+        COMPLETE = CL_PROFILING_COMMAND_COMPLETE,
+        // #endif;
+    }
 }
 
 #[non_exhaustive]
 pub struct KhronosVendorId;
-impl KhronosVendorId {
-    /* cl_khronos_vendor_id */
-    pub const CODEPLAY: cl_khronos_vendor_id = CL_KHRONOS_VENDOR_ID_CODEPLAY;
+gen_consts! {
+    KhronosVendorId: cl_khronos_vendor_id {
+        /* cl_khronos_vendor_id */
+        CODEPLAY = CL_KHRONOS_VENDOR_ID_CODEPLAY,
+    }
 }
 
 #[non_exhaustive]
@@ -867,3 +921,42 @@ fn make_version(major: cl_ulong, minor: cl_ulong, patch: cl_ulong) -> cl_bitfiel
     (((major) & (minor) & CL_VERSION_MINOR_MASK) << CL_VERSION_PATCH_BITS)
         | ((patch) & CL_VERSION_PATCH_MASK)
 }
+
+/************************/
+/* /\ /\ /\ /\ /\ /\ /\ */
+/*|__|__|__|__|__|__|__|*/
+/*|  |  |  |  |  |  |  |*/
+/*|  |  Unit Tests  |  |*/
+/*|__|__|__|__|__|__|__|*/
+/*|__|__|__|__|__|__|__|*/
+/************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_name_lookup() {
+        let global_mem_size = DeviceInfo::GLOBAL_MEM_SIZE;
+        assert_eq!(
+            DeviceInfo::name_of(global_mem_size),
+            Some("CL_DEVICE_GLOBAL_MEM_SIZE")
+        );
+        assert_eq!(
+            DeviceInfo::from_name("CL_DEVICE_GLOBAL_MEM_SIZE"),
+            Some(global_mem_size)
+        );
+        assert_eq!(
+            DeviceInfo::from_name("GLOBAL_MEM_SIZE"),
+            Some(global_mem_size)
+        );
+        assert_eq!(DeviceInfo::from_name("CL_MEM_SIZE"), None);
+        assert_eq!(MemInfo::from_name("CL_MEM_SIZE"), Some(MemInfo::SIZE));
+        assert_eq!(StatusCode::name_of(-30), Some("CL_INVALID_VALUE"));
+        assert_eq!(ChannelOrder::name_of(0xdead), None);
+        assert_eq!(
+            DevicePartitionProperty::name_of(DevicePartitionProperty::EQUALLY),
+            Some("CL_DEVICE_PARTITION_EQUALLY")
+        );
+    }
+}
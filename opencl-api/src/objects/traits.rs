@@ -27,3 +27,21 @@ where
     fn set(&mut self, value: cl_bitfield) -> BitfieldResult<()>;
     fn new(value: cl_bitfield) -> BitfieldResult<Self>;
 }
+
+/// Type that can be copied to and from device memory as bytes.
+///
+/// # Safety
+///
+/// The type must have no padding, no pointers and every bit pattern of its
+/// size must be a valid value.
+pub unsafe trait Pod: Copy + Send + Sync + 'static {}
+
+macro_rules! impl_pod {
+    ($($typ:ty),*) => {
+        $(unsafe impl Pod for $typ {})*
+    };
+}
+
+impl_pod!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize, f32, f64);
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
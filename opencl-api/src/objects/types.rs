@@ -50,4 +50,7 @@ pub type SVMPtr = NullMutPtr;
 
 pub type PlatformList = Vec<cl_platform_id>;
 pub type DeviceList = Vec<cl_device_id>;
+pub type ProgramList = Vec<cl_program>;
+pub type KernelList = Vec<cl_kernel>;
+pub type EventList = Vec<cl_event>;
 pub type MemFormatList = Vec<cl_image_format>;
@@ -0,0 +1,506 @@
+/*
+ * trace.rs - Tracing of the OpenCL entry points.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+//!
+//! With the `trace` feature, every ffi call of the api is recorded: the
+//! entry point, its arguments, the status it reported, the handles it
+//! returned and how long it took.
+//!
+//! Arguments are decoded where the api knows their meaning, flags by the
+//! names of their bits (`CL_MEM_READ_WRITE|CL_MEM_COPY_HOST_PTR`) and info
+//! params by the name of their constant (`CL_DEVICE_NAME`). Pointers and
+//! handles are shown as addresses.
+//!
+//! Records go to the sink installed with `set_sink`, nothing is recorded
+//! while none is. A sink must not call the api itself, the call would wait
+//! forever for the sink.
+//!
+//! ```no_run
+//! use opencl_api::trace::{self, RingBuffer};
+//!
+//! let ring = RingBuffer::new(1024);
+//! trace::set_sink(ring.clone());
+//! // ...
+//! trace::remove_sink();
+//! for record in ring.records() {
+//!     println!("{}", record);
+//! }
+//! ```
+//!
+use crate::ffi::Value;
+use crate::objects::bitfields::{
+    CommandQueueProperties, DeviceType, MapFlags, MemFlags, MemMigrationFlags,
+};
+use crate::objects::structs::{
+    AddressingMode, BufferCreateType, CommandExecutionStatus, CommandQueueInfo, ContextInfo,
+    DeviceInfo, EventInfo, FilterMode, ImageInfo, KernelArgInfo, KernelExecInfo, KernelInfo,
+    KernelSubGroupInfo, KernelWorkGroupInfo, MemInfo, PipeInfo, PlatformInfo, ProfilingInfo,
+    ProgramBuildInfo, ProgramInfo, SamplerInfo, StatusCode,
+};
+use opencl_heads::consts::CL_SUCCESS;
+use opencl_heads::types::*;
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, ThreadId};
+use std::time::{Duration, Instant};
+
+/// One call into the platform.
+#[derive(Clone, Debug)]
+pub struct Record {
+    /// Name of the entry point, e.g. `clCreateBuffer`.
+    pub function: &'static str,
+    /// Names and decoded values of the arguments, in order.
+    pub args: Vec<(&'static str, String)>,
+    /// Status reported by the call, if the entry point reports one.
+    pub status: Option<cl_int>,
+    /// Handles returned by the call, named `return` for the return value and
+    /// after the argument for the ones written through an out argument.
+    pub handles: Vec<(&'static str, usize)>,
+    /// Wall-clock time spent in the call.
+    pub duration: Duration,
+    /// Thread the call was made from.
+    pub thread: ThreadId,
+}
+
+impl Record {
+    /// Name of the status, e.g. `CL_SUCCESS`.
+    pub fn status_name(&self) -> Option<String> {
+        self.status.map(|x| constant(StatusCode::NAMES, x.into()))
+    }
+
+    /// Returns the record as a single line JSON object.
+    pub fn to_json(&self) -> String {
+        let mut json = format!("{{\"function\":\"{}\",\"args\":{{", escape(self.function));
+        for (i, (name, value)) in self.args.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            json.push_str(&format!("\"{}\":\"{}\"", escape(name), escape(value)));
+        }
+        json.push_str("},\"status\":");
+        match self.status_name() {
+            Some(name) => json.push_str(&format!("\"{}\"", escape(&name))),
+            None => json.push_str("null"),
+        }
+        json.push_str(",\"handles\":{");
+        for (i, (name, handle)) in self.handles.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            json.push_str(&format!("\"{}\":\"{:#x}\"", escape(name), handle));
+        }
+        json.push_str(&format!(
+            "}},\"duration_ns\":{},\"thread\":\"{}\"}}",
+            self.duration.as_nanos(),
+            escape(&format!("{:?}", self.thread))
+        ));
+        json
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}(", self.function)?;
+        for (i, (name, value)) in self.args.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}={}", name, value)?;
+        }
+        write!(f, ")")?;
+        if let Some(status) = self.status_name() {
+            write!(f, " = {}", status)?;
+        }
+        for (name, handle) in &self.handles {
+            write!(f, ", {}={:#x}", name, handle)?;
+        }
+        write!(f, " [{:?}]", self.duration)
+    }
+}
+
+/// Destination of the records.
+pub trait Sink: Send {
+    fn record(&mut self, record: &Record);
+}
+
+/// Prints every record on a line of stderr.
+pub struct Stderr;
+
+impl Sink for Stderr {
+    fn record(&mut self, record: &Record) {
+        eprintln!("{}", record);
+    }
+}
+
+/// Keeps the latest records in memory, dropping the oldest ones past its
+/// capacity. Clones share the same records, so a clone can be installed as
+/// the sink while the original reads them back.
+#[derive(Clone)]
+pub struct RingBuffer {
+    records: Arc<Mutex<VecDeque<Record>>>,
+    capacity: usize,
+}
+
+impl RingBuffer {
+    pub fn new(capacity: usize) -> Self {
+        RingBuffer {
+            records: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+        }
+    }
+
+    /// Returns the records kept, oldest first.
+    pub fn records(&self) -> Vec<Record> {
+        self.records.lock().unwrap().iter().cloned().collect()
+    }
+
+    pub fn clear(&self) {
+        self.records.lock().unwrap().clear();
+    }
+}
+
+impl Sink for RingBuffer {
+    fn record(&mut self, record: &Record) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut records = self.records.lock().unwrap();
+        if records.len() == self.capacity {
+            records.pop_front();
+        }
+        records.push_back(record.clone());
+    }
+}
+
+/// Writes every record as a JSON object on its own line.
+///
+/// Records are flushed as they are written, so that a trace is complete up
+/// to the last call even if the process then crashes. Write errors are
+/// ignored, tracing never fails a call.
+pub struct JsonLines<W: Write + Send> {
+    writer: W,
+}
+
+impl<W: Write + Send> JsonLines<W> {
+    pub fn new(writer: W) -> Self {
+        JsonLines { writer }
+    }
+}
+
+impl JsonLines<BufWriter<File>> {
+    /// Writes the records to the file at path, truncating it.
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(JsonLines::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write + Send> Sink for JsonLines<W> {
+    fn record(&mut self, record: &Record) {
+        let _ = writeln!(self.writer, "{}", record.to_json());
+        let _ = self.writer.flush();
+    }
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static SINK: Mutex<Option<Box<dyn Sink>>> = Mutex::new(None);
+
+/// Sends the records of all the threads to sink, replacing the previous one.
+pub fn set_sink<S: Sink + 'static>(sink: S) {
+    *SINK.lock().unwrap() = Some(Box::new(sink));
+    ENABLED.store(true, Ordering::Release);
+}
+
+/// Stops recording, returning the sink that was installed.
+pub fn remove_sink() -> Option<Box<dyn Sink>> {
+    let mut sink = SINK.lock().unwrap();
+    ENABLED.store(false, Ordering::Release);
+    sink.take()
+}
+
+/// Call in progress, recorded once it returns.
+pub(crate) struct Call {
+    function: &'static str,
+    args: Vec<(&'static str, Value)>,
+    start: Instant,
+}
+
+impl Call {
+    /// Starts recording a call, unless no sink is installed.
+    pub(crate) fn begin(function: &'static str, args: &[(&'static str, Value)]) -> Option<Call> {
+        if !ENABLED.load(Ordering::Acquire) {
+            return None;
+        }
+        Some(Call {
+            function,
+            args: args.to_vec(),
+            start: Instant::now(),
+        })
+    }
+
+    /// Records the call, which reported status and returned returned.
+    ///
+    /// # Safety
+    ///
+    /// The out arguments of the call must still be valid, the events it
+    /// created are read back from them.
+    pub(crate) unsafe fn end(self, status: Option<cl_int>, returned: Option<usize>) {
+        let duration = self.start.elapsed();
+        let mut handles = Vec::new();
+        if let Some(handle) = returned {
+            handles.push(("return", handle));
+        }
+        if status == Some(CL_SUCCESS) {
+            for (name, value) in &self.args {
+                if let (&"event", Value::Pointer(ptr)) = (name, value) {
+                    if *ptr != 0 {
+                        handles.push(("event", *(*ptr as *const cl_event) as usize));
+                    }
+                }
+            }
+        }
+        let record = Record {
+            function: self.function,
+            args: self
+                .args
+                .iter()
+                .map(|(name, value)| (*name, decode(self.function, name, *value)))
+                .collect(),
+            status,
+            handles,
+            duration,
+            thread: thread::current().id(),
+        };
+        if let Some(sink) = SINK.lock().unwrap().as_mut() {
+            sink.record(&record);
+        }
+    }
+}
+
+/// Renders the argument name of function.
+fn decode(function: &str, name: &str, value: Value) -> String {
+    let integer = match value {
+        Value::Pointer(0) | Value::Callback(false) => return "NULL".to_owned(),
+        Value::Pointer(ptr) => return format!("{:#x}", ptr),
+        Value::Callback(true) => return "fn".to_owned(),
+        Value::Int(x) => x as i128,
+        Value::Uint(x) => x as i128,
+    };
+    match name {
+        "param_name" => match info_names(function) {
+            Some(names) => constant(names, integer),
+            None => integer.to_string(),
+        },
+        "flags" => match function {
+            "clEnqueueMigrateMemObjects" | "clEnqueueSVMMigrateMem" => {
+                flags(MemMigrationFlags::NAMES, integer)
+            }
+            _ => flags(MemFlags::NAMES, integer),
+        },
+        "map_flags" => flags(MapFlags::NAMES, integer),
+        "device_type" => flags(DeviceType::NAMES, integer),
+        "properties" => flags(CommandQueueProperties::NAMES, integer),
+        "buffer_create_type" => constant(BufferCreateType::NAMES, integer),
+        "addressing_mode" => constant(AddressingMode::NAMES, integer),
+        "filter_mode" => constant(FilterMode::NAMES, integer),
+        "execution_status" => constant(CommandExecutionStatus::NAMES, integer),
+        "enable" | "normalized_coords" | "blocking_read" | "blocking_write" | "blocking_map"
+        | "blocking_copy" | "blocking_free" => match integer {
+            0 => "CL_FALSE".to_owned(),
+            1 => "CL_TRUE".to_owned(),
+            _ => integer.to_string(),
+        },
+        _ => integer.to_string(),
+    }
+}
+
+/// Returns the info params queried by function, if it is a clGet*Info.
+fn info_names(function: &str) -> Option<&'static [(&'static str, cl_uint)]> {
+    Some(match function {
+        "clGetPlatformInfo" => PlatformInfo::NAMES,
+        "clGetDeviceInfo" => DeviceInfo::NAMES,
+        "clGetContextInfo" => ContextInfo::NAMES,
+        "clGetCommandQueueInfo" => CommandQueueInfo::NAMES,
+        "clGetMemObjectInfo" => MemInfo::NAMES,
+        "clGetImageInfo" => ImageInfo::NAMES,
+        "clGetPipeInfo" => PipeInfo::NAMES,
+        "clGetSamplerInfo" => SamplerInfo::NAMES,
+        "clGetProgramInfo" => ProgramInfo::NAMES,
+        "clGetProgramBuildInfo" => ProgramBuildInfo::NAMES,
+        "clGetKernelInfo" => KernelInfo::NAMES,
+        "clGetKernelArgInfo" => KernelArgInfo::NAMES,
+        "clGetKernelWorkGroupInfo" => KernelWorkGroupInfo::NAMES,
+        "clGetKernelSubGroupInfo" => KernelSubGroupInfo::NAMES,
+        "clSetKernelExecInfo" => KernelExecInfo::NAMES,
+        "clGetEventInfo" => EventInfo::NAMES,
+        "clGetEventProfilingInfo" => ProfilingInfo::NAMES,
+        _ => return None,
+    })
+}
+
+/// Renders value as the name of the constant it matches, else as a number.
+fn constant<T: Copy + Into<i128>>(names: &[(&'static str, T)], value: i128) -> String {
+    names
+        .iter()
+        .find(|(_, x)| (*x).into() == value)
+        .map(|(name, _)| (*name).to_owned())
+        .unwrap_or_else(|| value.to_string())
+}
+
+/// Renders value as the constant it matches, else as the `|` separated
+/// names of its bits, the bits without a name being shown in hex.
+fn flags<T: Copy + Into<i128>>(names: &[(&'static str, T)], value: i128) -> String {
+    if let Some((name, _)) = names.iter().find(|(_, x)| (*x).into() == value) {
+        return (*name).to_owned();
+    }
+    let mut parts = Vec::new();
+    let mut rest = value;
+    for (name, bit) in names {
+        let bit = (*bit).into();
+        if bit.count_ones() == 1 && value & bit != 0 {
+            parts.push((*name).to_owned());
+            rest &= !bit;
+        }
+    }
+    if rest != 0 || parts.is_empty() {
+        parts.push(format!("{:#x}", rest));
+    }
+    parts.join("|")
+}
+
+/// Escapes s to be quoted in JSON.
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/************************/
+/* /‾‾\ /‾‾\ /‾‾\ /‾‾\  */
+/*|  |  |  |  |  |  |  |*/
+/*|  |  Unit Tests  |  |*/
+/*|__|__|__|__|__|__|__|*/
+/*|__|__|__|__|__|__|__|*/
+/************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libc::c_void;
+    use opencl_heads::consts::*;
+
+    #[test]
+    fn test_decode() {
+        let flags = CL_MEM_READ_WRITE | CL_MEM_COPY_HOST_PTR;
+        assert_eq!(
+            decode("clCreateBuffer", "flags", Value::from(flags)),
+            "CL_MEM_READ_WRITE|CL_MEM_COPY_HOST_PTR"
+        );
+        assert_eq!(
+            decode(
+                "clCreateBuffer",
+                "flags",
+                Value::from(CL_MEM_READ_ONLY | 1 << 40)
+            ),
+            "CL_MEM_READ_ONLY|0x10000000000"
+        );
+        assert_eq!(
+            decode(
+                "clGetDeviceIDs",
+                "device_type",
+                Value::from(CL_DEVICE_TYPE_ALL)
+            ),
+            "CL_DEVICE_TYPE_ALL"
+        );
+        assert_eq!(
+            decode("clGetDeviceInfo", "param_name", Value::from(CL_DEVICE_NAME)),
+            "CL_DEVICE_NAME"
+        );
+        assert_eq!(
+            decode("clGetMemObjectInfo", "param_name", Value::from(CL_MEM_SIZE)),
+            "CL_MEM_SIZE"
+        );
+        assert_eq!(
+            decode("clEnqueueReadBuffer", "blocking_read", Value::from(CL_TRUE)),
+            "CL_TRUE"
+        );
+        assert_eq!(decode("clFinish", "size", Value::from(16usize)), "16");
+        assert_eq!(
+            decode(
+                "clCreateBuffer",
+                "host_ptr",
+                Value::from(std::ptr::null_mut::<c_void>())
+            ),
+            "NULL"
+        );
+    }
+
+    #[test]
+    fn test_ring_buffer() {
+        let mut ring = RingBuffer::new(2);
+        let reader = ring.clone();
+        for function in &["clFlush", "clFinish", "clReleaseEvent"] {
+            ring.record(&Record {
+                function,
+                args: vec![("command_queue", "0x10".to_owned())],
+                status: Some(CL_SUCCESS),
+                handles: vec![],
+                duration: Duration::from_micros(1),
+                thread: thread::current().id(),
+            });
+        }
+        let records = reader.records();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].function, "clFinish");
+        assert_eq!(
+            records[0].to_string(),
+            "clFinish(command_queue=0x10) = CL_SUCCESS [1µs]"
+        );
+    }
+
+    #[test]
+    fn test_to_json() {
+        let record = Record {
+            function: "clCreateKernel",
+            args: vec![("kernel_name", "\"add\"\n".to_owned())],
+            status: Some(CL_INVALID_KERNEL_NAME),
+            handles: vec![("return", 0)],
+            duration: Duration::from_nanos(1500),
+            thread: thread::current().id(),
+        };
+        let json = record.to_json();
+        assert!(json.starts_with(
+            "{\"function\":\"clCreateKernel\",\"args\":{\"kernel_name\":\"\\\"add\\\"\\n\"},\
+             \"status\":\"CL_INVALID_KERNEL_NAME\",\"handles\":{\"return\":\"0x0\"},\
+             \"duration_ns\":1500,"
+        ));
+    }
+}
@@ -0,0 +1,325 @@
+/*
+ * bench.rs - Measures the bandwidth, latency and throughput of the devices.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//!
+//! Every measure is timed by the device, from the profiling information of
+//! the events of its commands. Each runs once to warm up and then
+//! `ITERATIONS` times, and reports the mean of the timed runs.
+//!
+use opencl::{Buffer, CommandQueue, Context, Device, Event, Kernel, Platform, Program};
+use opencl_api::objects::bitfields::{DeviceType, MemFlags};
+use opencl_api::objects::traits::GetSetGo;
+use serde::Serialize;
+use std::slice;
+
+/// Timed runs of every measure.
+const ITERATIONS: u64 = 8;
+
+/// Bytes moved by each transfer, unless devices allocate less at once.
+const TRANSFER_SIZE: u64 = 64 << 20;
+
+/// Loops of the compute kernels, each doing `MADS_PER_LOOP` multiply-adds.
+const LOOPS: usize = 64;
+const MADS_PER_LOOP: usize = 32;
+
+/// Work-items of the compute kernels per compute unit.
+const ITEMS_PER_UNIT: usize = 16 * 1024;
+
+const EMPTY_SOURCE: &str = "__kernel void empty(void) {}";
+
+/// Measures of a device.
+#[derive(Debug, Serialize)]
+pub struct DeviceBench {
+    pub platform: usize,
+    pub device: usize,
+    pub name: String,
+    pub measures: Vec<Measure>,
+}
+
+/// Outcome of a measure, with either a value or an error.
+#[derive(Debug, Serialize)]
+pub struct Measure {
+    pub test: &'static str,
+    pub unit: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl Measure {
+    fn new(test: &'static str, unit: &'static str, value: Result<f64, String>) -> Self {
+        let (value, error) = match value {
+            Ok(x) => (Some(x), None),
+            Err(e) => (None, Some(e)),
+        };
+        Measure {
+            test,
+            unit,
+            value,
+            error,
+        }
+    }
+}
+
+/// Benchmarks every device of every platform.
+pub fn run() -> opencl::Result<Vec<DeviceBench>> {
+    let all = DeviceType::new(DeviceType::ALL).unwrap();
+    let mut benches = Vec::new();
+    for (i, platform) in Platform::list()?.iter().enumerate() {
+        // A platform without devices answers CL_DEVICE_NOT_FOUND.
+        let devices = platform.devices(all.clone()).unwrap_or_default();
+        for (j, device) in devices.iter().enumerate() {
+            benches.push(DeviceBench {
+                platform: i,
+                device: j,
+                name: device.details().name.unwrap_or_default(),
+                measures: bench_device(device),
+            });
+        }
+    }
+    Ok(benches)
+}
+
+/// Width of the test column of the table.
+const TEST_WIDTH: usize = 40;
+
+/// Prints the measures of every device as a table.
+pub fn print_table(benches: &[DeviceBench]) {
+    for (k, bench) in benches.iter().enumerate() {
+        if k > 0 {
+            println!();
+        }
+        println!(
+            "Platform #{}, Device #{}: {}",
+            bench.platform, bench.device, bench.name
+        );
+        for measure in &bench.measures {
+            let value = match (&measure.value, &measure.error) {
+                (Some(x), _) => format!("{:>12.2} {}", x, measure.unit),
+                (None, Some(e)) => format!("<{}>", e),
+                (None, None) => String::new(),
+            };
+            println!("  {:<width$}{}", measure.test, value, width = TEST_WIDTH);
+        }
+    }
+}
+
+fn bench_device(device: &Device) -> Vec<Measure> {
+    let setup = Context::new(slice::from_ref(device)).and_then(|context| {
+        let queue = CommandQueue::with_profiling(&context, device)?;
+        Ok((context, queue))
+    });
+    let (context, queue) = match setup {
+        Ok(x) => x,
+        Err(e) => return vec![Measure::new("Profiling queue", "", Err(e.to_string()))],
+    };
+    let details = device.details();
+    let size = details
+        .max_mem_alloc_size
+        .map_or(TRANSFER_SIZE, |x| x.min(TRANSFER_SIZE)) as usize;
+    let items = details.max_compute_units.unwrap_or(1) as usize * ITEMS_PER_UNIT;
+    let fp64 = details
+        .extensions
+        .is_ok_and(|x| x.split_whitespace().any(|x| x == "cl_khr_fp64"));
+
+    let mut measures = transfers(&context, &queue, size);
+    measures.push(Measure::new(
+        "Kernel launch latency",
+        "us",
+        launch_latency(&context, device, &queue).map_err(|e| e.to_string()),
+    ));
+    measures.push(Measure::new(
+        "Single-precision compute",
+        "GFLOPS",
+        compute(&context, device, &queue, "float", items),
+    ));
+    let double = if fp64 {
+        compute(&context, device, &queue, "double", items)
+    } else {
+        Err("cl_khr_fp64 not supported".to_string())
+    };
+    measures.push(Measure::new("Double-precision compute", "GFLOPS", double));
+    measures
+}
+
+/// Nanoseconds the command of event ran for, once it completed.
+fn duration(event: &Event) -> opencl::Result<u64> {
+    event.wait()?;
+    Ok(event.profile()?.duration())
+}
+
+/// Runs command once to warm up and `ITERATIONS` times, returns the
+/// nanoseconds it reported for the timed runs in total.
+fn time<F>(mut command: F) -> opencl::Result<u64>
+where
+    F: FnMut() -> opencl::Result<u64>,
+{
+    command()?;
+    let mut total = 0;
+    for _ in 0..ITERATIONS {
+        total += command()?;
+    }
+    Ok(total)
+}
+
+/// Rate per nanosecond of doing amount in each of the timed runs.
+fn rate(amount: f64, total: opencl::Result<u64>) -> Result<f64, String> {
+    match total.map_err(|e| e.to_string())? {
+        0 => Err("faster than the device timer".to_string()),
+        x => Ok(amount * ITERATIONS as f64 / x as f64),
+    }
+}
+
+/// Bandwidths of transfers of size bytes, in GB/s, as bytes per ns.
+fn transfers(context: &Context, queue: &CommandQueue, size: usize) -> Vec<Measure> {
+    let flags = MemFlags::new(MemFlags::READ_WRITE).unwrap();
+    let buffers = Buffer::<u8>::new(context, flags.clone(), size).and_then(|device| {
+        let other = Buffer::<u8>::new(context, flags, size)?;
+        let pinned_flags = MemFlags::new(MemFlags::READ_WRITE | MemFlags::ALLOC_HOST_PTR).unwrap();
+        let pinned = Buffer::<u8>::new(context, pinned_flags, size)?;
+        Ok((device, other, pinned))
+    });
+    let (mut device, other, mut pinned) = match buffers {
+        Ok(x) => x,
+        Err(e) => return vec![Measure::new("Transfer buffers", "", Err(e.to_string()))],
+    };
+    let mut pageable = vec![0u8; size];
+
+    let write_from = |host: &[u8]| time(|| duration(&device.write_range(queue, 0..size, host)?));
+    let h2d = write_from(&pageable);
+    // The mapped buffers are only accessed through their guard, the
+    // transfers go to and from device.
+    let pinned_h2d = unsafe { pinned.map(queue, 0..size) }.and_then(|host| write_from(&host));
+
+    let read_into =
+        |host: &mut [u8]| time(|| duration(&device.read_range(queue, 0..size, host)?));
+    let d2h = read_into(&mut pageable);
+    let pinned_d2h =
+        unsafe { pinned.map_mut(queue, 0..size) }.and_then(|mut host| read_into(&mut host));
+
+    let d2d = time(|| duration(&device.copy_to(queue, &other)?));
+    let map = time(|| duration(unsafe { device.map(queue, 0..size) }?.event()));
+    let unmap = time(|| duration(&unsafe { device.map_mut(queue, 0..size) }?.unmap()?));
+
+    let bytes = size as f64;
+    vec![
+        Measure::new("Host to device, pageable", "GB/s", rate(bytes, h2d)),
+        Measure::new("Host to device, pinned", "GB/s", rate(bytes, pinned_h2d)),
+        Measure::new("Device to host, pageable", "GB/s", rate(bytes, d2h)),
+        Measure::new("Device to host, pinned", "GB/s", rate(bytes, pinned_d2h)),
+        Measure::new("Device to device", "GB/s", rate(bytes, d2d)),
+        Measure::new("Map for reading", "GB/s", rate(bytes, map)),
+        Measure::new("Unmap after writing", "GB/s", rate(bytes, unmap)),
+    ]
+}
+
+/// Mean microseconds from enqueueing an empty kernel to its start.
+fn launch_latency(context: &Context, device: &Device, queue: &CommandQueue) -> opencl::Result<f64> {
+    let program = Program::with_source(context, &[EMPTY_SOURCE])?;
+    program.build(slice::from_ref(device), None)?;
+    let kernel = Kernel::new(&program, "empty")?;
+    let total = time(|| {
+        let event = kernel.enqueue(queue, &[1], None)?;
+        event.wait()?;
+        Ok(event.profile()?.latency())
+    })?;
+    Ok(total as f64 / ITERATIONS as f64 / 1000.0)
+}
+
+/// Source of the kernel `flops` doing `LOOPS * MADS_PER_LOOP` dependent
+/// multiply-adds on values of typ per work-item.
+fn flops_source(typ: &str) -> String {
+    let pragma = if typ == "double" {
+        "#pragma OPENCL EXTENSION cl_khr_fp64 : enable\n"
+    } else {
+        ""
+    };
+    let mads = "        x = mad(y, x, y);\n        y = mad(x, y, x);\n".repeat(MADS_PER_LOOP / 2);
+    format!(
+        "{pragma}__kernel void flops(__global {typ}* out, {typ} a) {{
+    {typ} x = a;
+    {typ} y = ({typ})get_local_id(0);
+    for (int i = 0; i < {loops}; i++) {{
+{mads}    }}
+    out[get_global_id(0)] = x + y;
+}}
+",
+        pragma = pragma,
+        typ = typ,
+        loops = LOOPS,
+        mads = mads
+    )
+}
+
+/// Floating-point operations per ns, in GFLOPS, of items work-items of the
+/// `flops` kernel on values of typ, counting a multiply-add as two.
+fn compute(
+    context: &Context,
+    device: &Device,
+    queue: &CommandQueue,
+    typ: &str,
+    items: usize,
+) -> Result<f64, String> {
+    let total = Program::with_source(context, &[&flops_source(typ)]).and_then(|program| {
+        program.build(slice::from_ref(device), None)?;
+        let mut kernel = Kernel::new(&program, "flops")?;
+        let flags = MemFlags::new(MemFlags::WRITE_ONLY).unwrap();
+        // Large enough for doubles, the kernel only stores the results to
+        // keep them from being optimized out.
+        let out = Buffer::<f64>::new(context, flags, items)?;
+        kernel.set_arg_buffer(0, &out)?;
+        if typ == "double" {
+            kernel.set_arg(1, &1.3f64)?;
+        } else {
+            kernel.set_arg(1, &1.3f32)?;
+        }
+        time(|| duration(&kernel.enqueue(queue, &[items], None)?))
+    });
+    let flops = (2 * LOOPS * MADS_PER_LOOP * items) as f64;
+    rate(flops, total)
+}
+
+/************************/
+/* /\ /\ /\ /\ /\ /\ /\ */
+/*|__|__|__|__|__|__|__|*/
+/*|  |  |  |  |  |  |  |*/
+/*|  |  Unit Tests  |  |*/
+/*|__|__|__|__|__|__|__|*/
+/*|__|__|__|__|__|__|__|*/
+/************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flops_source() {
+        let source = flops_source("double");
+        assert!(source.starts_with("#pragma OPENCL EXTENSION cl_khr_fp64 : enable\n"));
+        assert!(source.contains("__kernel void flops(__global double* out, double a)"));
+        assert_eq!(MADS_PER_LOOP, source.matches("mad(").count());
+        assert!(!flops_source("float").contains("#pragma"));
+    }
+
+    #[test]
+    fn test_rate() {
+        assert_eq!(Ok(2.0 * ITERATIONS as f64), rate(4.0, Ok(2)));
+        assert!(rate(4.0, Ok(0)).is_err());
+    }
+}
@@ -0,0 +1,226 @@
+/*
+ * format.rs - Human readable rendering of the info values.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use opencl_api::api::info::{name_string, version_string};
+use opencl_api::errors::{OpenCLAPIError, ValidationError};
+use opencl_api::objects::enums::{DevicePartition, LocalMemType, MemCacheType, PartitionScheme};
+use opencl_api::objects::structs::StatusCode;
+use opencl_heads::types::*;
+use std::ffi::c_void;
+use std::fmt::Display;
+
+/// Describes why a query failed, by the name of its status when it has one.
+pub fn error(error: &OpenCLAPIError) -> String {
+    match error {
+        OpenCLAPIError::ObjectError(ValidationError::UnsupportedParam(..)) => {
+            "Unsupported".to_owned()
+        }
+        OpenCLAPIError::ObjectError(ValidationError::UnsupportedVersion(_, _, major, minor)) => {
+            format!("Unsupported before OpenCL {}.{}", major, minor)
+        }
+        OpenCLAPIError::StatusCodeError { int_code, .. } => StatusCode::name_of(*int_code)
+            .map(str::to_owned)
+            .unwrap_or_else(|| format!("Error {}", int_code)),
+        _ => error.to_string(),
+    }
+}
+
+pub fn text(value: &str) -> String {
+    value.trim().to_owned()
+}
+
+pub fn number<T: Display>(value: &T) -> String {
+    value.to_string()
+}
+
+pub fn boolean(value: &bool) -> String {
+    if *value { "Yes" } else { "No" }.to_owned()
+}
+
+/// Renders a count of bytes, followed by its value in the largest binary
+/// unit it reaches.
+pub fn bytes(value: &cl_ulong) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut scaled = *value as f64;
+    let mut unit = 0;
+    while scaled >= 1024.0 && unit + 1 < UNITS.len() {
+        scaled /= 1024.0;
+        unit += 1;
+    }
+    match unit {
+        0 => format!("{} B", value),
+        _ => format!("{} ({:.2} {})", value, scaled, UNITS[unit]),
+    }
+}
+
+pub fn size_bytes(value: &size_t) -> String {
+    bytes(&(*value as cl_ulong))
+}
+
+pub fn megahertz(value: &cl_uint) -> String {
+    format!("{} MHz", value)
+}
+
+pub fn nanoseconds<T: Display>(value: &T) -> String {
+    format!("{} ns", value)
+}
+
+pub fn pixels(value: &size_t) -> String {
+    format!("{} px", value)
+}
+
+pub fn dimensions(value: &[size_t]) -> String {
+    let sizes: Vec<_> = value.iter().map(size_t::to_string).collect();
+    sizes.join("x")
+}
+
+pub fn flags<T: Display>(value: &T) -> String {
+    value.to_string()
+}
+
+pub fn optional_flags<T: Display>(value: &Option<T>) -> String {
+    match value {
+        Some(x) => x.to_string(),
+        None => "None".to_owned(),
+    }
+}
+
+pub fn handle(value: &*mut c_void) -> String {
+    format!("{:p}", *value)
+}
+
+pub fn optional_handle(value: &Option<*mut c_void>) -> String {
+    match value {
+        Some(x) => format!("{:p}", *x),
+        None => "None".to_owned(),
+    }
+}
+
+pub fn version(value: &cl_version) -> String {
+    version_string(*value)
+}
+
+/// Renders a name-version list one entry per line.
+pub fn name_versions(value: &[cl_name_version]) -> String {
+    if value.is_empty() {
+        return "None".to_owned();
+    }
+    let lines: Vec<_> = value
+        .iter()
+        .map(|x| format!("{:<40}{}", name(x), version(&x.version)))
+        .collect();
+    lines.join("\n")
+}
+
+pub fn name(value: &cl_name_version) -> String {
+    name_string(value)
+}
+
+pub fn cache_type(value: &MemCacheType) -> String {
+    match value {
+        MemCacheType::None => "None".to_owned(),
+        MemCacheType::ReadOnly => "Read-Only".to_owned(),
+        MemCacheType::ReadWrite => "Read/Write".to_owned(),
+        MemCacheType::InvalidType(x) => format!("Unknown ({:#x})", x),
+    }
+}
+
+pub fn local_mem_type(value: &LocalMemType) -> String {
+    match value {
+        LocalMemType::Local => "Local".to_owned(),
+        LocalMemType::Global => "Global".to_owned(),
+        LocalMemType::InvalidType(x) => format!("Unknown ({:#x})", x),
+    }
+}
+
+pub fn partition_schemes(value: &[PartitionScheme]) -> String {
+    if value.is_empty() {
+        return "None".to_owned();
+    }
+    let schemes: Vec<_> = value
+        .iter()
+        .map(|x| match x {
+            PartitionScheme::Equally => "equally".to_owned(),
+            PartitionScheme::ByCounts => "by counts".to_owned(),
+            PartitionScheme::ByAffinityDomain => "by affinity domain".to_owned(),
+            PartitionScheme::InvalidScheme(x) => format!("unknown ({:#x})", x),
+        })
+        .collect();
+    schemes.join(", ")
+}
+
+pub fn partition_type(value: &Option<DevicePartition>) -> String {
+    match value {
+        None => "None".to_owned(),
+        Some(DevicePartition::Equally(x)) => format!("equally into {} compute units", x),
+        Some(DevicePartition::ByCounts(x)) => format!("by counts {:?}", x),
+        Some(DevicePartition::ByAffinityDomain(x)) => {
+            format!("by affinity domain {:#x}", x)
+        }
+        Some(DevicePartition::InvalidPartition(x)) => format!("unknown {:?}", x),
+    }
+}
+
+/************************/
+/* /\ /\ /\ /\ /\ /\ /\ */
+/*|__|__|__|__|__|__|__|*/
+/*|  |  |  |  |  |  |  |*/
+/*|  |  Unit Tests  |  |*/
+/*|__|__|__|__|__|__|__|*/
+/*|__|__|__|__|__|__|__|*/
+/************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opencl_api::errors::ToLibraryError;
+
+    #[test]
+    fn test_units() {
+        assert_eq!(bytes(&512), "512 B");
+        assert_eq!(bytes(&(64 * 1024)), "65536 (64.00 KiB)");
+        assert_eq!(bytes(&(3 << 29)), "1610612736 (1.50 GiB)");
+        assert_eq!(megahertz(&1500), "1500 MHz");
+        assert_eq!(dimensions(&[1024, 64, 1]), "1024x64x1");
+    }
+
+    #[test]
+    fn test_versions() {
+        let version_3_0_1 = (3 << 22) | 1;
+        assert_eq!(version(&version_3_0_1), "3.0.1");
+        let mut entry = cl_name_version::default();
+        for (x, y) in entry.name.iter_mut().zip(b"cl_khr_fp64") {
+            *x = *y as _;
+        }
+        entry.version = 1 << 22;
+        assert_eq!(name(&entry), "cl_khr_fp64");
+        assert!(name_versions(&[entry]).ends_with("1.0.0"));
+        assert_eq!(name_versions(&[]), "None");
+    }
+
+    #[test]
+    fn test_errors() {
+        let unsupported = ValidationError::UnsupportedParam("clGetDeviceInfo", 0x1000);
+        assert_eq!(error(&unsupported.to_error()), "Unsupported");
+        let unsupported = ValidationError::UnsupportedVersion("clGetDeviceInfo", 0x1000, 3, 0);
+        assert_eq!(
+            error(&unsupported.to_error()),
+            "Unsupported before OpenCL 3.0"
+        );
+    }
+}
@@ -0,0 +1,154 @@
+/*
+ * icd.rs - Diagnoses the installed ICD vendors.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::rows::Row;
+use crate::{print_rows, LABEL_WIDTH};
+use opencl_heads::probe::{self, Entry, Source, Status, FILENAMES_ENV, VENDORS_ENV};
+use std::path::PathBuf;
+
+/// Prints where the vendors are looked up and the check of every vendor
+/// library found, returns whether there are some and all are usable.
+pub fn run() -> bool {
+    let variable = |name| std::env::var(name).map_err(|_| String::from("unset"));
+    print_rows(
+        &[
+            Row {
+                label: FILENAMES_ENV,
+                value: variable(FILENAMES_ENV),
+            },
+            Row {
+                label: VENDORS_ENV,
+                value: variable(VENDORS_ENV),
+            },
+            Row {
+                label: "Vendors location",
+                value: location(probe::vendors_location()),
+            },
+        ],
+        0,
+    );
+
+    let entries = probe::entries();
+    println!();
+    println!(
+        "{:<width$}{}",
+        "Number of vendor libraries",
+        entries.len(),
+        width = LABEL_WIDTH
+    );
+    let mut usable = !entries.is_empty();
+    for (i, entry) in entries.iter().enumerate() {
+        let status = entry.library.as_ref().ok().map(probe::check);
+        usable &= status == Some(Status::Usable);
+        println!();
+        println!("Vendor #{}", i);
+        print_rows(&entry_rows(entry, status.as_ref()), 2);
+    }
+    usable
+}
+
+fn location(location: Option<PathBuf>) -> Result<String, String> {
+    match location {
+        Some(x) if x.exists() => Ok(x.display().to_string()),
+        Some(x) => Err(format!("{} not found", x.display())),
+        None => Err(String::from("none on this platform")),
+    }
+}
+
+/// Rows of entry, checked with status when it names a library.
+fn entry_rows(entry: &Entry, status: Option<&Status>) -> Vec<Row> {
+    let source = match &entry.source {
+        Source::Filenames => FILENAMES_ENV.to_string(),
+        Source::IcdFile(x) => x.display().to_string(),
+    };
+    let mut rows = vec![
+        Row {
+            label: "Listed in",
+            value: Ok(source),
+        },
+        Row {
+            label: "Library",
+            value: entry
+                .library
+                .as_ref()
+                .map(|x| x.display().to_string())
+                .map_err(Clone::clone),
+        },
+    ];
+    let value = match status {
+        Some(Status::Usable) => Ok(String::from("usable")),
+        Some(Status::MissingSymbols(x)) => Err(format!("missing {}", x.join(", "))),
+        Some(Status::LoadFailed(x)) => Err(x.to_string()),
+        None => return rows,
+    };
+    rows.push(Row {
+        label: "Status",
+        value,
+    });
+    rows
+}
+
+/************************/
+/* /\ /\ /\ /\ /\ /\ /\ */
+/*|__|__|__|__|__|__|__|*/
+/*|  |  |  |  |  |  |  |*/
+/*|  |  Unit Tests  |  |*/
+/*|__|__|__|__|__|__|__|*/
+/*|__|__|__|__|__|__|__|*/
+/************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(rows: &[Row]) -> Vec<(&str, Result<&str, &str>)> {
+        rows.iter()
+            .map(|x| (x.label, x.value.as_deref().map_err(String::as_str)))
+            .collect()
+    }
+
+    #[test]
+    fn test_entry_rows() {
+        let entry = Entry {
+            source: Source::IcdFile(PathBuf::from("/etc/OpenCL/vendors/a.icd")),
+            library: Ok(PathBuf::from("libA.so")),
+        };
+        let missing = Status::MissingSymbols(vec!["clIcdGetPlatformIDsKHR"]);
+        assert_eq!(
+            values(&entry_rows(&entry, Some(&missing))),
+            [
+                ("Listed in", Ok("/etc/OpenCL/vendors/a.icd")),
+                ("Library", Ok("libA.so")),
+                ("Status", Err("missing clIcdGetPlatformIDsKHR")),
+            ]
+        );
+
+        let entry = Entry {
+            source: Source::IcdFile(PathBuf::from("/etc/OpenCL/vendors/b.icd")),
+            library: Err(String::from("empty .icd file")),
+        };
+        assert_eq!(
+            values(&entry_rows(&entry, None)),
+            [
+                ("Listed in", Ok("/etc/OpenCL/vendors/b.icd")),
+                ("Library", Err("empty .icd file")),
+            ]
+        );
+        assert!(location(Some(PathBuf::from("/nonexistent/vendors"))).is_err());
+    }
+}
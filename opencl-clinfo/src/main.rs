@@ -1,3 +1,255 @@
+/*
+ * main.rs - Lists the OpenCL platforms and devices with all their properties.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod bench;
+mod format;
+mod icd;
+mod raw;
+mod rows;
+mod snapshot;
+
+use opencl::{Platform, SystemSnapshot};
+use opencl_api::errors::OpenCLAPIError;
+use opencl_api::objects::bitfields::DeviceType;
+use opencl_api::objects::traits::GetSetGo;
+use opencl_heads::consts::CL_DEVICE_NOT_FOUND;
+use rows::Row;
+use std::process;
+
+const USAGE: &str = "\
+Usage: clinfo [OPTIONS]
+       clinfo snapshot save <FILE>
+       clinfo snapshot show <FILE>
+       clinfo snapshot diff <BEFORE> <AFTER>
+       clinfo bench [--json]
+       clinfo icd
+
+Lists every OpenCL platform and device along with all their properties.
+
+Options:
+      --json    Print a JSON snapshot, its schema is documented by the
+                snapshot module of the opencl crate
+      --raw     Print the properties by their OpenCL names, as in
+                CL_PLATFORM_NAME
+  -h, --help    Print this help
+
+Snapshots:
+  save          Write the JSON snapshot of this machine to FILE
+  show          Print a saved snapshot as --raw does
+  diff          Print what changed from BEFORE to AFTER, exits with 1 when
+                anything did and with 2 on errors
+
+Benchmarks:
+  bench         Measure the transfer bandwidths, kernel launch latency and
+                single- and double-precision throughput of every device,
+                timed by event profiling; --json prints them as JSON
+
+Diagnostics:
+  icd           Check every vendor library the OpenCL ICD loader would
+                load, from OCL_ICD_FILENAMES and the .icd files of
+                OCL_ICD_VENDORS or /etc/OpenCL/vendors, exits with 1 when
+                none is found or one is unusable";
+
+/// How the platforms and devices are listed.
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    Human,
+    Json,
+    Raw,
+}
+
+/// Width of the label column, values start past it.
+const LABEL_WIDTH: usize = 48;
+
+fn print_rows(rows: &[Row], indent: usize) {
+    let pad = " ".repeat(indent);
+    let width = LABEL_WIDTH - indent;
+    for row in rows {
+        let value = match &row.value {
+            Ok(x) => x.clone(),
+            Err(e) => format!("<{}>", e),
+        };
+        let mut lines = value.lines();
+        println!(
+            "{}{:<width$}{}",
+            pad,
+            row.label,
+            lines.next().unwrap_or(""),
+            width = width
+        );
+        for line in lines {
+            println!("{}{}", " ".repeat(LABEL_WIDTH), line);
+        }
+    }
+}
+
+fn print_all() -> opencl::Result<()> {
+    let platforms = Platform::list()?;
+    println!(
+        "{:<width$}{}",
+        "Number of platforms",
+        platforms.len(),
+        width = LABEL_WIDTH
+    );
+    let all = DeviceType::new(DeviceType::ALL).unwrap();
+    for (i, platform) in platforms.iter().enumerate() {
+        println!();
+        println!("Platform #{}", i);
+        print_rows(&rows::platform_rows(&platform.details()), 2);
+
+        let devices = match platform.devices(all.clone()) {
+            Ok(x) => x,
+            // A platform without devices answers CL_DEVICE_NOT_FOUND.
+            Err(opencl::Error::Api(OpenCLAPIError::StatusCodeError {
+                int_code: CL_DEVICE_NOT_FOUND,
+                ..
+            })) => Vec::new(),
+            Err(e) => {
+                let value = match &e {
+                    opencl::Error::Api(x) => format::error(x),
+                    _ => e.to_string(),
+                };
+                let row = Row {
+                    label: "Number of devices",
+                    value: Err(value),
+                };
+                print_rows(&[row], 2);
+                continue;
+            }
+        };
+        println!(
+            "  {:<width$}{}",
+            "Number of devices",
+            devices.len(),
+            width = LABEL_WIDTH - 2
+        );
+        for (j, device) in devices.iter().enumerate() {
+            println!();
+            println!("  Device #{}", j);
+            let details = device.details();
+            print_rows(&rows::device_rows(&details), 4);
+            if let Ok(true) = details.image_support {
+                print_rows(&rows::image_format_rows(device), 4);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_snapshot(mode: Mode) -> opencl::Result<()> {
+    let snapshot = SystemSnapshot::capture()?;
+    match mode {
+        Mode::Json => match serde_json::to_string_pretty(&snapshot) {
+            Ok(x) => println!("{}", x),
+            Err(e) => {
+                eprintln!("clinfo: {}", e);
+                process::exit(1);
+            }
+        },
+        _ => raw::print(&snapshot),
+    }
+    Ok(())
+}
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("clinfo: {}\n\n{}", message, USAGE);
+    process::exit(2);
+}
+
+fn run_snapshot(args: &[String]) {
+    let args: Vec<_> = args.iter().map(String::as_str).collect();
+    let result = match args.as_slice() {
+        ["save", path] => snapshot::save(path).map(|_| false),
+        ["show", path] => snapshot::show(path).map(|_| false),
+        ["diff", before, after] => snapshot::diff(before, after),
+        _ => {
+            usage_error("expected `snapshot save|show <FILE>` or `snapshot diff <BEFORE> <AFTER>`")
+        }
+    };
+    match result {
+        Ok(differ) => process::exit(differ as i32),
+        Err(e) => {
+            eprintln!("clinfo: {}", e);
+            process::exit(2);
+        }
+    }
+}
+
+fn run_bench(args: &[String]) {
+    let json = match args {
+        [] => false,
+        [x] if x == "--json" => true,
+        _ => usage_error("expected `bench [--json]`"),
+    };
+    let benches = match bench::run() {
+        Ok(x) => x,
+        Err(e) => {
+            eprintln!("clinfo: {}", e);
+            process::exit(1);
+        }
+    };
+    if !json {
+        bench::print_table(&benches);
+        return;
+    }
+    match serde_json::to_string_pretty(&benches) {
+        Ok(x) => println!("{}", x),
+        Err(e) => {
+            eprintln!("clinfo: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
 fn main() {
-    println!("Coming Soon!");
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("snapshot") => run_snapshot(&args[1..]),
+        Some("bench") => {
+            run_bench(&args[1..]);
+            return;
+        }
+        Some("icd") if args.len() == 1 => process::exit(!icd::run() as i32),
+        Some("icd") => usage_error("`icd` takes no arguments"),
+        _ => {}
+    }
+    let mut mode = Mode::Human;
+    for arg in &args {
+        let selected = match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            "--json" => Mode::Json,
+            "--raw" => Mode::Raw,
+            _ => usage_error(&format!("unknown argument `{}`", arg)),
+        };
+        if mode != Mode::Human && mode != selected {
+            usage_error("`--json` and `--raw` cannot be combined");
+        }
+        mode = selected;
+    }
+    let result = match mode {
+        Mode::Human => print_all(),
+        _ => print_snapshot(mode),
+    };
+    if let Err(e) = result {
+        eprintln!("clinfo: {}", e);
+        process::exit(1);
+    }
 }
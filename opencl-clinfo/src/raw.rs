@@ -0,0 +1,146 @@
+/*
+ * raw.rs - The `--raw` listing, by the OpenCL names of the parameters.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use opencl::snapshot::SystemSnapshot;
+use opencl_api::api::info::Plain;
+use std::collections::BTreeMap;
+
+/// Width of the key column, enough for the longest device key.
+const KEY_WIDTH: usize = 60;
+
+/// Prints every platform info, then the devices of every platform, each
+/// line prefixed by `[platform/device]` as the classic clinfo does.
+pub fn print(snapshot: &SystemSnapshot) {
+    println!(
+        "{:<width$}{}",
+        "Number of platforms",
+        snapshot.platforms.len(),
+        width = KEY_WIDTH
+    );
+    for platform in &snapshot.platforms {
+        print_info("  ", &platform.info, &platform.errors);
+        println!();
+    }
+    for (i, platform) in snapshot.platforms.iter().enumerate() {
+        if let Some(name) = platform.info.get("CL_PLATFORM_NAME") {
+            println!(
+                "  {:<width$}{}",
+                "CL_PLATFORM_NAME",
+                value(name),
+                width = KEY_WIDTH - 2
+            );
+        }
+        println!(
+            "{:<width$}{}",
+            "Number of devices",
+            platform.devices.len(),
+            width = KEY_WIDTH
+        );
+        for (j, device) in platform.devices.iter().enumerate() {
+            let prefix = format!("  [{}/{}]  ", i, j);
+            print_info(&prefix, &device.info, &device.errors);
+            for (image_type, formats) in &device.image_formats {
+                for format in formats {
+                    let format = format!("{} {}", format.channel_order, format.channel_type);
+                    println!(
+                        "{}{:<width$}{}",
+                        prefix,
+                        image_type,
+                        format,
+                        width = width(&prefix)
+                    );
+                }
+            }
+        }
+        println!();
+    }
+}
+
+/// Prints the values and the failures together, sorted by key.
+fn print_info(prefix: &str, info: &BTreeMap<String, Plain>, errors: &BTreeMap<String, String>) {
+    let mut lines: Vec<_> = info
+        .iter()
+        .map(|(key, x)| (key, value(x)))
+        .chain(
+            errors
+                .iter()
+                .map(|(key, e)| (key, format!("<error: {}>", e))),
+        )
+        .collect();
+    lines.sort();
+    for (key, value) in lines {
+        let line = format!("{}{:<width$}{}", prefix, key, value, width = width(prefix));
+        println!("{}", line.trim_end());
+    }
+}
+
+fn width(prefix: &str) -> usize {
+    KEY_WIDTH.saturating_sub(prefix.len())
+}
+
+/// Renders a plain value on a single line: flags joined by `|`, versioned
+/// names by commas and numbers by spaces.
+pub fn value(value: &Plain) -> String {
+    match value {
+        Plain::Bool(true) => "CL_TRUE".to_owned(),
+        Plain::Bool(false) => "CL_FALSE".to_owned(),
+        Plain::Integer(x) => x.to_string(),
+        Plain::Text(x) => x.trim().to_owned(),
+        Plain::NameVersion { name, version } => format!("{} ({})", name, version),
+        Plain::List(x) => {
+            let separator = if x.iter().all(|x| matches!(x, Plain::Text(_))) {
+                " | "
+            } else if x.iter().any(|x| matches!(x, Plain::NameVersion { .. })) {
+                ", "
+            } else {
+                " "
+            };
+            let items: Vec<_> = x.iter().map(self::value).collect();
+            items.join(separator)
+        }
+    }
+}
+
+/************************/
+/* /\ /\ /\ /\ /\ /\ /\ */
+/*|__|__|__|__|__|__|__|*/
+/*|  |  |  |  |  |  |  |*/
+/*|  |  Unit Tests  |  |*/
+/*|__|__|__|__|__|__|__|*/
+/*|__|__|__|__|__|__|__|*/
+/************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_values() {
+        assert_eq!(value(&Plain::Bool(true)), "CL_TRUE");
+        let text = |x: &str| Plain::Text(x.to_owned());
+        let flags = Plain::List(vec![text("CL_FP_DENORM"), text("CL_FP_INF_NAN")]);
+        assert_eq!(value(&flags), "CL_FP_DENORM | CL_FP_INF_NAN");
+        let sizes = Plain::List(vec![Plain::Integer(1024), Plain::Integer(64)]);
+        assert_eq!(value(&sizes), "1024 64");
+        let extension = Plain::NameVersion {
+            name: "cl_khr_icd".to_owned(),
+            version: "1.0.0".to_owned(),
+        };
+        assert_eq!(value(&Plain::List(vec![extension])), "cl_khr_icd (1.0.0)");
+    }
+}
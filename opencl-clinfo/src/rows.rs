@@ -0,0 +1,240 @@
+/*
+ * rows.rs - The info parameters listed by clinfo, in display order.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::format::*;
+use opencl::{Context, Device};
+use opencl_api::api::device::DeviceDetails;
+use opencl_api::api::platform::PlatformDetails;
+use opencl_api::objects::bitfields::MemFlags;
+use opencl_api::objects::structs::{ChannelOrder, ChannelType, MemObjectType};
+use opencl_api::objects::traits::GetSetGo;
+use opencl_heads::types::*;
+
+/// A decoded info parameter, or the reason its query failed.
+pub struct Row {
+    /// Label of the parameter for humans.
+    pub label: &'static str,
+    pub value: Result<String, String>,
+}
+
+macro_rules! gen_rows {
+    (
+        $details:ident {
+            $($label:expr => $field:ident => $format:ident,)*
+        }
+    ) => {
+        vec![
+            $(Row {
+                label: $label,
+                value: match &$details.$field {
+                    Ok(x) => Ok($format(x)),
+                    Err(e) => Err(error(e)),
+                },
+            },)*
+        ]
+    };
+}
+
+pub fn platform_rows(details: &PlatformDetails) -> Vec<Row> {
+    gen_rows! {
+        details {
+            "Platform Name" => name => text,
+            "Platform Vendor" => vendor => text,
+            "Platform Version" => version => text,
+            "Platform Numeric Version" => numeric_version => version,
+            "Platform Profile" => profile => text,
+            "Platform Extensions" => extensions => text,
+            "Platform Extensions with Version" => extensions_with_version => name_versions,
+            "Platform Host Timer Resolution" => host_timer_resolution => nanoseconds,
+        }
+    }
+}
+
+pub fn device_rows(details: &DeviceDetails) -> Vec<Row> {
+    gen_rows! {
+        details {
+            "Device Name" => name => text,
+            "Device Vendor" => vendor => text,
+            "Device Vendor ID" => vendor_id => number,
+            "Device Version" => version => text,
+            "Device Numeric Version" => numeric_version => version,
+            "Driver Version" => driver_version => text,
+            "Device OpenCL C Version" => opencl_c_version => text,
+            "Device OpenCL C all versions" => opencl_c_all_versions => name_versions,
+            "Device OpenCL C features" => opencl_c_features => name_versions,
+            "Latest conformance test passed" => latest_conformance_version_passed => text,
+            "Device Type" => device_type => flags,
+            "Device Profile" => profile => text,
+            "Device Available" => available => boolean,
+            "Compiler Available" => compiler_available => boolean,
+            "Linker Available" => linker_available => boolean,
+            "Platform" => platform => handle,
+            "Parent Device" => parent_device => optional_handle,
+            "Reference Count" => reference_count => number,
+            "Max compute units" => max_compute_units => number,
+            "Max clock frequency" => max_clock_frequency => megahertz,
+            "Device Partition (max sub-devices)" => partition_max_sub_devices => number,
+            "Supported partition types" => partition_properties => partition_schemes,
+            "Supported affinity domains" => partition_affinity_domain => optional_flags,
+            "Partition type" => partition_type => partition_type,
+            "Max work item dimensions" => max_work_item_dimensions => number,
+            "Max work item sizes" => max_work_item_sizes => dimensions,
+            "Max work group size" => max_work_group_size => number,
+            "Preferred work group size multiple" => preferred_work_group_size_multiple => number,
+            "Max sub-groups per work group" => max_num_sub_groups => number,
+            "Sub-group independent forward progress" =>
+                sub_group_independent_forward_progress => boolean,
+            "Non-uniform work-groups" => non_uniform_work_group_support => boolean,
+            "Work-group collective functions" => work_group_collective_functions_support => boolean,
+            "Preferred vector width char" => preferred_vector_width_char => number,
+            "Preferred vector width short" => preferred_vector_width_short => number,
+            "Preferred vector width int" => preferred_vector_width_int => number,
+            "Preferred vector width long" => preferred_vector_width_long => number,
+            "Preferred vector width half" => preferred_vector_width_half => number,
+            "Preferred vector width float" => preferred_vector_width_float => number,
+            "Preferred vector width double" => preferred_vector_width_double => number,
+            "Native vector width char" => native_vector_width_char => number,
+            "Native vector width short" => native_vector_width_short => number,
+            "Native vector width int" => native_vector_width_int => number,
+            "Native vector width long" => native_vector_width_long => number,
+            "Native vector width half" => native_vector_width_half => number,
+            "Native vector width float" => native_vector_width_float => number,
+            "Native vector width double" => native_vector_width_double => number,
+            "Single-precision Floating-point support" => single_fp_config => flags,
+            "Double-precision Floating-point support" => double_fp_config => optional_flags,
+            "Address bits" => address_bits => number,
+            "Little-Endian" => endian_little => boolean,
+            "Global memory size" => global_mem_size => bytes,
+            "Error Correction support" => error_correction_support => boolean,
+            "Max memory allocation" => max_mem_alloc_size => bytes,
+            "Unified memory for Host and Device" => host_unified_memory => boolean,
+            "Shared Virtual Memory (SVM) capabilities" => svm_capabilities => optional_flags,
+            "Minimum alignment for any data type" => min_data_type_align_size => number,
+            "Alignment of base address (bits)" => mem_base_addr_align => number,
+            "Preferred alignment for atomics (platform)" =>
+                preferred_platform_atomic_alignment => number,
+            "Preferred alignment for atomics (global)" =>
+                preferred_global_atomic_alignment => number,
+            "Preferred alignment for atomics (local)" => preferred_local_atomic_alignment => number,
+            "Atomic memory capabilities" => atomic_memory_capabilities => optional_flags,
+            "Atomic fence capabilities" => atomic_fence_capabilities => optional_flags,
+            "Max size for global variable" => max_global_variable_size => size_bytes,
+            "Preferred total size of global vars" =>
+                global_variable_preferred_total_size => size_bytes,
+            "Global Memory cache type" => global_mem_cache_type => cache_type,
+            "Global Memory cache size" => global_mem_cache_size => bytes,
+            "Global Memory cache line size" => global_mem_cacheline_size => number,
+            "Image support" => image_support => boolean,
+            "Max number of samplers per kernel" => max_samplers => number,
+            "Max size for 1D images from buffer" => image_max_buffer_size => pixels,
+            "Max 1D or 2D image array size" => image_max_array_size => number,
+            "Base address alignment for 2D image buffers" => image_base_address_alignment => number,
+            "Pitch alignment for 2D image buffers" => image_pitch_alignment => number,
+            "Max 2D image width" => image2d_max_width => pixels,
+            "Max 2D image height" => image2d_max_height => pixels,
+            "Max 3D image width" => image3d_max_width => pixels,
+            "Max 3D image height" => image3d_max_height => pixels,
+            "Max 3D image depth" => image3d_max_depth => pixels,
+            "Max number of read image args" => max_read_image_args => number,
+            "Max number of write image args" => max_write_image_args => number,
+            "Max number of read/write image args" => max_read_write_image_args => number,
+            "Pipe support" => pipe_support => boolean,
+            "Max number of pipe args" => max_pipe_args => number,
+            "Max active pipe reservations" => pipe_max_active_reservations => number,
+            "Max pipe packet size" => pipe_max_packet_size => number,
+            "Local memory type" => local_mem_type => local_mem_type,
+            "Local memory size" => local_mem_size => bytes,
+            "Max number of constant args" => max_constant_args => number,
+            "Max constant buffer size" => max_constant_buffer_size => bytes,
+            "Generic address space support" => generic_address_space_support => boolean,
+            "Max size of kernel argument" => max_parameter_size => size_bytes,
+            "Queue properties (on host)" => queue_on_host_properties => flags,
+            "Queue properties (on device)" => queue_on_device_properties => optional_flags,
+            "Preferred size of on-device queues" => queue_on_device_preferred_size => number,
+            "Max size of on-device queues" => queue_on_device_max_size => number,
+            "Max on-device queues" => max_on_device_queues => number,
+            "Max on-device events" => max_on_device_events => number,
+            "Device enqueue capabilities" => device_enqueue_capabilities => optional_flags,
+            "Prefer user sync for interop" => preferred_interop_user_sync => boolean,
+            "Profiling timer resolution" => profiling_timer_resolution => nanoseconds,
+            "Execution capabilities" => execution_capabilities => flags,
+            "printf() buffer size" => printf_buffer_size => size_bytes,
+            "Built-in kernels" => built_in_kernels => text,
+            "Built-in kernels with version" => built_in_kernels_with_version => name_versions,
+            "IL version" => il_version => text,
+            "ILs with version" => ils_with_version => name_versions,
+            "Device Extensions" => extensions => text,
+            "Device Extensions with Version" => extensions_with_version => name_versions,
+        }
+    }
+}
+
+/// Lists the image formats supported for every image type, in a context of
+/// device alone.
+pub fn image_format_rows(device: &Device) -> Vec<Row> {
+    const IMAGE_TYPES: [(&str, cl_mem_object_type); 6] = [
+        ("Supported 1D image formats", MemObjectType::IMAGE1D),
+        (
+            "Supported 1D buffer image formats",
+            MemObjectType::IMAGE1D_BUFFER,
+        ),
+        (
+            "Supported 1D image array formats",
+            MemObjectType::IMAGE1D_ARRAY,
+        ),
+        ("Supported 2D image formats", MemObjectType::IMAGE2D),
+        (
+            "Supported 2D image array formats",
+            MemObjectType::IMAGE2D_ARRAY,
+        ),
+        ("Supported 3D image formats", MemObjectType::IMAGE3D),
+    ];
+    let context = Context::new(&[device.clone()]);
+    let flags = MemFlags::new(MemFlags::READ_WRITE).unwrap();
+    IMAGE_TYPES
+        .iter()
+        .map(|&(label, image_type)| Row {
+            label,
+            value: match &context {
+                Ok(x) => match x.supported_image_formats(flags.clone(), image_type) {
+                    Ok(formats) => Ok(image_formats(&formats)),
+                    Err(opencl::Error::Api(e)) => Err(error(&e)),
+                    Err(e) => Err(e.to_string()),
+                },
+                Err(opencl::Error::Api(e)) => Err(error(e)),
+                Err(e) => Err(e.to_string()),
+            },
+        })
+        .collect()
+}
+
+/// Renders the count of formats followed by one format per line.
+fn image_formats(formats: &[cl_image_format]) -> String {
+    let mut lines = vec![formats.len().to_string()];
+    for format in formats {
+        let order = format.image_channel_order;
+        let data_type = format.image_channel_data_type;
+        lines.push(format!(
+            "{:<24}{}",
+            ChannelOrder::name_of(order).map_or_else(|| format!("{:#x}", order), str::to_owned),
+            ChannelType::name_of(data_type)
+                .map_or_else(|| format!("{:#x}", data_type), str::to_owned),
+        ));
+    }
+    lines.join("\n")
+}
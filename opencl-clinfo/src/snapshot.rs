@@ -0,0 +1,97 @@
+/*
+ * snapshot.rs - Saving, showing and comparing snapshots offline.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::raw;
+use opencl::snapshot::{Change, Difference, SCHEMA_VERSION};
+use opencl::SystemSnapshot;
+use opencl_api::api::info::Plain;
+use std::fs;
+
+/// Width of the key column of the differences.
+const KEY_WIDTH: usize = 48;
+
+/// Captures the snapshot of this machine into path, as JSON.
+pub fn save(path: &str) -> Result<(), String> {
+    let snapshot = SystemSnapshot::capture().map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(&snapshot).map_err(|e| e.to_string())?;
+    fs::write(path, json + "\n").map_err(|e| format!("cannot write `{}`: {}", path, e))
+}
+
+/// Prints the snapshot saved at path, in the `--raw` layout.
+pub fn show(path: &str) -> Result<(), String> {
+    raw::print(&load(path)?);
+    Ok(())
+}
+
+/// Prints the differences from the snapshot at before to the one at
+/// after, returns whether there are some.
+pub fn diff(before: &str, after: &str) -> Result<bool, String> {
+    let differences = load(before)?.diff(&load(after)?);
+    print_differences(&differences);
+    Ok(!differences.is_empty())
+}
+
+fn load(path: &str) -> Result<SystemSnapshot, String> {
+    let json = fs::read_to_string(path).map_err(|e| format!("cannot read `{}`: {}", path, e))?;
+    let snapshot: SystemSnapshot =
+        serde_json::from_str(&json).map_err(|e| format!("invalid snapshot `{}`: {}", path, e))?;
+    if snapshot.schema != SCHEMA_VERSION {
+        return Err(format!(
+            "snapshot `{}` has schema {}, expected {}",
+            path, snapshot.schema, SCHEMA_VERSION
+        ));
+    }
+    Ok(snapshot)
+}
+
+/// Prints the differences grouped by platform and device: the changed
+/// values as `before -> after`, the items added with `+` and those removed
+/// with `-`.
+fn print_differences(differences: &[Difference]) {
+    let mut location = None;
+    for difference in differences {
+        if location != Some(difference.location) {
+            if location.is_some() {
+                println!();
+            }
+            println!("{}", difference.location);
+            location = Some(difference.location);
+        }
+        let lines = match &difference.change {
+            Change::Value { before, after } => {
+                vec![format!("{} -> {}", value(before), value(after))]
+            }
+            Change::Items { added, removed } => added
+                .iter()
+                .map(|x| format!("+ {}", x))
+                .chain(removed.iter().map(|x| format!("- {}", x)))
+                .collect(),
+        };
+        for (i, line) in lines.iter().enumerate() {
+            let key = if i == 0 { difference.key.as_str() } else { "" };
+            println!("  {:<width$}{}", key, line, width = KEY_WIDTH - 2);
+        }
+    }
+}
+
+fn value(value: &Option<Plain>) -> String {
+    match value {
+        Some(x) => raw::value(x),
+        None => "(none)".to_owned(),
+    }
+}
@@ -21,6 +21,9 @@ pub use super::types::*;
 // Constants associated with functions
 pub use super::consts::*;
 
+// Function pointer types
+pub use super::cl::pfn;
+
 // Functions
 pub use super::cl::{
     clBuildProgram, clCreateBuffer, clCreateContext, clCreateContextFromType, clCreateKernel,
@@ -31,7 +34,7 @@ pub use super::cl::{
     clEnqueueWriteBuffer, clEnqueueWriteImage, clFinish, clFlush, clGetCommandQueueInfo,
     clGetContextInfo, clGetDeviceIDs, clGetDeviceInfo, clGetEventInfo, clGetEventProfilingInfo,
     clGetImageInfo, clGetKernelInfo, clGetKernelWorkGroupInfo, clGetMemObjectInfo,
-    clGetPlatformIDs, clGetPlatformInfo, clGetProgramBuildInfo, clGetSamplerInfo,
+    clGetPlatformIDs, clGetPlatformInfo, clGetProgramBuildInfo, clGetProgramInfo, clGetSamplerInfo,
     clGetSupportedImageFormats, clReleaseCommandQueue, clReleaseContext, clReleaseEvent,
     clReleaseKernel, clReleaseMemObject, clReleaseProgram, clReleaseSampler, clRetainCommandQueue,
     clRetainContext, clRetainEvent, clRetainKernel, clRetainMemObject, clRetainProgram,
@@ -20,3 +20,6 @@ mod cl;
 pub mod consts;
 pub mod ffi;
 pub mod types;
+
+#[cfg(feature = "dynamic")]
+pub(crate) use self::cl::symbol_available;
@@ -127,7 +127,7 @@ pub type cl_mem_properties = cl_properties;
 pub type cl_version = cl_uint;
 // #endif
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(C)]
 pub struct cl_image_format {
     pub image_channel_order: cl_channel_order,
@@ -142,7 +142,7 @@ impl Default for cl_image_format {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(C)]
 pub struct cl_image_desc {
     pub image_type: cl_mem_object_type,
@@ -176,7 +176,7 @@ impl Default for cl_image_desc {
 }
 
 // #ifdef CL_VERSION_1_1
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(C)]
 pub struct cl_buffer_region {
     pub origin: size_t,
@@ -191,7 +191,7 @@ impl Default for cl_buffer_region {
 
 // #ifdef CL_VERSION_3_0
 pub const CL_NAME_VERSION_MAX_NAME_SIZE: usize = 64;
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(C)]
 pub struct cl_name_version {
     pub version: cl_version,
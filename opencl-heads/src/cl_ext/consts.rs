@@ -15,3 +15,9 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
 */
+
+use crate::cl::types::*;
+
+// cl_khr_icd
+pub const CL_PLATFORM_ICD_SUFFIX_KHR: cl_platform_info = 0x0920;
+pub const CL_PLATFORM_NOT_FOUND_KHR: cl_int = -1001;
@@ -0,0 +1,226 @@
+/*
+ * dispatch.rs - ICD dispatch table.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+//!
+//! The dispatch table shared between the ICD loader and vendor
+//! implementations (see `driver`), as laid out by `cl_icd.h`. Every object handed out by a
+//! vendor starts with a pointer to its table.
+//!
+//! Entries for extensions that are not part of the core headers (GL, D3D,
+//! DX9 media and EGL sharing) are kept as untyped pointers to preserve the
+//! layout.
+//!
+#![allow(non_camel_case_types, non_snake_case)]
+use crate::ffi::pfn;
+use libc::c_void;
+
+#[repr(C)]
+pub struct cl_icd_dispatch {
+    // OpenCL 1.0
+    pub clGetPlatformIDs: Option<pfn::clGetPlatformIDs>,
+    pub clGetPlatformInfo: Option<pfn::clGetPlatformInfo>,
+    pub clGetDeviceIDs: Option<pfn::clGetDeviceIDs>,
+    pub clGetDeviceInfo: Option<pfn::clGetDeviceInfo>,
+    pub clCreateContext: Option<pfn::clCreateContext>,
+    pub clCreateContextFromType: Option<pfn::clCreateContextFromType>,
+    pub clRetainContext: Option<pfn::clRetainContext>,
+    pub clReleaseContext: Option<pfn::clReleaseContext>,
+    pub clGetContextInfo: Option<pfn::clGetContextInfo>,
+    pub clCreateCommandQueue: Option<pfn::clCreateCommandQueue>,
+    pub clRetainCommandQueue: Option<pfn::clRetainCommandQueue>,
+    pub clReleaseCommandQueue: Option<pfn::clReleaseCommandQueue>,
+    pub clGetCommandQueueInfo: Option<pfn::clGetCommandQueueInfo>,
+    pub clSetCommandQueueProperty: Option<pfn::clSetCommandQueueProperty>,
+    pub clCreateBuffer: Option<pfn::clCreateBuffer>,
+    pub clCreateImage2D: Option<pfn::clCreateImage2D>,
+    pub clCreateImage3D: Option<pfn::clCreateImage3D>,
+    pub clRetainMemObject: Option<pfn::clRetainMemObject>,
+    pub clReleaseMemObject: Option<pfn::clReleaseMemObject>,
+    pub clGetSupportedImageFormats: Option<pfn::clGetSupportedImageFormats>,
+    pub clGetMemObjectInfo: Option<pfn::clGetMemObjectInfo>,
+    pub clGetImageInfo: Option<pfn::clGetImageInfo>,
+    pub clCreateSampler: Option<pfn::clCreateSampler>,
+    pub clRetainSampler: Option<pfn::clRetainSampler>,
+    pub clReleaseSampler: Option<pfn::clReleaseSampler>,
+    pub clGetSamplerInfo: Option<pfn::clGetSamplerInfo>,
+    pub clCreateProgramWithSource: Option<pfn::clCreateProgramWithSource>,
+    pub clCreateProgramWithBinary: Option<pfn::clCreateProgramWithBinary>,
+    pub clRetainProgram: Option<pfn::clRetainProgram>,
+    pub clReleaseProgram: Option<pfn::clReleaseProgram>,
+    pub clBuildProgram: Option<pfn::clBuildProgram>,
+    pub clUnloadCompiler: Option<pfn::clUnloadCompiler>,
+    pub clGetProgramInfo: Option<pfn::clGetProgramInfo>,
+    pub clGetProgramBuildInfo: Option<pfn::clGetProgramBuildInfo>,
+    pub clCreateKernel: Option<pfn::clCreateKernel>,
+    pub clCreateKernelsInProgram: Option<pfn::clCreateKernelsInProgram>,
+    pub clRetainKernel: Option<pfn::clRetainKernel>,
+    pub clReleaseKernel: Option<pfn::clReleaseKernel>,
+    pub clSetKernelArg: Option<pfn::clSetKernelArg>,
+    pub clGetKernelInfo: Option<pfn::clGetKernelInfo>,
+    pub clGetKernelWorkGroupInfo: Option<pfn::clGetKernelWorkGroupInfo>,
+    pub clWaitForEvents: Option<pfn::clWaitForEvents>,
+    pub clGetEventInfo: Option<pfn::clGetEventInfo>,
+    pub clRetainEvent: Option<pfn::clRetainEvent>,
+    pub clReleaseEvent: Option<pfn::clReleaseEvent>,
+    pub clGetEventProfilingInfo: Option<pfn::clGetEventProfilingInfo>,
+    pub clFlush: Option<pfn::clFlush>,
+    pub clFinish: Option<pfn::clFinish>,
+    pub clEnqueueReadBuffer: Option<pfn::clEnqueueReadBuffer>,
+    pub clEnqueueWriteBuffer: Option<pfn::clEnqueueWriteBuffer>,
+    pub clEnqueueCopyBuffer: Option<pfn::clEnqueueCopyBuffer>,
+    pub clEnqueueReadImage: Option<pfn::clEnqueueReadImage>,
+    pub clEnqueueWriteImage: Option<pfn::clEnqueueWriteImage>,
+    pub clEnqueueCopyImage: Option<pfn::clEnqueueCopyImage>,
+    pub clEnqueueCopyImageToBuffer: Option<pfn::clEnqueueCopyImageToBuffer>,
+    pub clEnqueueCopyBufferToImage: Option<pfn::clEnqueueCopyBufferToImage>,
+    pub clEnqueueMapBuffer: Option<pfn::clEnqueueMapBuffer>,
+    pub clEnqueueMapImage: Option<pfn::clEnqueueMapImage>,
+    pub clEnqueueUnmapMemObject: Option<pfn::clEnqueueUnmapMemObject>,
+    pub clEnqueueNDRangeKernel: Option<pfn::clEnqueueNDRangeKernel>,
+    pub clEnqueueTask: Option<pfn::clEnqueueTask>,
+    pub clEnqueueNativeKernel: Option<pfn::clEnqueueNativeKernel>,
+    pub clEnqueueMarker: Option<pfn::clEnqueueMarker>,
+    pub clEnqueueWaitForEvents: Option<pfn::clEnqueueWaitForEvents>,
+    pub clEnqueueBarrier: Option<pfn::clEnqueueBarrier>,
+    pub clGetExtensionFunctionAddress: Option<pfn::clGetExtensionFunctionAddress>,
+    // cl_khr_gl_sharing
+    pub clCreateFromGLBuffer: *mut c_void,
+    pub clCreateFromGLTexture2D: *mut c_void,
+    pub clCreateFromGLTexture3D: *mut c_void,
+    pub clCreateFromGLRenderbuffer: *mut c_void,
+    pub clGetGLObjectInfo: *mut c_void,
+    pub clGetGLTextureInfo: *mut c_void,
+    pub clEnqueueAcquireGLObjects: *mut c_void,
+    pub clEnqueueReleaseGLObjects: *mut c_void,
+    pub clGetGLContextInfoKHR: *mut c_void,
+    // cl_khr_d3d10_sharing
+    pub clGetDeviceIDsFromD3D10KHR: *mut c_void,
+    pub clCreateFromD3D10BufferKHR: *mut c_void,
+    pub clCreateFromD3D10Texture2DKHR: *mut c_void,
+    pub clCreateFromD3D10Texture3DKHR: *mut c_void,
+    pub clEnqueueAcquireD3D10ObjectsKHR: *mut c_void,
+    pub clEnqueueReleaseD3D10ObjectsKHR: *mut c_void,
+    // OpenCL 1.1
+    pub clSetEventCallback: Option<pfn::clSetEventCallback>,
+    pub clCreateSubBuffer: Option<pfn::clCreateSubBuffer>,
+    pub clSetMemObjectDestructorCallback: Option<pfn::clSetMemObjectDestructorCallback>,
+    pub clCreateUserEvent: Option<pfn::clCreateUserEvent>,
+    pub clSetUserEventStatus: Option<pfn::clSetUserEventStatus>,
+    pub clEnqueueReadBufferRect: Option<pfn::clEnqueueReadBufferRect>,
+    pub clEnqueueWriteBufferRect: Option<pfn::clEnqueueWriteBufferRect>,
+    pub clEnqueueCopyBufferRect: Option<pfn::clEnqueueCopyBufferRect>,
+    // cl_ext_device_fission
+    pub clCreateSubDevicesEXT: *mut c_void,
+    pub clRetainDeviceEXT: *mut c_void,
+    pub clReleaseDeviceEXT: *mut c_void,
+    // cl_khr_gl_event
+    pub clCreateEventFromGLsyncKHR: *mut c_void,
+    // OpenCL 1.2
+    pub clCreateSubDevices: Option<pfn::clCreateSubDevices>,
+    pub clRetainDevice: Option<pfn::clRetainDevice>,
+    pub clReleaseDevice: Option<pfn::clReleaseDevice>,
+    pub clCreateImage: Option<pfn::clCreateImage>,
+    pub clCreateProgramWithBuiltInKernels: Option<pfn::clCreateProgramWithBuiltInKernels>,
+    pub clCompileProgram: Option<pfn::clCompileProgram>,
+    pub clLinkProgram: Option<pfn::clLinkProgram>,
+    pub clUnloadPlatformCompiler: Option<pfn::clUnloadPlatformCompiler>,
+    pub clGetKernelArgInfo: Option<pfn::clGetKernelArgInfo>,
+    pub clEnqueueFillBuffer: Option<pfn::clEnqueueFillBuffer>,
+    pub clEnqueueFillImage: Option<pfn::clEnqueueFillImage>,
+    pub clEnqueueMigrateMemObjects: Option<pfn::clEnqueueMigrateMemObjects>,
+    pub clEnqueueMarkerWithWaitList: Option<pfn::clEnqueueMarkerWithWaitList>,
+    pub clEnqueueBarrierWithWaitList: Option<pfn::clEnqueueBarrierWithWaitList>,
+    pub clGetExtensionFunctionAddressForPlatform:
+        Option<pfn::clGetExtensionFunctionAddressForPlatform>,
+    pub clCreateFromGLTexture: *mut c_void,
+    // cl_khr_d3d11_sharing
+    pub clGetDeviceIDsFromD3D11KHR: *mut c_void,
+    pub clCreateFromD3D11BufferKHR: *mut c_void,
+    pub clCreateFromD3D11Texture2DKHR: *mut c_void,
+    pub clCreateFromD3D11Texture3DKHR: *mut c_void,
+    pub clCreateFromDX9MediaSurfaceKHR: *mut c_void,
+    pub clEnqueueAcquireD3D11ObjectsKHR: *mut c_void,
+    pub clEnqueueReleaseD3D11ObjectsKHR: *mut c_void,
+    // cl_khr_dx9_media_sharing
+    pub clGetDeviceIDsFromDX9MediaAdapterKHR: *mut c_void,
+    pub clEnqueueAcquireDX9MediaSurfacesKHR: *mut c_void,
+    pub clEnqueueReleaseDX9MediaSurfacesKHR: *mut c_void,
+    // cl_khr_egl_image
+    pub clCreateFromEGLImageKHR: *mut c_void,
+    pub clEnqueueAcquireEGLObjectsKHR: *mut c_void,
+    pub clEnqueueReleaseEGLObjectsKHR: *mut c_void,
+    // cl_khr_egl_event
+    pub clCreateEventFromEGLSyncKHR: *mut c_void,
+    // OpenCL 2.0
+    pub clCreateCommandQueueWithProperties: Option<pfn::clCreateCommandQueueWithProperties>,
+    pub clCreatePipe: Option<pfn::clCreatePipe>,
+    pub clGetPipeInfo: Option<pfn::clGetPipeInfo>,
+    pub clSVMAlloc: Option<pfn::clSVMAlloc>,
+    pub clSVMFree: Option<pfn::clSVMFree>,
+    pub clEnqueueSVMFree: Option<pfn::clEnqueueSVMFree>,
+    pub clEnqueueSVMMemcpy: Option<pfn::clEnqueueSVMMemcpy>,
+    pub clEnqueueSVMMemFill: Option<pfn::clEnqueueSVMMemFill>,
+    pub clEnqueueSVMMap: Option<pfn::clEnqueueSVMMap>,
+    pub clEnqueueSVMUnmap: Option<pfn::clEnqueueSVMUnmap>,
+    pub clCreateSamplerWithProperties: Option<pfn::clCreateSamplerWithProperties>,
+    pub clSetKernelArgSVMPointer: Option<pfn::clSetKernelArgSVMPointer>,
+    pub clSetKernelExecInfo: Option<pfn::clSetKernelExecInfo>,
+    // cl_khr_sub_groups
+    pub clGetKernelSubGroupInfoKHR: *mut c_void,
+    // OpenCL 2.1
+    pub clCloneKernel: Option<pfn::clCloneKernel>,
+    pub clCreateProgramWithIL: Option<pfn::clCreateProgramWithIL>,
+    pub clEnqueueSVMMigrateMem: Option<pfn::clEnqueueSVMMigrateMem>,
+    pub clGetDeviceAndHostTimer: Option<pfn::clGetDeviceAndHostTimer>,
+    pub clGetHostTimer: Option<pfn::clGetHostTimer>,
+    pub clGetKernelSubGroupInfo: Option<pfn::clGetKernelSubGroupInfo>,
+    pub clSetDefaultDeviceCommandQueue: Option<pfn::clSetDefaultDeviceCommandQueue>,
+    // OpenCL 2.2
+    pub clSetProgramReleaseCallback: Option<pfn::clSetProgramReleaseCallback>,
+    pub clSetProgramSpecializationConstant: Option<pfn::clSetProgramSpecializationConstant>,
+    // OpenCL 3.0
+    pub clCreateBufferWithProperties: Option<pfn::clCreateBufferWithProperties>,
+    pub clCreateImageWithProperties: Option<pfn::clCreateImageWithProperties>,
+    pub clSetContextDestructorCallback: Option<pfn::clSetContextDestructorCallback>,
+}
+
+// The entries are function addresses, shared by every object of a vendor.
+unsafe impl Send for cl_icd_dispatch {}
+unsafe impl Sync for cl_icd_dispatch {}
+
+/// An empty table, every entry unset.
+impl Default for cl_icd_dispatch {
+    fn default() -> Self {
+        // All entries are optional function pointers or raw pointers, for
+        // which all zero bits is None or null.
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+/// Reads the dispatch table of an ICD object, None for a null object or a
+/// null table.
+///
+/// # Safety
+///
+/// object must be null or point to an object whose first field is a
+/// dispatch table pointer, and the table must outlive the returned reference.
+pub unsafe fn dispatch_table<'a>(object: *mut c_void) -> Option<&'a cl_icd_dispatch> {
+    if object.is_null() {
+        return None;
+    }
+    (*(object as *const *const cl_icd_dispatch)).as_ref()
+}
@@ -0,0 +1,530 @@
+/*
+ * entry.rs - Entry points forwarding to a Driver.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+//!
+//! The `extern "system"` functions stored in a driver dispatch table. They
+//! check handles and arguments, then call into the `Driver` implementation.
+//!
+use super::{Driver, Handle, ObjectKind, Result};
+use crate::consts::*;
+use crate::dispatch::cl_icd_dispatch;
+use crate::types::*;
+use libc::c_void;
+use std::ffi::CStr;
+use std::ptr;
+use std::slice;
+
+type ContextNotify = Option<extern "C" fn(*const c_char, *const c_void, size_t, *mut c_void)>;
+
+/// Builds the dispatch table of a driver, with an entry for every function
+/// the framework implements.
+pub fn dispatch_table<D: Driver>() -> cl_icd_dispatch {
+    let mut table = cl_icd_dispatch {
+        clGetPlatformIDs: Some(get_platform_ids::<D>),
+        clGetPlatformInfo: Some(get_platform_info::<D>),
+        clGetDeviceIDs: Some(get_device_ids::<D>),
+        clGetDeviceInfo: Some(get_device_info::<D>),
+        clRetainDevice: Some(retain_device::<D>),
+        clReleaseDevice: Some(release_device::<D>),
+        clCreateContext: Some(create_context::<D>),
+        clCreateContextFromType: Some(create_context_from_type::<D>),
+        clRetainContext: Some(retain::<D::Context, CONTEXT>),
+        clReleaseContext: Some(release::<D::Context, CONTEXT>),
+        clGetContextInfo: Some(get_context_info::<D>),
+        clCreateCommandQueue: Some(create_command_queue::<D>),
+        clCreateCommandQueueWithProperties: Some(create_command_queue_with_properties::<D>),
+        clRetainCommandQueue: Some(retain::<D::Queue, COMMAND_QUEUE>),
+        clReleaseCommandQueue: Some(release::<D::Queue, COMMAND_QUEUE>),
+        clGetCommandQueueInfo: Some(get_command_queue_info::<D>),
+        clFlush: Some(flush::<D>),
+        clFinish: Some(finish::<D>),
+        clCreateBuffer: Some(create_buffer::<D>),
+        clRetainMemObject: Some(retain::<D::Mem, MEM>),
+        clReleaseMemObject: Some(release::<D::Mem, MEM>),
+        clGetMemObjectInfo: Some(get_mem_object_info::<D>),
+        clEnqueueReadBuffer: Some(enqueue_read_buffer::<D>),
+        clEnqueueWriteBuffer: Some(enqueue_write_buffer::<D>),
+        clGetExtensionFunctionAddress: Some(get_extension_function_address::<D>),
+        clGetExtensionFunctionAddressForPlatform: Some(
+            get_extension_function_address_for_platform::<D>,
+        ),
+        ..Default::default()
+    };
+    D::extend_dispatch(&mut table);
+    table
+}
+
+// Object kinds as const generic arguments of the shared retain and release.
+const CONTEXT: u32 = ObjectKind::Context as u32;
+const COMMAND_QUEUE: u32 = ObjectKind::CommandQueue as u32;
+const MEM: u32 = ObjectKind::Mem as u32;
+
+pub(crate) fn invalid_handle(kind: ObjectKind) -> cl_int {
+    match kind {
+        ObjectKind::Platform => CL_INVALID_PLATFORM,
+        ObjectKind::Device => CL_INVALID_DEVICE,
+        ObjectKind::Context => CL_INVALID_CONTEXT,
+        ObjectKind::CommandQueue => CL_INVALID_COMMAND_QUEUE,
+        ObjectKind::Mem => CL_INVALID_MEM_OBJECT,
+        ObjectKind::Sampler => CL_INVALID_SAMPLER,
+        ObjectKind::Program => CL_INVALID_PROGRAM,
+        ObjectKind::Kernel => CL_INVALID_KERNEL,
+        ObjectKind::Event => CL_INVALID_EVENT,
+    }
+}
+
+pub(crate) unsafe fn handle<T>(raw: *mut c_void, kind: ObjectKind) -> Result<Handle<T>> {
+    Handle::from_raw(raw, kind).ok_or_else(|| invalid_handle(kind))
+}
+
+pub(crate) fn status(result: Result<()>) -> cl_int {
+    match result {
+        Ok(()) => CL_SUCCESS,
+        Err(x) => x,
+    }
+}
+
+pub(crate) unsafe fn into_raw<T>(
+    result: Result<Handle<T>>,
+    errcode_ret: *mut cl_int,
+) -> *mut c_void {
+    let (raw, status_code) = match result {
+        Ok(x) => (x.as_raw(), CL_SUCCESS),
+        Err(x) => (ptr::null_mut(), x),
+    };
+    if !errcode_ret.is_null() {
+        *errcode_ret = status_code;
+    }
+    raw
+}
+
+/// Copies an info value out following the clGet*Info conventions.
+pub(crate) unsafe fn write_info(
+    info: Result<Vec<u8>>,
+    param_value_size: size_t,
+    param_value: *mut c_void,
+    param_value_size_ret: *mut size_t,
+) -> cl_int {
+    let bytes = match info {
+        Ok(x) => x,
+        Err(x) => return x,
+    };
+    if !param_value.is_null() {
+        if param_value_size < bytes.len() {
+            return CL_INVALID_VALUE;
+        }
+        ptr::copy_nonoverlapping(bytes.as_ptr(), param_value as *mut u8, bytes.len());
+    }
+    if !param_value_size_ret.is_null() {
+        *param_value_size_ret = bytes.len();
+    }
+    CL_SUCCESS
+}
+
+/// Copies out a list of handles following the clGet*IDs conventions.
+pub(crate) unsafe fn write_handles(
+    handles: &[*mut c_void],
+    num_entries: cl_uint,
+    entries: *mut *mut c_void,
+    num_entries_ret: *mut cl_uint,
+) -> cl_int {
+    if (num_entries == 0 && !entries.is_null()) || (entries.is_null() && num_entries_ret.is_null())
+    {
+        return CL_INVALID_VALUE;
+    }
+    if !entries.is_null() {
+        for (i, handle) in handles.iter().take(num_entries as usize).enumerate() {
+            *entries.add(i) = *handle;
+        }
+    }
+    if !num_entries_ret.is_null() {
+        *num_entries_ret = handles.len() as cl_uint;
+    }
+    CL_SUCCESS
+}
+
+/// Key value pairs of a zero terminated property list.
+pub(crate) unsafe fn properties<T: Copy + PartialEq + From<u8>>(list: *const T) -> Vec<T> {
+    let mut pairs = Vec::new();
+    let mut property = list;
+    while !property.is_null() && *property != T::from(0) {
+        pairs.push(*property);
+        pairs.push(*property.add(1));
+        property = property.add(2);
+    }
+    pairs
+}
+
+/// The driver platforms, as `clIcdGetPlatformIDsKHR`.
+///
+/// # Safety
+///
+/// Same pointer requirements as `clGetPlatformIDs`.
+pub unsafe extern "system" fn get_platform_ids<D: Driver>(
+    num_entries: cl_uint,
+    platforms: *mut cl_platform_id,
+    num_platforms: *mut cl_uint,
+) -> cl_int {
+    let handles: Vec<*mut c_void> = D::instance()
+        .platforms()
+        .iter()
+        .map(|x| x.as_raw())
+        .collect();
+    match write_handles(&handles, num_entries, platforms, num_platforms) {
+        CL_SUCCESS if handles.is_empty() => CL_PLATFORM_NOT_FOUND_KHR,
+        x => x,
+    }
+}
+
+unsafe extern "system" fn get_platform_info<D: Driver>(
+    platform: cl_platform_id,
+    param_name: cl_platform_info,
+    param_value_size: size_t,
+    param_value: *mut c_void,
+    param_value_size_ret: *mut size_t,
+) -> cl_int {
+    let info = handle::<D::Platform>(platform, ObjectKind::Platform).and_then(|platform| {
+        let driver = D::instance();
+        match param_name {
+            CL_PLATFORM_ICD_SUFFIX_KHR => Ok(super::info_string(driver.icd_suffix())),
+            _ => driver.platform_info(platform, param_name),
+        }
+    });
+    write_info(info, param_value_size, param_value, param_value_size_ret)
+}
+
+unsafe extern "system" fn get_device_ids<D: Driver>(
+    platform: cl_platform_id,
+    device_type: cl_device_type,
+    num_entries: cl_uint,
+    devices: *mut cl_device_id,
+    num_devices: *mut cl_uint,
+) -> cl_int {
+    let platform = match handle::<D::Platform>(platform, ObjectKind::Platform) {
+        Ok(x) => x,
+        Err(x) => return x,
+    };
+    let handles: Vec<*mut c_void> = D::instance()
+        .devices(platform, device_type)
+        .iter()
+        .map(|x| x.as_raw())
+        .collect();
+    match write_handles(&handles, num_entries, devices, num_devices) {
+        CL_SUCCESS if handles.is_empty() => CL_DEVICE_NOT_FOUND,
+        x => x,
+    }
+}
+
+unsafe extern "system" fn get_device_info<D: Driver>(
+    device: cl_device_id,
+    param_name: cl_device_info,
+    param_value_size: size_t,
+    param_value: *mut c_void,
+    param_value_size_ret: *mut size_t,
+) -> cl_int {
+    let info =
+        handle::<D::Device>(device, ObjectKind::Device).and_then(|device| match param_name {
+            // Devices are never released.
+            CL_DEVICE_REFERENCE_COUNT => Ok(super::info_value::<cl_uint>(1)),
+            _ => D::instance().device_info(device, param_name),
+        });
+    write_info(info, param_value_size, param_value, param_value_size_ret)
+}
+
+unsafe extern "system" fn retain_device<D: Driver>(device: cl_device_id) -> cl_int {
+    status(handle::<D::Device>(device, ObjectKind::Device).map(|_| ()))
+}
+
+// Root devices are not reference counted, releasing only checks the handle.
+unsafe extern "system" fn release_device<D: Driver>(device: cl_device_id) -> cl_int {
+    status(handle::<D::Device>(device, ObjectKind::Device).map(|_| ()))
+}
+
+unsafe extern "system" fn create_context<D: Driver>(
+    properties: *const cl_context_properties,
+    num_devices: cl_uint,
+    devices: *const cl_device_id,
+    _pfn_notify: ContextNotify,
+    _user_data: *mut c_void,
+    errcode_ret: *mut cl_int,
+) -> cl_context {
+    let context = (|| {
+        if devices.is_null() || num_devices == 0 {
+            return Err(CL_INVALID_VALUE);
+        }
+        let devices = slice::from_raw_parts(devices, num_devices as usize)
+            .iter()
+            .map(|x| handle::<D::Device>(*x, ObjectKind::Device))
+            .collect::<Result<Vec<_>>>()?;
+        let properties = self::properties(properties);
+        let context = D::instance().create_context(&properties, &devices)?;
+        Ok(Handle::new(D::dispatch(), ObjectKind::Context, context))
+    })();
+    into_raw(context, errcode_ret)
+}
+
+unsafe extern "system" fn create_context_from_type<D: Driver>(
+    properties: *const cl_context_properties,
+    device_type: cl_device_type,
+    _pfn_notify: ContextNotify,
+    _user_data: *mut c_void,
+    errcode_ret: *mut cl_int,
+) -> cl_context {
+    let context = (|| {
+        let driver = D::instance();
+        let properties = self::properties(properties);
+        let platform = properties
+            .chunks(2)
+            .find(|x| x[0] == CL_CONTEXT_PLATFORM)
+            .map(|x| handle::<D::Platform>(x[1] as *mut c_void, ObjectKind::Platform))
+            .unwrap_or_else(|| {
+                driver
+                    .platforms()
+                    .first()
+                    .copied()
+                    .ok_or(CL_INVALID_PLATFORM)
+            })?;
+        let devices = driver.devices(platform, device_type);
+        if devices.is_empty() {
+            return Err(CL_DEVICE_NOT_FOUND);
+        }
+        let context = driver.create_context(&properties, &devices)?;
+        Ok(Handle::new(D::dispatch(), ObjectKind::Context, context))
+    })();
+    into_raw(context, errcode_ret)
+}
+
+/// `clRetain*` for objects of KIND holding a T, e.g.
+/// `retain::<Event, { ObjectKind::Event as u32 }>`.
+///
+/// # Safety
+///
+/// object must be null or a handle of the driver.
+pub unsafe extern "system" fn retain<T, const KIND: u32>(object: *mut c_void) -> cl_int {
+    match ObjectKind::from_raw(KIND) {
+        Some(kind) => status(handle::<T>(object, kind).map(|x| x.retain())),
+        None => CL_INVALID_VALUE,
+    }
+}
+
+/// `clRelease*` for objects of KIND holding a T, dropping the T with the last
+/// reference.
+///
+/// # Safety
+///
+/// object must be null or a handle of the driver.
+pub unsafe extern "system" fn release<T, const KIND: u32>(object: *mut c_void) -> cl_int {
+    match ObjectKind::from_raw(KIND) {
+        Some(kind) => status(handle::<T>(object, kind).map(|x| {
+            x.release();
+        })),
+        None => CL_INVALID_VALUE,
+    }
+}
+
+unsafe extern "system" fn get_context_info<D: Driver>(
+    context: cl_context,
+    param_name: cl_context_info,
+    param_value_size: size_t,
+    param_value: *mut c_void,
+    param_value_size_ret: *mut size_t,
+) -> cl_int {
+    let info =
+        handle::<D::Context>(context, ObjectKind::Context).and_then(|context| match param_name {
+            CL_CONTEXT_REFERENCE_COUNT => Ok(super::info_value(context.ref_count())),
+            _ => D::instance().context_info(context, param_name),
+        });
+    write_info(info, param_value_size, param_value, param_value_size_ret)
+}
+
+unsafe fn new_queue<D: Driver>(
+    context: cl_context,
+    device: cl_device_id,
+    properties: cl_command_queue_properties,
+) -> Result<Handle<D::Queue>> {
+    let context = handle::<D::Context>(context, ObjectKind::Context)?;
+    let device = handle::<D::Device>(device, ObjectKind::Device)?;
+    let queue = D::instance().create_queue(context, device, properties)?;
+    Ok(Handle::new(D::dispatch(), ObjectKind::CommandQueue, queue))
+}
+
+unsafe extern "system" fn create_command_queue<D: Driver>(
+    context: cl_context,
+    device: cl_device_id,
+    properties: cl_command_queue_properties,
+    errcode_ret: *mut cl_int,
+) -> cl_command_queue {
+    into_raw(new_queue::<D>(context, device, properties), errcode_ret)
+}
+
+unsafe extern "system" fn create_command_queue_with_properties<D: Driver>(
+    context: cl_context,
+    device: cl_device_id,
+    properties: *const cl_queue_properties,
+    errcode_ret: *mut cl_int,
+) -> cl_command_queue {
+    let properties = self::properties(properties)
+        .chunks(2)
+        .find(|x| x[0] == CL_QUEUE_PROPERTIES as cl_queue_properties)
+        .map_or(0, |x| x[1]);
+    into_raw(new_queue::<D>(context, device, properties), errcode_ret)
+}
+
+unsafe extern "system" fn get_command_queue_info<D: Driver>(
+    command_queue: cl_command_queue,
+    param_name: cl_command_queue_info,
+    param_value_size: size_t,
+    param_value: *mut c_void,
+    param_value_size_ret: *mut size_t,
+) -> cl_int {
+    let info = handle::<D::Queue>(command_queue, ObjectKind::CommandQueue).and_then(|queue| {
+        match param_name {
+            CL_QUEUE_REFERENCE_COUNT => Ok(super::info_value(queue.ref_count())),
+            _ => D::instance().queue_info(queue, param_name),
+        }
+    });
+    write_info(info, param_value_size, param_value, param_value_size_ret)
+}
+
+unsafe extern "system" fn flush<D: Driver>(command_queue: cl_command_queue) -> cl_int {
+    status(
+        handle::<D::Queue>(command_queue, ObjectKind::CommandQueue)
+            .and_then(|x| D::instance().flush(x)),
+    )
+}
+
+unsafe extern "system" fn finish<D: Driver>(command_queue: cl_command_queue) -> cl_int {
+    status(
+        handle::<D::Queue>(command_queue, ObjectKind::CommandQueue)
+            .and_then(|x| D::instance().finish(x)),
+    )
+}
+
+unsafe extern "system" fn create_buffer<D: Driver>(
+    context: cl_context,
+    flags: cl_mem_flags,
+    size: size_t,
+    host_ptr: *mut c_void,
+    errcode_ret: *mut cl_int,
+) -> cl_mem {
+    let buffer = (|| {
+        let context = handle::<D::Context>(context, ObjectKind::Context)?;
+        if size == 0 {
+            return Err(CL_INVALID_BUFFER_SIZE);
+        }
+        let buffer = D::instance().create_buffer(context, flags, size, host_ptr)?;
+        Ok(Handle::new(D::dispatch(), ObjectKind::Mem, buffer))
+    })();
+    into_raw(buffer, errcode_ret)
+}
+
+unsafe extern "system" fn get_mem_object_info<D: Driver>(
+    memobj: cl_mem,
+    param_name: cl_mem_info,
+    param_value_size: size_t,
+    param_value: *mut c_void,
+    param_value_size_ret: *mut size_t,
+) -> cl_int {
+    let info = handle::<D::Mem>(memobj, ObjectKind::Mem).and_then(|mem| match param_name {
+        CL_MEM_REFERENCE_COUNT => Ok(super::info_value(mem.ref_count())),
+        _ => D::instance().mem_info(mem, param_name),
+    });
+    write_info(info, param_value_size, param_value, param_value_size_ret)
+}
+
+/// Commands complete before returning, so an event is never created.
+unsafe fn no_event(event: *mut cl_event) {
+    if !event.is_null() {
+        *event = ptr::null_mut();
+    }
+}
+
+unsafe extern "system" fn enqueue_read_buffer<D: Driver>(
+    command_queue: cl_command_queue,
+    buffer: cl_mem,
+    _blocking_read: cl_bool,
+    offset: size_t,
+    size: size_t,
+    ptr: *mut c_void,
+    _num_events_in_wait_list: cl_uint,
+    _event_wait_list: *const cl_event,
+    event: *mut cl_event,
+) -> cl_int {
+    no_event(event);
+    status((|| {
+        let queue = handle::<D::Queue>(command_queue, ObjectKind::CommandQueue)?;
+        let buffer = handle::<D::Mem>(buffer, ObjectKind::Mem)?;
+        if ptr.is_null() {
+            return Err(CL_INVALID_VALUE);
+        }
+        let data = slice::from_raw_parts_mut(ptr as *mut u8, size);
+        D::instance().read_buffer(queue, buffer, offset, data)
+    })())
+}
+
+unsafe extern "system" fn enqueue_write_buffer<D: Driver>(
+    command_queue: cl_command_queue,
+    buffer: cl_mem,
+    _blocking_write: cl_bool,
+    offset: size_t,
+    size: size_t,
+    ptr: *const c_void,
+    _num_events_in_wait_list: cl_uint,
+    _event_wait_list: *const cl_event,
+    event: *mut cl_event,
+) -> cl_int {
+    no_event(event);
+    status((|| {
+        let queue = handle::<D::Queue>(command_queue, ObjectKind::CommandQueue)?;
+        let buffer = handle::<D::Mem>(buffer, ObjectKind::Mem)?;
+        if ptr.is_null() {
+            return Err(CL_INVALID_VALUE);
+        }
+        let data = slice::from_raw_parts(ptr as *const u8, size);
+        D::instance().write_buffer(queue, buffer, offset, data)
+    })())
+}
+
+/// Answers `clIcdGetPlatformIDsKHR`, otherwise asks the driver.
+///
+/// # Safety
+///
+/// func_name must be null or a null terminated string.
+pub unsafe extern "system" fn get_extension_function_address<D: Driver>(
+    func_name: *const c_char,
+) -> *mut c_void {
+    if func_name.is_null() {
+        return ptr::null_mut();
+    }
+    match CStr::from_ptr(func_name).to_str() {
+        Ok("clIcdGetPlatformIDsKHR") => {
+            get_platform_ids::<D> as unsafe extern "system" fn(_, _, _) -> _ as *mut c_void
+        }
+        Ok(name) => D::instance().extension_function(name),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+unsafe extern "system" fn get_extension_function_address_for_platform<D: Driver>(
+    platform: cl_platform_id,
+    func_name: *const c_char,
+) -> *mut c_void {
+    match handle::<D::Platform>(platform, ObjectKind::Platform) {
+        Ok(_) => get_extension_function_address::<D>(func_name),
+        Err(_) => ptr::null_mut(),
+    }
+}
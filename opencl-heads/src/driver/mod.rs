@@ -0,0 +1,576 @@
+/*
+ * mod.rs - Framework for OpenCL ICD drivers.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+//!
+//! With the `icd_driver` feature, an OpenCL platform can be implemented in
+//! Rust and shipped as a `cdylib` that any ICD loader picks up. The driver
+//! implements `Driver` over its own platform, device, context, memory and
+//! queue types, and the framework provides the `extern "system"` entry
+//! points, the dispatch table and the object headers:
+//!
+//! ```ignore
+//! struct CiDriver { platforms: Vec<Handle<CiPlatform>> }
+//!
+//! impl Driver for CiDriver {
+//!     driver_instance!(CiDriver = CiDriver::new());
+//!     type Platform = CiPlatform;
+//!     // ...
+//! }
+//!
+//! export_icd_driver!(CiDriver);
+//! ```
+//!
+//! Handles are checked for their kind before reaching the driver, and the
+//! reference counts of contexts, queues and memory objects are kept by the
+//! framework, which drops the value with the last release. Commands run to
+//! completion inside the enqueue call, no event objects are returned.
+//!
+//! Entry points the trait does not cover can be added to the dispatch table
+//! with `Driver::extend_dispatch`, using `Handle::from_raw` on their object
+//! arguments and the generic `retain` and `release` for new object kinds.
+//!
+mod entry;
+mod object;
+
+pub use self::entry::{
+    dispatch_table, get_extension_function_address, get_platform_ids, release, retain,
+};
+pub use self::object::{Handle, ObjectKind};
+
+#[cfg(feature = "mock")]
+pub(crate) use self::entry::{handle, into_raw, properties, status, write_info};
+
+use crate::consts::*;
+use crate::dispatch::cl_icd_dispatch;
+use crate::types::*;
+use libc::c_void;
+
+/// Result of a driver operation, the error is an OpenCL status code.
+pub type Result<T> = std::result::Result<T, cl_int>;
+
+/// An OpenCL platform implementation.
+///
+/// Only the platform and device queries are required, the other operations
+/// fail with CL_INVALID_OPERATION and the info queries with CL_INVALID_VALUE
+/// unless implemented.
+pub trait Driver: Send + Sync + Sized + 'static {
+    type Platform: Send + Sync + 'static;
+    type Device: Send + Sync + 'static;
+    type Context: Send + Sync + 'static;
+    type Mem: Send + Sync + 'static;
+    type Queue: Send + Sync + 'static;
+
+    /// The driver singleton, implemented by `driver_instance!`.
+    fn instance() -> &'static Self;
+
+    /// The dispatch table of every handle, implemented by `driver_instance!`.
+    fn dispatch() -> &'static cl_icd_dispatch;
+
+    fn platforms(&self) -> &[Handle<Self::Platform>];
+
+    fn platform_info(
+        &self,
+        platform: Handle<Self::Platform>,
+        param_name: cl_platform_info,
+    ) -> Result<Vec<u8>>;
+
+    /// Reported for CL_PLATFORM_ICD_SUFFIX_KHR.
+    fn icd_suffix(&self) -> &str {
+        ""
+    }
+
+    /// Devices of the platform matching device_type, which may be
+    /// CL_DEVICE_TYPE_ALL.
+    fn devices(
+        &self,
+        platform: Handle<Self::Platform>,
+        device_type: cl_device_type,
+    ) -> Vec<Handle<Self::Device>>;
+
+    fn device_info(
+        &self,
+        device: Handle<Self::Device>,
+        param_name: cl_device_info,
+    ) -> Result<Vec<u8>>;
+
+    /// Properties are passed as key value pairs without the terminating zero.
+    fn create_context(
+        &self,
+        _properties: &[cl_context_properties],
+        _devices: &[Handle<Self::Device>],
+    ) -> Result<Self::Context> {
+        Err(CL_INVALID_OPERATION)
+    }
+
+    fn context_info(
+        &self,
+        _context: Handle<Self::Context>,
+        _param_name: cl_context_info,
+    ) -> Result<Vec<u8>> {
+        Err(CL_INVALID_VALUE)
+    }
+
+    fn create_buffer(
+        &self,
+        _context: Handle<Self::Context>,
+        _flags: cl_mem_flags,
+        _size: size_t,
+        _host_ptr: *mut c_void,
+    ) -> Result<Self::Mem> {
+        Err(CL_INVALID_OPERATION)
+    }
+
+    fn mem_info(&self, _mem: Handle<Self::Mem>, _param_name: cl_mem_info) -> Result<Vec<u8>> {
+        Err(CL_INVALID_VALUE)
+    }
+
+    fn create_queue(
+        &self,
+        _context: Handle<Self::Context>,
+        _device: Handle<Self::Device>,
+        _properties: cl_command_queue_properties,
+    ) -> Result<Self::Queue> {
+        Err(CL_INVALID_OPERATION)
+    }
+
+    fn queue_info(
+        &self,
+        _queue: Handle<Self::Queue>,
+        _param_name: cl_command_queue_info,
+    ) -> Result<Vec<u8>> {
+        Err(CL_INVALID_VALUE)
+    }
+
+    fn read_buffer(
+        &self,
+        _queue: Handle<Self::Queue>,
+        _mem: Handle<Self::Mem>,
+        _offset: size_t,
+        _data: &mut [u8],
+    ) -> Result<()> {
+        Err(CL_INVALID_OPERATION)
+    }
+
+    fn write_buffer(
+        &self,
+        _queue: Handle<Self::Queue>,
+        _mem: Handle<Self::Mem>,
+        _offset: size_t,
+        _data: &[u8],
+    ) -> Result<()> {
+        Err(CL_INVALID_OPERATION)
+    }
+
+    fn flush(&self, _queue: Handle<Self::Queue>) -> Result<()> {
+        Ok(())
+    }
+
+    fn finish(&self, _queue: Handle<Self::Queue>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Address of an extension function, null if unknown.
+    fn extension_function(&self, _name: &str) -> *mut c_void {
+        std::ptr::null_mut()
+    }
+
+    /// Adds or replaces entries of the dispatch table built by the
+    /// framework.
+    fn extend_dispatch(_table: &mut cl_icd_dispatch) {}
+}
+
+/// Info value of a null terminated string.
+pub fn info_string(value: &str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(value.len() + 1);
+    bytes.extend_from_slice(value.as_bytes());
+    bytes.push(0);
+    bytes
+}
+
+/// Info value of a scalar, e.g. a cl_uint or a handle from `Handle::as_raw`.
+pub fn info_value<T: Copy>(value: T) -> Vec<u8> {
+    info_slice(&[value])
+}
+
+/// Info value of an array.
+pub fn info_slice<T: Copy>(values: &[T]) -> Vec<u8> {
+    let size = std::mem::size_of_val(values);
+    let mut bytes = vec![0u8; size];
+    unsafe {
+        std::ptr::copy_nonoverlapping(values.as_ptr() as *const u8, bytes.as_mut_ptr(), size)
+    };
+    bytes
+}
+
+/// Implements `Driver::instance` and `Driver::dispatch` inside the `Driver`
+/// impl, creating the driver from an expression on first use.
+#[macro_export]
+macro_rules! driver_instance {
+    ($driver:ty = $init:expr) => {
+        fn instance() -> &'static Self {
+            static INSTANCE: std::sync::OnceLock<$driver> = std::sync::OnceLock::new();
+            INSTANCE.get_or_init(|| $init)
+        }
+
+        fn dispatch() -> &'static $crate::dispatch::cl_icd_dispatch {
+            static DISPATCH: std::sync::OnceLock<$crate::dispatch::cl_icd_dispatch> =
+                std::sync::OnceLock::new();
+            DISPATCH.get_or_init($crate::driver::dispatch_table::<$driver>)
+        }
+    };
+}
+
+/// Exports the entry points an ICD loader looks up in a vendor library,
+/// `clIcdGetPlatformIDsKHR` and `clGetExtensionFunctionAddress`. Every other
+/// call reaches the driver through the dispatch table.
+#[macro_export]
+macro_rules! export_icd_driver {
+    ($driver:ty) => {
+        #[no_mangle]
+        pub unsafe extern "system" fn clIcdGetPlatformIDsKHR(
+            num_entries: $crate::types::cl_uint,
+            platforms: *mut $crate::types::cl_platform_id,
+            num_platforms: *mut $crate::types::cl_uint,
+        ) -> $crate::types::cl_int {
+            $crate::driver::get_platform_ids::<$driver>(num_entries, platforms, num_platforms)
+        }
+
+        #[no_mangle]
+        pub unsafe extern "system" fn clGetExtensionFunctionAddress(
+            func_name: *const $crate::types::c_char,
+        ) -> *mut std::ffi::c_void {
+            $crate::driver::get_extension_function_address::<$driver>(func_name)
+        }
+    };
+}
+
+/************************/
+/* /\ /\ /\ /\ /\ /\ /\ */
+/*|__|__|__|__|__|__|__|*/
+/*|  |  |  |  |  |  |  |*/
+/*|  |  Unit Tests  |  |*/
+/*|__|__|__|__|__|__|__|*/
+/*|__|__|__|__|__|__|__|*/
+/************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dispatch::dispatch_table;
+    use std::ptr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    static BUFFERS_DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+    struct TestDriver {
+        platforms: Vec<Handle<TestPlatform>>,
+    }
+
+    struct TestPlatform {
+        devices: Vec<Handle<()>>,
+    }
+
+    struct TestBuffer(Mutex<Vec<u8>>);
+
+    impl Drop for TestBuffer {
+        fn drop(&mut self) {
+            BUFFERS_DROPPED.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    impl TestDriver {
+        fn new() -> Self {
+            let device = Handle::new(Self::dispatch(), ObjectKind::Device, ());
+            let platform = TestPlatform {
+                devices: vec![device],
+            };
+            TestDriver {
+                platforms: vec![Handle::new(
+                    Self::dispatch(),
+                    ObjectKind::Platform,
+                    platform,
+                )],
+            }
+        }
+    }
+
+    impl Driver for TestDriver {
+        driver_instance!(TestDriver = TestDriver::new());
+
+        type Platform = TestPlatform;
+        type Device = ();
+        type Context = ();
+        type Mem = TestBuffer;
+        type Queue = ();
+
+        fn platforms(&self) -> &[Handle<TestPlatform>] {
+            &self.platforms
+        }
+
+        fn platform_info(
+            &self,
+            _platform: Handle<TestPlatform>,
+            param_name: cl_platform_info,
+        ) -> Result<Vec<u8>> {
+            match param_name {
+                CL_PLATFORM_NAME => Ok(info_string("Test Platform")),
+                CL_PLATFORM_EXTENSIONS => Ok(info_string("cl_khr_icd")),
+                _ => Err(CL_INVALID_VALUE),
+            }
+        }
+
+        fn icd_suffix(&self) -> &str {
+            "TEST"
+        }
+
+        fn devices(
+            &self,
+            platform: Handle<TestPlatform>,
+            device_type: cl_device_type,
+        ) -> Vec<Handle<()>> {
+            match device_type {
+                CL_DEVICE_TYPE_ALL | CL_DEVICE_TYPE_CPU => platform.devices.clone(),
+                _ => Vec::new(),
+            }
+        }
+
+        fn device_info(&self, _device: Handle<()>, param_name: cl_device_info) -> Result<Vec<u8>> {
+            match param_name {
+                CL_DEVICE_TYPE => Ok(info_value(CL_DEVICE_TYPE_CPU)),
+                _ => Err(CL_INVALID_VALUE),
+            }
+        }
+
+        fn create_context(
+            &self,
+            _properties: &[cl_context_properties],
+            _devices: &[Handle<()>],
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        fn create_buffer(
+            &self,
+            _context: Handle<()>,
+            _flags: cl_mem_flags,
+            size: size_t,
+            _host_ptr: *mut c_void,
+        ) -> Result<TestBuffer> {
+            Ok(TestBuffer(Mutex::new(vec![0; size])))
+        }
+
+        fn create_queue(
+            &self,
+            _context: Handle<()>,
+            _device: Handle<()>,
+            _properties: cl_command_queue_properties,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        fn read_buffer(
+            &self,
+            _queue: Handle<()>,
+            mem: Handle<TestBuffer>,
+            offset: size_t,
+            data: &mut [u8],
+        ) -> Result<()> {
+            let buffer = mem.0.lock().unwrap();
+            let source = buffer
+                .get(offset..offset + data.len())
+                .ok_or(CL_INVALID_VALUE)?;
+            data.copy_from_slice(source);
+            Ok(())
+        }
+
+        fn write_buffer(
+            &self,
+            _queue: Handle<()>,
+            mem: Handle<TestBuffer>,
+            offset: size_t,
+            data: &[u8],
+        ) -> Result<()> {
+            let mut buffer = mem.0.lock().unwrap();
+            let target = buffer
+                .get_mut(offset..offset + data.len())
+                .ok_or(CL_INVALID_VALUE)?;
+            target.copy_from_slice(data);
+            Ok(())
+        }
+    }
+
+    export_icd_driver!(TestDriver);
+
+    #[test]
+    fn test_info_values() {
+        assert_eq!(b"CPU\0".to_vec(), info_string("CPU"));
+        assert_eq!(7u32.to_ne_bytes().to_vec(), info_value::<cl_uint>(7));
+        assert_eq!(16, info_slice(&[1u64, 2]).len());
+    }
+
+    #[test]
+    fn test_driver_through_dispatch_table() {
+        unsafe {
+            let get_address = clGetExtensionFunctionAddress(
+                b"clIcdGetPlatformIDsKHR\0".as_ptr() as *const c_char
+            );
+            assert!(!get_address.is_null());
+
+            let mut num_platforms: cl_uint = 0;
+            assert_eq!(
+                CL_SUCCESS,
+                clIcdGetPlatformIDsKHR(0, ptr::null_mut(), &mut num_platforms)
+            );
+            assert_eq!(1, num_platforms);
+            let mut platform: cl_platform_id = ptr::null_mut();
+            assert_eq!(
+                CL_SUCCESS,
+                clIcdGetPlatformIDsKHR(1, &mut platform, ptr::null_mut())
+            );
+
+            // Everything else is reached through the object header.
+            let table = dispatch_table(platform).unwrap();
+            let mut suffix = [0u8; 8];
+            let mut size: size_t = 0;
+            let status_code = table.clGetPlatformInfo.unwrap()(
+                platform,
+                CL_PLATFORM_ICD_SUFFIX_KHR,
+                suffix.len(),
+                suffix.as_mut_ptr() as *mut c_void,
+                &mut size,
+            );
+            assert_eq!(CL_SUCCESS, status_code);
+            assert_eq!(b"TEST\0", &suffix[..size]);
+            let status_code = table.clGetPlatformInfo.unwrap()(
+                platform,
+                CL_PLATFORM_NAME,
+                1,
+                suffix.as_mut_ptr() as *mut c_void,
+                ptr::null_mut(),
+            );
+            assert_eq!(CL_INVALID_VALUE, status_code);
+
+            let mut device: cl_device_id = ptr::null_mut();
+            let get_device_ids = table.clGetDeviceIDs.unwrap();
+            let status_code = get_device_ids(
+                platform,
+                CL_DEVICE_TYPE_GPU,
+                1,
+                &mut device,
+                ptr::null_mut(),
+            );
+            assert_eq!(CL_DEVICE_NOT_FOUND, status_code);
+            let status_code = get_device_ids(
+                platform,
+                CL_DEVICE_TYPE_ALL,
+                1,
+                &mut device,
+                ptr::null_mut(),
+            );
+            assert_eq!(CL_SUCCESS, status_code);
+            // A device is not a platform.
+            let status_code =
+                get_device_ids(device, CL_DEVICE_TYPE_ALL, 1, &mut device, ptr::null_mut());
+            assert_eq!(CL_INVALID_PLATFORM, status_code);
+            assert_eq!(CL_SUCCESS, table.clRetainDevice.unwrap()(device));
+            assert_eq!(CL_SUCCESS, table.clReleaseDevice.unwrap()(device));
+            assert_eq!(CL_INVALID_DEVICE, table.clReleaseDevice.unwrap()(platform));
+
+            let mut status_code = CL_INVALID_VALUE;
+            let properties = [CL_CONTEXT_PLATFORM, platform as cl_context_properties, 0];
+            let context = table.clCreateContextFromType.unwrap()(
+                properties.as_ptr(),
+                CL_DEVICE_TYPE_CPU,
+                None,
+                ptr::null_mut(),
+                &mut status_code,
+            );
+            assert_eq!(CL_SUCCESS, status_code);
+            let queue = table.clCreateCommandQueue.unwrap()(context, device, 0, &mut status_code);
+            assert_eq!(CL_SUCCESS, status_code);
+            let buffer =
+                table.clCreateBuffer.unwrap()(context, 0, 8, ptr::null_mut(), &mut status_code);
+            assert_eq!(CL_SUCCESS, status_code);
+            table.clCreateBuffer.unwrap()(context, 0, 0, ptr::null_mut(), &mut status_code);
+            assert_eq!(CL_INVALID_BUFFER_SIZE, status_code);
+
+            let input = [1u8, 2, 3, 4];
+            let mut output = [0u8; 4];
+            let mut event: cl_event = 1 as cl_event;
+            let status_code = table.clEnqueueWriteBuffer.unwrap()(
+                queue,
+                buffer,
+                CL_TRUE,
+                4,
+                4,
+                input.as_ptr() as *const c_void,
+                0,
+                ptr::null(),
+                &mut event,
+            );
+            assert_eq!(CL_SUCCESS, status_code);
+            assert!(event.is_null());
+            let status_code = table.clEnqueueReadBuffer.unwrap()(
+                queue,
+                buffer,
+                CL_TRUE,
+                4,
+                4,
+                output.as_mut_ptr() as *mut c_void,
+                0,
+                ptr::null(),
+                ptr::null_mut(),
+            );
+            assert_eq!(CL_SUCCESS, status_code);
+            assert_eq!(input, output);
+            // The queue is not a memory object.
+            let status_code = table.clEnqueueReadBuffer.unwrap()(
+                queue,
+                queue,
+                CL_TRUE,
+                0,
+                4,
+                output.as_mut_ptr() as *mut c_void,
+                0,
+                ptr::null(),
+                ptr::null_mut(),
+            );
+            assert_eq!(CL_INVALID_MEM_OBJECT, status_code);
+
+            let mut ref_count: cl_uint = 0;
+            assert_eq!(CL_SUCCESS, table.clRetainMemObject.unwrap()(buffer));
+            let status_code = table.clGetMemObjectInfo.unwrap()(
+                buffer,
+                CL_MEM_REFERENCE_COUNT,
+                std::mem::size_of::<cl_uint>(),
+                &mut ref_count as *mut cl_uint as *mut c_void,
+                ptr::null_mut(),
+            );
+            assert_eq!(CL_SUCCESS, status_code);
+            assert_eq!(2, ref_count);
+            assert_eq!(CL_SUCCESS, table.clReleaseMemObject.unwrap()(buffer));
+            assert_eq!(0, BUFFERS_DROPPED.load(Ordering::SeqCst));
+            assert_eq!(CL_SUCCESS, table.clReleaseMemObject.unwrap()(buffer));
+            assert_eq!(1, BUFFERS_DROPPED.load(Ordering::SeqCst));
+
+            assert_eq!(CL_SUCCESS, table.clReleaseCommandQueue.unwrap()(queue));
+            assert_eq!(CL_SUCCESS, table.clReleaseContext.unwrap()(context));
+            assert!(table.clBuildProgram.is_none());
+        }
+    }
+}
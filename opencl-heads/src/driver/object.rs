@@ -0,0 +1,166 @@
+/*
+ * object.rs - ICD object headers for driver handles.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+//!
+//! Every handle given out by a driver points to a heap object starting with
+//! the header ICD loaders expect, the dispatch table pointer, followed by the
+//! object kind and reference count kept by the framework.
+//!
+use crate::dispatch::cl_icd_dispatch;
+use libc::c_void;
+use std::ops::Deref;
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectKind {
+    Platform = 1,
+    Device,
+    Context,
+    CommandQueue,
+    Mem,
+    Sampler,
+    Program,
+    Kernel,
+    Event,
+}
+
+impl ObjectKind {
+    pub(crate) fn from_raw(kind: u32) -> Option<Self> {
+        use ObjectKind::*;
+        [
+            Platform,
+            Device,
+            Context,
+            CommandQueue,
+            Mem,
+            Sampler,
+            Program,
+            Kernel,
+            Event,
+        ]
+        .iter()
+        .copied()
+        .find(|x| *x as u32 == kind)
+    }
+}
+
+#[repr(C)]
+struct Header {
+    dispatch: *const cl_icd_dispatch,
+    // Kept as an integer, foreign handles may hold any value here.
+    kind: u32,
+    ref_count: AtomicU32,
+}
+
+#[repr(C)]
+struct Object<T> {
+    header: Header,
+    value: T,
+}
+
+/// A handle to a driver object holding a T, convertible to and from the raw
+/// OpenCL handle.
+pub struct Handle<T> {
+    object: NonNull<Object<T>>,
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Handle<T> {}
+
+unsafe impl<T: Send + Sync> Send for Handle<T> {}
+unsafe impl<T: Send + Sync> Sync for Handle<T> {}
+
+impl<T> Handle<T> {
+    /// Allocates an object with a reference count of one.
+    pub fn new(dispatch: &'static cl_icd_dispatch, kind: ObjectKind, value: T) -> Self {
+        let object = Box::new(Object {
+            header: Header {
+                dispatch,
+                kind: kind as u32,
+                ref_count: AtomicU32::new(1),
+            },
+            value,
+        });
+        Handle {
+            object: NonNull::from(Box::leak(object)),
+        }
+    }
+
+    /// Checks a raw handle, None if it is null, has no dispatch table or is
+    /// not of the given kind.
+    ///
+    /// # Safety
+    ///
+    /// raw must be null or point to a live object created by `Handle::new`
+    /// with a value of type T for that kind.
+    pub unsafe fn from_raw(raw: *mut c_void, kind: ObjectKind) -> Option<Self> {
+        let object = NonNull::new(raw as *mut Object<T>)?;
+        let header = &object.as_ref().header;
+        if header.dispatch.is_null() || header.kind != kind as u32 {
+            None
+        } else {
+            Some(Handle { object })
+        }
+    }
+
+    pub fn as_raw(self) -> *mut c_void {
+        self.object.as_ptr() as *mut c_void
+    }
+
+    pub fn ref_count(self) -> u32 {
+        self.header().ref_count.load(Ordering::Acquire)
+    }
+
+    /// Adds a reference, e.g. for an object kept alive by another one.
+    pub fn retain(self) {
+        self.header().ref_count.fetch_add(1, Ordering::AcqRel);
+    }
+
+    /// Drops a reference, freeing the object with the last one. Returns true
+    /// if the object was freed.
+    ///
+    /// # Safety
+    ///
+    /// The handle and its copies must not be used after the object is freed.
+    pub unsafe fn release(self) -> bool {
+        if self.header().ref_count.fetch_sub(1, Ordering::AcqRel) == 1 {
+            drop(Box::from_raw(self.object.as_ptr()));
+            true
+        } else {
+            false
+        }
+    }
+
+    fn header(&self) -> &Header {
+        unsafe { &self.object.as_ref().header }
+    }
+}
+
+impl<T> Deref for Handle<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &self.object.as_ref().value }
+    }
+}
@@ -0,0 +1,203 @@
+/*
+ * dynamic.rs - Runtime loading of the OpenCL library.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+//!
+//! With the `dynamic` feature, the functions in `ffi` are not linked against
+//! libOpenCL at build time. The library is opened at runtime instead, either
+//! explicitly with `load_from`, or implicitly from the default location on
+//! the first call into `ffi`. The default location can be overridden with the
+//! `OPENCL_LIBRARY` environment variable.
+//!
+//! Symbols that the loaded library does not export (e.g. 3.0 entry points on
+//! a 1.2 runtime) do not fail loading, check them with `is_available` before
+//! use. Calling a missing function panics with `LoadError::SymbolNotFound`.
+//!
+use crate::library::Library;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+pub use crate::library::LoadError;
+
+/// Environment variable overriding the default library location.
+pub const LIBRARY_ENV: &str = "OPENCL_LIBRARY";
+
+#[cfg(target_os = "macos")]
+const DEFAULT_LIBRARIES: &[&str] = &[
+    "/System/Library/Frameworks/OpenCL.framework/OpenCL",
+    "libOpenCL.dylib",
+];
+#[cfg(target_os = "windows")]
+const DEFAULT_LIBRARIES: &[&str] = &["OpenCL.dll"];
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const DEFAULT_LIBRARIES: &[&str] = &["libOpenCL.so.1", "libOpenCL.so"];
+
+static LIBRARY: OnceLock<Loaded> = OnceLock::new();
+static LOADING: Mutex<()> = Mutex::new(());
+
+struct Loaded {
+    library: Library,
+    path: String,
+}
+
+/// Loads the library from path, this must happen before any call into `ffi`.
+pub fn load_from<P: AsRef<Path>>(path: P) -> Result<(), LoadError> {
+    let path = path.as_ref().to_string_lossy().into_owned();
+    let _guard = LOADING.lock().unwrap_or_else(|x| x.into_inner());
+    if let Some(loaded) = LIBRARY.get() {
+        return Err(LoadError::AlreadyLoaded(loaded.path.clone()));
+    }
+    let library = Library::open(&path)?;
+    let _ = LIBRARY.set(Loaded { library, path });
+    Ok(())
+}
+
+/// Loads the library from `OPENCL_LIBRARY` or the platform default location,
+/// succeeds without reloading if a library is already loaded.
+pub fn load() -> Result<(), LoadError> {
+    let _guard = LOADING.lock().unwrap_or_else(|x| x.into_inner());
+    if LIBRARY.get().is_some() {
+        return Ok(());
+    }
+    let candidates = match std::env::var(LIBRARY_ENV) {
+        Ok(path) => vec![path],
+        Err(_) => DEFAULT_LIBRARIES.iter().map(|x| String::from(*x)).collect(),
+    };
+    let mut error = None;
+    for path in candidates {
+        match Library::open(&path) {
+            Ok(library) => {
+                let _ = LIBRARY.set(Loaded { library, path });
+                return Ok(());
+            }
+            Err(x) => error = Some(x),
+        }
+    }
+    Err(error.unwrap_or_else(|| LoadError::LibraryNotFound {
+        path: String::new(),
+        reason: String::from("no library candidates"),
+    }))
+}
+
+pub fn is_loaded() -> bool {
+    LIBRARY.get().is_some()
+}
+
+/// Path of the loaded library, if any.
+pub fn library_path() -> Option<&'static str> {
+    LIBRARY.get().map(|x| x.path.as_str())
+}
+
+/// Checks whether the OpenCL function named symbol can be called, loading
+/// the default library first if needed. Returns false if no library could
+/// be loaded.
+pub fn is_available(symbol: &str) -> bool {
+    if load().is_err() {
+        return false;
+    }
+    crate::cl::symbol_available(symbol)
+}
+
+pub(crate) fn library() -> &'static Library {
+    if let Err(x) = load() {
+        panic!("{}", x);
+    }
+    &LIBRARY.get().expect("opencl library is loaded").library
+}
+
+pub(crate) fn missing_symbol(symbol: &'static str) -> ! {
+    panic!("{}", LoadError::SymbolNotFound(symbol))
+}
+
+/************************/
+/* /\ /\ /\ /\ /\ /\ /\ */
+/*|__|__|__|__|__|__|__|*/
+/*|  |  |  |  |  |  |  |*/
+/*|  |  Unit Tests  |  |*/
+/*|__|__|__|__|__|__|__|*/
+/*|__|__|__|__|__|__|__|*/
+/************************/
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::ffi::{clGetDeviceIDs, clGetPlatformIDs};
+    use crate::types::*;
+    use std::process::Command;
+    use std::ptr;
+    use std::sync::Once;
+
+    // Exports only the platform query entry point.
+    const STUB_SOURCE: &str = "
+        int clGetPlatformIDs(unsigned int num_entries, void** platforms, unsigned int* num_platforms) {
+            if (platforms != 0 && num_entries > 0) platforms[0] = (void*) 0x1;
+            if (num_platforms != 0) *num_platforms = 1;
+            return 0;
+        }
+    ";
+
+    fn load_stub() {
+        static STUB: Once = Once::new();
+        STUB.call_once(|| {
+            let dir =
+                std::env::temp_dir().join(format!("opencl-heads-stub-{}", std::process::id()));
+            std::fs::create_dir_all(&dir).unwrap();
+            let source = dir.join("stub.c");
+            let library = dir.join("libOpenCLStub.so");
+            std::fs::write(&source, STUB_SOURCE).unwrap();
+            let status = Command::new("cc")
+                .args(["-shared", "-fPIC", "-o"])
+                .arg(&library)
+                .arg(&source)
+                .status()
+                .unwrap();
+            assert!(status.success());
+            load_from(&library).unwrap();
+        });
+    }
+
+    #[test]
+    fn test_load_stub_library() {
+        let error = Library::open("/nonexistent/libOpenCL.so").err().unwrap();
+        assert!(matches!(error, LoadError::LibraryNotFound { .. }));
+
+        load_stub();
+        assert!(is_loaded());
+        let error = load_from("/nonexistent/libOpenCL.so").unwrap_err();
+        assert!(matches!(error, LoadError::AlreadyLoaded(_)));
+        assert!(library_path().unwrap().ends_with("libOpenCLStub.so"));
+
+        assert!(is_available("clGetPlatformIDs"));
+        assert!(!is_available("clGetDeviceIDs"));
+        assert!(!is_available("clCreateBufferWithProperties"));
+        assert!(!is_available("notAnOpenCLFunction"));
+
+        let mut num_platforms: cl_uint = 0;
+        let mut platform: cl_platform_id = ptr::null_mut();
+        let status_code = unsafe { clGetPlatformIDs(1, &mut platform, &mut num_platforms) };
+        assert_eq!(0, status_code);
+        assert_eq!(1, num_platforms);
+        assert!(!platform.is_null());
+    }
+
+    #[test]
+    #[should_panic(expected = "symbol `clGetDeviceIDs` not found")]
+    fn test_missing_symbol() {
+        load_stub();
+        let mut num_devices: cl_uint = 0;
+        unsafe { clGetDeviceIDs(ptr::null_mut(), 0, 0, ptr::null_mut(), &mut num_devices) };
+    }
+}
@@ -0,0 +1,531 @@
+/*
+ * icd.rs - Installable client driver loader.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+//!
+//! With the `icd` feature, the functions in `ffi` are served by a loader
+//! written in Rust instead of the system libOpenCL. Every installed vendor
+//! implementation is opened at runtime and its platforms are merged into a
+//! single platform list, calls on an object are then forwarded through the
+//! dispatch table of the vendor that created it.
+//!
+//! Vendors are discovered the same way as the Khronos loader does, see
+//! `probe`: `OCL_ICD_FILENAMES` lists libraries to load first, then the
+//! `.icd` files of `OCL_ICD_VENDORS` or `/etc/OpenCL/vendors` each hold the
+//! path of one, read in name order so that the platform order is stable.
+//! `.icd` files without a path are skipped. Discovery happens on the
+//! first call into `ffi`, use `load_vendors` or `load_filtered` beforehand
+//! to choose the vendors explicitly.
+//!
+use crate::consts::*;
+use crate::dispatch::cl_icd_dispatch;
+use crate::library::Library;
+use crate::probe;
+use crate::types::*;
+use libc::{c_char, c_void};
+use std::path::{Path, PathBuf};
+use std::ptr;
+use std::sync::{Mutex, OnceLock};
+
+pub use crate::library::LoadError;
+pub use crate::probe::{FILENAMES_ENV, VENDORS_ENV};
+
+type IcdGetPlatformIDs = unsafe extern "system" fn(
+    num_entries: cl_uint,
+    platforms: *mut cl_platform_id,
+    num_platforms: *mut cl_uint,
+) -> cl_int;
+
+static VENDORS: OnceLock<Vec<Vendor>> = OnceLock::new();
+static LOADING: Mutex<()> = Mutex::new(());
+
+/// A vendor implementation and the platforms it exposes.
+pub struct Vendor {
+    // Keeps the library loaded, None for a vendor implemented in this
+    // process.
+    _library: Option<Library>,
+    path: String,
+    platforms: Vec<cl_platform_id>,
+}
+
+// Platforms are plain handles owned by the vendor library, which is never
+// unloaded.
+unsafe impl Send for Vendor {}
+unsafe impl Sync for Vendor {}
+
+impl Vendor {
+    /// Path the vendor library was opened from.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn platforms(&self) -> &[cl_platform_id] {
+        &self.platforms
+    }
+
+    /// Opens a vendor library and queries its platforms, the vendor is not
+    /// registered with the loader.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, LoadError> {
+        let path = path.as_ref().to_string_lossy().into_owned();
+        let library = Library::open(&path)?;
+        let get_platform_ids = platform_ids_function(&library)?;
+        let invalid = |reason: String| LoadError::InvalidVendor {
+            path: path.clone(),
+            reason,
+        };
+
+        let mut num_platforms: cl_uint = 0;
+        let status_code = unsafe { get_platform_ids(0, ptr::null_mut(), &mut num_platforms) };
+        if status_code == CL_PLATFORM_NOT_FOUND_KHR || num_platforms == 0 {
+            return Ok(Vendor {
+                _library: Some(library),
+                path,
+                platforms: Vec::new(),
+            });
+        }
+        if status_code != CL_SUCCESS {
+            return Err(invalid(format!(
+                "clIcdGetPlatformIDsKHR failed with {}",
+                status_code
+            )));
+        }
+        let mut platforms: Vec<cl_platform_id> = vec![ptr::null_mut(); num_platforms as usize];
+        let status_code =
+            unsafe { get_platform_ids(num_platforms, platforms.as_mut_ptr(), ptr::null_mut()) };
+        if status_code != CL_SUCCESS {
+            return Err(invalid(format!(
+                "clIcdGetPlatformIDsKHR failed with {}",
+                status_code
+            )));
+        }
+        // Platforms without a dispatch table can not be forwarded to.
+        platforms.retain(|x| unsafe { dispatch_table(*x) }.is_some());
+        Ok(Vendor {
+            _library: Some(library),
+            path,
+            platforms,
+        })
+    }
+
+    /// A vendor whose platforms are implemented in this process, the path is
+    /// only used as its name.
+    #[cfg(feature = "mock")]
+    pub(crate) fn in_process(name: &str, platforms: Vec<cl_platform_id>) -> Self {
+        Vendor {
+            _library: None,
+            path: name.to_string(),
+            platforms,
+        }
+    }
+}
+
+fn platform_ids_function(library: &Library) -> Result<IcdGetPlatformIDs, LoadError> {
+    const SYMBOL: &str = "clIcdGetPlatformIDsKHR\0";
+    let function = library.symbol(SYMBOL).or_else(|| {
+        // Older vendors only expose it as an extension function.
+        let get_address = library.symbol("clGetExtensionFunctionAddress\0")?;
+        let get_address = unsafe {
+            std::mem::transmute::<
+                *mut c_void,
+                unsafe extern "system" fn(*const c_char) -> *mut c_void,
+            >(get_address)
+        };
+        let address = unsafe { get_address(SYMBOL.as_ptr() as *const c_char) };
+        if address.is_null() {
+            None
+        } else {
+            Some(address)
+        }
+    });
+    match function {
+        Some(x) => Ok(unsafe { std::mem::transmute::<*mut c_void, IcdGetPlatformIDs>(x) }),
+        None => Err(LoadError::SymbolNotFound("clIcdGetPlatformIDsKHR")),
+    }
+}
+
+/// Vendor libraries found from the environment and the vendors directory, in
+/// load order.
+pub fn vendor_libraries() -> Vec<PathBuf> {
+    discover(
+        std::env::var(FILENAMES_ENV).ok(),
+        std::env::var(VENDORS_ENV).ok(),
+    )
+}
+
+fn discover(filenames: Option<String>, vendors: Option<String>) -> Vec<PathBuf> {
+    probe::entries_from(filenames, vendors)
+        .into_iter()
+        .filter_map(|x| x.library.ok())
+        .collect()
+}
+
+/// Loads the given vendor libraries in order, this must happen before any
+/// call into `ffi`. Vendors that fail to load are skipped, an error is
+/// returned only if none of them exposes a platform.
+pub fn load_vendors<P: AsRef<Path>>(libraries: &[P]) -> Result<(), LoadError> {
+    let _guard = LOADING.lock().unwrap_or_else(|x| x.into_inner());
+    if VENDORS.get().is_some() {
+        return Err(LoadError::AlreadyLoaded(
+            vendors()
+                .iter()
+                .map(|x| x.path())
+                .collect::<Vec<_>>()
+                .join(", "),
+        ));
+    }
+    register(libraries.iter().map(|x| x.as_ref().to_path_buf()))
+}
+
+/// Discovers the vendor libraries and loads those accepted by filter.
+pub fn load_filtered<F: FnMut(&Path) -> bool>(mut filter: F) -> Result<(), LoadError> {
+    let libraries: Vec<PathBuf> = vendor_libraries()
+        .into_iter()
+        .filter(|x| filter(x))
+        .collect();
+    load_vendors(&libraries)
+}
+
+/// Discovers and loads every vendor library, succeeds without reloading if
+/// the vendors are already loaded.
+pub fn load() -> Result<(), LoadError> {
+    let _guard = LOADING.lock().unwrap_or_else(|x| x.into_inner());
+    match VENDORS.get() {
+        Some(x) if x.is_empty() => Err(LoadError::PlatformNotFound),
+        Some(_) => Ok(()),
+        None => register_default(),
+    }
+}
+
+// Must be called with LOADING held.
+#[cfg(not(feature = "mock"))]
+fn register_default() -> Result<(), LoadError> {
+    register(vendor_libraries().into_iter())
+}
+
+// The mock platform replaces the installed vendors.
+#[cfg(feature = "mock")]
+fn register_default() -> Result<(), LoadError> {
+    let _ = VENDORS.set(vec![Vendor::in_process("mock", crate::mock::platforms())]);
+    Ok(())
+}
+
+// Must be called with LOADING held.
+fn register<I: Iterator<Item = PathBuf>>(libraries: I) -> Result<(), LoadError> {
+    let mut error = None;
+    let mut loaded = Vec::new();
+    for path in libraries {
+        match Vendor::open(&path) {
+            Ok(vendor) if !vendor.platforms.is_empty() => loaded.push(vendor),
+            Ok(_) => {}
+            Err(x) => error = Some(x),
+        }
+    }
+    let found = !loaded.is_empty();
+    let _ = VENDORS.set(loaded);
+    if found {
+        Ok(())
+    } else {
+        Err(error.unwrap_or(LoadError::PlatformNotFound))
+    }
+}
+
+pub fn is_loaded() -> bool {
+    VENDORS.get().is_some()
+}
+
+/// Loaded vendors in platform order, empty before loading.
+pub fn vendors() -> &'static [Vendor] {
+    VENDORS.get().map_or(&[], |x| x.as_slice())
+}
+
+fn platforms() -> impl Iterator<Item = cl_platform_id> {
+    let _ = load();
+    vendors().iter().flat_map(|x| x.platforms.iter().copied())
+}
+
+/// Return value of a call that could not be forwarded to a vendor.
+pub(crate) trait IcdReturn {
+    /// The object argument is null or has no dispatch table, code is the
+    /// error of its object type.
+    fn invalid_object(code: cl_int) -> Self;
+    /// The vendor does not implement the entry point.
+    fn unsupported() -> Self;
+}
+
+impl IcdReturn for cl_int {
+    fn invalid_object(code: cl_int) -> Self {
+        code
+    }
+
+    fn unsupported() -> Self {
+        CL_INVALID_OPERATION
+    }
+}
+
+// Covers every handle type and untyped pointer.
+impl IcdReturn for *mut c_void {
+    fn invalid_object(_: cl_int) -> Self {
+        ptr::null_mut()
+    }
+
+    fn unsupported() -> Self {
+        ptr::null_mut()
+    }
+}
+
+impl IcdReturn for () {
+    fn invalid_object(_: cl_int) -> Self {}
+
+    fn unsupported() -> Self {}
+}
+
+pub(crate) unsafe fn dispatch_table(object: *mut c_void) -> Option<&'static cl_icd_dispatch> {
+    crate::dispatch::dispatch_table(object)
+}
+
+pub(crate) unsafe fn get_platform_ids(
+    num_entries: cl_uint,
+    platforms: *mut cl_platform_id,
+    num_platforms: *mut cl_uint,
+) -> cl_int {
+    if (num_entries == 0 && !platforms.is_null())
+        || (platforms.is_null() && num_platforms.is_null())
+    {
+        return CL_INVALID_VALUE;
+    }
+    let all: Vec<cl_platform_id> = self::platforms().collect();
+    if !num_platforms.is_null() {
+        *num_platforms = all.len() as cl_uint;
+    }
+    if all.is_empty() {
+        return CL_PLATFORM_NOT_FOUND_KHR;
+    }
+    if !platforms.is_null() {
+        for (i, platform) in all.iter().take(num_entries as usize).enumerate() {
+            *platforms.add(i) = *platform;
+        }
+    }
+    CL_SUCCESS
+}
+
+/// A null platform selects the first platform, as the specification leaves
+/// it implementation defined.
+pub(crate) fn platform_or_default(platform: cl_platform_id) -> cl_platform_id {
+    if platform.is_null() {
+        platforms().next().unwrap_or(ptr::null_mut())
+    } else {
+        platform
+    }
+}
+
+/// The platform named by the CL_CONTEXT_PLATFORM property, or the default
+/// platform.
+pub(crate) unsafe fn context_platform(properties: *const cl_context_properties) -> *mut c_void {
+    let mut property = properties;
+    while !property.is_null() && *property != 0 {
+        if *property == CL_CONTEXT_PLATFORM {
+            return *property.add(1) as cl_platform_id;
+        }
+        property = property.add(2);
+    }
+    platform_or_default(ptr::null_mut())
+}
+
+pub(crate) unsafe fn first_object(list: *const *mut c_void, count: cl_uint) -> *mut c_void {
+    if list.is_null() || count == 0 {
+        ptr::null_mut()
+    } else {
+        *list
+    }
+}
+
+/// Asks every vendor in turn, as extension functions are not tied to an
+/// object.
+pub(crate) unsafe fn get_extension_function_address(func_name: *const c_char) -> *mut c_void {
+    if func_name.is_null() {
+        return ptr::null_mut();
+    }
+    let _ = load();
+    for vendor in vendors() {
+        let table = vendor.platforms.first().and_then(|x| dispatch_table(*x));
+        if let Some(function) = table.and_then(|x| x.clGetExtensionFunctionAddress) {
+            let address = function(func_name);
+            if !address.is_null() {
+                return address;
+            }
+        }
+    }
+    ptr::null_mut()
+}
+
+/************************/
+/* /\ /\ /\ /\ /\ /\ /\ */
+/*|__|__|__|__|__|__|__|*/
+/*|  |  |  |  |  |  |  |*/
+/*|  |  Unit Tests  |  |*/
+/*|__|__|__|__|__|__|__|*/
+/*|__|__|__|__|__|__|__|*/
+/************************/
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::ffi::*;
+    use std::process::Command;
+
+    // A vendor exposing one platform and one device, with only the platform
+    // info and device query entries of its dispatch table filled.
+    const VENDOR_SOURCE: &str = "
+        #include <stddef.h>
+        #include <string.h>
+        struct object { void** dispatch; };
+        static void* dispatch[149];
+        static struct object platform = { dispatch };
+        static struct object device = { dispatch };
+
+        static int get_platform_info(void* p, unsigned int name, size_t size, void* value, size_t* size_ret) {
+            static const char vendor[] = \"Fake Vendor\";
+            if (size_ret != 0) *size_ret = sizeof(vendor);
+            if (value != 0 && size >= sizeof(vendor)) memcpy(value, vendor, sizeof(vendor));
+            return 0;
+        }
+
+        static int get_device_ids(void* p, unsigned long type, unsigned int n, void** devices, unsigned int* num) {
+            if (p != &platform) return -32;
+            if (devices != 0 && n > 0) devices[0] = &device;
+            if (num != 0) *num = 1;
+            return 0;
+        }
+
+        int clIcdGetPlatformIDsKHR(unsigned int n, void** platforms, unsigned int* num) {
+            dispatch[1] = (void*) get_platform_info;
+            dispatch[2] = (void*) get_device_ids;
+            if (platforms != 0 && n > 0) platforms[0] = &platform;
+            if (num != 0) *num = 1;
+            return 0;
+        }
+    ";
+
+    fn compile(name: &str, source: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("opencl-heads-icd-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let source_path = dir.join(format!("{}.c", name));
+        let library = dir.join(format!("lib{}.so", name));
+        std::fs::write(&source_path, source).unwrap();
+        let status = Command::new("cc")
+            .args(["-shared", "-fPIC", "-o"])
+            .arg(&library)
+            .arg(&source_path)
+            .status()
+            .unwrap();
+        assert!(status.success());
+        library
+    }
+
+    #[test]
+    fn test_discover_vendors() {
+        let dir = std::env::temp_dir().join(format!("opencl-heads-vendors-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("b.icd"), "/opt/b/libB.so\n").unwrap();
+        std::fs::write(dir.join("a.icd"), "/opt/a/libA.so").unwrap();
+        std::fs::write(dir.join("empty.icd"), "\n").unwrap();
+        std::fs::write(dir.join("readme.txt"), "/opt/c/libC.so").unwrap();
+
+        let dir_name = dir.to_string_lossy().into_owned();
+        let libraries = discover(Some(String::from("/x/libX.so::/y/libY.so")), Some(dir_name));
+        let expected: Vec<PathBuf> = [
+            "/x/libX.so",
+            "/y/libY.so",
+            "/opt/a/libA.so",
+            "/opt/b/libB.so",
+        ]
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+        assert_eq!(expected, libraries);
+
+        let icd_file = dir.join("b.icd").to_string_lossy().into_owned();
+        assert_eq!(
+            vec![PathBuf::from("/opt/b/libB.so")],
+            discover(None, Some(icd_file))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "mock", ignore = "the mock platform is the only vendor")]
+    fn test_dispatch_to_fake_vendor() {
+        let unrelated = compile("NotAVendor", "int unrelated(void) { return 0; }");
+        let error = Vendor::open(&unrelated).err().unwrap();
+        assert_eq!(LoadError::SymbolNotFound("clIcdGetPlatformIDsKHR"), error);
+
+        let library = compile("FakeVendor", VENDOR_SOURCE);
+        load_vendors(&[PathBuf::from("/nonexistent/libOpenCL.so"), library]).unwrap();
+        let error = load_vendors(&["/nonexistent/libOpenCL.so"]).unwrap_err();
+        assert!(matches!(error, LoadError::AlreadyLoaded(_)));
+        assert_eq!(1, vendors().len());
+        assert!(vendors()[0].path().ends_with("libFakeVendor.so"));
+
+        unsafe {
+            assert_eq!(
+                CL_INVALID_VALUE,
+                clGetPlatformIDs(0, ptr::null_mut(), ptr::null_mut())
+            );
+            let mut num_platforms: cl_uint = 0;
+            let mut platform: cl_platform_id = ptr::null_mut();
+            assert_eq!(
+                CL_SUCCESS,
+                clGetPlatformIDs(1, &mut platform, &mut num_platforms)
+            );
+            assert_eq!(1, num_platforms);
+            assert_eq!(vendors()[0].platforms(), &[platform]);
+
+            // A null platform is forwarded to the first platform.
+            let mut name = [0u8; 32];
+            let mut size: size_t = 0;
+            let status_code = clGetPlatformInfo(
+                ptr::null_mut(),
+                CL_PLATFORM_VENDOR,
+                name.len(),
+                name.as_mut_ptr() as *mut c_void,
+                &mut size,
+            );
+            assert_eq!(CL_SUCCESS, status_code);
+            assert_eq!(b"Fake Vendor\0", &name[..size]);
+
+            let mut device: cl_device_id = ptr::null_mut();
+            let status_code = clGetDeviceIDs(
+                platform,
+                CL_DEVICE_TYPE_ALL,
+                1,
+                &mut device,
+                ptr::null_mut(),
+            );
+            assert_eq!(CL_SUCCESS, status_code);
+            assert!(!device.is_null());
+
+            let status_code =
+                clGetDeviceInfo(device, CL_DEVICE_NAME, 0, ptr::null_mut(), &mut size);
+            assert_eq!(CL_INVALID_OPERATION, status_code);
+            assert_eq!(CL_INVALID_CONTEXT, clRetainContext(ptr::null_mut()));
+            assert_eq!(CL_INVALID_DEVICE, clRetainDevice(ptr::null_mut()));
+            assert_eq!(CL_INVALID_COMMAND_QUEUE, clFinish(ptr::null_mut()));
+            assert_eq!(CL_INVALID_MEM_OBJECT, clReleaseMemObject(ptr::null_mut()));
+            assert_eq!(CL_INVALID_EVENT, clWaitForEvents(1, &ptr::null_mut()));
+            assert_eq!(CL_INVALID_VALUE, clWaitForEvents(0, ptr::null()));
+            assert_eq!(CL_SUCCESS, clUnloadCompiler());
+        }
+    }
+}
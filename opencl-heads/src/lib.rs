@@ -16,8 +16,28 @@
  * limitations under the License.
 */
 
+#[macro_use]
+mod macros;
+
 mod cl;
 mod cl_ext;
 pub mod consts;
+#[cfg(feature = "icd")]
+pub mod dispatch;
+#[cfg(feature = "icd_driver")]
+pub mod driver;
+#[cfg(feature = "dynamic")]
+pub mod dynamic;
 pub mod ffi;
+#[cfg(feature = "icd")]
+pub mod icd;
+#[cfg(any(feature = "dynamic", feature = "icd", feature = "probe"))]
+mod library;
+#[cfg(feature = "mock")]
+pub mod mock;
+#[cfg(feature = "probe")]
+pub mod probe;
 pub mod types;
+
+#[cfg(all(feature = "dynamic", feature = "icd"))]
+compile_error!("features `dynamic` and `icd` are mutually exclusive");
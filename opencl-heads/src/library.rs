@@ -0,0 +1,150 @@
+/*
+ * library.rs - Shared library loading for the runtime backends.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+//!
+//! Opening of shared libraries and symbol lookup, shared by the `dynamic`,
+//! `icd` and `probe` features.
+//!
+use libc::c_void;
+use std::error::Error;
+use std::ffi::CString;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LoadError {
+    /// The library could not be opened from path.
+    LibraryNotFound { path: String, reason: String },
+    /// The loaded library does not export the symbol.
+    SymbolNotFound(&'static str),
+    /// A library has already been loaded from the given path.
+    AlreadyLoaded(String),
+    /// The library is not a usable ICD vendor implementation.
+    InvalidVendor { path: String, reason: String },
+    /// None of the ICD vendors exposes a platform.
+    PlatformNotFound,
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::LibraryNotFound { path, reason } => {
+                write!(f, "unable to load opencl library `{}`: {}", path, reason)
+            }
+            LoadError::SymbolNotFound(symbol) => {
+                write!(
+                    f,
+                    "symbol `{}` not found in the loaded opencl library",
+                    symbol
+                )
+            }
+            LoadError::AlreadyLoaded(path) => {
+                write!(f, "opencl library has already been loaded from `{}`", path)
+            }
+            LoadError::InvalidVendor { path, reason } => {
+                write!(f, "invalid icd vendor library `{}`: {}", path, reason)
+            }
+            LoadError::PlatformNotFound => write!(f, "no opencl platform found"),
+        }
+    }
+}
+
+impl Error for LoadError {}
+
+/// An opened shared library, it stays loaded for the lifetime of the process.
+pub(crate) struct Library {
+    handle: *mut c_void,
+}
+
+// The handle is only used to look up symbols, which is thread safe.
+unsafe impl Send for Library {}
+unsafe impl Sync for Library {}
+
+impl Library {
+    pub(crate) fn open(path: &str) -> Result<Self, LoadError> {
+        let not_found = |reason: String| LoadError::LibraryNotFound {
+            path: String::from(path),
+            reason,
+        };
+        let c_path = CString::new(path).map_err(|_| not_found(String::from("invalid path")))?;
+        let handle = unsafe { sys::open(&c_path) };
+        if handle.is_null() {
+            Err(not_found(sys::last_error()))
+        } else {
+            Ok(Library { handle })
+        }
+    }
+
+    /// Looks up a null terminated symbol name.
+    pub(crate) fn symbol(&self, name: &str) -> Option<*mut c_void> {
+        let symbol = unsafe { sys::symbol(self.handle, name.as_ptr() as *const libc::c_char) };
+        if symbol.is_null() {
+            None
+        } else {
+            Some(symbol)
+        }
+    }
+}
+
+#[cfg(unix)]
+mod sys {
+    use libc::{c_char, c_void};
+    use std::ffi::{CStr, CString};
+
+    pub unsafe fn open(path: &CString) -> *mut c_void {
+        libc::dlopen(path.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL)
+    }
+
+    pub unsafe fn symbol(handle: *mut c_void, name: *const c_char) -> *mut c_void {
+        libc::dlsym(handle, name)
+    }
+
+    pub fn last_error() -> String {
+        let error = unsafe { libc::dlerror() };
+        if error.is_null() {
+            String::from("unknown error")
+        } else {
+            unsafe { CStr::from_ptr(error) }
+                .to_string_lossy()
+                .into_owned()
+        }
+    }
+}
+
+#[cfg(windows)]
+mod sys {
+    use libc::{c_char, c_void};
+    use std::ffi::CString;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn LoadLibraryA(name: *const c_char) -> *mut c_void;
+        fn GetProcAddress(module: *mut c_void, name: *const c_char) -> *mut c_void;
+        fn GetLastError() -> u32;
+    }
+
+    pub unsafe fn open(path: &CString) -> *mut c_void {
+        LoadLibraryA(path.as_ptr())
+    }
+
+    pub unsafe fn symbol(handle: *mut c_void, name: *const c_char) -> *mut c_void {
+        GetProcAddress(handle, name)
+    }
+
+    pub fn last_error() -> String {
+        format!("system error code {}", unsafe { GetLastError() })
+    }
+}
@@ -0,0 +1,273 @@
+/*
+ * macros.rs - OpenCL entry point declaration macros.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+
+/// Declares `NAMES`, pairing the name of every listed constant with its
+/// value widened to i128, which holds every OpenCL integer type.
+macro_rules! const_names {
+    ($($name:ident,)*) => {
+        /// Name and value of every constant of the headers, in header order.
+        pub const NAMES: &[(&str, i128)] = &[$((stringify!($name), $name as i128),)*];
+    };
+}
+
+/// Declares the OpenCL entry points from an extern block.
+///
+/// By default the block is emitted as is and linked against libOpenCL at
+/// build time. With the `dynamic` feature every function is instead emitted
+/// as an `unsafe fn` of the same signature that calls through a symbol table
+/// resolved at runtime from the library loaded by `crate::dynamic`. With the
+/// `icd` feature the function forwards to the vendor dispatch table of its
+/// first object argument, see `icd_call!`.
+///
+/// A `pfn` module with a function pointer type per entry point is emitted in
+/// every configuration.
+macro_rules! opencl_functions {
+    (
+        $(#[$link:meta])*
+        extern "system" {
+            $(
+                $(#[$attr:meta])*
+                pub fn $name:ident($($arg:ident: $ty:ty),* $(,)?) $(-> $ret:ty)?;
+            )*
+        }
+    ) => {
+        #[cfg(not(any(feature = "dynamic", feature = "icd")))]
+        $(#[$link])*
+        extern "system" {
+            $(
+                $(#[$attr])*
+                pub fn $name($($arg: $ty),*) $(-> $ret)?;
+            )*
+        }
+
+        /// Function pointer types of the entry points.
+        pub mod pfn {
+            use super::*;
+
+            $(
+                $(#[$attr])*
+                #[allow(non_camel_case_types)]
+                pub type $name = unsafe extern "system" fn($($ty),*) $(-> $ret)?;
+            )*
+        }
+
+        /// Entry points resolved from the dynamically loaded library, a
+        /// missing symbol is kept as None.
+        #[cfg(feature = "dynamic")]
+        #[allow(non_snake_case)]
+        pub(crate) struct Functions {
+            $(
+                $(#[$attr])*
+                $name: Option<unsafe extern "system" fn($($ty),*) $(-> $ret)?>,
+            )*
+        }
+
+        #[cfg(feature = "dynamic")]
+        #[allow(unused_doc_comments)]
+        impl Functions {
+            fn resolve(library: &crate::library::Library) -> Self {
+                Functions {
+                    $(
+                        $(#[$attr])*
+                        $name: library
+                            .symbol(concat!(stringify!($name), "\0"))
+                            .map(|x| unsafe {
+                                std::mem::transmute::<
+                                    *mut c_void,
+                                    unsafe extern "system" fn($($ty),*) $(-> $ret)?,
+                                >(x)
+                            }),
+                    )*
+                }
+            }
+
+            /// Returns None if symbol is not an entry point known to this
+            /// build (e.g. its version feature is disabled).
+            fn contains(&self, symbol: &str) -> Option<bool> {
+                match symbol {
+                    $(
+                        $(#[$attr])*
+                        stringify!($name) => Some(self.$name.is_some()),
+                    )*
+                    _ => None,
+                }
+            }
+        }
+
+        #[cfg(feature = "dynamic")]
+        static FUNCTIONS: std::sync::OnceLock<Functions> = std::sync::OnceLock::new();
+
+        #[cfg(feature = "dynamic")]
+        fn functions() -> &'static Functions {
+            FUNCTIONS.get_or_init(|| Functions::resolve(crate::dynamic::library()))
+        }
+
+        /// Checks whether symbol was resolved from the loaded library.
+        #[cfg(feature = "dynamic")]
+        pub(crate) fn symbol_available(symbol: &str) -> bool {
+            functions().contains(symbol).unwrap_or(false)
+        }
+
+        $(
+            #[cfg(feature = "dynamic")]
+            $(#[$attr])*
+            #[allow(non_snake_case, clippy::too_many_arguments, clippy::missing_safety_doc)]
+            pub unsafe fn $name($($arg: $ty),*) $(-> $ret)? {
+                match functions().$name {
+                    Some(function) => function($($arg),*),
+                    None => crate::dynamic::missing_symbol(stringify!($name)),
+                }
+            }
+
+            #[cfg(feature = "icd")]
+            $(#[$attr])*
+            #[allow(non_snake_case, clippy::too_many_arguments, clippy::missing_safety_doc)]
+            pub unsafe fn $name($($arg: $ty),*) $(-> $ret)? {
+                icd_call!($name($($arg),*))
+            }
+        )*
+    };
+}
+
+/// Forwards an entry point to the ICD loader.
+///
+/// Calls on an object are dispatched through the table of its vendor, which
+/// is the first field of every ICD object. The entry points that take no
+/// object, or take it further down the argument list, are handled by the
+/// loader itself.
+#[cfg(feature = "icd")]
+macro_rules! icd_call {
+    (clGetPlatformIDs($($arg:ident),*)) => {
+        crate::icd::get_platform_ids($($arg),*)
+    };
+    (clGetExtensionFunctionAddress($func_name:ident)) => {
+        crate::icd::get_extension_function_address($func_name)
+    };
+    (clUnloadCompiler()) => {
+        crate::consts::CL_SUCCESS
+    };
+    (clGetPlatformInfo($platform:ident $(, $arg:ident)*)) => {
+        icd_call!(
+            @dispatch clGetPlatformInfo,
+            crate::icd::platform_or_default($platform),
+            crate::consts::CL_INVALID_PLATFORM;
+            $($arg),*
+        )
+    };
+    (clGetDeviceIDs($platform:ident $(, $arg:ident)*)) => {
+        icd_call!(
+            @dispatch clGetDeviceIDs,
+            crate::icd::platform_or_default($platform),
+            crate::consts::CL_INVALID_PLATFORM;
+            $($arg),*
+        )
+    };
+    (clCreateContext($properties:ident, $num_devices:ident, $devices:ident $(, $arg:ident)*)) => {
+        icd_call!(
+            @object clCreateContext,
+            crate::icd::first_object($devices, $num_devices),
+            crate::consts::CL_INVALID_DEVICE;
+            $properties, $num_devices, $devices $(, $arg)*
+        )
+    };
+    (clCreateContextFromType($properties:ident $(, $arg:ident)*)) => {
+        icd_call!(
+            @object clCreateContextFromType,
+            crate::icd::context_platform($properties),
+            crate::consts::CL_INVALID_PLATFORM;
+            $properties $(, $arg)*
+        )
+    };
+    (clWaitForEvents($num_events:ident, $event_list:ident)) => {
+        icd_call!(
+            @object clWaitForEvents,
+            crate::icd::first_object($event_list, $num_events),
+            if $num_events == 0 {
+                crate::consts::CL_INVALID_VALUE
+            } else {
+                crate::consts::CL_INVALID_EVENT
+            };
+            $num_events, $event_list
+        )
+    };
+    ($name:ident($object:ident $(, $arg:ident)*)) => {
+        icd_call!(@dispatch $name, $object, icd_invalid_object!($object); $($arg),*)
+    };
+    // Object passed as the first argument.
+    (@dispatch $name:ident, $object:expr, $invalid:expr; $($arg:ident),*) => {{
+        let object = $object;
+        icd_call!(@object $name, object, $invalid; object $(, $arg)*)
+    }};
+    // Object used only to select the vendor, arguments passed unchanged.
+    (@object $name:ident, $object:expr, $invalid:expr; $($arg:expr),*) => {
+        match crate::icd::dispatch_table($object) {
+            Some(table) => match table.$name {
+                Some(function) => function($($arg),*),
+                None => crate::icd::IcdReturn::unsupported(),
+            },
+            None => crate::icd::IcdReturn::invalid_object($invalid),
+        }
+    };
+}
+
+/// Error code of an invalid object, from the name of its argument.
+#[cfg(feature = "icd")]
+macro_rules! icd_invalid_object {
+    (platform) => {
+        crate::consts::CL_INVALID_PLATFORM
+    };
+    (device) => {
+        crate::consts::CL_INVALID_DEVICE
+    };
+    (in_device) => {
+        crate::consts::CL_INVALID_DEVICE
+    };
+    (context) => {
+        crate::consts::CL_INVALID_CONTEXT
+    };
+    (command_queue) => {
+        crate::consts::CL_INVALID_COMMAND_QUEUE
+    };
+    (memobj) => {
+        crate::consts::CL_INVALID_MEM_OBJECT
+    };
+    (buffer) => {
+        crate::consts::CL_INVALID_MEM_OBJECT
+    };
+    (image) => {
+        crate::consts::CL_INVALID_MEM_OBJECT
+    };
+    (pipe) => {
+        crate::consts::CL_INVALID_MEM_OBJECT
+    };
+    (sampler) => {
+        crate::consts::CL_INVALID_SAMPLER
+    };
+    (program) => {
+        crate::consts::CL_INVALID_PROGRAM
+    };
+    (kernel) => {
+        crate::consts::CL_INVALID_KERNEL
+    };
+    (source_kernel) => {
+        crate::consts::CL_INVALID_KERNEL
+    };
+    (event) => {
+        crate::consts::CL_INVALID_EVENT
+    };
+}
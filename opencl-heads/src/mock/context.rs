@@ -0,0 +1,167 @@
+/*
+ * context.rs - Contexts of the mock platform.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+//!
+//! A context holds its devices and the properties it was created with. The
+//! objects created in it keep it alive until they are released.
+//!
+use super::device::Device;
+use super::{HostPtr, Mock};
+use crate::consts::*;
+use crate::dispatch::cl_icd_dispatch;
+use crate::driver::{handle, info_slice, info_value, status, Driver, Handle, ObjectKind, Result};
+use crate::types::*;
+use libc::c_void;
+use std::sync::Mutex;
+
+type DestructorNotify = extern "C" fn(context: cl_context, user_data: *mut c_void);
+
+pub(crate) struct Context {
+    pub(crate) devices: Vec<Handle<Device>>,
+    properties: Vec<cl_context_properties>,
+    // Called in reverse order of registration with the context handle.
+    destructors: Mutex<Vec<(DestructorNotify, HostPtr, HostPtr)>>,
+}
+
+impl Context {
+    pub(crate) fn new(
+        properties: &[cl_context_properties],
+        devices: &[Handle<Device>],
+    ) -> Result<Self> {
+        let mut seen = Vec::new();
+        for pair in properties.chunks(2) {
+            if seen.contains(&pair[0]) {
+                return Err(CL_INVALID_PROPERTY);
+            }
+            seen.push(pair[0]);
+            match pair[0] {
+                CL_CONTEXT_PLATFORM => {
+                    if pair[1] as *mut c_void != Mock::instance().platform().as_raw() {
+                        return Err(CL_INVALID_PLATFORM);
+                    }
+                }
+                CL_CONTEXT_INTEROP_USER_SYNC => {}
+                _ => return Err(CL_INVALID_PROPERTY),
+            }
+        }
+        let mut list = properties.to_vec();
+        if !list.is_empty() {
+            list.push(0);
+        }
+        Ok(Context {
+            devices: devices.to_vec(),
+            properties: list,
+            destructors: Mutex::new(Vec::new()),
+        })
+    }
+
+    pub(crate) fn info(&self, param_name: cl_context_info) -> Result<Vec<u8>> {
+        match param_name {
+            CL_CONTEXT_NUM_DEVICES => Ok(info_value(self.devices.len() as cl_uint)),
+            CL_CONTEXT_DEVICES => {
+                let devices: Vec<cl_device_id> = self.devices.iter().map(|x| x.as_raw()).collect();
+                Ok(info_slice(&devices))
+            }
+            CL_CONTEXT_PROPERTIES => Ok(info_slice(&self.properties)),
+            _ => Err(CL_INVALID_VALUE),
+        }
+    }
+
+    pub(crate) fn has_device(&self, device: cl_device_id) -> bool {
+        self.devices.iter().any(|x| x.as_raw() == device)
+    }
+
+    /// The device an object query refers to, which may be omitted for a
+    /// context with a single device.
+    pub(crate) fn device(&self, device: cl_device_id) -> Result<Handle<Device>> {
+        if device.is_null() && self.devices.len() == 1 {
+            return Ok(self.devices[0]);
+        }
+        self.devices
+            .iter()
+            .copied()
+            .find(|x| x.as_raw() == device)
+            .ok_or(CL_INVALID_DEVICE)
+    }
+
+    /// Devices of a device list argument, all devices of the context if the
+    /// list is empty.
+    pub(crate) unsafe fn device_list(
+        &self,
+        num_devices: cl_uint,
+        device_list: *const cl_device_id,
+    ) -> Result<Vec<Handle<Device>>> {
+        if (num_devices == 0) != device_list.is_null() {
+            return Err(CL_INVALID_VALUE);
+        }
+        if device_list.is_null() {
+            return Ok(self.devices.clone());
+        }
+        std::slice::from_raw_parts(device_list, num_devices as usize)
+            .iter()
+            .map(|x| {
+                self.devices
+                    .iter()
+                    .copied()
+                    .find(|device| device.as_raw() == *x)
+                    .ok_or(CL_INVALID_DEVICE)
+            })
+            .collect()
+    }
+}
+
+impl Drop for Context {
+    fn drop(&mut self) {
+        let destructors = self
+            .destructors
+            .get_mut()
+            .unwrap_or_else(|x| x.into_inner());
+        while let Some((notify, context, user_data)) = destructors.pop() {
+            notify(context.0, user_data.0);
+        }
+    }
+}
+
+unsafe extern "system" fn set_context_destructor_callback(
+    context: cl_context,
+    pfn_notify: DestructorNotify,
+    user_data: *mut c_void,
+) -> cl_int {
+    status(handle::<Context>(context, ObjectKind::Context).map(|x| {
+        x.destructors
+            .lock()
+            .unwrap()
+            .push((pfn_notify, HostPtr(context), HostPtr(user_data)))
+    }))
+}
+
+pub(crate) fn extend_dispatch(table: &mut cl_icd_dispatch) {
+    table.clSetContextDestructorCallback = Some(set_context_destructor_callback);
+}
+
+/// The context of an object argument, checked against the context of the
+/// queue it is used with.
+pub(crate) fn same_context(
+    queue_context: Handle<Context>,
+    object_context: Handle<Context>,
+) -> Result<()> {
+    if queue_context.as_raw() == object_context.as_raw() {
+        Ok(())
+    } else {
+        Err(CL_INVALID_CONTEXT)
+    }
+}
@@ -0,0 +1,312 @@
+/*
+ * device.rs - The simulated CPU device of the mock platform.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+//!
+//! The device answers every clGetDeviceInfo query of OpenCL 3.0 with values
+//! typical for a CPU, the configurable ones come from `DeviceConfig`.
+//!
+use super::event::now;
+use super::{name_versions, version, Mock, VENDOR, VERSION};
+use crate::consts::*;
+use crate::dispatch::cl_icd_dispatch;
+use crate::driver::{
+    handle, info_slice, info_string, info_value, status, Driver, ObjectKind, Result,
+};
+use crate::types::*;
+use std::ptr;
+
+const EXTENSIONS: &[&str] = &["cl_khr_icd", "cl_khr_byte_addressable_store", "cl_khr_fp64"];
+
+/// Properties of the mock device, see `configure`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeviceConfig {
+    pub name: String,
+    pub vendor: String,
+    pub vendor_id: cl_uint,
+    pub device_type: cl_device_type,
+    pub compute_units: cl_uint,
+    /// In MHz.
+    pub clock_frequency: cl_uint,
+    pub max_work_group_size: size_t,
+    pub max_work_item_sizes: [size_t; 3],
+    pub global_mem_size: cl_ulong,
+    pub max_mem_alloc_size: cl_ulong,
+    pub local_mem_size: cl_ulong,
+    pub image_support: bool,
+}
+
+impl Default for DeviceConfig {
+    fn default() -> Self {
+        DeviceConfig {
+            name: String::from("Mock CPU"),
+            vendor: String::from(VENDOR),
+            vendor_id: 0x4d4f_434b,
+            device_type: CL_DEVICE_TYPE_CPU,
+            compute_units: 4,
+            clock_frequency: 1000,
+            max_work_group_size: 1024,
+            max_work_item_sizes: [1024, 1024, 1024],
+            global_mem_size: 1 << 30,
+            max_mem_alloc_size: 1 << 28,
+            local_mem_size: 32 << 10,
+            image_support: true,
+        }
+    }
+}
+
+pub(crate) struct Device {
+    pub(crate) config: DeviceConfig,
+}
+
+impl Device {
+    pub(crate) fn new(config: DeviceConfig) -> Self {
+        Device { config }
+    }
+
+    /// Alignment of buffer and sub-buffer addresses in bytes.
+    pub(crate) const BASE_ADDRESS_ALIGN: usize = 128;
+
+    pub(crate) const IMAGE2D_MAX_SIZE: size_t = 16384;
+    pub(crate) const IMAGE3D_MAX_SIZE: size_t = 2048;
+    pub(crate) const IMAGE_MAX_BUFFER_SIZE: size_t = 1 << 16;
+    pub(crate) const IMAGE_MAX_ARRAY_SIZE: size_t = 2048;
+
+    pub(crate) fn info(&self, param_name: cl_device_info) -> Result<Vec<u8>> {
+        let config = &self.config;
+        // Image limits are zero without image support.
+        let image = |x: size_t| if config.image_support { x } else { 0 };
+        let image_args = |x: cl_uint| if config.image_support { x } else { 0 };
+        let fp_config = CL_FP_DENORM
+            | CL_FP_INF_NAN
+            | CL_FP_ROUND_TO_NEAREST
+            | CL_FP_ROUND_TO_ZERO
+            | CL_FP_ROUND_TO_INF
+            | CL_FP_FMA;
+        let queue_properties = CL_QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE | CL_QUEUE_PROFILING_ENABLE;
+        let info = match param_name {
+            CL_DEVICE_NAME => info_string(&config.name),
+            CL_DEVICE_VENDOR => info_string(&config.vendor),
+            CL_DEVICE_VERSION => info_string(VERSION),
+            CL_DEVICE_PROFILE => info_string("FULL_PROFILE"),
+            CL_DRIVER_VERSION => info_string(env!("CARGO_PKG_VERSION")),
+            CL_DEVICE_EXTENSIONS => info_string(&EXTENSIONS.join(" ")),
+            CL_DEVICE_OPENCL_C_VERSION => info_string("OpenCL C 1.2 Mock"),
+            CL_DEVICE_BUILT_IN_KERNELS | CL_DEVICE_IL_VERSION => info_string(""),
+            CL_DEVICE_LATEST_CONFORMANCE_VERSION_PASSED => info_string("v0000-00-00-00"),
+
+            CL_DEVICE_VENDOR_ID => info_value(config.vendor_id),
+            CL_DEVICE_MAX_COMPUTE_UNITS => info_value(config.compute_units),
+            CL_DEVICE_MAX_WORK_ITEM_DIMENSIONS => info_value::<cl_uint>(3),
+            CL_DEVICE_PREFERRED_VECTOR_WIDTH_CHAR | CL_DEVICE_NATIVE_VECTOR_WIDTH_CHAR => {
+                info_value::<cl_uint>(16)
+            }
+            CL_DEVICE_PREFERRED_VECTOR_WIDTH_SHORT | CL_DEVICE_NATIVE_VECTOR_WIDTH_SHORT => {
+                info_value::<cl_uint>(8)
+            }
+            CL_DEVICE_PREFERRED_VECTOR_WIDTH_INT
+            | CL_DEVICE_NATIVE_VECTOR_WIDTH_INT
+            | CL_DEVICE_PREFERRED_VECTOR_WIDTH_FLOAT
+            | CL_DEVICE_NATIVE_VECTOR_WIDTH_FLOAT => info_value::<cl_uint>(4),
+            CL_DEVICE_PREFERRED_VECTOR_WIDTH_LONG
+            | CL_DEVICE_NATIVE_VECTOR_WIDTH_LONG
+            | CL_DEVICE_PREFERRED_VECTOR_WIDTH_DOUBLE
+            | CL_DEVICE_NATIVE_VECTOR_WIDTH_DOUBLE => info_value::<cl_uint>(2),
+            CL_DEVICE_PREFERRED_VECTOR_WIDTH_HALF | CL_DEVICE_NATIVE_VECTOR_WIDTH_HALF => {
+                info_value::<cl_uint>(0)
+            }
+            CL_DEVICE_MAX_CLOCK_FREQUENCY => info_value(config.clock_frequency),
+            CL_DEVICE_ADDRESS_BITS => info_value(usize::BITS),
+            CL_DEVICE_MAX_READ_IMAGE_ARGS => info_value(image_args(128)),
+            CL_DEVICE_MAX_WRITE_IMAGE_ARGS | CL_DEVICE_MAX_READ_WRITE_IMAGE_ARGS => {
+                info_value(image_args(64))
+            }
+            CL_DEVICE_MAX_SAMPLERS => info_value(image_args(16)),
+            CL_DEVICE_IMAGE_PITCH_ALIGNMENT | CL_DEVICE_IMAGE_BASE_ADDRESS_ALIGNMENT => {
+                info_value(image_args(1))
+            }
+            CL_DEVICE_MEM_BASE_ADDR_ALIGN => info_value((Self::BASE_ADDRESS_ALIGN * 8) as cl_uint),
+            CL_DEVICE_MIN_DATA_TYPE_ALIGN_SIZE => info_value::<cl_uint>(128),
+            CL_DEVICE_GLOBAL_MEM_CACHELINE_SIZE => info_value::<cl_uint>(64),
+            CL_DEVICE_MAX_CONSTANT_ARGS => info_value::<cl_uint>(8),
+            CL_DEVICE_MAX_PIPE_ARGS
+            | CL_DEVICE_PIPE_MAX_ACTIVE_RESERVATIONS
+            | CL_DEVICE_PIPE_MAX_PACKET_SIZE
+            | CL_DEVICE_QUEUE_ON_DEVICE_PREFERRED_SIZE
+            | CL_DEVICE_QUEUE_ON_DEVICE_MAX_SIZE
+            | CL_DEVICE_MAX_ON_DEVICE_QUEUES
+            | CL_DEVICE_MAX_ON_DEVICE_EVENTS
+            | CL_DEVICE_PARTITION_MAX_SUB_DEVICES
+            | CL_DEVICE_PREFERRED_PLATFORM_ATOMIC_ALIGNMENT
+            | CL_DEVICE_PREFERRED_GLOBAL_ATOMIC_ALIGNMENT
+            | CL_DEVICE_PREFERRED_LOCAL_ATOMIC_ALIGNMENT => info_value::<cl_uint>(0),
+            CL_DEVICE_MAX_NUM_SUB_GROUPS => info_value(config.max_work_group_size as cl_uint),
+            CL_DEVICE_IMAGE_SUPPORT => info_value(bool(config.image_support)),
+            CL_DEVICE_ENDIAN_LITTLE => info_value(bool(cfg!(target_endian = "little"))),
+            CL_DEVICE_HOST_UNIFIED_MEMORY
+            | CL_DEVICE_AVAILABLE
+            | CL_DEVICE_COMPILER_AVAILABLE
+            | CL_DEVICE_LINKER_AVAILABLE
+            | CL_DEVICE_PREFERRED_INTEROP_USER_SYNC
+            | CL_DEVICE_NON_UNIFORM_WORK_GROUP_SUPPORT => info_value(CL_TRUE),
+            CL_DEVICE_ERROR_CORRECTION_SUPPORT
+            | CL_DEVICE_SUB_GROUP_INDEPENDENT_FORWARD_PROGRESS
+            | CL_DEVICE_WORK_GROUP_COLLECTIVE_FUNCTIONS_SUPPORT
+            | CL_DEVICE_GENERIC_ADDRESS_SPACE_SUPPORT
+            | CL_DEVICE_PIPE_SUPPORT => info_value(CL_FALSE),
+            CL_DEVICE_NUMERIC_VERSION => info_value(version(3, 0, 0)),
+            CL_DEVICE_GLOBAL_MEM_CACHE_TYPE => info_value(CL_READ_WRITE_CACHE),
+            // The local memory of a CPU is regular memory.
+            CL_DEVICE_LOCAL_MEM_TYPE => info_value(CL_GLOBAL),
+
+            CL_DEVICE_MAX_MEM_ALLOC_SIZE => info_value(config.max_mem_alloc_size),
+            CL_DEVICE_GLOBAL_MEM_CACHE_SIZE => info_value::<cl_ulong>(1 << 20),
+            CL_DEVICE_GLOBAL_MEM_SIZE => info_value(config.global_mem_size),
+            CL_DEVICE_MAX_CONSTANT_BUFFER_SIZE => info_value::<cl_ulong>(64 << 10),
+            CL_DEVICE_LOCAL_MEM_SIZE => info_value(config.local_mem_size),
+            CL_DEVICE_TYPE => info_value(config.device_type),
+            CL_DEVICE_SINGLE_FP_CONFIG => {
+                info_value(fp_config | CL_FP_CORRECTLY_ROUNDED_DIVIDE_SQRT)
+            }
+            CL_DEVICE_DOUBLE_FP_CONFIG => info_value(fp_config),
+            CL_DEVICE_EXECUTION_CAPABILITIES => info_value(CL_EXEC_KERNEL | CL_EXEC_NATIVE_KERNEL),
+            CL_DEVICE_QUEUE_ON_HOST_PROPERTIES => info_value(queue_properties),
+            CL_DEVICE_QUEUE_ON_DEVICE_PROPERTIES => info_value::<cl_command_queue_properties>(0),
+            CL_DEVICE_PARTITION_AFFINITY_DOMAIN => info_value::<cl_device_affinity_domain>(0),
+            CL_DEVICE_SVM_CAPABILITIES => {
+                info_value(CL_DEVICE_SVM_COARSE_GRAIN_BUFFER | CL_DEVICE_SVM_FINE_GRAIN_BUFFER)
+            }
+            CL_DEVICE_ATOMIC_MEMORY_CAPABILITIES => {
+                info_value(CL_DEVICE_ATOMIC_ORDER_RELAXED | CL_DEVICE_ATOMIC_SCOPE_WORK_GROUP)
+            }
+            CL_DEVICE_ATOMIC_FENCE_CAPABILITIES => info_value(
+                CL_DEVICE_ATOMIC_ORDER_RELAXED
+                    | CL_DEVICE_ATOMIC_ORDER_ACQ_REL
+                    | CL_DEVICE_ATOMIC_SCOPE_WORK_GROUP,
+            ),
+            CL_DEVICE_DEVICE_ENQUEUE_CAPABILITIES => {
+                info_value::<cl_device_device_enqueue_capabilities>(0)
+            }
+
+            CL_DEVICE_EXTENSIONS_WITH_VERSION => {
+                info_slice(&name_versions(EXTENSIONS, version(1, 0, 0)))
+            }
+            CL_DEVICE_OPENCL_C_ALL_VERSIONS => {
+                let mut versions = name_versions(&["OpenCL C"; 3], 0);
+                for (entry, minor) in versions.iter_mut().zip(0..) {
+                    entry.version = version(1, minor, 0);
+                }
+                info_slice(&versions)
+            }
+            CL_DEVICE_OPENCL_C_FEATURES => {
+                let features: &[&str] = if config.image_support {
+                    &["__opencl_c_int64", "__opencl_c_images", "__opencl_c_fp64"]
+                } else {
+                    &["__opencl_c_int64", "__opencl_c_fp64"]
+                };
+                info_slice(&name_versions(features, version(3, 0, 0)))
+            }
+            CL_DEVICE_ILS_WITH_VERSION | CL_DEVICE_BUILT_IN_KERNELS_WITH_VERSION => Vec::new(),
+
+            CL_DEVICE_MAX_WORK_GROUP_SIZE => info_value(config.max_work_group_size),
+            CL_DEVICE_MAX_WORK_ITEM_SIZES => info_slice(&config.max_work_item_sizes),
+            CL_DEVICE_IMAGE2D_MAX_WIDTH | CL_DEVICE_IMAGE2D_MAX_HEIGHT => {
+                info_value(image(Self::IMAGE2D_MAX_SIZE))
+            }
+            CL_DEVICE_IMAGE3D_MAX_WIDTH
+            | CL_DEVICE_IMAGE3D_MAX_HEIGHT
+            | CL_DEVICE_IMAGE3D_MAX_DEPTH => info_value(image(Self::IMAGE3D_MAX_SIZE)),
+            CL_DEVICE_IMAGE_MAX_BUFFER_SIZE => info_value(image(Self::IMAGE_MAX_BUFFER_SIZE)),
+            CL_DEVICE_IMAGE_MAX_ARRAY_SIZE => info_value(image(Self::IMAGE_MAX_ARRAY_SIZE)),
+            CL_DEVICE_MAX_PARAMETER_SIZE => info_value::<size_t>(1024),
+            CL_DEVICE_MAX_GLOBAL_VARIABLE_SIZE | CL_DEVICE_GLOBAL_VARIABLE_PREFERRED_TOTAL_SIZE => {
+                info_value::<size_t>(0)
+            }
+            CL_DEVICE_PROFILING_TIMER_RESOLUTION | CL_DEVICE_PREFERRED_WORK_GROUP_SIZE_MULTIPLE => {
+                info_value::<size_t>(1)
+            }
+            CL_DEVICE_PRINTF_BUFFER_SIZE => info_value::<size_t>(1 << 20),
+
+            CL_DEVICE_PLATFORM => info_value(Mock::instance().platform().as_raw()),
+            CL_DEVICE_PARENT_DEVICE => info_value::<cl_device_id>(ptr::null_mut()),
+            // Partitioning is not supported.
+            CL_DEVICE_PARTITION_PROPERTIES => info_value::<cl_device_partition_property>(0),
+            CL_DEVICE_PARTITION_TYPE => Vec::new(),
+            _ => return Err(CL_INVALID_VALUE),
+        };
+        Ok(info)
+    }
+}
+
+fn bool(value: bool) -> cl_bool {
+    if value {
+        CL_TRUE
+    } else {
+        CL_FALSE
+    }
+}
+
+unsafe extern "system" fn create_sub_devices(
+    in_device: cl_device_id,
+    _properties: *const cl_device_partition_property,
+    _num_devices: cl_uint,
+    _out_devices: *mut cl_device_id,
+    _num_devices_ret: *mut cl_uint,
+) -> cl_int {
+    // No partition type is supported.
+    status(handle::<Device>(in_device, ObjectKind::Device).and(Err(CL_INVALID_VALUE)))
+}
+
+unsafe extern "system" fn get_device_and_host_timer(
+    device: cl_device_id,
+    device_timestamp: *mut cl_ulong,
+    host_timestamp: *mut cl_ulong,
+) -> cl_int {
+    status(handle::<Device>(device, ObjectKind::Device).and_then(|_| {
+        if device_timestamp.is_null() || host_timestamp.is_null() {
+            return Err(CL_INVALID_VALUE);
+        }
+        // The device shares the host clock.
+        let timestamp = now();
+        *device_timestamp = timestamp;
+        *host_timestamp = timestamp;
+        Ok(())
+    }))
+}
+
+unsafe extern "system" fn get_host_timer(
+    device: cl_device_id,
+    host_timestamp: *mut cl_ulong,
+) -> cl_int {
+    status(handle::<Device>(device, ObjectKind::Device).and_then(|_| {
+        if host_timestamp.is_null() {
+            return Err(CL_INVALID_VALUE);
+        }
+        *host_timestamp = now();
+        Ok(())
+    }))
+}
+
+unsafe extern "system" fn unload_platform_compiler(platform: cl_platform_id) -> cl_int {
+    status(handle::<super::Platform>(platform, ObjectKind::Platform).map(|_| ()))
+}
+
+pub(crate) fn extend_dispatch(table: &mut cl_icd_dispatch) {
+    table.clCreateSubDevices = Some(create_sub_devices);
+    table.clGetDeviceAndHostTimer = Some(get_device_and_host_timer);
+    table.clGetHostTimer = Some(get_host_timer);
+    table.clUnloadPlatformCompiler = Some(unload_platform_compiler);
+}
@@ -0,0 +1,453 @@
+/*
+ * event.rs - Events of the mock platform.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+//!
+//! An event is created for every enqueued command and by clCreateUserEvent.
+//! Queues waiting on an event are pumped when it completes, which is how
+//! a user event releases the commands gated on it.
+//!
+use super::context::Context;
+use super::queue::Queue;
+use super::{HostPtr, Mock, Retained};
+use crate::consts::*;
+use crate::dispatch::cl_icd_dispatch;
+use crate::driver::{
+    handle, info_value, into_raw, release, retain, status, write_info, Driver, Handle, ObjectKind,
+    Result,
+};
+use crate::types::*;
+use libc::c_void;
+use std::ptr;
+use std::slice;
+use std::sync::{Condvar, Mutex, OnceLock};
+use std::time::Instant;
+
+type EventNotify =
+    extern "C" fn(event: cl_event, event_command_status: cl_int, user_data: *mut c_void);
+
+const EVENT: u32 = ObjectKind::Event as u32;
+
+/// Nanoseconds since the first use of the mock timer.
+pub(crate) fn now() -> cl_ulong {
+    static EPOCH: OnceLock<Instant> = OnceLock::new();
+    EPOCH.get_or_init(Instant::now).elapsed().as_nanos() as cl_ulong
+}
+
+pub(crate) struct Event {
+    context: Retained<Context>,
+    // None for user events.
+    queue: Option<Retained<Queue>>,
+    command_type: cl_command_type,
+    profiling: bool,
+    state: Mutex<State>,
+    changed: Condvar,
+}
+
+struct State {
+    status: cl_int,
+    // Timestamps of CL_PROFILING_COMMAND_QUEUED to CL_PROFILING_COMMAND_COMPLETE.
+    times: [cl_ulong; 5],
+    callbacks: Vec<(cl_int, EventNotify, HostPtr)>,
+    // Queues with a command waiting on the event.
+    waiters: Vec<Retained<Queue>>,
+}
+
+impl Event {
+    pub(crate) fn new(
+        context: Retained<Context>,
+        queue: Option<Retained<Queue>>,
+        command_type: cl_command_type,
+        profiling: bool,
+    ) -> Self {
+        let status = if queue.is_some() {
+            CL_QUEUED
+        } else {
+            CL_SUBMITTED
+        };
+        Event {
+            context,
+            queue,
+            command_type,
+            profiling,
+            state: Mutex::new(State {
+                status: status as cl_int,
+                times: [now(), 0, 0, 0, 0],
+                callbacks: Vec::new(),
+                waiters: Vec::new(),
+            }),
+            changed: Condvar::new(),
+        }
+    }
+
+    pub(crate) fn context(&self) -> Handle<Context> {
+        self.context.handle()
+    }
+
+    pub(crate) fn status(&self) -> cl_int {
+        self.state.lock().unwrap().status
+    }
+
+    /// Moves the event to status, running the callbacks it reaches and the
+    /// queues waiting for its completion.
+    pub(crate) fn set_status(event: Handle<Event>, status: cl_int) {
+        let (callbacks, waiters) = {
+            let mut state = event.state.lock().unwrap();
+            state.status = status;
+            let timestamp = now();
+            match status {
+                x if x == CL_SUBMITTED as cl_int => state.times[1] = timestamp,
+                x if x == CL_RUNNING as cl_int => state.times[2] = timestamp,
+                _ => {
+                    if state.times[2] == 0 {
+                        state.times[2] = timestamp;
+                    }
+                    state.times[3] = timestamp;
+                    state.times[4] = timestamp;
+                }
+            }
+            let (reached, pending) = state
+                .callbacks
+                .drain(..)
+                .partition::<Vec<_>, _>(|x| status <= x.0);
+            state.callbacks = pending;
+            let waiters = if status <= CL_COMPLETE as cl_int {
+                std::mem::take(&mut state.waiters)
+            } else {
+                Vec::new()
+            };
+            (reached, waiters)
+        };
+        event.changed.notify_all();
+        for (_, notify, user_data) in callbacks {
+            notify(event.as_raw(), status, user_data.0);
+        }
+        for queue in waiters {
+            Queue::pump(queue.handle());
+        }
+    }
+
+    /// Blocks until the event completed or failed, returning its status.
+    pub(crate) fn wait(&self) -> cl_int {
+        let mut state = self.state.lock().unwrap();
+        while state.status > CL_COMPLETE as cl_int {
+            state = self.changed.wait(state).unwrap();
+        }
+        state.status
+    }
+
+    /// Registers queue to be pumped on completion, false if the event
+    /// already completed.
+    pub(crate) fn add_waiter(&self, queue: Handle<Queue>) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if state.status <= CL_COMPLETE as cl_int {
+            return false;
+        }
+        state.waiters.push(Retained::new(queue));
+        true
+    }
+
+    fn info(event: Handle<Event>, param_name: cl_event_info) -> Result<Vec<u8>> {
+        let info = match param_name {
+            CL_EVENT_COMMAND_QUEUE => {
+                let queue = event
+                    .queue
+                    .as_ref()
+                    .map_or(ptr::null_mut(), |x| x.handle().as_raw());
+                info_value(queue)
+            }
+            CL_EVENT_CONTEXT => info_value(event.context().as_raw()),
+            CL_EVENT_COMMAND_TYPE => info_value(event.command_type),
+            CL_EVENT_COMMAND_EXECUTION_STATUS => info_value(event.status()),
+            CL_EVENT_REFERENCE_COUNT => info_value(event.ref_count()),
+            _ => return Err(CL_INVALID_VALUE),
+        };
+        Ok(info)
+    }
+
+    fn profiling_info(&self, param_name: cl_profiling_info) -> Result<Vec<u8>> {
+        let state = self.state.lock().unwrap();
+        if !self.profiling || state.status != CL_COMPLETE as cl_int {
+            return Err(CL_PROFILING_INFO_NOT_AVAILABLE);
+        }
+        let index = match param_name {
+            CL_PROFILING_COMMAND_QUEUED => 0,
+            CL_PROFILING_COMMAND_SUBMIT => 1,
+            CL_PROFILING_COMMAND_START => 2,
+            CL_PROFILING_COMMAND_END => 3,
+            CL_PROFILING_COMMAND_COMPLETE => 4,
+            _ => return Err(CL_INVALID_VALUE),
+        };
+        Ok(info_value(state.times[index]))
+    }
+}
+
+/// Events of a wait list argument, which must belong to context.
+pub(crate) unsafe fn wait_list(
+    context: Handle<Context>,
+    num_events: cl_uint,
+    event_list: *const cl_event,
+) -> Result<Vec<Retained<Event>>> {
+    if (num_events == 0) != event_list.is_null() {
+        return Err(CL_INVALID_EVENT_WAIT_LIST);
+    }
+    if event_list.is_null() {
+        return Ok(Vec::new());
+    }
+    slice::from_raw_parts(event_list, num_events as usize)
+        .iter()
+        .map(|x| {
+            let event =
+                handle::<Event>(*x, ObjectKind::Event).map_err(|_| CL_INVALID_EVENT_WAIT_LIST)?;
+            if event.context().as_raw() != context.as_raw() {
+                return Err(CL_INVALID_CONTEXT);
+            }
+            Ok(Retained::new(event))
+        })
+        .collect()
+}
+
+unsafe extern "system" fn create_user_event(
+    context: cl_context,
+    errcode_ret: *mut cl_int,
+) -> cl_event {
+    let event = handle::<Context>(context, ObjectKind::Context).map(|context| {
+        let event = Event::new(Retained::new(context), None, CL_COMMAND_USER, false);
+        Handle::new(Mock::dispatch(), ObjectKind::Event, event)
+    });
+    into_raw(event, errcode_ret)
+}
+
+unsafe extern "system" fn set_user_event_status(
+    event: cl_event,
+    execution_status: cl_int,
+) -> cl_int {
+    status((|| {
+        let event = handle::<Event>(event, ObjectKind::Event)?;
+        if event.queue.is_some() {
+            return Err(CL_INVALID_EVENT);
+        }
+        if execution_status > CL_COMPLETE as cl_int {
+            return Err(CL_INVALID_VALUE);
+        }
+        if event.status() <= CL_COMPLETE as cl_int {
+            return Err(CL_INVALID_OPERATION);
+        }
+        Event::set_status(event, execution_status);
+        Ok(())
+    })())
+}
+
+unsafe extern "system" fn wait_for_events(
+    num_events: cl_uint,
+    event_list: *const cl_event,
+) -> cl_int {
+    status((|| {
+        if num_events == 0 || event_list.is_null() {
+            return Err(CL_INVALID_VALUE);
+        }
+        let first = handle::<Event>(*event_list, ObjectKind::Event)?;
+        let events = wait_list(first.context(), num_events, event_list).map_err(|x| match x {
+            CL_INVALID_EVENT_WAIT_LIST => CL_INVALID_EVENT,
+            x => x,
+        })?;
+        // Commands only run when their queue is pumped.
+        for event in &events {
+            if let Some(queue) = &event.queue {
+                Queue::pump(queue.handle());
+            }
+        }
+        let mut result = Ok(());
+        for event in &events {
+            if event.wait() < 0 {
+                result = Err(CL_EXEC_STATUS_ERROR_FOR_EVENTS_IN_WAIT_LIST);
+            }
+        }
+        result
+    })())
+}
+
+unsafe extern "system" fn get_event_info(
+    event: cl_event,
+    param_name: cl_event_info,
+    param_value_size: size_t,
+    param_value: *mut c_void,
+    param_value_size_ret: *mut size_t,
+) -> cl_int {
+    let info = handle::<Event>(event, ObjectKind::Event).and_then(|x| Event::info(x, param_name));
+    write_info(info, param_value_size, param_value, param_value_size_ret)
+}
+
+unsafe extern "system" fn get_event_profiling_info(
+    event: cl_event,
+    param_name: cl_profiling_info,
+    param_value_size: size_t,
+    param_value: *mut c_void,
+    param_value_size_ret: *mut size_t,
+) -> cl_int {
+    let info = handle::<Event>(event, ObjectKind::Event).and_then(|x| x.profiling_info(param_name));
+    write_info(info, param_value_size, param_value, param_value_size_ret)
+}
+
+unsafe extern "system" fn set_event_callback(
+    event: cl_event,
+    command_exec_callback_type: cl_int,
+    pfn_notify: Option<EventNotify>,
+    user_data: *mut c_void,
+) -> cl_int {
+    status((|| {
+        let event = handle::<Event>(event, ObjectKind::Event)?;
+        let notify = pfn_notify.ok_or(CL_INVALID_VALUE)?;
+        match command_exec_callback_type as cl_uint {
+            CL_COMPLETE | CL_RUNNING | CL_SUBMITTED => {}
+            _ => return Err(CL_INVALID_VALUE),
+        }
+        let reached = {
+            let mut state = event.state.lock().unwrap();
+            if state.status <= command_exec_callback_type {
+                Some(state.status)
+            } else {
+                state
+                    .callbacks
+                    .push((command_exec_callback_type, notify, HostPtr(user_data)));
+                None
+            }
+        };
+        if let Some(x) = reached {
+            notify(event.as_raw(), x, user_data);
+        }
+        Ok(())
+    })())
+}
+
+pub(crate) fn extend_dispatch(table: &mut cl_icd_dispatch) {
+    table.clCreateUserEvent = Some(create_user_event);
+    table.clSetUserEventStatus = Some(set_user_event_status);
+    table.clWaitForEvents = Some(wait_for_events);
+    table.clGetEventInfo = Some(get_event_info);
+    table.clGetEventProfilingInfo = Some(get_event_profiling_info);
+    table.clSetEventCallback = Some(set_event_callback);
+    table.clRetainEvent = Some(retain::<Event, EVENT>);
+    table.clReleaseEvent = Some(release::<Event, EVENT>);
+}
+
+/************************/
+/* /\ /\ /\ /\ /\ /\ /\ */
+/*|__|__|__|__|__|__|__|*/
+/*|  |  |  |  |  |  |  |*/
+/*|  |  Unit Tests  |  |*/
+/*|__|__|__|__|__|__|__|*/
+/*|__|__|__|__|__|__|__|*/
+/************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ffi;
+    use crate::mock::tests::context;
+    use std::sync::atomic::{AtomicI32, Ordering};
+
+    static CALLBACK_STATUS: AtomicI32 = AtomicI32::new(1);
+
+    extern "C" fn on_complete(_event: cl_event, status: cl_int, _user_data: *mut c_void) {
+        CALLBACK_STATUS.store(status, Ordering::SeqCst);
+    }
+
+    #[test]
+    fn test_user_event_gates_queue() {
+        let (device, context) = context();
+        let mut status_code = CL_INVALID_VALUE;
+        let data = [7u8; 16];
+        let mut read = [0u8; 16];
+        unsafe {
+            let queue = ffi::clCreateCommandQueue(context, device, 0, &mut status_code);
+            assert_eq!(CL_SUCCESS, status_code);
+            let buffer = ffi::clCreateBuffer(
+                context,
+                CL_MEM_READ_WRITE,
+                data.len(),
+                ptr::null_mut(),
+                &mut status_code,
+            );
+            let gate = ffi::clCreateUserEvent(context, &mut status_code);
+            assert_eq!(CL_SUCCESS, status_code);
+            assert_eq!(
+                CL_SUCCESS,
+                ffi::clSetEventCallback(
+                    gate,
+                    CL_COMPLETE as cl_int,
+                    Some(on_complete),
+                    ptr::null_mut()
+                )
+            );
+            let mut write: cl_event = ptr::null_mut();
+            assert_eq!(
+                CL_SUCCESS,
+                ffi::clEnqueueWriteBuffer(
+                    queue,
+                    buffer,
+                    CL_NON_BLOCKING,
+                    0,
+                    data.len(),
+                    data.as_ptr() as *const c_void,
+                    1,
+                    &gate,
+                    &mut write,
+                )
+            );
+            let mut event_status: cl_int = 0;
+            ffi::clGetEventInfo(
+                write,
+                CL_EVENT_COMMAND_EXECUTION_STATUS,
+                std::mem::size_of::<cl_int>(),
+                &mut event_status as *mut cl_int as *mut c_void,
+                ptr::null_mut(),
+            );
+            assert_eq!(CL_SUBMITTED as cl_int, event_status);
+
+            assert_eq!(
+                CL_SUCCESS,
+                ffi::clSetUserEventStatus(gate, CL_COMPLETE as cl_int)
+            );
+            assert_eq!(
+                CL_COMPLETE as cl_int,
+                CALLBACK_STATUS.load(Ordering::SeqCst)
+            );
+            assert_eq!(
+                CL_INVALID_OPERATION,
+                ffi::clSetUserEventStatus(gate, CL_COMPLETE as cl_int)
+            );
+            assert_eq!(CL_SUCCESS, ffi::clWaitForEvents(1, &write));
+            ffi::clEnqueueReadBuffer(
+                queue,
+                buffer,
+                CL_BLOCKING,
+                0,
+                read.len(),
+                read.as_mut_ptr() as *mut c_void,
+                0,
+                ptr::null(),
+                ptr::null_mut(),
+            );
+            ffi::clReleaseEvent(write);
+            ffi::clReleaseEvent(gate);
+            ffi::clReleaseMemObject(buffer);
+            ffi::clReleaseCommandQueue(queue);
+            ffi::clReleaseContext(context);
+        }
+        assert_eq!(data, read);
+    }
+}
@@ -0,0 +1,946 @@
+/*
+ * kernel.rs - Kernels of the mock platform.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+//!
+//! Kernel arguments are checked against the signature read from the
+//! program source. An NDRange launch runs the closure registered for the
+//! kernel name once for the whole range, with the argument values taken
+//! when the command was enqueued.
+//!
+use super::context::same_context;
+use super::device::Device;
+use super::memory::Mem;
+use super::program::{ArgDecl, KernelDecl, Program};
+use super::queue::{Queue, Work};
+use super::sampler::Sampler;
+use super::{HostPtr, Mock, Retained};
+use crate::consts::*;
+use crate::dispatch::cl_icd_dispatch;
+use crate::driver::{
+    handle, info_slice, info_string, info_value, into_raw, release, retain, status, write_info,
+    Driver, Handle, ObjectKind, Result,
+};
+use crate::types::*;
+use libc::{c_char, c_void};
+use std::collections::BTreeMap;
+use std::mem::size_of;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::slice;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
+
+type KernelBody = Arc<dyn Fn(&KernelCall) + Send + Sync>;
+
+const KERNEL: u32 = ObjectKind::Kernel as u32;
+
+// Bodies of the kernels by function name.
+static KERNELS: Mutex<BTreeMap<String, KernelBody>> = Mutex::new(BTreeMap::new());
+
+/// Registers the body run when a kernel named `name` is launched, replacing
+/// any previous one. It applies to every program of the mock platform.
+pub fn register_kernel<F>(name: &str, body: F)
+where
+    F: Fn(&KernelCall) + Send + Sync + 'static,
+{
+    KERNELS
+        .lock()
+        .unwrap_or_else(|x| x.into_inner())
+        .insert(name.to_string(), Arc::new(body));
+}
+
+/// An NDRange launch as seen by a registered kernel body.
+///
+/// Unused dimensions have a size of 1 and an offset of 0.
+pub struct KernelCall {
+    name: String,
+    work_dim: cl_uint,
+    global_size: [size_t; 3],
+    global_offset: [size_t; 3],
+    local_size: [size_t; 3],
+    args: Vec<CallArg>,
+}
+
+enum CallArg {
+    Bytes(Vec<u8>),
+    Mem(Option<Retained<Mem>>),
+    Local(size_t),
+    Svm(HostPtr),
+    Sampler,
+}
+
+impl KernelCall {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn work_dim(&self) -> cl_uint {
+        self.work_dim
+    }
+
+    pub fn global_size(&self) -> [size_t; 3] {
+        self.global_size
+    }
+
+    pub fn global_offset(&self) -> [size_t; 3] {
+        self.global_offset
+    }
+
+    /// The local work size given to the launch, or the size chosen by the
+    /// platform.
+    pub fn local_size(&self) -> [size_t; 3] {
+        self.local_size
+    }
+
+    /// Global ids of every work-item of the range, offset included.
+    pub fn global_ids(&self) -> impl Iterator<Item = [size_t; 3]> {
+        let (size, offset) = (self.global_size, self.global_offset);
+        (0..size[2]).flat_map(move |z| {
+            (0..size[1]).flat_map(move |y| {
+                (0..size[0]).map(move |x| [x + offset[0], y + offset[1], z + offset[2]])
+            })
+        })
+    }
+
+    /// Value of a by-value argument, None if its size is not the size of T.
+    pub fn scalar<T: Copy>(&self, index: usize) -> Option<T> {
+        match self.args.get(index) {
+            Some(CallArg::Bytes(bytes)) if bytes.len() == size_of::<T>() => {
+                Some(unsafe { ptr::read_unaligned(bytes.as_ptr() as *const T) })
+            }
+            _ => None,
+        }
+    }
+
+    /// Contents of a buffer or image argument, None if the argument is not
+    /// a memory object or was set to null.
+    ///
+    /// # Safety
+    ///
+    /// No other slice of the same memory may be alive at the same time, and
+    /// the memory must be suitably aligned for T.
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn buffer<T: Copy>(&self, index: usize) -> Option<&mut [T]> {
+        match self.args.get(index) {
+            Some(CallArg::Mem(Some(mem))) => Some(slice::from_raw_parts_mut(
+                mem.data() as *mut T,
+                mem.size / size_of::<T>(),
+            )),
+            _ => None,
+        }
+    }
+
+    /// Pointer of an argument set with clSetKernelArgSVMPointer.
+    pub fn svm_pointer(&self, index: usize) -> Option<*mut c_void> {
+        match self.args.get(index) {
+            Some(CallArg::Svm(x)) => Some(x.0),
+            _ => None,
+        }
+    }
+
+    /// Size of a __local argument.
+    pub fn local_mem_size(&self, index: usize) -> Option<size_t> {
+        match self.args.get(index) {
+            Some(CallArg::Local(x)) => Some(*x),
+            _ => None,
+        }
+    }
+
+    pub fn is_sampler(&self, index: usize) -> bool {
+        matches!(self.args.get(index), Some(CallArg::Sampler))
+    }
+}
+
+#[derive(Clone)]
+enum Arg {
+    Bytes(Vec<u8>),
+    Mem(Option<Retained<Mem>>),
+    Local(size_t),
+    Svm(HostPtr),
+    // Only held, the sampler state is not used by kernel bodies.
+    Sampler(#[allow(dead_code)] Retained<Sampler>),
+}
+
+pub(crate) struct Kernel {
+    program: Retained<Program>,
+    decl: KernelDecl,
+    arg_info: bool,
+    args: Mutex<Vec<Option<Arg>>>,
+}
+
+impl Kernel {
+    fn new(program: Handle<Program>, decl: KernelDecl, args: Vec<Option<Arg>>) -> Self {
+        program.kernel_count.fetch_add(1, Ordering::AcqRel);
+        Kernel {
+            program: Retained::new(program),
+            arg_info: program.has_arg_info(),
+            decl,
+            args: Mutex::new(args),
+        }
+    }
+
+    fn with_decl(program: Handle<Program>, decl: KernelDecl) -> Self {
+        let args = vec![None; decl.args.len()];
+        Self::new(program, decl, args)
+    }
+
+    fn arg_decl(&self, arg_index: cl_uint) -> Result<&ArgDecl> {
+        self.decl
+            .args
+            .get(arg_index as usize)
+            .ok_or(CL_INVALID_ARG_INDEX)
+    }
+
+    unsafe fn set_arg(
+        &self,
+        arg_index: cl_uint,
+        arg_size: size_t,
+        arg_value: *const c_void,
+    ) -> Result<()> {
+        let decl = self.arg_decl(arg_index)?;
+        let context = self.program.context.handle();
+        let arg = if decl.address == CL_KERNEL_ARG_ADDRESS_LOCAL {
+            if !arg_value.is_null() {
+                return Err(CL_INVALID_ARG_VALUE);
+            }
+            if arg_size == 0 {
+                return Err(CL_INVALID_ARG_SIZE);
+            }
+            Arg::Local(arg_size)
+        } else if decl.type_name == "sampler_t" {
+            if arg_size != size_of::<cl_sampler>() {
+                return Err(CL_INVALID_ARG_SIZE);
+            }
+            if arg_value.is_null() {
+                return Err(CL_INVALID_SAMPLER);
+            }
+            let sampler =
+                handle::<Sampler>(*(arg_value as *const cl_sampler), ObjectKind::Sampler)?;
+            same_context(context, sampler.context.handle()).map_err(|_| CL_INVALID_SAMPLER)?;
+            Arg::Sampler(Retained::new(sampler))
+        } else if decl.is_pointer() || is_mem_type(&decl.type_name) {
+            if arg_size != size_of::<cl_mem>() {
+                return Err(CL_INVALID_ARG_SIZE);
+            }
+            let raw = if arg_value.is_null() {
+                ptr::null_mut()
+            } else {
+                *(arg_value as *const cl_mem)
+            };
+            // Null buffers are allowed, null images are not.
+            if raw.is_null() && decl.is_pointer() {
+                Arg::Mem(None)
+            } else {
+                let mem = handle::<Mem>(raw, ObjectKind::Mem)?;
+                same_context(context, mem.context.handle()).map_err(|_| CL_INVALID_MEM_OBJECT)?;
+                if decl.is_pointer() != mem.is_buffer() {
+                    return Err(CL_INVALID_MEM_OBJECT);
+                }
+                Arg::Mem(Some(Retained::new(mem)))
+            }
+        } else {
+            if arg_value.is_null() {
+                return Err(CL_INVALID_ARG_VALUE);
+            }
+            // Structures and unknown types take any size.
+            match type_size(&decl.type_name) {
+                Some(x) if x != arg_size => return Err(CL_INVALID_ARG_SIZE),
+                _ => {}
+            }
+            Arg::Bytes(slice::from_raw_parts(arg_value as *const u8, arg_size).to_vec())
+        };
+        self.args.lock().unwrap()[arg_index as usize] = Some(arg);
+        Ok(())
+    }
+
+    fn info(kernel: Handle<Kernel>, param_name: cl_kernel_info) -> Result<Vec<u8>> {
+        let info = match param_name {
+            CL_KERNEL_FUNCTION_NAME => info_string(&kernel.decl.name),
+            CL_KERNEL_NUM_ARGS => info_value(kernel.decl.args.len() as cl_uint),
+            CL_KERNEL_REFERENCE_COUNT => info_value(kernel.ref_count()),
+            CL_KERNEL_CONTEXT => info_value(kernel.program.context.handle().as_raw()),
+            CL_KERNEL_PROGRAM => info_value(kernel.program.handle().as_raw()),
+            CL_KERNEL_ATTRIBUTES => info_string(""),
+            _ => return Err(CL_INVALID_VALUE),
+        };
+        Ok(info)
+    }
+
+    fn arg_info(&self, arg_index: cl_uint, param_name: cl_kernel_arg_info) -> Result<Vec<u8>> {
+        let decl = self.arg_decl(arg_index)?;
+        if !self.arg_info {
+            return Err(CL_KERNEL_ARG_INFO_NOT_AVAILABLE);
+        }
+        let info = match param_name {
+            CL_KERNEL_ARG_ADDRESS_QUALIFIER => info_value(decl.address),
+            CL_KERNEL_ARG_ACCESS_QUALIFIER => info_value(decl.access),
+            CL_KERNEL_ARG_TYPE_NAME => info_string(&decl.type_name),
+            CL_KERNEL_ARG_TYPE_QUALIFIER => info_value(decl.type_qualifier),
+            CL_KERNEL_ARG_NAME => info_string(&decl.name),
+            _ => return Err(CL_INVALID_VALUE),
+        };
+        Ok(info)
+    }
+
+    fn local_mem_size(&self) -> cl_ulong {
+        let args = self.args.lock().unwrap();
+        args.iter()
+            .map(|x| match x {
+                Some(Arg::Local(size)) => *size as cl_ulong,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    unsafe fn device(&self, device: cl_device_id) -> Result<Handle<Device>> {
+        if !device.is_null() {
+            handle::<Device>(device, ObjectKind::Device)?;
+        }
+        self.program.context.device(device)
+    }
+
+    unsafe fn work_group_info(
+        &self,
+        device: cl_device_id,
+        param_name: cl_kernel_work_group_info,
+    ) -> Result<Vec<u8>> {
+        let device = self.device(device)?;
+        let info = match param_name {
+            CL_KERNEL_WORK_GROUP_SIZE => info_value(device.config.max_work_group_size),
+            CL_KERNEL_COMPILE_WORK_GROUP_SIZE => info_slice::<size_t>(&[0; 3]),
+            CL_KERNEL_LOCAL_MEM_SIZE => info_value(self.local_mem_size()),
+            CL_KERNEL_PREFERRED_WORK_GROUP_SIZE_MULTIPLE => info_value::<size_t>(1),
+            CL_KERNEL_PRIVATE_MEM_SIZE => info_value::<cl_ulong>(0),
+            // Only defined for built-in kernels and custom devices.
+            CL_KERNEL_GLOBAL_WORK_SIZE => return Err(CL_INVALID_VALUE),
+            _ => return Err(CL_INVALID_VALUE),
+        };
+        Ok(info)
+    }
+
+    /// Sub-group info, with sub-groups of a single work-item.
+    unsafe fn sub_group_info(
+        &self,
+        device: cl_device_id,
+        param_name: cl_kernel_sub_group_info,
+        input: &[size_t],
+        output_dims: usize,
+    ) -> Result<Vec<u8>> {
+        let device = self.device(device)?;
+        let max_work_group_size = device.config.max_work_group_size;
+        let info = match param_name {
+            CL_KERNEL_MAX_SUB_GROUP_SIZE_FOR_NDRANGE | CL_KERNEL_SUB_GROUP_COUNT_FOR_NDRANGE => {
+                if input.is_empty() || input.len() > 3 {
+                    return Err(CL_INVALID_VALUE);
+                }
+                if param_name == CL_KERNEL_MAX_SUB_GROUP_SIZE_FOR_NDRANGE {
+                    info_value::<size_t>(1)
+                } else {
+                    info_value::<size_t>(input.iter().product())
+                }
+            }
+            CL_KERNEL_LOCAL_SIZE_FOR_SUB_GROUP_COUNT => {
+                let count = *input.first().ok_or(CL_INVALID_VALUE)?;
+                let mut local_size = vec![1; output_dims];
+                if count > max_work_group_size {
+                    local_size = vec![0; output_dims];
+                } else if let Some(x) = local_size.first_mut() {
+                    *x = count;
+                }
+                info_slice(&local_size)
+            }
+            CL_KERNEL_MAX_NUM_SUB_GROUPS => info_value(max_work_group_size),
+            CL_KERNEL_COMPILE_NUM_SUB_GROUPS => info_value::<size_t>(0),
+            _ => return Err(CL_INVALID_VALUE),
+        };
+        Ok(info)
+    }
+}
+
+impl Drop for Kernel {
+    fn drop(&mut self) {
+        self.program.kernel_count.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+fn is_mem_type(type_name: &str) -> bool {
+    type_name.starts_with("image") || type_name == "pipe"
+}
+
+/// Size of a by-value argument of a built-in scalar or vector type.
+fn type_size(type_name: &str) -> Option<size_t> {
+    let type_name = match type_name.strip_prefix("unsigned ") {
+        Some(x) => format!("u{}", x),
+        None => type_name.to_string(),
+    };
+    let split = type_name
+        .find(|x: char| x.is_ascii_digit())
+        .unwrap_or(type_name.len());
+    let (scalar, width) = type_name.split_at(split);
+    let scalar_size = match scalar {
+        "bool" | "char" | "uchar" => 1,
+        "short" | "ushort" | "half" => 2,
+        "int" | "uint" | "float" => 4,
+        "long" | "ulong" | "double" => 8,
+        "size_t" | "ptrdiff_t" | "intptr_t" | "uintptr_t" => size_of::<size_t>(),
+        _ => return None,
+    };
+    // Three component vectors take the size of four.
+    let count = match width {
+        "" => 1,
+        "2" => 2,
+        "3" | "4" => 4,
+        "8" => 8,
+        "16" => 16,
+        _ => return None,
+    };
+    Some(scalar_size * count)
+}
+
+unsafe fn new_kernel(kernel: Result<Kernel>, errcode_ret: *mut cl_int) -> cl_kernel {
+    let kernel = kernel.map(|x| Handle::new(Mock::dispatch(), ObjectKind::Kernel, x));
+    into_raw(kernel, errcode_ret)
+}
+
+unsafe extern "system" fn create_kernel(
+    program: cl_program,
+    kernel_name: *const c_char,
+    errcode_ret: *mut cl_int,
+) -> cl_kernel {
+    let kernel = (|| {
+        let program = handle::<Program>(program, ObjectKind::Program)?;
+        let name = super::c_string(kernel_name).ok_or(CL_INVALID_VALUE)?;
+        let decl = program.kernel(&name)?;
+        Ok(Kernel::with_decl(program, decl))
+    })();
+    new_kernel(kernel, errcode_ret)
+}
+
+unsafe extern "system" fn create_kernels_in_program(
+    program: cl_program,
+    num_kernels: cl_uint,
+    kernels: *mut cl_kernel,
+    num_kernels_ret: *mut cl_uint,
+) -> cl_int {
+    status((|| {
+        let program = handle::<Program>(program, ObjectKind::Program)?;
+        let decls = program.kernels()?;
+        if !kernels.is_null() {
+            if (num_kernels as usize) < decls.len() {
+                return Err(CL_INVALID_VALUE);
+            }
+            for (i, decl) in decls.iter().enumerate() {
+                let kernel = Kernel::with_decl(program, decl.clone());
+                *kernels.add(i) =
+                    Handle::new(Mock::dispatch(), ObjectKind::Kernel, kernel).as_raw();
+            }
+        }
+        if !num_kernels_ret.is_null() {
+            *num_kernels_ret = decls.len() as cl_uint;
+        }
+        Ok(())
+    })())
+}
+
+unsafe extern "system" fn clone_kernel(
+    source_kernel: cl_kernel,
+    errcode_ret: *mut cl_int,
+) -> cl_kernel {
+    let kernel = handle::<Kernel>(source_kernel, ObjectKind::Kernel).map(|source| {
+        let args = source.args.lock().unwrap().clone();
+        Kernel::new(source.program.handle(), source.decl.clone(), args)
+    });
+    new_kernel(kernel, errcode_ret)
+}
+
+unsafe extern "system" fn set_kernel_arg(
+    kernel: cl_kernel,
+    arg_index: cl_uint,
+    arg_size: size_t,
+    arg_value: *const c_void,
+) -> cl_int {
+    status(
+        handle::<Kernel>(kernel, ObjectKind::Kernel)
+            .and_then(|x| x.set_arg(arg_index, arg_size, arg_value)),
+    )
+}
+
+unsafe extern "system" fn set_kernel_arg_svm_pointer(
+    kernel: cl_kernel,
+    arg_index: cl_uint,
+    arg_value: *const c_void,
+) -> cl_int {
+    status((|| {
+        let kernel = handle::<Kernel>(kernel, ObjectKind::Kernel)?;
+        if !kernel.arg_decl(arg_index)?.is_pointer() {
+            return Err(CL_INVALID_ARG_VALUE);
+        }
+        kernel.args.lock().unwrap()[arg_index as usize] =
+            Some(Arg::Svm(HostPtr(arg_value as *mut c_void)));
+        Ok(())
+    })())
+}
+
+unsafe extern "system" fn set_kernel_exec_info(
+    kernel: cl_kernel,
+    param_name: cl_kernel_exec_info,
+    param_value_size: size_t,
+    param_value: *const c_void,
+) -> cl_int {
+    status((|| {
+        handle::<Kernel>(kernel, ObjectKind::Kernel)?;
+        if param_value.is_null() {
+            return Err(CL_INVALID_VALUE);
+        }
+        match param_name {
+            // All memory is host memory, so every pointer is accessible.
+            CL_KERNEL_EXEC_INFO_SVM_PTRS
+                if param_value_size.is_multiple_of(size_of::<*mut c_void>()) =>
+            {
+                Ok(())
+            }
+            CL_KERNEL_EXEC_INFO_SVM_FINE_GRAIN_SYSTEM
+                if param_value_size == size_of::<cl_bool>() =>
+            {
+                match *(param_value as *const cl_bool) {
+                    CL_FALSE => Ok(()),
+                    _ => Err(CL_INVALID_OPERATION),
+                }
+            }
+            _ => Err(CL_INVALID_VALUE),
+        }
+    })())
+}
+
+unsafe extern "system" fn get_kernel_info(
+    kernel: cl_kernel,
+    param_name: cl_kernel_info,
+    param_value_size: size_t,
+    param_value: *mut c_void,
+    param_value_size_ret: *mut size_t,
+) -> cl_int {
+    let info =
+        handle::<Kernel>(kernel, ObjectKind::Kernel).and_then(|x| Kernel::info(x, param_name));
+    write_info(info, param_value_size, param_value, param_value_size_ret)
+}
+
+unsafe extern "system" fn get_kernel_arg_info(
+    kernel: cl_kernel,
+    arg_index: cl_uint,
+    param_name: cl_kernel_arg_info,
+    param_value_size: size_t,
+    param_value: *mut c_void,
+    param_value_size_ret: *mut size_t,
+) -> cl_int {
+    let info = handle::<Kernel>(kernel, ObjectKind::Kernel)
+        .and_then(|x| x.arg_info(arg_index, param_name));
+    write_info(info, param_value_size, param_value, param_value_size_ret)
+}
+
+unsafe extern "system" fn get_kernel_work_group_info(
+    kernel: cl_kernel,
+    device: cl_device_id,
+    param_name: cl_kernel_work_group_info,
+    param_value_size: size_t,
+    param_value: *mut c_void,
+    param_value_size_ret: *mut size_t,
+) -> cl_int {
+    let info = handle::<Kernel>(kernel, ObjectKind::Kernel)
+        .and_then(|x| x.work_group_info(device, param_name));
+    write_info(info, param_value_size, param_value, param_value_size_ret)
+}
+
+#[allow(clippy::too_many_arguments)]
+unsafe extern "system" fn get_kernel_sub_group_info(
+    kernel: cl_kernel,
+    device: cl_device_id,
+    param_name: cl_kernel_sub_group_info,
+    input_value_size: size_t,
+    input_value: *const c_void,
+    param_value_size: size_t,
+    param_value: *mut c_void,
+    param_value_size_ret: *mut size_t,
+) -> cl_int {
+    let info = handle::<Kernel>(kernel, ObjectKind::Kernel).and_then(|x| {
+        let input = if input_value.is_null() {
+            &[][..]
+        } else {
+            slice::from_raw_parts(
+                input_value as *const size_t,
+                input_value_size / size_of::<size_t>(),
+            )
+        };
+        // The number of dimensions returned follows the size of the value.
+        let output_dims = match param_value_size / size_of::<size_t>() {
+            0 => 3,
+            x => x.min(3),
+        };
+        x.sub_group_info(device, param_name, input, output_dims)
+    });
+    write_info(info, param_value_size, param_value, param_value_size_ret)
+}
+
+unsafe fn work_sizes(work_dim: cl_uint, sizes: *const size_t, default: size_t) -> [size_t; 3] {
+    let mut result = [default; 3];
+    if !sizes.is_null() {
+        result[..work_dim as usize]
+            .copy_from_slice(slice::from_raw_parts(sizes, work_dim as usize));
+    }
+    result
+}
+
+#[allow(clippy::too_many_arguments)]
+unsafe fn enqueue_kernel(
+    command_queue: cl_command_queue,
+    kernel: cl_kernel,
+    command_type: cl_command_type,
+    work_dim: cl_uint,
+    global_work_offset: *const size_t,
+    global_work_size: *const size_t,
+    local_work_size: *const size_t,
+    wait_list: (cl_uint, *const cl_event),
+    event: *mut cl_event,
+) -> Result<()> {
+    let queue = handle::<Queue>(command_queue, ObjectKind::CommandQueue)?;
+    let kernel = handle::<Kernel>(kernel, ObjectKind::Kernel)?;
+    same_context(queue.context.handle(), kernel.program.context.handle())?;
+    if !(1..=3).contains(&work_dim) {
+        return Err(CL_INVALID_WORK_DIMENSION);
+    }
+    if global_work_size.is_null() {
+        return Err(CL_INVALID_GLOBAL_WORK_SIZE);
+    }
+    let global_size = work_sizes(work_dim, global_work_size, 1);
+    let global_offset = work_sizes(work_dim, global_work_offset, 0);
+    if (0..3).any(|i| global_size[i].checked_add(global_offset[i]).is_none()) {
+        return Err(CL_INVALID_GLOBAL_OFFSET);
+    }
+    let config = &queue.device.config;
+    let local_size = if local_work_size.is_null() {
+        // Work-groups of a single work-item, as non-uniform groups are allowed.
+        [1; 3]
+    } else {
+        let local_size = work_sizes(work_dim, local_work_size, 1);
+        if local_size.contains(&0) {
+            return Err(CL_INVALID_WORK_GROUP_SIZE);
+        }
+        if (0..3).any(|i| local_size[i] > config.max_work_item_sizes[i]) {
+            return Err(CL_INVALID_WORK_ITEM_SIZE);
+        }
+        if local_size.iter().product::<size_t>() > config.max_work_group_size {
+            return Err(CL_INVALID_WORK_GROUP_SIZE);
+        }
+        local_size
+    };
+    let args = kernel
+        .args
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|x| match x {
+            Some(Arg::Bytes(bytes)) => Ok(CallArg::Bytes(bytes.clone())),
+            Some(Arg::Mem(mem)) => Ok(CallArg::Mem(mem.clone())),
+            Some(Arg::Local(size)) => Ok(CallArg::Local(*size)),
+            Some(Arg::Svm(pointer)) => Ok(CallArg::Svm(*pointer)),
+            Some(Arg::Sampler(_)) => Ok(CallArg::Sampler),
+            None => Err(CL_INVALID_KERNEL_ARGS),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let call = KernelCall {
+        name: kernel.decl.name.clone(),
+        work_dim,
+        global_size,
+        global_offset,
+        local_size,
+        args,
+    };
+    let body = KERNELS
+        .lock()
+        .unwrap_or_else(|x| x.into_inner())
+        .get(&call.name)
+        .cloned();
+    let work: Work = Box::new(move || {
+        let body = match body {
+            Some(x) => x,
+            None => return CL_COMPLETE as cl_int,
+        };
+        match panic::catch_unwind(AssertUnwindSafe(|| body(&call))) {
+            Ok(()) => CL_COMPLETE as cl_int,
+            // A panicking kernel fails like a device running out of resources.
+            Err(_) => CL_OUT_OF_RESOURCES,
+        }
+    });
+    Queue::enqueue(
+        queue,
+        command_type,
+        wait_list.0,
+        wait_list.1,
+        event,
+        false,
+        work,
+    )
+}
+
+unsafe extern "system" fn enqueue_nd_range_kernel(
+    command_queue: cl_command_queue,
+    kernel: cl_kernel,
+    work_dim: cl_uint,
+    global_work_offset: *const size_t,
+    global_work_size: *const size_t,
+    local_work_size: *const size_t,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: *const cl_event,
+    event: *mut cl_event,
+) -> cl_int {
+    status(enqueue_kernel(
+        command_queue,
+        kernel,
+        CL_COMMAND_NDRANGE_KERNEL,
+        work_dim,
+        global_work_offset,
+        global_work_size,
+        local_work_size,
+        (num_events_in_wait_list, event_wait_list),
+        event,
+    ))
+}
+
+unsafe extern "system" fn enqueue_task(
+    command_queue: cl_command_queue,
+    kernel: cl_kernel,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: *const cl_event,
+    event: *mut cl_event,
+) -> cl_int {
+    let size: size_t = 1;
+    status(enqueue_kernel(
+        command_queue,
+        kernel,
+        CL_COMMAND_TASK,
+        1,
+        ptr::null(),
+        &size,
+        &size,
+        (num_events_in_wait_list, event_wait_list),
+        event,
+    ))
+}
+
+unsafe extern "system" fn enqueue_native_kernel(
+    command_queue: cl_command_queue,
+    user_func: Option<extern "C" fn(*mut c_void)>,
+    args: *mut c_void,
+    cb_args: size_t,
+    num_mem_objects: cl_uint,
+    mem_list: *const cl_mem,
+    args_mem_loc: *const *const c_void,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: *const cl_event,
+    event: *mut cl_event,
+) -> cl_int {
+    status((|| {
+        let queue = handle::<Queue>(command_queue, ObjectKind::CommandQueue)?;
+        let user_func = user_func.ok_or(CL_INVALID_VALUE)?;
+        if args.is_null() != (cb_args == 0)
+            || (num_mem_objects > 0 && (args.is_null() || mem_list.is_null()))
+            || (num_mem_objects > 0) == args_mem_loc.is_null()
+        {
+            return Err(CL_INVALID_VALUE);
+        }
+        // The arguments are copied, with the memory object handles at
+        // args_mem_loc replaced by their data pointers when run.
+        let mut block = if args.is_null() {
+            Vec::new()
+        } else {
+            slice::from_raw_parts(args as *const u8, cb_args).to_vec()
+        };
+        let mut mems = Vec::with_capacity(num_mem_objects as usize);
+        for i in 0..num_mem_objects as usize {
+            let mem = handle::<Mem>(*mem_list.add(i), ObjectKind::Mem)?;
+            same_context(queue.context.handle(), mem.context.handle())?;
+            let offset = (*args_mem_loc.add(i) as usize).wrapping_sub(args as usize);
+            if offset + size_of::<*mut c_void>() > cb_args {
+                return Err(CL_INVALID_VALUE);
+            }
+            mems.push((Retained::new(mem), offset));
+        }
+        let work: Work = Box::new(move || {
+            for (mem, offset) in &mems {
+                let target = block.as_mut_ptr().add(*offset) as *mut *mut u8;
+                ptr::write_unaligned(target, mem.data());
+            }
+            let block_ptr = if block.is_empty() {
+                ptr::null_mut()
+            } else {
+                block.as_mut_ptr() as *mut c_void
+            };
+            user_func(block_ptr);
+            CL_COMPLETE as cl_int
+        });
+        Queue::enqueue(
+            queue,
+            CL_COMMAND_NATIVE_KERNEL,
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            false,
+            work,
+        )
+    })())
+}
+
+pub(crate) fn extend_dispatch(table: &mut cl_icd_dispatch) {
+    table.clCreateKernel = Some(create_kernel);
+    table.clCreateKernelsInProgram = Some(create_kernels_in_program);
+    table.clCloneKernel = Some(clone_kernel);
+    table.clSetKernelArg = Some(set_kernel_arg);
+    table.clSetKernelArgSVMPointer = Some(set_kernel_arg_svm_pointer);
+    table.clSetKernelExecInfo = Some(set_kernel_exec_info);
+    table.clGetKernelInfo = Some(get_kernel_info);
+    table.clGetKernelArgInfo = Some(get_kernel_arg_info);
+    table.clGetKernelWorkGroupInfo = Some(get_kernel_work_group_info);
+    table.clGetKernelSubGroupInfo = Some(get_kernel_sub_group_info);
+    table.clEnqueueNDRangeKernel = Some(enqueue_nd_range_kernel);
+    table.clEnqueueTask = Some(enqueue_task);
+    table.clEnqueueNativeKernel = Some(enqueue_native_kernel);
+    table.clRetainKernel = Some(retain::<Kernel, KERNEL>);
+    table.clReleaseKernel = Some(release::<Kernel, KERNEL>);
+}
+
+/************************/
+/* /\ /\ /\ /\ /\ /\ /\ */
+/*|__|__|__|__|__|__|__|*/
+/*|  |  |  |  |  |  |  |*/
+/*|  |  Unit Tests  |  |*/
+/*|__|__|__|__|__|__|__|*/
+/*|__|__|__|__|__|__|__|*/
+/************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ffi;
+    use crate::mock::tests::context;
+
+    const SOURCE: &str = "kernel void mock_scale(global float* a, float b, local int* c) {}\0";
+
+    #[test]
+    fn test_type_size() {
+        assert_eq!(Some(4), type_size("float"));
+        assert_eq!(Some(4), type_size("unsigned int"));
+        assert_eq!(Some(16), type_size("int3"));
+        assert_eq!(Some(64), type_size("double8"));
+        assert_eq!(None, type_size("struct point"));
+    }
+
+    #[test]
+    fn test_registered_kernel() {
+        register_kernel("mock_scale", |call| {
+            let data = unsafe { call.buffer::<f32>(0) }.unwrap();
+            let factor = call.scalar::<f32>(1).unwrap();
+            assert_eq!(Some(16), call.local_mem_size(2));
+            for id in call.global_ids() {
+                data[id[0]] *= factor;
+            }
+        });
+        let (device, context) = context();
+        let mut status_code = CL_INVALID_VALUE;
+        let mut data = [1.0f32, 2.0, 3.0, 4.0];
+        unsafe {
+            let queue = ffi::clCreateCommandQueue(context, device, 0, &mut status_code);
+            let strings = [SOURCE.as_ptr() as *const c_char];
+            let program = ffi::clCreateProgramWithSource(
+                context,
+                1,
+                strings.as_ptr(),
+                ptr::null(),
+                &mut status_code,
+            );
+            ffi::clBuildProgram(program, 0, ptr::null(), ptr::null(), None, ptr::null_mut());
+            let kernel = ffi::clCreateKernel(
+                program,
+                "mock_scale\0".as_ptr() as *const c_char,
+                &mut status_code,
+            );
+            assert_eq!(CL_SUCCESS, status_code);
+            let buffer = ffi::clCreateBuffer(
+                context,
+                CL_MEM_READ_WRITE | CL_MEM_COPY_HOST_PTR,
+                std::mem::size_of_val(&data),
+                data.as_mut_ptr() as *mut c_void,
+                &mut status_code,
+            );
+            let factor = 2.0f32;
+            let size = [4usize];
+            let launch = || {
+                ffi::clEnqueueNDRangeKernel(
+                    queue,
+                    kernel,
+                    1,
+                    ptr::null(),
+                    size.as_ptr(),
+                    ptr::null(),
+                    0,
+                    ptr::null(),
+                    ptr::null_mut(),
+                )
+            };
+            assert_eq!(CL_INVALID_KERNEL_ARGS, launch());
+            let set_arg =
+                |index, size, value: *const c_void| ffi::clSetKernelArg(kernel, index, size, value);
+            assert_eq!(
+                CL_SUCCESS,
+                set_arg(
+                    0,
+                    size_of::<cl_mem>(),
+                    &buffer as *const cl_mem as *const c_void
+                )
+            );
+            assert_eq!(
+                CL_INVALID_ARG_SIZE,
+                set_arg(1, 8, &factor as *const f32 as *const c_void)
+            );
+            assert_eq!(
+                CL_SUCCESS,
+                set_arg(1, 4, &factor as *const f32 as *const c_void)
+            );
+            assert_eq!(CL_SUCCESS, set_arg(2, 16, ptr::null()));
+            assert_eq!(CL_SUCCESS, launch());
+            ffi::clEnqueueReadBuffer(
+                queue,
+                buffer,
+                CL_BLOCKING,
+                0,
+                std::mem::size_of_val(&data),
+                data.as_mut_ptr() as *mut c_void,
+                0,
+                ptr::null(),
+                ptr::null_mut(),
+            );
+            assert_eq!(
+                CL_INVALID_OPERATION,
+                ffi::clBuildProgram(program, 0, ptr::null(), ptr::null(), None, ptr::null_mut())
+            );
+            ffi::clReleaseMemObject(buffer);
+            ffi::clReleaseKernel(kernel);
+            ffi::clReleaseProgram(program);
+            ffi::clReleaseCommandQueue(queue);
+            ffi::clReleaseContext(context);
+        }
+        assert_eq!([2.0, 4.0, 6.0, 8.0], data);
+    }
+}
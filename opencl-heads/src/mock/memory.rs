@@ -0,0 +1,1022 @@
+/*
+ * memory.rs - Buffers, images and shared virtual memory of the mock platform.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+//!
+//! Memory objects are plain host allocations, or the application memory for
+//! CL_MEM_USE_HOST_PTR. Sub-buffers and images created from a buffer point
+//! into the memory of their parent and keep it alive.
+//!
+use super::context::Context;
+use super::device::Device;
+use super::{HostPtr, Mock, Retained};
+use crate::consts::*;
+use crate::dispatch::cl_icd_dispatch;
+use crate::driver::{
+    handle, info_slice, info_value, into_raw, properties, status, write_info, Driver, Handle,
+    ObjectKind, Result,
+};
+use crate::types::*;
+use libc::c_void;
+use std::alloc::{self, Layout};
+use std::collections::BTreeMap;
+use std::ptr;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+
+type MemNotify = extern "C" fn(memobj: cl_mem, user_data: *mut c_void);
+
+const ACCESS_FLAGS: cl_mem_flags = CL_MEM_READ_WRITE | CL_MEM_WRITE_ONLY | CL_MEM_READ_ONLY;
+const HOST_ACCESS_FLAGS: cl_mem_flags =
+    CL_MEM_HOST_WRITE_ONLY | CL_MEM_HOST_READ_ONLY | CL_MEM_HOST_NO_ACCESS;
+const HOST_PTR_FLAGS: cl_mem_flags =
+    CL_MEM_USE_HOST_PTR | CL_MEM_ALLOC_HOST_PTR | CL_MEM_COPY_HOST_PTR;
+const MEM_FLAGS: cl_mem_flags =
+    ACCESS_FLAGS | HOST_ACCESS_FLAGS | HOST_PTR_FLAGS | CL_MEM_KERNEL_READ_AND_WRITE;
+
+const IMAGE_ORDERS: [cl_channel_order; 3] = [CL_R, CL_RG, CL_RGBA];
+const IMAGE_TYPES: [cl_channel_type; 12] = [
+    CL_SNORM_INT8,
+    CL_SNORM_INT16,
+    CL_UNORM_INT8,
+    CL_UNORM_INT16,
+    CL_SIGNED_INT8,
+    CL_SIGNED_INT16,
+    CL_SIGNED_INT32,
+    CL_UNSIGNED_INT8,
+    CL_UNSIGNED_INT16,
+    CL_UNSIGNED_INT32,
+    CL_HALF_FLOAT,
+    CL_FLOAT,
+];
+
+// Start and layout of every live allocation of clSVMAlloc.
+static SVM: Mutex<BTreeMap<usize, Layout>> = Mutex::new(BTreeMap::new());
+
+pub(crate) struct Mem {
+    pub(crate) context: Retained<Context>,
+    pub(crate) flags: cl_mem_flags,
+    pub(crate) mem_type: cl_mem_object_type,
+    data: HostPtr,
+    pub(crate) size: size_t,
+    host_ptr: HostPtr,
+    // Allocation owned by the object, freed on drop.
+    layout: Option<Layout>,
+    parent: Option<Retained<Mem>>,
+    offset: size_t,
+    uses_svm_pointer: bool,
+    properties: Vec<cl_mem_properties>,
+    pub(crate) image: Option<Image>,
+    map_count: AtomicU32,
+    destructors: Mutex<Vec<(MemNotify, HostPtr, HostPtr)>>,
+}
+
+/// Format and layout of an image object.
+pub(crate) struct Image {
+    pub(crate) format: cl_image_format,
+    pub(crate) element_size: size_t,
+    // Without the buffer, which is kept as the parent.
+    desc: cl_image_desc,
+}
+
+// The only pointer of the descriptor is cleared.
+unsafe impl Send for Image {}
+unsafe impl Sync for Image {}
+
+impl Mem {
+    pub(crate) fn buffer(
+        context: Handle<Context>,
+        flags: cl_mem_flags,
+        size: size_t,
+        host_ptr: *mut c_void,
+        properties: Vec<cl_mem_properties>,
+    ) -> Result<Self> {
+        check_flags(flags)?;
+        if size == 0 || size as cl_ulong > max_alloc_size(&context) {
+            return Err(CL_INVALID_BUFFER_SIZE);
+        }
+        check_host_ptr(flags, host_ptr)?;
+        let (data, layout) = if flags & CL_MEM_USE_HOST_PTR != 0 {
+            (HostPtr(host_ptr), None)
+        } else {
+            let (data, layout) = allocate(size)?;
+            if flags & CL_MEM_COPY_HOST_PTR != 0 {
+                unsafe { ptr::copy_nonoverlapping(host_ptr as *const u8, data.bytes(), size) };
+            }
+            (data, Some(layout))
+        };
+        Ok(Mem {
+            context: Retained::new(context),
+            flags,
+            mem_type: CL_MEM_OBJECT_BUFFER,
+            data,
+            size,
+            host_ptr: Self::host_ptr(flags, host_ptr),
+            layout,
+            parent: None,
+            offset: 0,
+            uses_svm_pointer: flags & CL_MEM_USE_HOST_PTR != 0 && svm_contains(host_ptr),
+            properties,
+            image: None,
+            map_count: AtomicU32::new(0),
+            destructors: Mutex::new(Vec::new()),
+        })
+    }
+
+    fn sub_buffer(
+        parent: Handle<Mem>,
+        flags: cl_mem_flags,
+        region: cl_buffer_region,
+    ) -> Result<Self> {
+        if parent.parent.is_some() || parent.image.is_some() {
+            return Err(CL_INVALID_MEM_OBJECT);
+        }
+        if flags & HOST_PTR_FLAGS != 0 {
+            return Err(CL_INVALID_VALUE);
+        }
+        check_flags(flags)?;
+        let flags = inherit_flags(parent.flags, flags)?;
+        if region.size == 0 {
+            return Err(CL_INVALID_BUFFER_SIZE);
+        }
+        match region.origin.checked_add(region.size) {
+            Some(end) if end <= parent.size => {}
+            _ => return Err(CL_INVALID_VALUE),
+        }
+        if !region.origin.is_multiple_of(Device::BASE_ADDRESS_ALIGN) {
+            return Err(CL_MISALIGNED_SUB_BUFFER_OFFSET);
+        }
+        let host_ptr = if parent.host_ptr.0.is_null() {
+            HostPtr::null()
+        } else {
+            HostPtr(unsafe { parent.host_ptr.bytes().add(region.origin) } as *mut c_void)
+        };
+        Ok(Mem {
+            context: parent.context.clone(),
+            flags,
+            mem_type: CL_MEM_OBJECT_BUFFER,
+            data: HostPtr(unsafe { parent.data.bytes().add(region.origin) } as *mut c_void),
+            size: region.size,
+            host_ptr,
+            layout: None,
+            parent: Some(Retained::new(parent)),
+            offset: region.origin,
+            uses_svm_pointer: parent.uses_svm_pointer,
+            properties: Vec::new(),
+            image: None,
+            map_count: AtomicU32::new(0),
+            destructors: Mutex::new(Vec::new()),
+        })
+    }
+
+    unsafe fn image(
+        context: Handle<Context>,
+        flags: cl_mem_flags,
+        format: *const cl_image_format,
+        desc: *const cl_image_desc,
+        host_ptr: *mut c_void,
+        properties: Vec<cl_mem_properties>,
+    ) -> Result<Self> {
+        check_flags(flags)?;
+        if format.is_null() {
+            return Err(CL_INVALID_IMAGE_FORMAT_DESCRIPTOR);
+        }
+        if desc.is_null() {
+            return Err(CL_INVALID_IMAGE_DESCRIPTOR);
+        }
+        let (format, mut desc) = (*format, *desc);
+        if !context.devices.iter().any(|x| x.config.image_support) {
+            return Err(CL_INVALID_OPERATION);
+        }
+        let element_size = match element_size(&format) {
+            Some(x) if is_supported(&format) => x,
+            Some(_) => return Err(CL_IMAGE_FORMAT_NOT_SUPPORTED),
+            None => return Err(CL_INVALID_IMAGE_FORMAT_DESCRIPTOR),
+        };
+        if desc.num_mip_levels != 0 || desc.mem_samples != 0 {
+            return Err(CL_INVALID_IMAGE_DESCRIPTOR);
+        }
+        let [width, height, depth] = match desc.image_type {
+            CL_MEM_OBJECT_IMAGE1D | CL_MEM_OBJECT_IMAGE1D_BUFFER => [desc.image_width, 1, 1],
+            CL_MEM_OBJECT_IMAGE1D_ARRAY => [desc.image_width, desc.image_array_size, 1],
+            CL_MEM_OBJECT_IMAGE2D => [desc.image_width, desc.image_height, 1],
+            CL_MEM_OBJECT_IMAGE2D_ARRAY => {
+                [desc.image_width, desc.image_height, desc.image_array_size]
+            }
+            CL_MEM_OBJECT_IMAGE3D => [desc.image_width, desc.image_height, desc.image_depth],
+            _ => return Err(CL_INVALID_IMAGE_DESCRIPTOR),
+        };
+        let (max_width, max_height, max_depth) = match desc.image_type {
+            CL_MEM_OBJECT_IMAGE1D_BUFFER => (Device::IMAGE_MAX_BUFFER_SIZE, 1, 1),
+            CL_MEM_OBJECT_IMAGE1D => (Device::IMAGE2D_MAX_SIZE, 1, 1),
+            CL_MEM_OBJECT_IMAGE1D_ARRAY => {
+                (Device::IMAGE2D_MAX_SIZE, Device::IMAGE_MAX_ARRAY_SIZE, 1)
+            }
+            CL_MEM_OBJECT_IMAGE2D => (Device::IMAGE2D_MAX_SIZE, Device::IMAGE2D_MAX_SIZE, 1),
+            CL_MEM_OBJECT_IMAGE2D_ARRAY => (
+                Device::IMAGE2D_MAX_SIZE,
+                Device::IMAGE2D_MAX_SIZE,
+                Device::IMAGE_MAX_ARRAY_SIZE,
+            ),
+            _ => (
+                Device::IMAGE3D_MAX_SIZE,
+                Device::IMAGE3D_MAX_SIZE,
+                Device::IMAGE3D_MAX_SIZE,
+            ),
+        };
+        if width == 0
+            || height == 0
+            || depth == 0
+            || width > max_width
+            || height > max_height
+            || depth > max_depth
+        {
+            return Err(CL_INVALID_IMAGE_SIZE);
+        }
+
+        let buffer = if desc.mem_object.is_null() {
+            None
+        } else {
+            match desc.image_type {
+                CL_MEM_OBJECT_IMAGE1D_BUFFER | CL_MEM_OBJECT_IMAGE2D => {}
+                _ => return Err(CL_INVALID_IMAGE_DESCRIPTOR),
+            }
+            let buffer = handle::<Mem>(desc.mem_object, ObjectKind::Mem)
+                .map_err(|_| CL_INVALID_IMAGE_DESCRIPTOR)?;
+            if buffer.image.is_some() || buffer.context.handle().as_raw() != context.as_raw() {
+                return Err(CL_INVALID_IMAGE_DESCRIPTOR);
+            }
+            if !host_ptr.is_null() || flags & HOST_PTR_FLAGS != 0 {
+                return Err(CL_INVALID_VALUE);
+            }
+            Some(buffer)
+        };
+        if buffer.is_none() && desc.image_type == CL_MEM_OBJECT_IMAGE1D_BUFFER {
+            return Err(CL_INVALID_IMAGE_DESCRIPTOR);
+        }
+        let flags = match buffer {
+            Some(x) => inherit_flags(x.flags, flags)? | (x.flags & HOST_PTR_FLAGS),
+            None => flags,
+        };
+
+        // Pitches may only be given for memory provided by the application.
+        let provided = !host_ptr.is_null() || buffer.is_some();
+        let min_row_pitch = width * element_size;
+        if desc.image_row_pitch == 0 {
+            desc.image_row_pitch = min_row_pitch;
+        } else if !provided
+            || desc.image_row_pitch < min_row_pitch
+            || desc.image_row_pitch % element_size != 0
+        {
+            return Err(CL_INVALID_IMAGE_DESCRIPTOR);
+        }
+        let size = match desc.image_type {
+            CL_MEM_OBJECT_IMAGE1D_ARRAY | CL_MEM_OBJECT_IMAGE2D_ARRAY | CL_MEM_OBJECT_IMAGE3D => {
+                let rows = if desc.image_type == CL_MEM_OBJECT_IMAGE1D_ARRAY {
+                    1
+                } else {
+                    height
+                };
+                let min_slice_pitch = desc.image_row_pitch * rows;
+                if desc.image_slice_pitch == 0 {
+                    desc.image_slice_pitch = min_slice_pitch;
+                } else if !provided
+                    || desc.image_slice_pitch < min_slice_pitch
+                    || desc.image_slice_pitch % desc.image_row_pitch != 0
+                {
+                    return Err(CL_INVALID_IMAGE_DESCRIPTOR);
+                }
+                let slices = if desc.image_type == CL_MEM_OBJECT_IMAGE1D_ARRAY {
+                    height
+                } else {
+                    depth
+                };
+                desc.image_slice_pitch * slices
+            }
+            _ => {
+                desc.image_slice_pitch = 0;
+                desc.image_row_pitch * height
+            }
+        };
+
+        desc.mem_object = ptr::null_mut();
+        let image = Image {
+            format,
+            element_size,
+            desc,
+        };
+        let mut mem = match buffer {
+            Some(buffer) => {
+                if size > buffer.size {
+                    return Err(CL_INVALID_IMAGE_SIZE);
+                }
+                Mem {
+                    context: buffer.context.clone(),
+                    flags,
+                    mem_type: desc.image_type,
+                    data: buffer.data,
+                    size,
+                    host_ptr: buffer.host_ptr,
+                    layout: None,
+                    parent: Some(Retained::new(buffer)),
+                    offset: 0,
+                    uses_svm_pointer: buffer.uses_svm_pointer,
+                    properties,
+                    image: None,
+                    map_count: AtomicU32::new(0),
+                    destructors: Mutex::new(Vec::new()),
+                }
+            }
+            None => {
+                let mut mem = Mem::buffer(context, flags, size, host_ptr, properties).map_err(
+                    |x| match x {
+                        CL_INVALID_BUFFER_SIZE => CL_INVALID_IMAGE_SIZE,
+                        x => x,
+                    },
+                )?;
+                mem.mem_type = desc.image_type;
+                mem
+            }
+        };
+        mem.image = Some(image);
+        Ok(mem)
+    }
+
+    fn host_ptr(flags: cl_mem_flags, host_ptr: *mut c_void) -> HostPtr {
+        if flags & CL_MEM_USE_HOST_PTR != 0 {
+            HostPtr(host_ptr)
+        } else {
+            HostPtr::null()
+        }
+    }
+
+    /// First byte of the object.
+    pub(crate) fn data(&self) -> *mut u8 {
+        self.data.bytes()
+    }
+
+    /// Checks that a byte range lies within the object.
+    pub(crate) fn check_range(&self, offset: size_t, size: size_t) -> Result<()> {
+        match offset.checked_add(size) {
+            Some(end) if end <= self.size => Ok(()),
+            _ => Err(CL_INVALID_VALUE),
+        }
+    }
+
+    pub(crate) fn host_readable(&self) -> Result<()> {
+        if self.flags & (CL_MEM_HOST_WRITE_ONLY | CL_MEM_HOST_NO_ACCESS) != 0 {
+            Err(CL_INVALID_OPERATION)
+        } else {
+            Ok(())
+        }
+    }
+
+    pub(crate) fn host_writable(&self) -> Result<()> {
+        if self.flags & (CL_MEM_HOST_READ_ONLY | CL_MEM_HOST_NO_ACCESS) != 0 {
+            Err(CL_INVALID_OPERATION)
+        } else {
+            Ok(())
+        }
+    }
+
+    pub(crate) fn is_buffer(&self) -> bool {
+        self.mem_type == CL_MEM_OBJECT_BUFFER
+    }
+
+    /// The image of the object, CL_INVALID_MEM_OBJECT for buffers.
+    pub(crate) fn as_image(&self) -> Result<&Image> {
+        self.image.as_ref().ok_or(CL_INVALID_MEM_OBJECT)
+    }
+
+    pub(crate) fn map(&self) {
+        self.map_count.fetch_add(1, Ordering::AcqRel);
+    }
+
+    /// Ends a mapping at mapped_ptr, CL_INVALID_VALUE if it was not returned
+    /// by a map of the object.
+    pub(crate) fn unmap(&self, mapped_ptr: *mut c_void) -> Result<()> {
+        let start = self.data() as usize;
+        let inside = (start..start + self.size).contains(&(mapped_ptr as usize));
+        if !inside {
+            return Err(CL_INVALID_VALUE);
+        }
+        self.map_count
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |x| x.checked_sub(1))
+            .map(|_| ())
+            .map_err(|_| CL_INVALID_VALUE)
+    }
+
+    pub(crate) fn info(&self, param_name: cl_mem_info) -> Result<Vec<u8>> {
+        let info = match param_name {
+            CL_MEM_TYPE => info_value(self.mem_type),
+            CL_MEM_FLAGS => info_value(self.flags),
+            CL_MEM_SIZE => info_value(self.size),
+            CL_MEM_HOST_PTR => info_value(self.host_ptr.0),
+            CL_MEM_MAP_COUNT => info_value(self.map_count.load(Ordering::Acquire)),
+            CL_MEM_CONTEXT => info_value(self.context.handle().as_raw()),
+            CL_MEM_ASSOCIATED_MEMOBJECT => info_value(self.parent_raw()),
+            CL_MEM_OFFSET => info_value(self.offset),
+            CL_MEM_USES_SVM_POINTER => info_value(if self.uses_svm_pointer {
+                CL_TRUE
+            } else {
+                CL_FALSE
+            }),
+            CL_MEM_PROPERTIES => info_slice(&self.properties),
+            _ => return Err(CL_INVALID_VALUE),
+        };
+        Ok(info)
+    }
+
+    fn image_info(&self, param_name: cl_image_info) -> Result<Vec<u8>> {
+        let image = self.as_image()?;
+        let desc = &image.desc;
+        let info = match param_name {
+            CL_IMAGE_FORMAT => info_value(image.format),
+            CL_IMAGE_ELEMENT_SIZE => info_value(image.element_size),
+            CL_IMAGE_ROW_PITCH => info_value(desc.image_row_pitch),
+            CL_IMAGE_SLICE_PITCH => info_value(desc.image_slice_pitch),
+            CL_IMAGE_WIDTH => info_value(desc.image_width),
+            CL_IMAGE_HEIGHT => info_value(match desc.image_type {
+                CL_MEM_OBJECT_IMAGE2D | CL_MEM_OBJECT_IMAGE2D_ARRAY | CL_MEM_OBJECT_IMAGE3D => {
+                    desc.image_height
+                }
+                _ => 0,
+            }),
+            CL_IMAGE_DEPTH => info_value(match desc.image_type {
+                CL_MEM_OBJECT_IMAGE3D => desc.image_depth,
+                _ => 0,
+            }),
+            CL_IMAGE_ARRAY_SIZE => info_value(match desc.image_type {
+                CL_MEM_OBJECT_IMAGE1D_ARRAY | CL_MEM_OBJECT_IMAGE2D_ARRAY => desc.image_array_size,
+                _ => 0,
+            }),
+            CL_IMAGE_BUFFER => info_value(self.parent_raw()),
+            CL_IMAGE_NUM_MIP_LEVELS | CL_IMAGE_NUM_SAMPLES => info_value::<cl_uint>(0),
+            _ => return Err(CL_INVALID_VALUE),
+        };
+        Ok(info)
+    }
+
+    fn parent_raw(&self) -> cl_mem {
+        self.parent
+            .as_ref()
+            .map_or(ptr::null_mut(), |x| x.handle().as_raw())
+    }
+}
+
+impl Drop for Mem {
+    fn drop(&mut self) {
+        let destructors = self
+            .destructors
+            .get_mut()
+            .unwrap_or_else(|x| x.into_inner());
+        while let Some((notify, memobj, user_data)) = destructors.pop() {
+            notify(memobj.0, user_data.0);
+        }
+        if let Some(layout) = self.layout {
+            unsafe { alloc::dealloc(self.data.bytes(), layout) };
+        }
+    }
+}
+
+impl Image {
+    /// Pixels, rows and slices or array elements of the image.
+    pub(crate) fn extent(&self) -> [size_t; 3] {
+        let desc = &self.desc;
+        match desc.image_type {
+            CL_MEM_OBJECT_IMAGE1D_ARRAY => [desc.image_width, desc.image_array_size, 1],
+            CL_MEM_OBJECT_IMAGE2D => [desc.image_width, desc.image_height, 1],
+            CL_MEM_OBJECT_IMAGE2D_ARRAY => {
+                [desc.image_width, desc.image_height, desc.image_array_size]
+            }
+            CL_MEM_OBJECT_IMAGE3D => [desc.image_width, desc.image_height, desc.image_depth],
+            _ => [desc.image_width, 1, 1],
+        }
+    }
+
+    /// Bytes between neighbouring elements in each dimension of `extent`.
+    pub(crate) fn steps(&self) -> [size_t; 3] {
+        let desc = &self.desc;
+        match desc.image_type {
+            // The array elements of a 1D array are slices.
+            CL_MEM_OBJECT_IMAGE1D_ARRAY => [
+                self.element_size,
+                desc.image_slice_pitch,
+                desc.image_slice_pitch,
+            ],
+            _ => [
+                self.element_size,
+                desc.image_row_pitch,
+                desc.image_slice_pitch,
+            ],
+        }
+    }
+
+    pub(crate) fn is_1d_array(&self) -> bool {
+        self.desc.image_type == CL_MEM_OBJECT_IMAGE1D_ARRAY
+    }
+
+    /// Checks that a region lies within the image, returning the byte offset
+    /// of its origin.
+    pub(crate) fn region_offset(&self, origin: [size_t; 3], region: [size_t; 3]) -> Result<size_t> {
+        let extent = self.extent();
+        for i in 0..3 {
+            match origin[i].checked_add(region[i]) {
+                Some(end) if region[i] != 0 && end <= extent[i] => {}
+                _ => return Err(CL_INVALID_VALUE),
+            }
+        }
+        let steps = self.steps();
+        Ok((0..3).map(|i| origin[i] * steps[i]).sum())
+    }
+
+    /// Bytes of one pixel of fill_color, which holds four channels of
+    /// cl_float, cl_int or cl_uint depending on the channel type.
+    pub(crate) unsafe fn fill_pixel(&self, fill_color: *const c_void) -> Vec<u8> {
+        let channels: &[usize] = match self.format.image_channel_order {
+            CL_R => &[0],
+            CL_RG => &[0, 1],
+            CL_BGRA => &[2, 1, 0, 3],
+            _ => &[0, 1, 2, 3],
+        };
+        let float = ptr::read_unaligned(fill_color as *const [f32; 4]);
+        let int = ptr::read_unaligned(fill_color as *const [i32; 4]);
+        let uint = ptr::read_unaligned(fill_color as *const [u32; 4]);
+        let normalize = |x: f32, min: f32, max: f32| (x.max(min).min(1.0) * max).round();
+        let mut pixel = Vec::with_capacity(self.element_size);
+        for &c in channels {
+            match self.format.image_channel_data_type {
+                CL_SNORM_INT8 => pixel.push(normalize(float[c], -1.0, 127.0) as i8 as u8),
+                CL_SNORM_INT16 => pixel
+                    .extend_from_slice(&(normalize(float[c], -1.0, 32767.0) as i16).to_ne_bytes()),
+                CL_UNORM_INT8 => pixel.push(normalize(float[c], 0.0, 255.0) as u8),
+                CL_UNORM_INT16 => pixel
+                    .extend_from_slice(&(normalize(float[c], 0.0, 65535.0) as u16).to_ne_bytes()),
+                CL_SIGNED_INT8 => pixel.push(int[c].clamp(i8::MIN.into(), i8::MAX.into()) as u8),
+                CL_SIGNED_INT16 => pixel.extend_from_slice(
+                    &(int[c].clamp(i16::MIN.into(), i16::MAX.into()) as i16).to_ne_bytes(),
+                ),
+                CL_SIGNED_INT32 => pixel.extend_from_slice(&int[c].to_ne_bytes()),
+                CL_UNSIGNED_INT8 => pixel.push(uint[c].min(u8::MAX.into()) as u8),
+                CL_UNSIGNED_INT16 => {
+                    pixel.extend_from_slice(&(uint[c].min(u16::MAX.into()) as u16).to_ne_bytes())
+                }
+                CL_UNSIGNED_INT32 => pixel.extend_from_slice(&uint[c].to_ne_bytes()),
+                CL_HALF_FLOAT => pixel.extend_from_slice(&half(float[c]).to_ne_bytes()),
+                _ => pixel.extend_from_slice(&float[c].to_ne_bytes()),
+            }
+        }
+        pixel
+    }
+}
+
+/// Converts to IEEE 754 half precision, rounding to nearest.
+fn half(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x7f_ffff;
+    if exponent == 0xff {
+        let nan = if mantissa != 0 { 0x200 } else { 0 };
+        return sign | 0x7c00 | nan;
+    }
+    let exponent = exponent - 127 + 15;
+    if exponent >= 0x1f {
+        sign | 0x7c00
+    } else if exponent <= 0 {
+        if exponent < -10 {
+            return sign;
+        }
+        let mantissa = mantissa | 0x80_0000;
+        let shift = (14 - exponent) as u32;
+        let round = (mantissa >> (shift - 1)) & 1;
+        sign | ((mantissa >> shift) + round) as u16
+    } else {
+        let half = sign | ((exponent as u16) << 10) | (mantissa >> 13) as u16;
+        // A carry into the exponent rounds up to the next power of two.
+        half + ((mantissa >> 12) & 1) as u16
+    }
+}
+
+#[allow(non_upper_case_globals)]
+fn channel_count(order: cl_channel_order) -> Option<size_t> {
+    match order {
+        CL_R | CL_A | CL_INTENSITY | CL_LUMINANCE | CL_DEPTH => Some(1),
+        CL_RG | CL_RA | CL_Rx => Some(2),
+        CL_RGB | CL_RGx => Some(3),
+        CL_RGBA | CL_BGRA | CL_ARGB | CL_RGBx => Some(4),
+        _ => None,
+    }
+}
+
+fn element_size(format: &cl_image_format) -> Option<size_t> {
+    let channel_size = match format.image_channel_data_type {
+        CL_SNORM_INT8 | CL_UNORM_INT8 | CL_SIGNED_INT8 | CL_UNSIGNED_INT8 => 1,
+        CL_SNORM_INT16 | CL_UNORM_INT16 | CL_SIGNED_INT16 | CL_UNSIGNED_INT16 | CL_HALF_FLOAT => 2,
+        CL_SIGNED_INT32 | CL_UNSIGNED_INT32 | CL_FLOAT => 4,
+        // Packed formats, reported as unsupported.
+        CL_UNORM_SHORT_565 | CL_UNORM_SHORT_555 => return Some(2),
+        CL_UNORM_INT_101010 | CL_UNORM_INT_101010_2 | CL_UNORM_INT24 => return Some(4),
+        _ => return None,
+    };
+    Some(channel_count(format.image_channel_order)? * channel_size)
+}
+
+fn is_supported(format: &cl_image_format) -> bool {
+    supported_formats().contains(format)
+}
+
+fn supported_formats() -> Vec<cl_image_format> {
+    let mut formats = Vec::with_capacity(IMAGE_ORDERS.len() * IMAGE_TYPES.len() + 1);
+    for &image_channel_order in &IMAGE_ORDERS {
+        for &image_channel_data_type in &IMAGE_TYPES {
+            formats.push(cl_image_format {
+                image_channel_order,
+                image_channel_data_type,
+            });
+        }
+    }
+    formats.push(cl_image_format {
+        image_channel_order: CL_BGRA,
+        image_channel_data_type: CL_UNORM_INT8,
+    });
+    formats
+}
+
+fn check_flags(flags: cl_mem_flags) -> Result<()> {
+    if flags & !MEM_FLAGS != 0
+        || (flags & ACCESS_FLAGS).count_ones() > 1
+        || (flags & HOST_ACCESS_FLAGS).count_ones() > 1
+        || (flags & CL_MEM_USE_HOST_PTR != 0
+            && flags & (CL_MEM_ALLOC_HOST_PTR | CL_MEM_COPY_HOST_PTR) != 0)
+    {
+        Err(CL_INVALID_VALUE)
+    } else {
+        Ok(())
+    }
+}
+
+fn check_host_ptr(flags: cl_mem_flags, host_ptr: *mut c_void) -> Result<()> {
+    let needed = flags & (CL_MEM_USE_HOST_PTR | CL_MEM_COPY_HOST_PTR) != 0;
+    if needed == host_ptr.is_null() {
+        Err(CL_INVALID_HOST_PTR)
+    } else {
+        Ok(())
+    }
+}
+
+/// Flags of an object sharing the memory of parent, taking the access
+/// flags not given from it.
+fn inherit_flags(parent: cl_mem_flags, flags: cl_mem_flags) -> Result<cl_mem_flags> {
+    let conflict = (parent & CL_MEM_WRITE_ONLY != 0
+        && flags & (CL_MEM_READ_WRITE | CL_MEM_READ_ONLY) != 0)
+        || (parent & CL_MEM_READ_ONLY != 0 && flags & (CL_MEM_READ_WRITE | CL_MEM_WRITE_ONLY) != 0)
+        || (parent & CL_MEM_HOST_WRITE_ONLY != 0 && flags & CL_MEM_HOST_READ_ONLY != 0)
+        || (parent & CL_MEM_HOST_READ_ONLY != 0 && flags & CL_MEM_HOST_WRITE_ONLY != 0)
+        || (parent & CL_MEM_HOST_NO_ACCESS != 0
+            && flags & (CL_MEM_HOST_READ_ONLY | CL_MEM_HOST_WRITE_ONLY) != 0);
+    if conflict {
+        return Err(CL_INVALID_VALUE);
+    }
+    let mut flags = flags;
+    if flags & ACCESS_FLAGS == 0 {
+        flags |= parent & ACCESS_FLAGS;
+    }
+    if flags & HOST_ACCESS_FLAGS == 0 {
+        flags |= parent & HOST_ACCESS_FLAGS;
+    }
+    Ok(flags | (parent & HOST_PTR_FLAGS))
+}
+
+fn max_alloc_size(context: &Context) -> cl_ulong {
+    context
+        .devices
+        .iter()
+        .map(|x| x.config.max_mem_alloc_size)
+        .min()
+        .unwrap_or(0)
+}
+
+fn allocate(size: size_t) -> Result<(HostPtr, Layout)> {
+    let layout = Layout::from_size_align(size, Device::BASE_ADDRESS_ALIGN)
+        .map_err(|_| CL_INVALID_BUFFER_SIZE)?;
+    let data = unsafe { alloc::alloc_zeroed(layout) };
+    if data.is_null() {
+        return Err(CL_MEM_OBJECT_ALLOCATION_FAILURE);
+    }
+    Ok((HostPtr(data as *mut c_void), layout))
+}
+
+/// The memory property list of a create call, empty if the list is null and
+/// otherwise including the terminating zero. No properties are supported.
+unsafe fn mem_properties(list: *const cl_mem_properties) -> Result<Vec<cl_mem_properties>> {
+    if list.is_null() {
+        return Ok(Vec::new());
+    }
+    if !properties(list).is_empty() {
+        return Err(CL_INVALID_PROPERTY);
+    }
+    Ok(vec![0])
+}
+
+/// Checks that ptr lies within an allocation of clSVMAlloc.
+pub(crate) fn svm_contains(ptr: *const c_void) -> bool {
+    let address = ptr as usize;
+    let allocations = SVM.lock().unwrap();
+    allocations
+        .range(..=address)
+        .next_back()
+        .is_some_and(|(start, layout)| address < start + layout.size())
+}
+
+/// Frees an allocation of clSVMAlloc, ignoring other pointers.
+pub(crate) fn svm_free(ptr: *mut c_void) {
+    let layout = SVM.lock().unwrap().remove(&(ptr as usize));
+    if let Some(layout) = layout {
+        unsafe { alloc::dealloc(ptr as *mut u8, layout) };
+    }
+}
+
+unsafe fn new_mem(mem: Result<Mem>, errcode_ret: *mut cl_int) -> cl_mem {
+    let mem = mem.map(|x| Handle::new(Mock::dispatch(), ObjectKind::Mem, x));
+    into_raw(mem, errcode_ret)
+}
+
+unsafe extern "system" fn create_buffer_with_properties(
+    context: cl_context,
+    properties: *const cl_mem_properties,
+    flags: cl_mem_flags,
+    size: size_t,
+    host_ptr: *mut c_void,
+    errcode_ret: *mut cl_int,
+) -> cl_mem {
+    let mem = handle::<Context>(context, ObjectKind::Context).and_then(|context| {
+        Mem::buffer(context, flags, size, host_ptr, mem_properties(properties)?)
+    });
+    new_mem(mem, errcode_ret)
+}
+
+unsafe extern "system" fn create_sub_buffer(
+    buffer: cl_mem,
+    flags: cl_mem_flags,
+    buffer_create_type: cl_buffer_create_type,
+    buffer_create_info: *const c_void,
+    errcode_ret: *mut cl_int,
+) -> cl_mem {
+    let mem = handle::<Mem>(buffer, ObjectKind::Mem).and_then(|buffer| {
+        if buffer_create_type != CL_BUFFER_CREATE_TYPE_REGION || buffer_create_info.is_null() {
+            return Err(CL_INVALID_VALUE);
+        }
+        Mem::sub_buffer(
+            buffer,
+            flags,
+            *(buffer_create_info as *const cl_buffer_region),
+        )
+    });
+    new_mem(mem, errcode_ret)
+}
+
+unsafe extern "system" fn create_image(
+    context: cl_context,
+    flags: cl_mem_flags,
+    image_format: *const cl_image_format,
+    image_desc: *const cl_image_desc,
+    host_ptr: *mut c_void,
+    errcode_ret: *mut cl_int,
+) -> cl_mem {
+    create_image_with_properties(
+        context,
+        ptr::null(),
+        flags,
+        image_format,
+        image_desc,
+        host_ptr,
+        errcode_ret,
+    )
+}
+
+unsafe extern "system" fn create_image_with_properties(
+    context: cl_context,
+    properties: *const cl_mem_properties,
+    flags: cl_mem_flags,
+    image_format: *const cl_image_format,
+    image_desc: *const cl_image_desc,
+    host_ptr: *mut c_void,
+    errcode_ret: *mut cl_int,
+) -> cl_mem {
+    let mem = handle::<Context>(context, ObjectKind::Context).and_then(|context| {
+        let properties = mem_properties(properties)?;
+        Mem::image(
+            context,
+            flags,
+            image_format,
+            image_desc,
+            host_ptr,
+            properties,
+        )
+    });
+    new_mem(mem, errcode_ret)
+}
+
+unsafe extern "system" fn create_image_2d(
+    context: cl_context,
+    flags: cl_mem_flags,
+    image_format: *const cl_image_format,
+    image_width: size_t,
+    image_height: size_t,
+    image_row_pitch: size_t,
+    host_ptr: *mut c_void,
+    errcode_ret: *mut cl_int,
+) -> cl_mem {
+    let desc = cl_image_desc {
+        image_type: CL_MEM_OBJECT_IMAGE2D,
+        image_width,
+        image_height,
+        image_row_pitch,
+        ..Default::default()
+    };
+    create_image(context, flags, image_format, &desc, host_ptr, errcode_ret)
+}
+
+unsafe extern "system" fn create_image_3d(
+    context: cl_context,
+    flags: cl_mem_flags,
+    image_format: *const cl_image_format,
+    image_width: size_t,
+    image_height: size_t,
+    image_depth: size_t,
+    image_row_pitch: size_t,
+    image_slice_pitch: size_t,
+    host_ptr: *mut c_void,
+    errcode_ret: *mut cl_int,
+) -> cl_mem {
+    let desc = cl_image_desc {
+        image_type: CL_MEM_OBJECT_IMAGE3D,
+        image_width,
+        image_height,
+        image_depth,
+        image_row_pitch,
+        image_slice_pitch,
+        ..Default::default()
+    };
+    create_image(context, flags, image_format, &desc, host_ptr, errcode_ret)
+}
+
+unsafe extern "system" fn get_supported_image_formats(
+    context: cl_context,
+    flags: cl_mem_flags,
+    image_type: cl_mem_object_type,
+    num_entries: cl_uint,
+    image_formats: *mut cl_image_format,
+    num_image_formats: *mut cl_uint,
+) -> cl_int {
+    status((|| {
+        let context = handle::<Context>(context, ObjectKind::Context)?;
+        check_flags(flags)?;
+        match image_type {
+            CL_MEM_OBJECT_IMAGE1D
+            | CL_MEM_OBJECT_IMAGE1D_BUFFER
+            | CL_MEM_OBJECT_IMAGE1D_ARRAY
+            | CL_MEM_OBJECT_IMAGE2D
+            | CL_MEM_OBJECT_IMAGE2D_ARRAY
+            | CL_MEM_OBJECT_IMAGE3D => {}
+            _ => return Err(CL_INVALID_VALUE),
+        }
+        if num_entries == 0 && !image_formats.is_null() {
+            return Err(CL_INVALID_VALUE);
+        }
+        let formats = if context.devices.iter().any(|x| x.config.image_support) {
+            supported_formats()
+        } else {
+            Vec::new()
+        };
+        if !image_formats.is_null() {
+            for (i, format) in formats.iter().take(num_entries as usize).enumerate() {
+                *image_formats.add(i) = *format;
+            }
+        }
+        if !num_image_formats.is_null() {
+            *num_image_formats = formats.len() as cl_uint;
+        }
+        Ok(())
+    })())
+}
+
+unsafe extern "system" fn get_image_info(
+    image: cl_mem,
+    param_name: cl_image_info,
+    param_value_size: size_t,
+    param_value: *mut c_void,
+    param_value_size_ret: *mut size_t,
+) -> cl_int {
+    let info = handle::<Mem>(image, ObjectKind::Mem).and_then(|x| x.image_info(param_name));
+    write_info(info, param_value_size, param_value, param_value_size_ret)
+}
+
+unsafe extern "system" fn set_mem_object_destructor_callback(
+    memobj: cl_mem,
+    pfn_notify: Option<MemNotify>,
+    user_data: *mut c_void,
+) -> cl_int {
+    status(handle::<Mem>(memobj, ObjectKind::Mem).and_then(|mem| {
+        let notify = pfn_notify.ok_or(CL_INVALID_VALUE)?;
+        mem.destructors
+            .lock()
+            .unwrap()
+            .push((notify, HostPtr(memobj), HostPtr(user_data)));
+        Ok(())
+    }))
+}
+
+unsafe extern "system" fn create_pipe(
+    context: cl_context,
+    _flags: cl_mem_flags,
+    _pipe_packet_size: cl_uint,
+    _pipe_max_packets: cl_uint,
+    _properties: *const cl_pipe_properties,
+    errcode_ret: *mut cl_int,
+) -> cl_mem {
+    // Pipes are not supported by the device.
+    let mem = handle::<Context>(context, ObjectKind::Context).and(Err(CL_INVALID_OPERATION));
+    new_mem(mem, errcode_ret)
+}
+
+unsafe extern "system" fn get_pipe_info(
+    pipe: cl_mem,
+    _param_name: cl_pipe_info,
+    _param_value_size: size_t,
+    _param_value: *mut c_void,
+    _param_value_size_ret: *mut size_t,
+) -> cl_int {
+    // No memory object is a pipe.
+    status(handle::<Mem>(pipe, ObjectKind::Mem).and(Err(CL_INVALID_MEM_OBJECT)))
+}
+
+unsafe extern "system" fn svm_alloc(
+    context: cl_context,
+    flags: cl_svm_mem_flags,
+    size: size_t,
+    alignment: cl_uint,
+) -> *mut c_void {
+    let context = match handle::<Context>(context, ObjectKind::Context) {
+        Ok(x) => x,
+        Err(_) => return ptr::null_mut(),
+    };
+    let valid_flags = ACCESS_FLAGS | CL_MEM_SVM_FINE_GRAIN_BUFFER | CL_MEM_SVM_ATOMICS;
+    let invalid = flags & !valid_flags != 0
+        || (flags & ACCESS_FLAGS).count_ones() > 1
+        || (flags & CL_MEM_SVM_ATOMICS != 0 && flags & CL_MEM_SVM_FINE_GRAIN_BUFFER == 0)
+        || size == 0
+        || size as cl_ulong > max_alloc_size(&context);
+    if invalid {
+        return ptr::null_mut();
+    }
+    let alignment = match alignment {
+        0 => Device::BASE_ADDRESS_ALIGN,
+        x => x as usize,
+    };
+    let layout = match Layout::from_size_align(size, alignment) {
+        Ok(x) => x,
+        Err(_) => return ptr::null_mut(),
+    };
+    let data = alloc::alloc_zeroed(layout);
+    if !data.is_null() {
+        SVM.lock().unwrap().insert(data as usize, layout);
+    }
+    data as *mut c_void
+}
+
+unsafe extern "system" fn svm_free_entry(context: cl_context, svm_pointer: *mut c_void) {
+    if handle::<Context>(context, ObjectKind::Context).is_ok() {
+        svm_free(svm_pointer);
+    }
+}
+
+pub(crate) fn extend_dispatch(table: &mut cl_icd_dispatch) {
+    table.clCreateBufferWithProperties = Some(create_buffer_with_properties);
+    table.clCreateSubBuffer = Some(create_sub_buffer);
+    table.clCreateImage = Some(create_image);
+    table.clCreateImageWithProperties = Some(create_image_with_properties);
+    table.clCreateImage2D = Some(create_image_2d);
+    table.clCreateImage3D = Some(create_image_3d);
+    table.clGetSupportedImageFormats = Some(get_supported_image_formats);
+    table.clGetImageInfo = Some(get_image_info);
+    table.clSetMemObjectDestructorCallback = Some(set_mem_object_destructor_callback);
+    table.clCreatePipe = Some(create_pipe);
+    table.clGetPipeInfo = Some(get_pipe_info);
+    table.clSVMAlloc = Some(svm_alloc);
+    table.clSVMFree = Some(svm_free_entry);
+}
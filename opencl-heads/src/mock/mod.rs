@@ -0,0 +1,435 @@
+/*
+ * mod.rs - Simulated OpenCL platform for testing.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+//!
+//! With the `mock` feature, the functions in `ffi` are served by a platform
+//! simulated in this process instead of the installed vendors, so that code
+//! built on `ffi` can be tested on machines without an OpenCL device. The
+//! platform has a single CPU device described by `DeviceConfig`:
+//!
+//! * Buffers, sub-buffers and images live in host memory, mapping returns a
+//!   pointer into that memory.
+//! * Queues run their commands in order on the calling thread once the wait
+//!   lists are complete. Events record profiling timestamps on queues created
+//!   with CL_QUEUE_PROFILING_ENABLE.
+//! * Programs are not compiled, a build records the kernels declared in the
+//!   source. Launching a kernel runs the Rust closure registered for its name
+//!   with `register_kernel`, kernels without one complete without effect.
+//! * Native kernels call the host function directly.
+//!
+//! ```ignore
+//! opencl_heads::mock::register_kernel("fill", |call| {
+//!     let data = unsafe { call.buffer::<i32>(0) }.unwrap();
+//!     let value = call.scalar::<i32>(1).unwrap();
+//!     for id in call.global_ids() {
+//!         data[id[0]] = value;
+//!     }
+//! });
+//! ```
+//!
+mod context;
+mod device;
+mod event;
+mod kernel;
+mod memory;
+mod program;
+mod queue;
+mod sampler;
+mod transfer;
+
+pub use self::device::DeviceConfig;
+pub use self::kernel::{register_kernel, KernelCall};
+
+use self::context::Context;
+use self::device::Device;
+use self::memory::Mem;
+use self::queue::Queue;
+use crate::consts::*;
+use crate::dispatch::cl_icd_dispatch;
+use crate::driver::{info_slice, info_string, info_value, Driver, Handle, ObjectKind, Result};
+use crate::library::LoadError;
+use crate::types::*;
+use libc::c_void;
+use std::ops::Deref;
+use std::sync::Mutex;
+
+const PLATFORM_NAME: &str = "Mock Platform";
+const VENDOR: &str = "opencl-rs";
+const VERSION: &str = "OpenCL 3.0 Mock";
+const EXTENSIONS: &[&str] = &["cl_khr_icd"];
+
+struct Settings {
+    config: Option<DeviceConfig>,
+    started: bool,
+}
+
+static SETTINGS: Mutex<Settings> = Mutex::new(Settings {
+    config: None,
+    started: false,
+});
+
+/// Sets the device of the mock platform, this must happen before any call
+/// into `ffi`.
+pub fn configure(config: DeviceConfig) -> std::result::Result<(), LoadError> {
+    let mut settings = SETTINGS.lock().unwrap_or_else(|x| x.into_inner());
+    if settings.started {
+        return Err(LoadError::AlreadyLoaded(String::from("mock")));
+    }
+    settings.config = Some(config);
+    Ok(())
+}
+
+/// Platforms of the mock vendor registered with the ICD loader.
+pub(crate) fn platforms() -> Vec<cl_platform_id> {
+    Mock::instance()
+        .platforms
+        .iter()
+        .map(|x| x.as_raw())
+        .collect()
+}
+
+pub(crate) struct Mock {
+    platforms: Vec<Handle<Platform>>,
+}
+
+pub(crate) struct Platform {
+    devices: Vec<Handle<Device>>,
+}
+
+impl Mock {
+    fn new() -> Self {
+        let config = {
+            let mut settings = SETTINGS.lock().unwrap_or_else(|x| x.into_inner());
+            settings.started = true;
+            settings.config.take().unwrap_or_default()
+        };
+        let device = Handle::new(Self::dispatch(), ObjectKind::Device, Device::new(config));
+        let platform = Platform {
+            devices: vec![device],
+        };
+        Mock {
+            platforms: vec![Handle::new(
+                Self::dispatch(),
+                ObjectKind::Platform,
+                platform,
+            )],
+        }
+    }
+
+    pub(crate) fn platform(&self) -> Handle<Platform> {
+        self.platforms[0]
+    }
+}
+
+impl Driver for Mock {
+    crate::driver_instance!(Mock = Mock::new());
+
+    type Platform = Platform;
+    type Device = Device;
+    type Context = Context;
+    type Mem = Mem;
+    type Queue = Queue;
+
+    fn platforms(&self) -> &[Handle<Platform>] {
+        &self.platforms
+    }
+
+    fn platform_info(
+        &self,
+        _platform: Handle<Platform>,
+        param_name: cl_platform_info,
+    ) -> Result<Vec<u8>> {
+        match param_name {
+            CL_PLATFORM_PROFILE => Ok(info_string("FULL_PROFILE")),
+            CL_PLATFORM_VERSION => Ok(info_string(VERSION)),
+            CL_PLATFORM_NAME => Ok(info_string(PLATFORM_NAME)),
+            CL_PLATFORM_VENDOR => Ok(info_string(VENDOR)),
+            CL_PLATFORM_EXTENSIONS => Ok(info_string(&EXTENSIONS.join(" "))),
+            CL_PLATFORM_HOST_TIMER_RESOLUTION => Ok(info_value::<cl_ulong>(1)),
+            CL_PLATFORM_NUMERIC_VERSION => Ok(info_value(version(3, 0, 0))),
+            CL_PLATFORM_EXTENSIONS_WITH_VERSION => {
+                Ok(info_slice(&name_versions(EXTENSIONS, version(1, 0, 0))))
+            }
+            _ => Err(CL_INVALID_VALUE),
+        }
+    }
+
+    fn icd_suffix(&self) -> &str {
+        "MOCK"
+    }
+
+    fn devices(
+        &self,
+        platform: Handle<Platform>,
+        device_type: cl_device_type,
+    ) -> Vec<Handle<Device>> {
+        platform
+            .devices
+            .iter()
+            .copied()
+            .filter(|x| {
+                device_type == CL_DEVICE_TYPE_ALL
+                    || device_type & (x.config.device_type | CL_DEVICE_TYPE_DEFAULT) != 0
+            })
+            .collect()
+    }
+
+    fn device_info(&self, device: Handle<Device>, param_name: cl_device_info) -> Result<Vec<u8>> {
+        device.info(param_name)
+    }
+
+    fn create_context(
+        &self,
+        properties: &[cl_context_properties],
+        devices: &[Handle<Device>],
+    ) -> Result<Context> {
+        Context::new(properties, devices)
+    }
+
+    fn context_info(
+        &self,
+        context: Handle<Context>,
+        param_name: cl_context_info,
+    ) -> Result<Vec<u8>> {
+        context.info(param_name)
+    }
+
+    fn create_buffer(
+        &self,
+        context: Handle<Context>,
+        flags: cl_mem_flags,
+        size: size_t,
+        host_ptr: *mut c_void,
+    ) -> Result<Mem> {
+        Mem::buffer(context, flags, size, host_ptr, Vec::new())
+    }
+
+    fn mem_info(&self, mem: Handle<Mem>, param_name: cl_mem_info) -> Result<Vec<u8>> {
+        mem.info(param_name)
+    }
+
+    fn create_queue(
+        &self,
+        context: Handle<Context>,
+        device: Handle<Device>,
+        properties: cl_command_queue_properties,
+    ) -> Result<Queue> {
+        Queue::new(context, device, properties)
+    }
+
+    fn queue_info(
+        &self,
+        queue: Handle<Queue>,
+        param_name: cl_command_queue_info,
+    ) -> Result<Vec<u8>> {
+        queue.info(param_name)
+    }
+
+    fn flush(&self, queue: Handle<Queue>) -> Result<()> {
+        Queue::pump(queue);
+        Ok(())
+    }
+
+    fn finish(&self, queue: Handle<Queue>) -> Result<()> {
+        Queue::finish(queue);
+        Ok(())
+    }
+
+    fn extend_dispatch(table: &mut cl_icd_dispatch) {
+        context::extend_dispatch(table);
+        device::extend_dispatch(table);
+        event::extend_dispatch(table);
+        kernel::extend_dispatch(table);
+        memory::extend_dispatch(table);
+        program::extend_dispatch(table);
+        queue::extend_dispatch(table);
+        sampler::extend_dispatch(table);
+        transfer::extend_dispatch(table);
+    }
+}
+
+/// Reference held by one mock object on another, released on drop.
+pub(crate) struct Retained<T>(Handle<T>);
+
+impl<T> Retained<T> {
+    pub(crate) fn new(handle: Handle<T>) -> Self {
+        handle.retain();
+        Retained(handle)
+    }
+
+    /// Takes over the reference of a newly created object.
+    pub(crate) fn adopt(handle: Handle<T>) -> Self {
+        Retained(handle)
+    }
+
+    pub(crate) fn handle(&self) -> Handle<T> {
+        self.0
+    }
+}
+
+impl<T> Clone for Retained<T> {
+    fn clone(&self) -> Self {
+        Retained::new(self.0)
+    }
+}
+
+impl<T> Drop for Retained<T> {
+    fn drop(&mut self) {
+        unsafe { self.0.release() };
+    }
+}
+
+impl<T> Deref for Retained<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+/// A pointer owned by the application, e.g. callback user data or a mapped
+/// region, whose validity is the application's responsibility as in OpenCL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct HostPtr(pub(crate) *mut c_void);
+
+unsafe impl Send for HostPtr {}
+unsafe impl Sync for HostPtr {}
+
+impl HostPtr {
+    pub(crate) fn null() -> Self {
+        HostPtr(std::ptr::null_mut())
+    }
+
+    pub(crate) fn bytes(self) -> *mut u8 {
+        self.0 as *mut u8
+    }
+}
+
+/// Encodes a version as `CL_MAKE_VERSION` does.
+pub(crate) const fn version(major: cl_uint, minor: cl_uint, patch: cl_uint) -> cl_version {
+    (major << (CL_VERSION_MINOR_BITS + CL_VERSION_PATCH_BITS))
+        | (minor << CL_VERSION_PATCH_BITS)
+        | patch
+}
+
+pub(crate) fn name_versions(names: &[&str], version: cl_version) -> Vec<cl_name_version> {
+    names
+        .iter()
+        .map(|name| {
+            let mut entry = cl_name_version {
+                version,
+                ..Default::default()
+            };
+            for (target, byte) in entry.name.iter_mut().zip(name.bytes()) {
+                *target = byte as c_char;
+            }
+            entry
+        })
+        .collect()
+}
+
+/// Null terminated string argument of an entry point.
+pub(crate) unsafe fn c_string(value: *const c_char) -> Option<String> {
+    if value.is_null() {
+        None
+    } else {
+        Some(
+            std::ffi::CStr::from_ptr(value)
+                .to_string_lossy()
+                .into_owned(),
+        )
+    }
+}
+
+/************************/
+/* /\ /\ /\ /\ /\ /\ /\ */
+/*|__|__|__|__|__|__|__|*/
+/*|  |  |  |  |  |  |  |*/
+/*|  |  Unit Tests  |  |*/
+/*|__|__|__|__|__|__|__|*/
+/*|__|__|__|__|__|__|__|*/
+/************************/
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::ffi;
+    use std::ptr;
+
+    /// The mock device and a context on it.
+    pub(crate) fn context() -> (cl_device_id, cl_context) {
+        let mut platform: cl_platform_id = ptr::null_mut();
+        let mut device: cl_device_id = ptr::null_mut();
+        let mut status_code = CL_INVALID_VALUE;
+        unsafe {
+            assert_eq!(
+                CL_SUCCESS,
+                ffi::clGetPlatformIDs(1, &mut platform, ptr::null_mut())
+            );
+            assert_eq!(
+                CL_SUCCESS,
+                ffi::clGetDeviceIDs(
+                    platform,
+                    CL_DEVICE_TYPE_DEFAULT,
+                    1,
+                    &mut device,
+                    ptr::null_mut()
+                )
+            );
+            let context = ffi::clCreateContext(
+                ptr::null(),
+                1,
+                &device,
+                None,
+                ptr::null_mut(),
+                &mut status_code,
+            );
+            assert_eq!(CL_SUCCESS, status_code);
+            (device, context)
+        }
+    }
+
+    #[test]
+    fn test_configure_after_start() {
+        let (_, context) = context();
+        assert!(matches!(
+            configure(DeviceConfig::default()),
+            Err(LoadError::AlreadyLoaded(_))
+        ));
+        unsafe { ffi::clReleaseContext(context) };
+    }
+
+    #[test]
+    fn test_platform_info() {
+        let mut platform: cl_platform_id = ptr::null_mut();
+        let mut name = [0u8; 32];
+        let mut size: size_t = 0;
+        unsafe {
+            ffi::clGetPlatformIDs(1, &mut platform, ptr::null_mut());
+            let status_code = ffi::clGetPlatformInfo(
+                platform,
+                CL_PLATFORM_NAME,
+                name.len(),
+                name.as_mut_ptr() as *mut c_void,
+                &mut size,
+            );
+            assert_eq!(CL_SUCCESS, status_code);
+        }
+        assert_eq!(b"Mock Platform\0", &name[..size]);
+        assert_eq!(0x00c0_0000, version(3, 0, 0));
+    }
+}
@@ -0,0 +1,907 @@
+/*
+ * program.rs - Programs of the mock platform.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+//!
+//! Nothing is compiled, a build only reads the kernel signatures of the
+//! source so that kernels and their argument info can be created. A source
+//! containing `#error` fails to build. Binaries are the source behind a
+//! short header and can be loaded back with clCreateProgramWithBinary.
+//!
+use super::context::Context;
+use super::device::Device;
+use super::{c_string, Mock, Retained};
+use crate::consts::*;
+use crate::dispatch::cl_icd_dispatch;
+use crate::driver::{
+    handle, info_slice, info_string, info_value, into_raw, release, retain, status, write_info,
+    Driver, Handle, ObjectKind, Result,
+};
+use crate::types::*;
+use libc::{c_char, c_uchar, c_void};
+use std::ptr;
+use std::slice;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+type BuildNotify = extern "C" fn(program: cl_program, user_data: *mut c_void);
+
+const PROGRAM: u32 = ObjectKind::Program as u32;
+const BINARY_MAGIC: &[u8] = b"MOCKCL\0";
+const ARG_INFO_OPTION: &str = "-cl-kernel-arg-info";
+
+/// Signature of a kernel function found in a program source.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct KernelDecl {
+    pub(crate) name: String,
+    pub(crate) args: Vec<ArgDecl>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ArgDecl {
+    pub(crate) address: cl_kernel_arg_address_qualifier,
+    pub(crate) access: cl_kernel_arg_access_qualifier,
+    pub(crate) type_qualifier: cl_kernel_arg_type_qualifier,
+    pub(crate) type_name: String,
+    pub(crate) name: String,
+}
+
+impl ArgDecl {
+    pub(crate) fn is_pointer(&self) -> bool {
+        self.type_name.ends_with('*')
+    }
+}
+
+pub(crate) struct Program {
+    pub(crate) context: Retained<Context>,
+    devices: Vec<Handle<Device>>,
+    // Empty for programs created from binaries.
+    source: String,
+    // Source of the kernels, also the payload of the binary.
+    code: String,
+    state: Mutex<Build>,
+    // Kernels created from the program, which prevent a rebuild.
+    pub(crate) kernel_count: AtomicUsize,
+}
+
+struct Build {
+    status: cl_build_status,
+    options: String,
+    log: String,
+    binary_type: cl_program_binary_type,
+    kernels: Vec<KernelDecl>,
+}
+
+impl Program {
+    fn new(
+        context: Handle<Context>,
+        devices: Vec<Handle<Device>>,
+        source: String,
+        code: String,
+        binary_type: cl_program_binary_type,
+    ) -> Self {
+        Program {
+            context: Retained::new(context),
+            devices,
+            source,
+            code,
+            state: Mutex::new(Build {
+                status: CL_BUILD_NONE,
+                options: String::new(),
+                log: String::new(),
+                binary_type,
+                kernels: Vec::new(),
+            }),
+            kernel_count: AtomicUsize::new(0),
+        }
+    }
+
+    /// Declaration of a kernel of the built program.
+    pub(crate) fn kernel(&self, name: &str) -> Result<KernelDecl> {
+        self.kernels()?
+            .into_iter()
+            .find(|x| x.name == name)
+            .ok_or(CL_INVALID_KERNEL_NAME)
+    }
+
+    /// Kernels of the program, CL_INVALID_PROGRAM_EXECUTABLE unless it was
+    /// built successfully.
+    pub(crate) fn kernels(&self) -> Result<Vec<KernelDecl>> {
+        let state = self.state.lock().unwrap();
+        if state.status != CL_BUILD_SUCCESS
+            || state.binary_type != CL_PROGRAM_BINARY_TYPE_EXECUTABLE
+        {
+            return Err(CL_INVALID_PROGRAM_EXECUTABLE);
+        }
+        Ok(state.kernels.clone())
+    }
+
+    /// Whether the argument info of the kernels is kept.
+    pub(crate) fn has_arg_info(&self) -> bool {
+        let state = self.state.lock().unwrap();
+        state
+            .options
+            .split_whitespace()
+            .any(|x| x == ARG_INFO_OPTION)
+    }
+
+    fn binary(&self) -> Vec<u8> {
+        let state = self.state.lock().unwrap();
+        if state.binary_type == CL_PROGRAM_BINARY_TYPE_NONE {
+            return Vec::new();
+        }
+        let mut binary = BINARY_MAGIC.to_vec();
+        binary.push(state.binary_type as u8);
+        binary.extend_from_slice(self.code.as_bytes());
+        binary
+    }
+
+    /// Builds, compiles or links the program into binary_type.
+    fn build(&self, options: String, binary_type: cl_program_binary_type) -> Result<()> {
+        if self.kernel_count.load(Ordering::Acquire) > 0 {
+            return Err(CL_INVALID_OPERATION);
+        }
+        let mut state = self.state.lock().unwrap();
+        state.options = options;
+        match self
+            .code
+            .lines()
+            .find(|x| x.trim_start().starts_with("#error"))
+        {
+            Some(line) => {
+                state.status = CL_BUILD_ERROR;
+                state.log = format!("error: {}", line.trim());
+                state.binary_type = CL_PROGRAM_BINARY_TYPE_NONE;
+                state.kernels = Vec::new();
+                Err(match binary_type {
+                    CL_PROGRAM_BINARY_TYPE_COMPILED_OBJECT => CL_COMPILE_PROGRAM_FAILURE,
+                    CL_PROGRAM_BINARY_TYPE_EXECUTABLE => CL_BUILD_PROGRAM_FAILURE,
+                    _ => CL_LINK_PROGRAM_FAILURE,
+                })
+            }
+            None => {
+                state.status = CL_BUILD_SUCCESS;
+                state.log = String::new();
+                state.binary_type = binary_type;
+                state.kernels = parse_kernels(&self.code);
+                Ok(())
+            }
+        }
+    }
+
+    fn info(program: Handle<Program>, param_name: cl_program_info) -> Result<Vec<u8>> {
+        let info = match param_name {
+            CL_PROGRAM_REFERENCE_COUNT => info_value(program.ref_count()),
+            CL_PROGRAM_CONTEXT => info_value(program.context.handle().as_raw()),
+            CL_PROGRAM_NUM_DEVICES => info_value(program.devices.len() as cl_uint),
+            CL_PROGRAM_DEVICES => {
+                let devices: Vec<cl_device_id> =
+                    program.devices.iter().map(|x| x.as_raw()).collect();
+                info_slice(&devices)
+            }
+            CL_PROGRAM_SOURCE => info_string(&program.source),
+            CL_PROGRAM_IL => Vec::new(),
+            CL_PROGRAM_BINARY_SIZES => {
+                info_slice(&vec![program.binary().len(); program.devices.len()])
+            }
+            CL_PROGRAM_NUM_KERNELS => info_value(program.kernels()?.len()),
+            CL_PROGRAM_KERNEL_NAMES => {
+                let names: Vec<String> = program.kernels()?.into_iter().map(|x| x.name).collect();
+                info_string(&names.join(";"))
+            }
+            CL_PROGRAM_SCOPE_GLOBAL_CTORS_PRESENT | CL_PROGRAM_SCOPE_GLOBAL_DTORS_PRESENT => {
+                info_value(CL_FALSE)
+            }
+            _ => return Err(CL_INVALID_VALUE),
+        };
+        Ok(info)
+    }
+
+    fn build_info(&self, param_name: cl_program_build_info) -> Result<Vec<u8>> {
+        let state = self.state.lock().unwrap();
+        let info = match param_name {
+            CL_PROGRAM_BUILD_STATUS => info_value(state.status),
+            CL_PROGRAM_BUILD_OPTIONS => info_string(&state.options),
+            CL_PROGRAM_BUILD_LOG => info_string(&state.log),
+            CL_PROGRAM_BINARY_TYPE => info_value(state.binary_type),
+            CL_PROGRAM_BUILD_GLOBAL_VARIABLE_TOTAL_SIZE => info_value::<size_t>(0),
+            _ => return Err(CL_INVALID_VALUE),
+        };
+        Ok(info)
+    }
+
+    fn check_devices(&self, num_devices: cl_uint, device_list: *const cl_device_id) -> Result<()> {
+        let devices = unsafe { self.context.device_list(num_devices, device_list)? };
+        if devices
+            .iter()
+            .all(|x| self.devices.iter().any(|y| y.as_raw() == x.as_raw()))
+        {
+            Ok(())
+        } else {
+            Err(CL_INVALID_DEVICE)
+        }
+    }
+}
+
+/// Splits a source into identifiers and single punctuation characters,
+/// without comments, preprocessor lines or literals.
+fn tokenize(source: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+    let mut line_start = true;
+    while let Some(c) = chars.next() {
+        match c {
+            '/' if chars.peek() == Some(&'/') => {
+                chars.by_ref().find(|x| *x == '\n');
+                line_start = true;
+                continue;
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut last = ' ';
+                for x in chars.by_ref() {
+                    if last == '*' && x == '/' {
+                        break;
+                    }
+                    last = x;
+                }
+                continue;
+            }
+            '#' if line_start => {
+                chars.by_ref().find(|x| *x == '\n');
+                line_start = true;
+                continue;
+            }
+            '"' | '\'' => {
+                let mut escaped = false;
+                for x in chars.by_ref() {
+                    if x == c && !escaped {
+                        break;
+                    }
+                    escaped = x == '\\' && !escaped;
+                }
+            }
+            x if x.is_alphanumeric() || x == '_' => {
+                let mut token = x.to_string();
+                while let Some(&y) = chars.peek() {
+                    if !(y.is_alphanumeric() || y == '_') {
+                        break;
+                    }
+                    token.push(y);
+                    chars.next();
+                }
+                tokens.push(token);
+            }
+            x if x.is_whitespace() => {}
+            x => tokens.push(x.to_string()),
+        }
+        line_start = c == '\n' || (line_start && c.is_whitespace());
+    }
+    tokens
+}
+
+/// Index of the parenthesis closing the one at open, or the end of tokens.
+fn closing(tokens: &[String], open: usize) -> usize {
+    let mut depth = 0;
+    for (i, token) in tokens.iter().enumerate().skip(open) {
+        match token.as_str() {
+            "(" => depth += 1,
+            ")" => depth -= 1,
+            _ => {}
+        }
+        if depth == 0 {
+            return i;
+        }
+    }
+    tokens.len()
+}
+
+/// Kernel signatures of a program source.
+fn parse_kernels(source: &str) -> Vec<KernelDecl> {
+    let tokens = tokenize(source);
+    let mut kernels = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        if tokens[i] != "kernel" && tokens[i] != "__kernel" {
+            i += 1;
+            continue;
+        }
+        // Attributes may precede the return type.
+        let mut j = i + 1;
+        while tokens.get(j).map(String::as_str) == Some("__attribute__") {
+            j = closing(&tokens, j + 1) + 1;
+        }
+        if tokens.get(j).map(String::as_str) != Some("void")
+            || tokens.get(j + 2).map(String::as_str) != Some("(")
+        {
+            i += 1;
+            continue;
+        }
+        let (name, open) = (tokens[j + 1].clone(), j + 2);
+        let end = closing(&tokens, open);
+        let params = &tokens[(open + 1).min(end)..end];
+        let args = params
+            .split(|x| x == ",")
+            .filter(|x| !x.is_empty() && *x != ["void"])
+            .map(parse_arg)
+            .collect();
+        kernels.push(KernelDecl { name, args });
+        i = end + 1;
+    }
+    kernels
+}
+
+fn parse_arg(tokens: &[String]) -> ArgDecl {
+    let mut arg = ArgDecl {
+        address: CL_KERNEL_ARG_ADDRESS_PRIVATE,
+        access: CL_KERNEL_ARG_ACCESS_NONE,
+        type_qualifier: CL_KERNEL_ARG_TYPE_NONE,
+        type_name: String::new(),
+        name: String::new(),
+    };
+    let mut type_words: Vec<&str> = Vec::new();
+    let mut pointers = 0;
+    let mut access = None;
+    // Array dimensions are not part of the type name.
+    let tokens = match tokens.iter().position(|x| x == "[") {
+        Some(x) => &tokens[..x],
+        None => tokens,
+    };
+    let (name, tokens) = match tokens.split_last() {
+        Some((name, rest)) => (name.clone(), rest),
+        None => return arg,
+    };
+    for token in tokens {
+        match token.trim_start_matches("__") {
+            "global" => arg.address = CL_KERNEL_ARG_ADDRESS_GLOBAL,
+            "local" => arg.address = CL_KERNEL_ARG_ADDRESS_LOCAL,
+            "constant" => arg.address = CL_KERNEL_ARG_ADDRESS_CONSTANT,
+            "private" => arg.address = CL_KERNEL_ARG_ADDRESS_PRIVATE,
+            "read_only" => access = Some(CL_KERNEL_ARG_ACCESS_READ_ONLY),
+            "write_only" => access = Some(CL_KERNEL_ARG_ACCESS_WRITE_ONLY),
+            "read_write" => access = Some(CL_KERNEL_ARG_ACCESS_READ_WRITE),
+            "const" => arg.type_qualifier |= CL_KERNEL_ARG_TYPE_CONST,
+            "restrict" => arg.type_qualifier |= CL_KERNEL_ARG_TYPE_RESTRICT,
+            "volatile" => arg.type_qualifier |= CL_KERNEL_ARG_TYPE_VOLATILE,
+            "pipe" => arg.type_qualifier |= CL_KERNEL_ARG_TYPE_PIPE,
+            "*" => pointers += 1,
+            _ => type_words.push(token),
+        }
+    }
+    arg.type_name = type_words.join(" ") + &"*".repeat(pointers);
+    // Images are read only unless qualified otherwise.
+    arg.access = match access {
+        Some(x) => x,
+        None if arg.type_name.starts_with("image") => CL_KERNEL_ARG_ACCESS_READ_ONLY,
+        None => CL_KERNEL_ARG_ACCESS_NONE,
+    };
+    arg.name = name;
+    arg
+}
+
+unsafe fn new_program(program: Result<Program>, errcode_ret: *mut cl_int) -> cl_program {
+    let program = program.map(|x| Handle::new(Mock::dispatch(), ObjectKind::Program, x));
+    into_raw(program, errcode_ret)
+}
+
+/// Calls the notification of a build, which always completes before the
+/// build entry point returns.
+unsafe fn notify(
+    program: cl_program,
+    pfn_notify: Option<BuildNotify>,
+    user_data: *mut c_void,
+    result: Result<()>,
+) -> Result<()> {
+    if let Some(notify) = pfn_notify {
+        notify(program, user_data);
+    }
+    result
+}
+
+fn check_notify(pfn_notify: Option<BuildNotify>, user_data: *mut c_void) -> Result<()> {
+    if pfn_notify.is_none() && !user_data.is_null() {
+        Err(CL_INVALID_VALUE)
+    } else {
+        Ok(())
+    }
+}
+
+unsafe extern "system" fn create_program_with_source(
+    context: cl_context,
+    count: cl_uint,
+    strings: *const *const c_char,
+    lengths: *const size_t,
+    errcode_ret: *mut cl_int,
+) -> cl_program {
+    let program = (|| {
+        let context = handle::<Context>(context, ObjectKind::Context)?;
+        if count == 0 || strings.is_null() {
+            return Err(CL_INVALID_VALUE);
+        }
+        let mut source = String::new();
+        for i in 0..count as usize {
+            let string = *strings.add(i);
+            if string.is_null() {
+                return Err(CL_INVALID_VALUE);
+            }
+            let length = if lengths.is_null() {
+                0
+            } else {
+                *lengths.add(i)
+            };
+            if length == 0 {
+                source.push_str(&c_string(string).unwrap_or_default());
+            } else {
+                let bytes = slice::from_raw_parts(string as *const u8, length);
+                source.push_str(&String::from_utf8_lossy(bytes));
+            }
+        }
+        let devices = context.devices.clone();
+        Ok(Program::new(
+            context,
+            devices,
+            source.clone(),
+            source,
+            CL_PROGRAM_BINARY_TYPE_NONE,
+        ))
+    })();
+    new_program(program, errcode_ret)
+}
+
+unsafe extern "system" fn create_program_with_binary(
+    context: cl_context,
+    num_devices: cl_uint,
+    device_list: *const cl_device_id,
+    lengths: *const size_t,
+    binaries: *const *const c_uchar,
+    binary_status: *mut cl_int,
+    errcode_ret: *mut cl_int,
+) -> cl_program {
+    let program = (|| {
+        let context = handle::<Context>(context, ObjectKind::Context)?;
+        if num_devices == 0 || device_list.is_null() || lengths.is_null() || binaries.is_null() {
+            return Err(CL_INVALID_VALUE);
+        }
+        let devices = context.device_list(num_devices, device_list)?;
+        let mut program = None;
+        let mut result = Ok(());
+        for i in 0..num_devices as usize {
+            let (binary, length) = (*binaries.add(i), *lengths.add(i));
+            if binary.is_null() || length == 0 {
+                return Err(CL_INVALID_VALUE);
+            }
+            let binary = slice::from_raw_parts(binary, length);
+            let header = BINARY_MAGIC.len() + 1;
+            let binary_type = binary.get(header - 1).map(|x| *x as cl_program_binary_type);
+            let valid = binary.starts_with(BINARY_MAGIC)
+                && matches!(
+                    binary_type,
+                    Some(CL_PROGRAM_BINARY_TYPE_COMPILED_OBJECT)
+                        | Some(CL_PROGRAM_BINARY_TYPE_LIBRARY)
+                        | Some(CL_PROGRAM_BINARY_TYPE_EXECUTABLE)
+                );
+            if !binary_status.is_null() {
+                *binary_status.add(i) = if valid { CL_SUCCESS } else { CL_INVALID_BINARY };
+            }
+            if !valid {
+                result = Err(CL_INVALID_BINARY);
+            } else if program.is_none() {
+                let code = String::from_utf8_lossy(&binary[header..]).into_owned();
+                program = Some((code, binary_type.unwrap_or_default()));
+            }
+        }
+        result?;
+        let (code, binary_type) = program.unwrap_or_default();
+        Ok(Program::new(
+            context,
+            devices,
+            String::new(),
+            code,
+            binary_type,
+        ))
+    })();
+    new_program(program, errcode_ret)
+}
+
+unsafe extern "system" fn create_program_with_built_in_kernels(
+    context: cl_context,
+    num_devices: cl_uint,
+    device_list: *const cl_device_id,
+    kernel_names: *const c_char,
+    errcode_ret: *mut cl_int,
+) -> cl_program {
+    let program = (|| {
+        let context = handle::<Context>(context, ObjectKind::Context)?;
+        context.device_list(num_devices, device_list)?;
+        // The devices have no built-in kernels.
+        let _ = kernel_names;
+        Err(CL_INVALID_VALUE)
+    })();
+    new_program(program, errcode_ret)
+}
+
+unsafe extern "system" fn create_program_with_il(
+    context: cl_context,
+    il: *const c_void,
+    length: size_t,
+    errcode_ret: *mut cl_int,
+) -> cl_program {
+    let program = (|| {
+        handle::<Context>(context, ObjectKind::Context)?;
+        if il.is_null() || length == 0 {
+            return Err(CL_INVALID_VALUE);
+        }
+        // No device reports an intermediate language.
+        Err(CL_INVALID_OPERATION)
+    })();
+    new_program(program, errcode_ret)
+}
+
+unsafe extern "system" fn build_program(
+    program: cl_program,
+    num_devices: cl_uint,
+    device_list: *const cl_device_id,
+    options: *const c_char,
+    pfn_notify: Option<BuildNotify>,
+    user_data: *mut c_void,
+) -> cl_int {
+    status((|| {
+        let handle = handle::<Program>(program, ObjectKind::Program)?;
+        handle.check_devices(num_devices, device_list)?;
+        check_notify(pfn_notify, user_data)?;
+        // Compiled objects and libraries must be linked instead.
+        let binary_type = handle.state.lock().unwrap().binary_type;
+        if handle.source.is_empty() && binary_type != CL_PROGRAM_BINARY_TYPE_EXECUTABLE {
+            return Err(CL_INVALID_BINARY);
+        }
+        let options = c_string(options).unwrap_or_default();
+        let result = handle.build(options, CL_PROGRAM_BINARY_TYPE_EXECUTABLE);
+        notify(program, pfn_notify, user_data, result)
+    })())
+}
+
+unsafe extern "system" fn compile_program(
+    program: cl_program,
+    num_devices: cl_uint,
+    device_list: *const cl_device_id,
+    options: *const c_char,
+    num_input_headers: cl_uint,
+    input_headers: *const cl_program,
+    header_include_names: *const *const c_char,
+    pfn_notify: Option<BuildNotify>,
+    user_data: *mut c_void,
+) -> cl_int {
+    status((|| {
+        let handle = handle::<Program>(program, ObjectKind::Program)?;
+        handle.check_devices(num_devices, device_list)?;
+        check_notify(pfn_notify, user_data)?;
+        if (num_input_headers == 0) != input_headers.is_null()
+            || input_headers.is_null() != header_include_names.is_null()
+        {
+            return Err(CL_INVALID_VALUE);
+        }
+        if handle.source.is_empty() {
+            return Err(CL_INVALID_OPERATION);
+        }
+        let options = c_string(options).unwrap_or_default();
+        let result = handle.build(options, CL_PROGRAM_BINARY_TYPE_COMPILED_OBJECT);
+        notify(program, pfn_notify, user_data, result)
+    })())
+}
+
+unsafe extern "system" fn link_program(
+    context: cl_context,
+    num_devices: cl_uint,
+    device_list: *const cl_device_id,
+    options: *const c_char,
+    num_input_programs: cl_uint,
+    input_programs: *const cl_program,
+    pfn_notify: Option<BuildNotify>,
+    user_data: *mut c_void,
+    errcode_ret: *mut cl_int,
+) -> cl_program {
+    let linked = (|| {
+        let context = handle::<Context>(context, ObjectKind::Context)?;
+        let devices = context.device_list(num_devices, device_list)?;
+        check_notify(pfn_notify, user_data)?;
+        if num_input_programs == 0 || input_programs.is_null() {
+            return Err(CL_INVALID_VALUE);
+        }
+        let mut code = String::new();
+        for input in slice::from_raw_parts(input_programs, num_input_programs as usize) {
+            let input = handle::<Program>(*input, ObjectKind::Program)?;
+            let state = input.state.lock().unwrap();
+            match state.binary_type {
+                CL_PROGRAM_BINARY_TYPE_COMPILED_OBJECT | CL_PROGRAM_BINARY_TYPE_LIBRARY => {}
+                _ => return Err(CL_INVALID_OPERATION),
+            }
+            code.push_str(&input.code);
+            code.push('\n');
+        }
+        let options = c_string(options).unwrap_or_default();
+        let binary_type = if options.split_whitespace().any(|x| x == "-create-library") {
+            CL_PROGRAM_BINARY_TYPE_LIBRARY
+        } else {
+            CL_PROGRAM_BINARY_TYPE_EXECUTABLE
+        };
+        let program = Program::new(
+            context,
+            devices,
+            String::new(),
+            code,
+            CL_PROGRAM_BINARY_TYPE_NONE,
+        );
+        let result = program.build(options, binary_type);
+        let program = Handle::new(Mock::dispatch(), ObjectKind::Program, program);
+        // The program is returned with its build log even if linking failed.
+        notify(program.as_raw(), pfn_notify, user_data, Ok(()))?;
+        if let Err(x) = result {
+            program.release();
+            return Err(x);
+        }
+        Ok(program)
+    })();
+    into_raw(linked, errcode_ret)
+}
+
+unsafe extern "system" fn get_program_info(
+    program: cl_program,
+    param_name: cl_program_info,
+    param_value_size: size_t,
+    param_value: *mut c_void,
+    param_value_size_ret: *mut size_t,
+) -> cl_int {
+    let program = match handle::<Program>(program, ObjectKind::Program) {
+        Ok(x) => x,
+        Err(x) => return x,
+    };
+    if param_name != CL_PROGRAM_BINARIES {
+        let info = Program::info(program, param_name);
+        return write_info(info, param_value_size, param_value, param_value_size_ret);
+    }
+    // The value is an array of pointers to the buffers receiving the
+    // binary of each device.
+    let size = program.devices.len() * std::mem::size_of::<*mut c_uchar>();
+    if !param_value.is_null() {
+        if param_value_size < size {
+            return CL_INVALID_VALUE;
+        }
+        let binary = program.binary();
+        let targets =
+            slice::from_raw_parts(param_value as *const *mut c_uchar, program.devices.len());
+        for target in targets.iter().filter(|x| !x.is_null()) {
+            ptr::copy_nonoverlapping(binary.as_ptr(), *target, binary.len());
+        }
+    }
+    if !param_value_size_ret.is_null() {
+        *param_value_size_ret = size;
+    }
+    CL_SUCCESS
+}
+
+unsafe extern "system" fn get_program_build_info(
+    program: cl_program,
+    device: cl_device_id,
+    param_name: cl_program_build_info,
+    param_value_size: size_t,
+    param_value: *mut c_void,
+    param_value_size_ret: *mut size_t,
+) -> cl_int {
+    let info = handle::<Program>(program, ObjectKind::Program).and_then(|x| {
+        handle::<Device>(device, ObjectKind::Device)?;
+        if !x.devices.iter().any(|y| y.as_raw() == device) {
+            return Err(CL_INVALID_DEVICE);
+        }
+        x.build_info(param_name)
+    });
+    write_info(info, param_value_size, param_value, param_value_size_ret)
+}
+
+unsafe extern "system" fn set_program_release_callback(
+    program: cl_program,
+    _pfn_notify: Option<extern "C" fn(cl_program)>,
+    _user_data: *mut c_void,
+) -> cl_int {
+    // Deprecated and unsupported, as there are no global destructors.
+    status(handle::<Program>(program, ObjectKind::Program).and(Err(CL_INVALID_OPERATION)))
+}
+
+unsafe extern "system" fn set_program_specialization_constant(
+    program: cl_program,
+    _spec_id: cl_uint,
+    _spec_size: size_t,
+    _spec_value: *const c_void,
+) -> cl_int {
+    // Only programs created from an intermediate language have constants.
+    status(handle::<Program>(program, ObjectKind::Program).and(Err(CL_INVALID_PROGRAM)))
+}
+
+pub(crate) fn extend_dispatch(table: &mut cl_icd_dispatch) {
+    table.clCreateProgramWithSource = Some(create_program_with_source);
+    table.clCreateProgramWithBinary = Some(create_program_with_binary);
+    table.clCreateProgramWithBuiltInKernels = Some(create_program_with_built_in_kernels);
+    table.clCreateProgramWithIL = Some(create_program_with_il);
+    table.clBuildProgram = Some(build_program);
+    table.clCompileProgram = Some(compile_program);
+    table.clLinkProgram = Some(link_program);
+    table.clGetProgramInfo = Some(get_program_info);
+    table.clGetProgramBuildInfo = Some(get_program_build_info);
+    table.clSetProgramReleaseCallback = Some(set_program_release_callback);
+    table.clSetProgramSpecializationConstant = Some(set_program_specialization_constant);
+    table.clRetainProgram = Some(retain::<Program, PROGRAM>);
+    table.clReleaseProgram = Some(release::<Program, PROGRAM>);
+}
+
+/************************/
+/* /\ /\ /\ /\ /\ /\ /\ */
+/*|__|__|__|__|__|__|__|*/
+/*|  |  |  |  |  |  |  |*/
+/*|  |  Unit Tests  |  |*/
+/*|__|__|__|__|__|__|__|*/
+/*|__|__|__|__|__|__|__|*/
+/************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ffi;
+    use crate::mock::tests::context;
+
+    #[test]
+    fn test_parse_kernels() {
+        let source = "
+            #define N 4
+            // kernel void commented(int a) {}
+            __kernel __attribute__((reqd_work_group_size(1, 1, 1)))
+            void blur(read_only image2d_t src, __global const float4 *restrict dst,
+                      __local uint* scratch, int width) {}
+            kernel void empty(void) {}";
+        let kernels = parse_kernels(source);
+        assert_eq!(2, kernels.len());
+        assert_eq!("blur", kernels[0].name);
+        assert!(kernels[1].args.is_empty());
+        let args = &kernels[0].args;
+        assert_eq!(CL_KERNEL_ARG_ACCESS_READ_ONLY, args[0].access);
+        assert_eq!("image2d_t", args[0].type_name);
+        assert_eq!(CL_KERNEL_ARG_ADDRESS_GLOBAL, args[1].address);
+        assert_eq!(
+            CL_KERNEL_ARG_TYPE_CONST | CL_KERNEL_ARG_TYPE_RESTRICT,
+            args[1].type_qualifier
+        );
+        assert_eq!("float4*", args[1].type_name);
+        assert_eq!(CL_KERNEL_ARG_ADDRESS_LOCAL, args[2].address);
+        assert_eq!(CL_KERNEL_ARG_ADDRESS_PRIVATE, args[3].address);
+        assert_eq!("width", args[3].name);
+    }
+
+    #[test]
+    fn test_binary_round_trip() {
+        let (device, context) = context();
+        let source = "kernel void copy(global int* a, global int* b) {}\0";
+        let mut status_code = CL_INVALID_VALUE;
+        unsafe {
+            let strings = [source.as_ptr() as *const c_char];
+            let program = ffi::clCreateProgramWithSource(
+                context,
+                1,
+                strings.as_ptr(),
+                ptr::null(),
+                &mut status_code,
+            );
+            assert_eq!(CL_SUCCESS, status_code);
+            let mut size = 0usize;
+            ffi::clGetProgramInfo(
+                program,
+                CL_PROGRAM_BINARY_SIZES,
+                std::mem::size_of::<usize>(),
+                &mut size as *mut usize as *mut c_void,
+                ptr::null_mut(),
+            );
+            assert_eq!(0, size);
+            assert_eq!(
+                CL_SUCCESS,
+                ffi::clBuildProgram(program, 0, ptr::null(), ptr::null(), None, ptr::null_mut())
+            );
+            ffi::clGetProgramInfo(
+                program,
+                CL_PROGRAM_BINARY_SIZES,
+                std::mem::size_of::<usize>(),
+                &mut size as *mut usize as *mut c_void,
+                ptr::null_mut(),
+            );
+            let mut binary = vec![0u8; size];
+            let mut targets = [binary.as_mut_ptr()];
+            assert_eq!(
+                CL_SUCCESS,
+                ffi::clGetProgramInfo(
+                    program,
+                    CL_PROGRAM_BINARIES,
+                    std::mem::size_of_val(&targets),
+                    targets.as_mut_ptr() as *mut c_void,
+                    ptr::null_mut(),
+                )
+            );
+            ffi::clReleaseProgram(program);
+
+            let mut binary_status = CL_INVALID_VALUE;
+            let loaded = ffi::clCreateProgramWithBinary(
+                context,
+                1,
+                &device,
+                &size,
+                [binary.as_ptr()].as_ptr(),
+                &mut binary_status,
+                &mut status_code,
+            );
+            assert_eq!(CL_SUCCESS, binary_status);
+            ffi::clBuildProgram(loaded, 0, ptr::null(), ptr::null(), None, ptr::null_mut());
+            let kernel =
+                ffi::clCreateKernel(loaded, "copy\0".as_ptr() as *const c_char, &mut status_code);
+            assert_eq!(CL_SUCCESS, status_code);
+            ffi::clReleaseKernel(kernel);
+            ffi::clReleaseProgram(loaded);
+
+            let garbage = [0u8; 16];
+            ffi::clCreateProgramWithBinary(
+                context,
+                1,
+                &device,
+                &garbage.len(),
+                [garbage.as_ptr()].as_ptr(),
+                &mut binary_status,
+                &mut status_code,
+            );
+            assert_eq!(CL_INVALID_BINARY, status_code);
+            assert_eq!(CL_INVALID_BINARY, binary_status);
+            ffi::clReleaseContext(context);
+        }
+    }
+
+    #[test]
+    fn test_build_error() {
+        let (device, context) = context();
+        let source = "#error unsupported\nkernel void f() {}";
+        let mut status_code = CL_INVALID_VALUE;
+        let mut log = [0u8; 64];
+        unsafe {
+            let strings = [source.as_ptr() as *const c_char];
+            let program = ffi::clCreateProgramWithSource(
+                context,
+                1,
+                strings.as_ptr(),
+                &source.len(),
+                &mut status_code,
+            );
+            assert_eq!(
+                CL_BUILD_PROGRAM_FAILURE,
+                ffi::clBuildProgram(program, 0, ptr::null(), ptr::null(), None, ptr::null_mut())
+            );
+            ffi::clGetProgramBuildInfo(
+                program,
+                device,
+                CL_PROGRAM_BUILD_LOG,
+                log.len(),
+                log.as_mut_ptr() as *mut c_void,
+                ptr::null_mut(),
+            );
+            ffi::clCreateKernel(program, "f\0".as_ptr() as *const c_char, &mut status_code);
+            assert_eq!(CL_INVALID_PROGRAM_EXECUTABLE, status_code);
+            ffi::clReleaseProgram(program);
+            ffi::clReleaseContext(context);
+        }
+        assert!(log.starts_with(b"error: #error unsupported\0"));
+    }
+}
@@ -0,0 +1,352 @@
+/*
+ * queue.rs - Command queues of the mock platform.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+//!
+//! Commands are kept in order and run on the thread that enqueues them,
+//! flushes the queue or completes the event the next command waits on.
+//! Out of order queues are accepted and run in order as well.
+//!
+use super::context::Context;
+use super::device::Device;
+use super::event::{self, Event};
+use super::{Mock, Retained};
+use crate::consts::*;
+use crate::dispatch::cl_icd_dispatch;
+use crate::driver::{handle, info_slice, info_value, status, Driver, Handle, ObjectKind, Result};
+use crate::types::*;
+use std::collections::VecDeque;
+use std::ptr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// The operation of a command, returning CL_COMPLETE or an error status.
+pub(crate) type Work = Box<dyn FnOnce() -> cl_int + Send>;
+
+const PROPERTIES: cl_command_queue_properties =
+    CL_QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE | CL_QUEUE_PROFILING_ENABLE;
+
+pub(crate) struct Queue {
+    pub(crate) context: Retained<Context>,
+    pub(crate) device: Handle<Device>,
+    properties: AtomicU64,
+    pending: Mutex<VecDeque<Command>>,
+    // Event of the last command until it completes, for clFinish.
+    last: Mutex<Option<Retained<Event>>>,
+    running: Mutex<()>,
+}
+
+struct Command {
+    event: Retained<Event>,
+    wait_list: Vec<Retained<Event>>,
+    work: Work,
+}
+
+impl Queue {
+    pub(crate) fn new(
+        context: Handle<Context>,
+        device: Handle<Device>,
+        properties: cl_command_queue_properties,
+    ) -> Result<Self> {
+        if properties & CL_QUEUE_ON_DEVICE != 0 {
+            return Err(CL_INVALID_QUEUE_PROPERTIES);
+        }
+        if properties & !PROPERTIES != 0 {
+            return Err(CL_INVALID_VALUE);
+        }
+        if !context.has_device(device.as_raw()) {
+            return Err(CL_INVALID_DEVICE);
+        }
+        Ok(Queue {
+            context: Retained::new(context),
+            device,
+            properties: AtomicU64::new(properties),
+            pending: Mutex::new(VecDeque::new()),
+            last: Mutex::new(None),
+            running: Mutex::new(()),
+        })
+    }
+
+    pub(crate) fn info(&self, param_name: cl_command_queue_info) -> Result<Vec<u8>> {
+        let info = match param_name {
+            CL_QUEUE_CONTEXT => info_value(self.context.handle().as_raw()),
+            CL_QUEUE_DEVICE => info_value(self.device.as_raw()),
+            CL_QUEUE_PROPERTIES => info_value(self.properties.load(Ordering::Acquire)),
+            CL_QUEUE_PROPERTIES_ARRAY => info_slice::<cl_queue_properties>(&[]),
+            CL_QUEUE_DEVICE_DEFAULT => info_value::<cl_command_queue>(ptr::null_mut()),
+            // Only valid for device queues.
+            CL_QUEUE_SIZE => return Err(CL_INVALID_COMMAND_QUEUE),
+            _ => return Err(CL_INVALID_VALUE),
+        };
+        Ok(info)
+    }
+
+    /// Adds a command running work once the events of the wait list
+    /// completed, returning its event in event_ret if not null.
+    ///
+    /// # Safety
+    ///
+    /// The wait list and event_ret follow the clEnqueue* conventions.
+    pub(crate) unsafe fn enqueue(
+        queue: Handle<Queue>,
+        command_type: cl_command_type,
+        num_events_in_wait_list: cl_uint,
+        event_wait_list: *const cl_event,
+        event_ret: *mut cl_event,
+        blocking: bool,
+        work: Work,
+    ) -> Result<()> {
+        let wait_list = event::wait_list(
+            queue.context.handle(),
+            num_events_in_wait_list,
+            event_wait_list,
+        )?;
+        let profiling = queue.properties.load(Ordering::Acquire) & CL_QUEUE_PROFILING_ENABLE != 0;
+        let event = Retained::adopt(Handle::new(
+            Mock::dispatch(),
+            ObjectKind::Event,
+            Event::new(
+                queue.context.clone(),
+                Some(Retained::new(queue)),
+                command_type,
+                profiling,
+            ),
+        ));
+        if !event_ret.is_null() {
+            event.handle().retain();
+            *event_ret = event.handle().as_raw();
+        }
+        for dependency in &wait_list {
+            dependency.add_waiter(queue);
+        }
+        Event::set_status(event.handle(), CL_SUBMITTED as cl_int);
+        *queue.last.lock().unwrap() = Some(event.clone());
+        queue.pending.lock().unwrap().push_back(Command {
+            event: event.clone(),
+            wait_list,
+            work,
+        });
+        Self::pump(queue);
+        if blocking && event.wait() < 0 {
+            return Err(CL_EXEC_STATUS_ERROR_FOR_EVENTS_IN_WAIT_LIST);
+        }
+        Ok(())
+    }
+
+    /// Runs the commands whose wait lists completed, in order.
+    pub(crate) fn pump(queue: Handle<Queue>) {
+        // The last reference may be held by an event of a command run here.
+        let _queue = Retained::new(queue);
+        loop {
+            {
+                let _running = match queue.running.try_lock() {
+                    Ok(x) => x,
+                    // Another thread is running the queue.
+                    Err(_) => return,
+                };
+                while let Some(command) = queue.next() {
+                    let event = command.event.handle();
+                    let failed = command.wait_list.iter().any(|x| x.status() < 0);
+                    let result = if failed {
+                        CL_EXEC_STATUS_ERROR_FOR_EVENTS_IN_WAIT_LIST
+                    } else {
+                        Event::set_status(event, CL_RUNNING as cl_int);
+                        (command.work)()
+                    };
+                    let mut last = queue.last.lock().unwrap();
+                    if last.as_ref().map(|x| x.handle().as_raw()) == Some(event.as_raw()) {
+                        *last = None;
+                    }
+                    drop(last);
+                    Event::set_status(event, result);
+                }
+            }
+            // A command may have been added after the last check and before
+            // the lock was released.
+            if !queue.ready() {
+                return;
+            }
+        }
+    }
+
+    /// Runs the queue and waits for its last command.
+    pub(crate) fn finish(queue: Handle<Queue>) {
+        Self::pump(queue);
+        let last = queue.last.lock().unwrap().clone();
+        if let Some(event) = last {
+            event.wait();
+        }
+    }
+
+    fn ready(&self) -> bool {
+        let pending = self.pending.lock().unwrap();
+        match pending.front() {
+            Some(command) => command
+                .wait_list
+                .iter()
+                .all(|x| x.status() <= CL_COMPLETE as cl_int),
+            None => false,
+        }
+    }
+
+    fn next(&self) -> Option<Command> {
+        if self.ready() {
+            self.pending.lock().unwrap().pop_front()
+        } else {
+            None
+        }
+    }
+}
+
+unsafe fn enqueue_marker_with_wait_list(
+    command_queue: cl_command_queue,
+    command_type: cl_command_type,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: *const cl_event,
+    event: *mut cl_event,
+) -> cl_int {
+    status(
+        handle::<Queue>(command_queue, ObjectKind::CommandQueue).and_then(|queue| {
+            Queue::enqueue(
+                queue,
+                command_type,
+                num_events_in_wait_list,
+                event_wait_list,
+                event,
+                false,
+                Box::new(|| CL_COMPLETE as cl_int),
+            )
+        }),
+    )
+}
+
+unsafe extern "system" fn enqueue_marker_with_wait_list_entry(
+    command_queue: cl_command_queue,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: *const cl_event,
+    event: *mut cl_event,
+) -> cl_int {
+    enqueue_marker_with_wait_list(
+        command_queue,
+        CL_COMMAND_MARKER,
+        num_events_in_wait_list,
+        event_wait_list,
+        event,
+    )
+}
+
+unsafe extern "system" fn enqueue_barrier_with_wait_list(
+    command_queue: cl_command_queue,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: *const cl_event,
+    event: *mut cl_event,
+) -> cl_int {
+    enqueue_marker_with_wait_list(
+        command_queue,
+        CL_COMMAND_BARRIER,
+        num_events_in_wait_list,
+        event_wait_list,
+        event,
+    )
+}
+
+unsafe extern "system" fn enqueue_marker(
+    command_queue: cl_command_queue,
+    event: *mut cl_event,
+) -> cl_int {
+    if event.is_null() {
+        return CL_INVALID_VALUE;
+    }
+    enqueue_marker_with_wait_list(command_queue, CL_COMMAND_MARKER, 0, ptr::null(), event)
+}
+
+unsafe extern "system" fn enqueue_barrier(command_queue: cl_command_queue) -> cl_int {
+    enqueue_marker_with_wait_list(
+        command_queue,
+        CL_COMMAND_BARRIER,
+        0,
+        ptr::null(),
+        ptr::null_mut(),
+    )
+}
+
+unsafe extern "system" fn enqueue_wait_for_events(
+    command_queue: cl_command_queue,
+    num_events: cl_uint,
+    event_list: *const cl_event,
+) -> cl_int {
+    if num_events == 0 || event_list.is_null() {
+        return CL_INVALID_VALUE;
+    }
+    let status_code = enqueue_marker_with_wait_list(
+        command_queue,
+        CL_COMMAND_BARRIER,
+        num_events,
+        event_list,
+        ptr::null_mut(),
+    );
+    match status_code {
+        CL_INVALID_EVENT_WAIT_LIST => CL_INVALID_EVENT,
+        x => x,
+    }
+}
+
+unsafe extern "system" fn set_command_queue_property(
+    command_queue: cl_command_queue,
+    properties: cl_command_queue_properties,
+    enable: cl_bool,
+    old_properties: *mut cl_command_queue_properties,
+) -> cl_int {
+    status((|| {
+        let queue = handle::<Queue>(command_queue, ObjectKind::CommandQueue)?;
+        if properties & !PROPERTIES != 0 {
+            return Err(CL_INVALID_VALUE);
+        }
+        let old = if enable == CL_FALSE {
+            queue.properties.fetch_and(!properties, Ordering::AcqRel)
+        } else {
+            queue.properties.fetch_or(properties, Ordering::AcqRel)
+        };
+        if !old_properties.is_null() {
+            *old_properties = old;
+        }
+        Ok(())
+    })())
+}
+
+unsafe extern "system" fn set_default_device_command_queue(
+    context: cl_context,
+    device: cl_device_id,
+    command_queue: cl_command_queue,
+) -> cl_int {
+    status((|| {
+        handle::<Context>(context, ObjectKind::Context)?;
+        handle::<Device>(device, ObjectKind::Device)?;
+        handle::<Queue>(command_queue, ObjectKind::CommandQueue)?;
+        // There are no device queues.
+        Err(CL_INVALID_COMMAND_QUEUE)
+    })())
+}
+
+pub(crate) fn extend_dispatch(table: &mut cl_icd_dispatch) {
+    table.clEnqueueMarkerWithWaitList = Some(enqueue_marker_with_wait_list_entry);
+    table.clEnqueueBarrierWithWaitList = Some(enqueue_barrier_with_wait_list);
+    table.clEnqueueMarker = Some(enqueue_marker);
+    table.clEnqueueBarrier = Some(enqueue_barrier);
+    table.clEnqueueWaitForEvents = Some(enqueue_wait_for_events);
+    table.clSetCommandQueueProperty = Some(set_command_queue_property);
+    table.clSetDefaultDeviceCommandQueue = Some(set_default_device_command_queue);
+}
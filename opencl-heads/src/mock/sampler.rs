@@ -0,0 +1,168 @@
+/*
+ * sampler.rs - Samplers of the mock platform.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+//!
+//! Samplers only record their state, as kernels are not compiled.
+//!
+use super::context::Context;
+use super::{Mock, Retained};
+use crate::consts::*;
+use crate::dispatch::cl_icd_dispatch;
+use crate::driver::{
+    handle, info_slice, info_value, into_raw, properties, release, retain, write_info, Driver,
+    Handle, ObjectKind, Result,
+};
+use crate::types::*;
+use libc::c_void;
+
+const SAMPLER: u32 = ObjectKind::Sampler as u32;
+
+pub(crate) struct Sampler {
+    pub(crate) context: Retained<Context>,
+    normalized_coords: cl_bool,
+    addressing_mode: cl_addressing_mode,
+    filter_mode: cl_filter_mode,
+    properties: Vec<cl_sampler_properties>,
+}
+
+impl Sampler {
+    fn new(
+        context: Handle<Context>,
+        normalized_coords: cl_bool,
+        addressing_mode: cl_addressing_mode,
+        filter_mode: cl_filter_mode,
+        properties: Vec<cl_sampler_properties>,
+    ) -> Result<Self> {
+        if !context.devices.iter().any(|x| x.config.image_support) {
+            return Err(CL_INVALID_OPERATION);
+        }
+        match addressing_mode {
+            CL_ADDRESS_NONE
+            | CL_ADDRESS_CLAMP_TO_EDGE
+            | CL_ADDRESS_CLAMP
+            | CL_ADDRESS_REPEAT
+            | CL_ADDRESS_MIRRORED_REPEAT => {}
+            _ => return Err(CL_INVALID_VALUE),
+        }
+        match filter_mode {
+            CL_FILTER_NEAREST | CL_FILTER_LINEAR => {}
+            _ => return Err(CL_INVALID_VALUE),
+        }
+        Ok(Sampler {
+            context: Retained::new(context),
+            normalized_coords: (normalized_coords != CL_FALSE) as cl_bool,
+            addressing_mode,
+            filter_mode,
+            properties,
+        })
+    }
+
+    fn info(sampler: Handle<Sampler>, param_name: cl_sampler_info) -> Result<Vec<u8>> {
+        let info = match param_name {
+            CL_SAMPLER_REFERENCE_COUNT => info_value(sampler.ref_count()),
+            CL_SAMPLER_CONTEXT => info_value(sampler.context.handle().as_raw()),
+            CL_SAMPLER_NORMALIZED_COORDS => info_value(sampler.normalized_coords),
+            CL_SAMPLER_ADDRESSING_MODE => info_value(sampler.addressing_mode),
+            CL_SAMPLER_FILTER_MODE => info_value(sampler.filter_mode),
+            CL_SAMPLER_PROPERTIES => info_slice(&sampler.properties),
+            _ => return Err(CL_INVALID_VALUE),
+        };
+        Ok(info)
+    }
+}
+
+unsafe fn new_sampler(sampler: Result<Sampler>, errcode_ret: *mut cl_int) -> cl_sampler {
+    let sampler = sampler.map(|x| Handle::new(Mock::dispatch(), ObjectKind::Sampler, x));
+    into_raw(sampler, errcode_ret)
+}
+
+unsafe extern "system" fn create_sampler(
+    context: cl_context,
+    normalized_coords: cl_bool,
+    addressing_mode: cl_addressing_mode,
+    filter_mode: cl_filter_mode,
+    errcode_ret: *mut cl_int,
+) -> cl_sampler {
+    let sampler = handle::<Context>(context, ObjectKind::Context).and_then(|x| {
+        Sampler::new(
+            x,
+            normalized_coords,
+            addressing_mode,
+            filter_mode,
+            Vec::new(),
+        )
+    });
+    new_sampler(sampler, errcode_ret)
+}
+
+unsafe extern "system" fn create_sampler_with_properties(
+    context: cl_context,
+    sampler_properties: *const cl_sampler_properties,
+    errcode_ret: *mut cl_int,
+) -> cl_sampler {
+    let sampler = (|| {
+        let context = handle::<Context>(context, ObjectKind::Context)?;
+        let list = properties(sampler_properties);
+        let (mut normalized_coords, mut addressing_mode, mut filter_mode) =
+            (CL_TRUE, CL_ADDRESS_CLAMP, CL_FILTER_NEAREST);
+        let mut seen = Vec::new();
+        for pair in list.chunks(2) {
+            if seen.contains(&pair[0]) {
+                return Err(CL_INVALID_VALUE);
+            }
+            seen.push(pair[0]);
+            match pair[0] as cl_sampler_info {
+                CL_SAMPLER_NORMALIZED_COORDS => normalized_coords = pair[1] as cl_bool,
+                CL_SAMPLER_ADDRESSING_MODE => addressing_mode = pair[1] as cl_addressing_mode,
+                CL_SAMPLER_FILTER_MODE => filter_mode = pair[1] as cl_filter_mode,
+                _ => return Err(CL_INVALID_VALUE),
+            }
+        }
+        let mut stored = list.clone();
+        if !sampler_properties.is_null() {
+            stored.push(0);
+        }
+        Sampler::new(
+            context,
+            normalized_coords,
+            addressing_mode,
+            filter_mode,
+            stored,
+        )
+    })();
+    new_sampler(sampler, errcode_ret)
+}
+
+unsafe extern "system" fn get_sampler_info(
+    sampler: cl_sampler,
+    param_name: cl_sampler_info,
+    param_value_size: size_t,
+    param_value: *mut c_void,
+    param_value_size_ret: *mut size_t,
+) -> cl_int {
+    let info =
+        handle::<Sampler>(sampler, ObjectKind::Sampler).and_then(|x| Sampler::info(x, param_name));
+    write_info(info, param_value_size, param_value, param_value_size_ret)
+}
+
+pub(crate) fn extend_dispatch(table: &mut cl_icd_dispatch) {
+    table.clCreateSampler = Some(create_sampler);
+    table.clCreateSamplerWithProperties = Some(create_sampler_with_properties);
+    table.clGetSamplerInfo = Some(get_sampler_info);
+    table.clRetainSampler = Some(retain::<Sampler, SAMPLER>);
+    table.clReleaseSampler = Some(release::<Sampler, SAMPLER>);
+}
@@ -0,0 +1,1333 @@
+/*
+ * transfer.rs - Memory commands of the mock platform.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+//!
+//! Reads, writes, copies, fills and maps of buffers, images and SVM. The
+//! arguments are checked when the command is enqueued and the bytes moved
+//! when it runs, the commands keep their memory objects alive until then.
+//!
+use super::context::same_context;
+use super::memory::{svm_free, Mem};
+use super::queue::{Queue, Work};
+use super::{HostPtr, Retained};
+use crate::consts::*;
+use crate::dispatch::cl_icd_dispatch;
+use crate::driver::{handle, status, Handle, ObjectKind, Result};
+use crate::types::*;
+use libc::c_void;
+use std::ptr;
+use std::slice;
+
+const COMPLETE: cl_int = CL_COMPLETE as cl_int;
+
+type SvmFreeNotify = extern "C" fn(
+    queue: cl_command_queue,
+    num_svm_pointers: cl_uint,
+    svm_pointers: *const *const c_void,
+    user_data: *mut c_void,
+);
+
+/// A memory argument of a command on queue.
+unsafe fn queue_mem(
+    command_queue: cl_command_queue,
+    mem: cl_mem,
+) -> Result<(Handle<Queue>, Handle<Mem>)> {
+    let queue = handle::<Queue>(command_queue, ObjectKind::CommandQueue)?;
+    let mem = handle::<Mem>(mem, ObjectKind::Mem)?;
+    same_context(queue.context.handle(), mem.context.handle())?;
+    Ok((queue, mem))
+}
+
+unsafe fn triple(values: *const size_t) -> Result<[size_t; 3]> {
+    if values.is_null() {
+        Err(CL_INVALID_VALUE)
+    } else {
+        Ok([*values, *values.add(1), *values.add(2)])
+    }
+}
+
+/// Copies region[0] bytes of region[1] rows in region[2] slices between
+/// two layouts given by their row and slice pitches.
+unsafe fn copy_rect(
+    src: *const u8,
+    src_pitch: [size_t; 2],
+    dst: *mut u8,
+    dst_pitch: [size_t; 2],
+    region: [size_t; 3],
+) {
+    for z in 0..region[2] {
+        for y in 0..region[1] {
+            ptr::copy(
+                src.add(y * src_pitch[0] + z * src_pitch[1]),
+                dst.add(y * dst_pitch[0] + z * dst_pitch[1]),
+                region[0],
+            );
+        }
+    }
+}
+
+/// Layout of a rectangle in a buffer or host memory, as the offset of its
+/// origin, its row and slice pitches and the end of its last row.
+fn rect(
+    origin: [size_t; 3],
+    region: [size_t; 3],
+    row_pitch: size_t,
+    slice_pitch: size_t,
+) -> Result<(size_t, [size_t; 2], size_t)> {
+    if region.contains(&0) {
+        return Err(CL_INVALID_VALUE);
+    }
+    let row_pitch = match row_pitch {
+        0 => region[0],
+        x if x < region[0] => return Err(CL_INVALID_VALUE),
+        x => x,
+    };
+    let slice_pitch = match slice_pitch {
+        0 => row_pitch * region[1],
+        x if x < row_pitch * region[1] || x % row_pitch != 0 => return Err(CL_INVALID_VALUE),
+        x => x,
+    };
+    let offset = origin[0] + origin[1] * row_pitch + origin[2] * slice_pitch;
+    let end = offset + (region[2] - 1) * slice_pitch + (region[1] - 1) * row_pitch + region[0];
+    Ok((offset, [row_pitch, slice_pitch], end))
+}
+
+fn overlap(a: *const u8, a_size: size_t, b: *const u8, b_size: size_t) -> bool {
+    let (a, b) = (a as usize, b as usize);
+    a < b + b_size && b < a + a_size
+}
+
+fn check_pattern(pattern: *const c_void, pattern_size: size_t) -> Result<()> {
+    if pattern.is_null() || !pattern_size.is_power_of_two() || pattern_size > 128 {
+        Err(CL_INVALID_VALUE)
+    } else {
+        Ok(())
+    }
+}
+
+unsafe fn fill(target: *mut u8, pattern: &[u8], size: size_t) {
+    for i in (0..size).step_by(pattern.len()) {
+        ptr::copy_nonoverlapping(pattern.as_ptr(), target.add(i), pattern.len());
+    }
+}
+
+unsafe extern "system" fn enqueue_read_buffer(
+    command_queue: cl_command_queue,
+    buffer: cl_mem,
+    blocking_read: cl_bool,
+    offset: size_t,
+    size: size_t,
+    ptr: *mut c_void,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: *const cl_event,
+    event: *mut cl_event,
+) -> cl_int {
+    status((|| {
+        let (queue, buffer) = queue_mem(command_queue, buffer)?;
+        buffer.check_range(offset, size)?;
+        buffer.host_readable()?;
+        if ptr.is_null() || size == 0 {
+            return Err(CL_INVALID_VALUE);
+        }
+        let (buffer, host) = (Retained::new(buffer), HostPtr(ptr));
+        let work: Work = Box::new(move || {
+            ptr::copy(buffer.data().add(offset), host.bytes(), size);
+            COMPLETE
+        });
+        Queue::enqueue(
+            queue,
+            CL_COMMAND_READ_BUFFER,
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            blocking_read != CL_FALSE,
+            work,
+        )
+    })())
+}
+
+unsafe extern "system" fn enqueue_write_buffer(
+    command_queue: cl_command_queue,
+    buffer: cl_mem,
+    blocking_write: cl_bool,
+    offset: size_t,
+    size: size_t,
+    ptr: *const c_void,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: *const cl_event,
+    event: *mut cl_event,
+) -> cl_int {
+    status((|| {
+        let (queue, buffer) = queue_mem(command_queue, buffer)?;
+        buffer.check_range(offset, size)?;
+        buffer.host_writable()?;
+        if ptr.is_null() || size == 0 {
+            return Err(CL_INVALID_VALUE);
+        }
+        let (buffer, host) = (Retained::new(buffer), HostPtr(ptr as *mut c_void));
+        let work: Work = Box::new(move || {
+            ptr::copy(host.bytes(), buffer.data().add(offset), size);
+            COMPLETE
+        });
+        Queue::enqueue(
+            queue,
+            CL_COMMAND_WRITE_BUFFER,
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            blocking_write != CL_FALSE,
+            work,
+        )
+    })())
+}
+
+/// Shared part of the rect reads and writes, host_to_buffer selects the
+/// direction.
+#[allow(clippy::too_many_arguments)]
+unsafe fn buffer_rect(
+    command_queue: cl_command_queue,
+    buffer: cl_mem,
+    host_to_buffer: bool,
+    blocking: cl_bool,
+    buffer_origin: *const size_t,
+    host_origin: *const size_t,
+    region: *const size_t,
+    buffer_pitch: [size_t; 2],
+    host_pitch: [size_t; 2],
+    ptr: *mut c_void,
+    wait_list: (cl_uint, *const cl_event),
+    event: *mut cl_event,
+) -> Result<()> {
+    let (queue, buffer) = queue_mem(command_queue, buffer)?;
+    let region = triple(region)?;
+    let (buffer_offset, buffer_pitch, end) = rect(
+        triple(buffer_origin)?,
+        region,
+        buffer_pitch[0],
+        buffer_pitch[1],
+    )?;
+    let (host_offset, host_pitch, _) =
+        rect(triple(host_origin)?, region, host_pitch[0], host_pitch[1])?;
+    buffer.check_range(0, end)?;
+    if ptr.is_null() {
+        return Err(CL_INVALID_VALUE);
+    }
+    let command_type = if host_to_buffer {
+        buffer.host_writable()?;
+        CL_COMMAND_WRITE_BUFFER_RECT
+    } else {
+        buffer.host_readable()?;
+        CL_COMMAND_READ_BUFFER_RECT
+    };
+    let (buffer, host) = (Retained::new(buffer), HostPtr(ptr));
+    let work: Work = Box::new(move || {
+        let buffer_start = buffer.data().add(buffer_offset);
+        let host_start = host.bytes().add(host_offset);
+        if host_to_buffer {
+            copy_rect(host_start, host_pitch, buffer_start, buffer_pitch, region);
+        } else {
+            copy_rect(buffer_start, buffer_pitch, host_start, host_pitch, region);
+        }
+        COMPLETE
+    });
+    Queue::enqueue(
+        queue,
+        command_type,
+        wait_list.0,
+        wait_list.1,
+        event,
+        blocking != CL_FALSE,
+        work,
+    )
+}
+
+unsafe extern "system" fn enqueue_read_buffer_rect(
+    command_queue: cl_command_queue,
+    buffer: cl_mem,
+    blocking_read: cl_bool,
+    buffer_origin: *const size_t,
+    host_origin: *const size_t,
+    region: *const size_t,
+    buffer_row_pitch: size_t,
+    buffer_slice_pitch: size_t,
+    host_row_pitch: size_t,
+    host_slice_pitch: size_t,
+    ptr: *mut c_void,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: *const cl_event,
+    event: *mut cl_event,
+) -> cl_int {
+    status(buffer_rect(
+        command_queue,
+        buffer,
+        false,
+        blocking_read,
+        buffer_origin,
+        host_origin,
+        region,
+        [buffer_row_pitch, buffer_slice_pitch],
+        [host_row_pitch, host_slice_pitch],
+        ptr,
+        (num_events_in_wait_list, event_wait_list),
+        event,
+    ))
+}
+
+unsafe extern "system" fn enqueue_write_buffer_rect(
+    command_queue: cl_command_queue,
+    buffer: cl_mem,
+    blocking_write: cl_bool,
+    buffer_origin: *const size_t,
+    host_origin: *const size_t,
+    region: *const size_t,
+    buffer_row_pitch: size_t,
+    buffer_slice_pitch: size_t,
+    host_row_pitch: size_t,
+    host_slice_pitch: size_t,
+    ptr: *const c_void,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: *const cl_event,
+    event: *mut cl_event,
+) -> cl_int {
+    status(buffer_rect(
+        command_queue,
+        buffer,
+        true,
+        blocking_write,
+        buffer_origin,
+        host_origin,
+        region,
+        [buffer_row_pitch, buffer_slice_pitch],
+        [host_row_pitch, host_slice_pitch],
+        ptr as *mut c_void,
+        (num_events_in_wait_list, event_wait_list),
+        event,
+    ))
+}
+
+unsafe extern "system" fn enqueue_copy_buffer(
+    command_queue: cl_command_queue,
+    src_buffer: cl_mem,
+    dst_buffer: cl_mem,
+    src_offset: size_t,
+    dst_offset: size_t,
+    size: size_t,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: *const cl_event,
+    event: *mut cl_event,
+) -> cl_int {
+    status((|| {
+        let (queue, src) = queue_mem(command_queue, src_buffer)?;
+        let (_, dst) = queue_mem(command_queue, dst_buffer)?;
+        src.check_range(src_offset, size)?;
+        dst.check_range(dst_offset, size)?;
+        if size == 0 {
+            return Err(CL_INVALID_VALUE);
+        }
+        if overlap(
+            src.data().add(src_offset),
+            size,
+            dst.data().add(dst_offset),
+            size,
+        ) {
+            return Err(CL_MEM_COPY_OVERLAP);
+        }
+        let (src, dst) = (Retained::new(src), Retained::new(dst));
+        let work: Work = Box::new(move || {
+            ptr::copy(src.data().add(src_offset), dst.data().add(dst_offset), size);
+            COMPLETE
+        });
+        Queue::enqueue(
+            queue,
+            CL_COMMAND_COPY_BUFFER,
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            false,
+            work,
+        )
+    })())
+}
+
+unsafe extern "system" fn enqueue_copy_buffer_rect(
+    command_queue: cl_command_queue,
+    src_buffer: cl_mem,
+    dst_buffer: cl_mem,
+    src_origin: *const size_t,
+    dst_origin: *const size_t,
+    region: *const size_t,
+    src_row_pitch: size_t,
+    src_slice_pitch: size_t,
+    dst_row_pitch: size_t,
+    dst_slice_pitch: size_t,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: *const cl_event,
+    event: *mut cl_event,
+) -> cl_int {
+    status((|| {
+        let (queue, src) = queue_mem(command_queue, src_buffer)?;
+        let (_, dst) = queue_mem(command_queue, dst_buffer)?;
+        let region = triple(region)?;
+        let (src_offset, src_pitch, src_end) =
+            rect(triple(src_origin)?, region, src_row_pitch, src_slice_pitch)?;
+        let (dst_offset, dst_pitch, dst_end) =
+            rect(triple(dst_origin)?, region, dst_row_pitch, dst_slice_pitch)?;
+        src.check_range(0, src_end)?;
+        dst.check_range(0, dst_end)?;
+        let src_start = src.data().add(src_offset);
+        let dst_start = dst.data().add(dst_offset);
+        if overlap(
+            src_start,
+            src_end - src_offset,
+            dst_start,
+            dst_end - dst_offset,
+        ) {
+            return Err(CL_MEM_COPY_OVERLAP);
+        }
+        let (src, dst) = (Retained::new(src), Retained::new(dst));
+        let work: Work = Box::new(move || {
+            let src_start = src.data().add(src_offset);
+            let dst_start = dst.data().add(dst_offset);
+            copy_rect(src_start, src_pitch, dst_start, dst_pitch, region);
+            COMPLETE
+        });
+        Queue::enqueue(
+            queue,
+            CL_COMMAND_COPY_BUFFER_RECT,
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            false,
+            work,
+        )
+    })())
+}
+
+unsafe extern "system" fn enqueue_fill_buffer(
+    command_queue: cl_command_queue,
+    buffer: cl_mem,
+    pattern: *const c_void,
+    pattern_size: size_t,
+    offset: size_t,
+    size: size_t,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: *const cl_event,
+    event: *mut cl_event,
+) -> cl_int {
+    status((|| {
+        let (queue, buffer) = queue_mem(command_queue, buffer)?;
+        check_pattern(pattern, pattern_size)?;
+        buffer.check_range(offset, size)?;
+        if !offset.is_multiple_of(pattern_size) || !size.is_multiple_of(pattern_size) {
+            return Err(CL_INVALID_VALUE);
+        }
+        let pattern = slice::from_raw_parts(pattern as *const u8, pattern_size).to_vec();
+        let buffer = Retained::new(buffer);
+        let work: Work = Box::new(move || {
+            fill(buffer.data().add(offset), &pattern, size);
+            COMPLETE
+        });
+        Queue::enqueue(
+            queue,
+            CL_COMMAND_FILL_BUFFER,
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            false,
+            work,
+        )
+    })())
+}
+
+/// Shared part of the image reads and writes, host_to_image selects the
+/// direction.
+#[allow(clippy::too_many_arguments)]
+unsafe fn image_rect(
+    command_queue: cl_command_queue,
+    image: cl_mem,
+    host_to_image: bool,
+    blocking: cl_bool,
+    origin: *const size_t,
+    region: *const size_t,
+    row_pitch: size_t,
+    slice_pitch: size_t,
+    ptr: *mut c_void,
+    wait_list: (cl_uint, *const cl_event),
+    event: *mut cl_event,
+) -> Result<()> {
+    let (queue, mem) = queue_mem(command_queue, image)?;
+    let image = mem.as_image()?;
+    let region = triple(region)?;
+    let offset = image.region_offset(triple(origin)?, region)?;
+    if ptr.is_null() {
+        return Err(CL_INVALID_VALUE);
+    }
+    let row_bytes = region[0] * image.element_size;
+    let host_row_pitch = match row_pitch {
+        0 => row_bytes,
+        x if x < row_bytes => return Err(CL_INVALID_VALUE),
+        x => x,
+    };
+    // The images of a 1D array are its rows.
+    let rows = if image.is_1d_array() { 1 } else { region[1] };
+    let host_slice_pitch = match slice_pitch {
+        0 => host_row_pitch * rows,
+        x if x < host_row_pitch * rows => return Err(CL_INVALID_VALUE),
+        x => x,
+    };
+    let host_pitch = if image.is_1d_array() {
+        [host_slice_pitch, host_slice_pitch]
+    } else {
+        [host_row_pitch, host_slice_pitch]
+    };
+    let steps = image.steps();
+    let image_pitch = [steps[1], steps[2]];
+    let bytes = [row_bytes, region[1], region[2]];
+    let command_type = if host_to_image {
+        mem.host_writable()?;
+        CL_COMMAND_WRITE_IMAGE
+    } else {
+        mem.host_readable()?;
+        CL_COMMAND_READ_IMAGE
+    };
+    let (mem, host) = (Retained::new(mem), HostPtr(ptr));
+    let work: Work = Box::new(move || {
+        let start = mem.data().add(offset);
+        if host_to_image {
+            copy_rect(host.bytes(), host_pitch, start, image_pitch, bytes);
+        } else {
+            copy_rect(start, image_pitch, host.bytes(), host_pitch, bytes);
+        }
+        COMPLETE
+    });
+    Queue::enqueue(
+        queue,
+        command_type,
+        wait_list.0,
+        wait_list.1,
+        event,
+        blocking != CL_FALSE,
+        work,
+    )
+}
+
+unsafe extern "system" fn enqueue_read_image(
+    command_queue: cl_command_queue,
+    image: cl_mem,
+    blocking_read: cl_bool,
+    origin: *const size_t,
+    region: *const size_t,
+    row_pitch: size_t,
+    slice_pitch: size_t,
+    ptr: *mut c_void,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: *const cl_event,
+    event: *mut cl_event,
+) -> cl_int {
+    status(image_rect(
+        command_queue,
+        image,
+        false,
+        blocking_read,
+        origin,
+        region,
+        row_pitch,
+        slice_pitch,
+        ptr,
+        (num_events_in_wait_list, event_wait_list),
+        event,
+    ))
+}
+
+unsafe extern "system" fn enqueue_write_image(
+    command_queue: cl_command_queue,
+    image: cl_mem,
+    blocking_write: cl_bool,
+    origin: *const size_t,
+    region: *const size_t,
+    input_row_pitch: size_t,
+    input_slice_pitch: size_t,
+    ptr: *const c_void,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: *const cl_event,
+    event: *mut cl_event,
+) -> cl_int {
+    status(image_rect(
+        command_queue,
+        image,
+        true,
+        blocking_write,
+        origin,
+        region,
+        input_row_pitch,
+        input_slice_pitch,
+        ptr as *mut c_void,
+        (num_events_in_wait_list, event_wait_list),
+        event,
+    ))
+}
+
+unsafe extern "system" fn enqueue_copy_image(
+    command_queue: cl_command_queue,
+    src_image: cl_mem,
+    dst_image: cl_mem,
+    src_origin: *const size_t,
+    dst_origin: *const size_t,
+    region: *const size_t,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: *const cl_event,
+    event: *mut cl_event,
+) -> cl_int {
+    status((|| {
+        let (queue, src) = queue_mem(command_queue, src_image)?;
+        let (_, dst) = queue_mem(command_queue, dst_image)?;
+        let (src_image, dst_image) = (src.as_image()?, dst.as_image()?);
+        if src_image.format != dst_image.format {
+            return Err(CL_IMAGE_FORMAT_MISMATCH);
+        }
+        let region = triple(region)?;
+        let (src_origin, dst_origin) = (triple(src_origin)?, triple(dst_origin)?);
+        let src_offset = src_image.region_offset(src_origin, region)?;
+        let dst_offset = dst_image.region_offset(dst_origin, region)?;
+        let intersects = (0..3).all(|i| {
+            src_origin[i] < dst_origin[i] + region[i] && dst_origin[i] < src_origin[i] + region[i]
+        });
+        if src.data() == dst.data() && intersects {
+            return Err(CL_MEM_COPY_OVERLAP);
+        }
+        let (src_steps, dst_steps) = (src_image.steps(), dst_image.steps());
+        let bytes = [region[0] * src_image.element_size, region[1], region[2]];
+        let (src, dst) = (Retained::new(src), Retained::new(dst));
+        let work: Work = Box::new(move || {
+            copy_rect(
+                src.data().add(src_offset),
+                [src_steps[1], src_steps[2]],
+                dst.data().add(dst_offset),
+                [dst_steps[1], dst_steps[2]],
+                bytes,
+            );
+            COMPLETE
+        });
+        Queue::enqueue(
+            queue,
+            CL_COMMAND_COPY_IMAGE,
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            false,
+            work,
+        )
+    })())
+}
+
+unsafe extern "system" fn enqueue_fill_image(
+    command_queue: cl_command_queue,
+    image: cl_mem,
+    fill_color: *const c_void,
+    origin: *const size_t,
+    region: *const size_t,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: *const cl_event,
+    event: *mut cl_event,
+) -> cl_int {
+    status((|| {
+        let (queue, mem) = queue_mem(command_queue, image)?;
+        let image = mem.as_image()?;
+        let region = triple(region)?;
+        let offset = image.region_offset(triple(origin)?, region)?;
+        if fill_color.is_null() {
+            return Err(CL_INVALID_VALUE);
+        }
+        let pixel = image.fill_pixel(fill_color);
+        let steps = image.steps();
+        let mem = Retained::new(mem);
+        let work: Work = Box::new(move || {
+            let start = mem.data().add(offset);
+            for z in 0..region[2] {
+                for y in 0..region[1] {
+                    let row = start.add(y * steps[1] + z * steps[2]);
+                    fill(row, &pixel, region[0] * pixel.len());
+                }
+            }
+            COMPLETE
+        });
+        Queue::enqueue(
+            queue,
+            CL_COMMAND_FILL_IMAGE,
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            false,
+            work,
+        )
+    })())
+}
+
+/// Shared part of the copies between images and buffers, the buffer holds
+/// the region tightly packed.
+#[allow(clippy::too_many_arguments)]
+unsafe fn image_buffer(
+    command_queue: cl_command_queue,
+    image: cl_mem,
+    buffer: cl_mem,
+    image_to_buffer: bool,
+    origin: *const size_t,
+    region: *const size_t,
+    buffer_offset: size_t,
+    wait_list: (cl_uint, *const cl_event),
+    event: *mut cl_event,
+) -> Result<()> {
+    let (queue, image) = queue_mem(command_queue, image)?;
+    let (_, buffer) = queue_mem(command_queue, buffer)?;
+    if !buffer.is_buffer() {
+        return Err(CL_INVALID_MEM_OBJECT);
+    }
+    let format = image.as_image()?;
+    let region = triple(region)?;
+    let offset = format.region_offset(triple(origin)?, region)?;
+    let row_bytes = region[0] * format.element_size;
+    buffer.check_range(buffer_offset, row_bytes * region[1] * region[2])?;
+    let steps = format.steps();
+    let image_pitch = [steps[1], steps[2]];
+    let buffer_pitch = [row_bytes, row_bytes * region[1]];
+    let bytes = [row_bytes, region[1], region[2]];
+    let command_type = if image_to_buffer {
+        CL_COMMAND_COPY_IMAGE_TO_BUFFER
+    } else {
+        CL_COMMAND_COPY_BUFFER_TO_IMAGE
+    };
+    let (image, buffer) = (Retained::new(image), Retained::new(buffer));
+    let work: Work = Box::new(move || {
+        let image_start = image.data().add(offset);
+        let buffer_start = buffer.data().add(buffer_offset);
+        if image_to_buffer {
+            copy_rect(image_start, image_pitch, buffer_start, buffer_pitch, bytes);
+        } else {
+            copy_rect(buffer_start, buffer_pitch, image_start, image_pitch, bytes);
+        }
+        COMPLETE
+    });
+    Queue::enqueue(
+        queue,
+        command_type,
+        wait_list.0,
+        wait_list.1,
+        event,
+        false,
+        work,
+    )
+}
+
+unsafe extern "system" fn enqueue_copy_image_to_buffer(
+    command_queue: cl_command_queue,
+    src_image: cl_mem,
+    dst_buffer: cl_mem,
+    src_origin: *const size_t,
+    region: *const size_t,
+    dst_offset: size_t,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: *const cl_event,
+    event: *mut cl_event,
+) -> cl_int {
+    status(image_buffer(
+        command_queue,
+        src_image,
+        dst_buffer,
+        true,
+        src_origin,
+        region,
+        dst_offset,
+        (num_events_in_wait_list, event_wait_list),
+        event,
+    ))
+}
+
+unsafe extern "system" fn enqueue_copy_buffer_to_image(
+    command_queue: cl_command_queue,
+    src_buffer: cl_mem,
+    dst_image: cl_mem,
+    src_offset: size_t,
+    dst_origin: *const size_t,
+    region: *const size_t,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: *const cl_event,
+    event: *mut cl_event,
+) -> cl_int {
+    status(image_buffer(
+        command_queue,
+        dst_image,
+        src_buffer,
+        false,
+        dst_origin,
+        region,
+        src_offset,
+        (num_events_in_wait_list, event_wait_list),
+        event,
+    ))
+}
+
+fn check_map_flags(mem: &Mem, map_flags: cl_map_flags) -> Result<()> {
+    let read_write = CL_MAP_READ | CL_MAP_WRITE;
+    if map_flags & !(read_write | CL_MAP_WRITE_INVALIDATE_REGION) != 0
+        || (map_flags & read_write != 0 && map_flags & CL_MAP_WRITE_INVALIDATE_REGION != 0)
+    {
+        return Err(CL_INVALID_VALUE);
+    }
+    if map_flags & CL_MAP_READ != 0 {
+        mem.host_readable()?;
+    }
+    if map_flags & (CL_MAP_WRITE | CL_MAP_WRITE_INVALIDATE_REGION) != 0 {
+        mem.host_writable()?;
+    }
+    Ok(())
+}
+
+/// Enqueues the command of a map, which only orders it since the mapped
+/// pointer is the memory of the object.
+unsafe fn enqueue_map(
+    queue: Handle<Queue>,
+    mem: Handle<Mem>,
+    command_type: cl_command_type,
+    blocking: cl_bool,
+    wait_list: (cl_uint, *const cl_event),
+    event: *mut cl_event,
+) -> Result<()> {
+    let mem = Retained::new(mem);
+    let work: Work = Box::new(move || {
+        mem.map();
+        COMPLETE
+    });
+    Queue::enqueue(
+        queue,
+        command_type,
+        wait_list.0,
+        wait_list.1,
+        event,
+        blocking != CL_FALSE,
+        work,
+    )
+}
+
+unsafe fn mapped(result: Result<*mut u8>, errcode_ret: *mut cl_int) -> *mut c_void {
+    let (mapped, status_code) = match result {
+        Ok(x) => (x as *mut c_void, CL_SUCCESS),
+        Err(x) => (ptr::null_mut(), x),
+    };
+    if !errcode_ret.is_null() {
+        *errcode_ret = status_code;
+    }
+    mapped
+}
+
+unsafe extern "system" fn enqueue_map_buffer(
+    command_queue: cl_command_queue,
+    buffer: cl_mem,
+    blocking_map: cl_bool,
+    map_flags: cl_map_flags,
+    offset: size_t,
+    size: size_t,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: *const cl_event,
+    event: *mut cl_event,
+    errcode_ret: *mut cl_int,
+) -> *mut c_void {
+    let result = (|| {
+        let (queue, buffer) = queue_mem(command_queue, buffer)?;
+        if !buffer.is_buffer() {
+            return Err(CL_INVALID_MEM_OBJECT);
+        }
+        buffer.check_range(offset, size)?;
+        if size == 0 {
+            return Err(CL_INVALID_VALUE);
+        }
+        check_map_flags(&buffer, map_flags)?;
+        enqueue_map(
+            queue,
+            buffer,
+            CL_COMMAND_MAP_BUFFER,
+            blocking_map,
+            (num_events_in_wait_list, event_wait_list),
+            event,
+        )?;
+        Ok(buffer.data().add(offset))
+    })();
+    mapped(result, errcode_ret)
+}
+
+unsafe extern "system" fn enqueue_map_image(
+    command_queue: cl_command_queue,
+    image: cl_mem,
+    blocking_map: cl_bool,
+    map_flags: cl_map_flags,
+    origin: *const size_t,
+    region: *const size_t,
+    image_row_pitch: *mut size_t,
+    image_slice_pitch: *mut size_t,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: *const cl_event,
+    event: *mut cl_event,
+    errcode_ret: *mut cl_int,
+) -> *mut c_void {
+    let result = (|| {
+        let (queue, mem) = queue_mem(command_queue, image)?;
+        let image = mem.as_image()?;
+        let offset = image.region_offset(triple(origin)?, triple(region)?)?;
+        let has_slices = matches!(
+            mem.mem_type,
+            CL_MEM_OBJECT_IMAGE1D_ARRAY | CL_MEM_OBJECT_IMAGE2D_ARRAY | CL_MEM_OBJECT_IMAGE3D
+        );
+        if image_row_pitch.is_null() || (has_slices && image_slice_pitch.is_null()) {
+            return Err(CL_INVALID_VALUE);
+        }
+        check_map_flags(&mem, map_flags)?;
+        let steps = image.steps();
+        *image_row_pitch = steps[1];
+        if !image_slice_pitch.is_null() {
+            *image_slice_pitch = if has_slices { steps[2] } else { 0 };
+        }
+        enqueue_map(
+            queue,
+            mem,
+            CL_COMMAND_MAP_IMAGE,
+            blocking_map,
+            (num_events_in_wait_list, event_wait_list),
+            event,
+        )?;
+        Ok(mem.data().add(offset))
+    })();
+    mapped(result, errcode_ret)
+}
+
+unsafe extern "system" fn enqueue_unmap_mem_object(
+    command_queue: cl_command_queue,
+    memobj: cl_mem,
+    mapped_ptr: *mut c_void,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: *const cl_event,
+    event: *mut cl_event,
+) -> cl_int {
+    status((|| {
+        let (queue, mem) = queue_mem(command_queue, memobj)?;
+        // The mapping ends when the command is enqueued, so that a bad
+        // pointer is reported here.
+        mem.unmap(mapped_ptr)?;
+        let work: Work = Box::new(|| COMPLETE);
+        Queue::enqueue(
+            queue,
+            CL_COMMAND_UNMAP_MEM_OBJECT,
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            false,
+            work,
+        )
+    })())
+}
+
+unsafe extern "system" fn enqueue_migrate_mem_objects(
+    command_queue: cl_command_queue,
+    num_mem_objects: cl_uint,
+    mem_objects: *const cl_mem,
+    flags: cl_mem_migration_flags,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: *const cl_event,
+    event: *mut cl_event,
+) -> cl_int {
+    status((|| {
+        let queue = handle::<Queue>(command_queue, ObjectKind::CommandQueue)?;
+        if num_mem_objects == 0 || mem_objects.is_null() {
+            return Err(CL_INVALID_VALUE);
+        }
+        if flags & !(CL_MIGRATE_MEM_OBJECT_HOST | CL_MIGRATE_MEM_OBJECT_CONTENT_UNDEFINED) != 0 {
+            return Err(CL_INVALID_VALUE);
+        }
+        for mem in slice::from_raw_parts(mem_objects, num_mem_objects as usize) {
+            queue_mem(command_queue, *mem)?;
+        }
+        // All memory is host memory already.
+        Queue::enqueue(
+            queue,
+            CL_COMMAND_MIGRATE_MEM_OBJECTS,
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            false,
+            Box::new(|| COMPLETE),
+        )
+    })())
+}
+
+unsafe extern "system" fn enqueue_svm_free(
+    command_queue: cl_command_queue,
+    num_svm_pointers: cl_uint,
+    svm_pointers: *const *const c_void,
+    pfn_free_func: Option<SvmFreeNotify>,
+    user_data: *mut c_void,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: *const cl_event,
+    event: *mut cl_event,
+) -> cl_int {
+    status((|| {
+        let queue = handle::<Queue>(command_queue, ObjectKind::CommandQueue)?;
+        if (num_svm_pointers == 0) != svm_pointers.is_null() {
+            return Err(CL_INVALID_VALUE);
+        }
+        let pointers: Vec<HostPtr> = if svm_pointers.is_null() {
+            Vec::new()
+        } else {
+            slice::from_raw_parts(svm_pointers, num_svm_pointers as usize)
+                .iter()
+                .map(|x| HostPtr(*x as *mut c_void))
+                .collect()
+        };
+        let (raw_queue, user_data) = (HostPtr(command_queue), HostPtr(user_data));
+        let work: Work = Box::new(move || {
+            match pfn_free_func {
+                Some(free) => {
+                    let list: Vec<*const c_void> =
+                        pointers.iter().map(|x| x.0 as *const c_void).collect();
+                    free(
+                        raw_queue.0,
+                        list.len() as cl_uint,
+                        list.as_ptr(),
+                        user_data.0,
+                    );
+                }
+                None => pointers.iter().for_each(|x| svm_free(x.0)),
+            }
+            COMPLETE
+        });
+        Queue::enqueue(
+            queue,
+            CL_COMMAND_SVM_FREE,
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            false,
+            work,
+        )
+    })())
+}
+
+unsafe extern "system" fn enqueue_svm_memcpy(
+    command_queue: cl_command_queue,
+    blocking_copy: cl_bool,
+    dst_ptr: *mut c_void,
+    src_ptr: *const c_void,
+    size: size_t,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: *const cl_event,
+    event: *mut cl_event,
+) -> cl_int {
+    status((|| {
+        let queue = handle::<Queue>(command_queue, ObjectKind::CommandQueue)?;
+        if dst_ptr.is_null() || src_ptr.is_null() {
+            return Err(CL_INVALID_VALUE);
+        }
+        if overlap(src_ptr as *const u8, size, dst_ptr as *const u8, size) {
+            return Err(CL_MEM_COPY_OVERLAP);
+        }
+        let (dst, src) = (HostPtr(dst_ptr), HostPtr(src_ptr as *mut c_void));
+        let work: Work = Box::new(move || {
+            ptr::copy_nonoverlapping(src.bytes(), dst.bytes(), size);
+            COMPLETE
+        });
+        Queue::enqueue(
+            queue,
+            CL_COMMAND_SVM_MEMCPY,
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            blocking_copy != CL_FALSE,
+            work,
+        )
+    })())
+}
+
+unsafe extern "system" fn enqueue_svm_mem_fill(
+    command_queue: cl_command_queue,
+    svm_ptr: *mut c_void,
+    pattern: *const c_void,
+    pattern_size: size_t,
+    size: size_t,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: *const cl_event,
+    event: *mut cl_event,
+) -> cl_int {
+    status((|| {
+        let queue = handle::<Queue>(command_queue, ObjectKind::CommandQueue)?;
+        check_pattern(pattern, pattern_size)?;
+        if svm_ptr.is_null()
+            || !(svm_ptr as usize).is_multiple_of(pattern_size)
+            || !size.is_multiple_of(pattern_size)
+        {
+            return Err(CL_INVALID_VALUE);
+        }
+        let pattern = slice::from_raw_parts(pattern as *const u8, pattern_size).to_vec();
+        let target = HostPtr(svm_ptr);
+        let work: Work = Box::new(move || {
+            fill(target.bytes(), &pattern, size);
+            COMPLETE
+        });
+        Queue::enqueue(
+            queue,
+            CL_COMMAND_SVM_MEMFILL,
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            false,
+            work,
+        )
+    })())
+}
+
+unsafe extern "system" fn enqueue_svm_map(
+    command_queue: cl_command_queue,
+    blocking_map: cl_bool,
+    flags: cl_map_flags,
+    svm_ptr: *mut c_void,
+    size: size_t,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: *const cl_event,
+    event: *mut cl_event,
+) -> cl_int {
+    status((|| {
+        let queue = handle::<Queue>(command_queue, ObjectKind::CommandQueue)?;
+        let valid = CL_MAP_READ | CL_MAP_WRITE | CL_MAP_WRITE_INVALIDATE_REGION;
+        if svm_ptr.is_null() || size == 0 || flags & !valid != 0 {
+            return Err(CL_INVALID_VALUE);
+        }
+        Queue::enqueue(
+            queue,
+            CL_COMMAND_SVM_MAP,
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            blocking_map != CL_FALSE,
+            Box::new(|| COMPLETE),
+        )
+    })())
+}
+
+unsafe extern "system" fn enqueue_svm_unmap(
+    command_queue: cl_command_queue,
+    svm_ptr: *mut c_void,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: *const cl_event,
+    event: *mut cl_event,
+) -> cl_int {
+    status((|| {
+        let queue = handle::<Queue>(command_queue, ObjectKind::CommandQueue)?;
+        if svm_ptr.is_null() {
+            return Err(CL_INVALID_VALUE);
+        }
+        Queue::enqueue(
+            queue,
+            CL_COMMAND_SVM_UNMAP,
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            false,
+            Box::new(|| COMPLETE),
+        )
+    })())
+}
+
+unsafe extern "system" fn enqueue_svm_migrate_mem(
+    command_queue: cl_command_queue,
+    num_svm_pointers: cl_uint,
+    svm_pointers: *const *const c_void,
+    _sizes: *const size_t,
+    flags: cl_mem_migration_flags,
+    num_events_in_wait_list: cl_uint,
+    event_wait_list: *const cl_event,
+    event: *mut cl_event,
+) -> cl_int {
+    status((|| {
+        let queue = handle::<Queue>(command_queue, ObjectKind::CommandQueue)?;
+        if num_svm_pointers == 0 || svm_pointers.is_null() {
+            return Err(CL_INVALID_VALUE);
+        }
+        if flags & !(CL_MIGRATE_MEM_OBJECT_HOST | CL_MIGRATE_MEM_OBJECT_CONTENT_UNDEFINED) != 0 {
+            return Err(CL_INVALID_VALUE);
+        }
+        Queue::enqueue(
+            queue,
+            CL_COMMAND_SVM_MIGRATE_MEM,
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            false,
+            Box::new(|| COMPLETE),
+        )
+    })())
+}
+
+pub(crate) fn extend_dispatch(table: &mut cl_icd_dispatch) {
+    table.clEnqueueReadBuffer = Some(enqueue_read_buffer);
+    table.clEnqueueWriteBuffer = Some(enqueue_write_buffer);
+    table.clEnqueueReadBufferRect = Some(enqueue_read_buffer_rect);
+    table.clEnqueueWriteBufferRect = Some(enqueue_write_buffer_rect);
+    table.clEnqueueCopyBuffer = Some(enqueue_copy_buffer);
+    table.clEnqueueCopyBufferRect = Some(enqueue_copy_buffer_rect);
+    table.clEnqueueFillBuffer = Some(enqueue_fill_buffer);
+    table.clEnqueueReadImage = Some(enqueue_read_image);
+    table.clEnqueueWriteImage = Some(enqueue_write_image);
+    table.clEnqueueCopyImage = Some(enqueue_copy_image);
+    table.clEnqueueFillImage = Some(enqueue_fill_image);
+    table.clEnqueueCopyImageToBuffer = Some(enqueue_copy_image_to_buffer);
+    table.clEnqueueCopyBufferToImage = Some(enqueue_copy_buffer_to_image);
+    table.clEnqueueMapBuffer = Some(enqueue_map_buffer);
+    table.clEnqueueMapImage = Some(enqueue_map_image);
+    table.clEnqueueUnmapMemObject = Some(enqueue_unmap_mem_object);
+    table.clEnqueueMigrateMemObjects = Some(enqueue_migrate_mem_objects);
+    table.clEnqueueSVMFree = Some(enqueue_svm_free);
+    table.clEnqueueSVMMemcpy = Some(enqueue_svm_memcpy);
+    table.clEnqueueSVMMemFill = Some(enqueue_svm_mem_fill);
+    table.clEnqueueSVMMap = Some(enqueue_svm_map);
+    table.clEnqueueSVMUnmap = Some(enqueue_svm_unmap);
+    table.clEnqueueSVMMigrateMem = Some(enqueue_svm_migrate_mem);
+}
+
+/************************/
+/* /\ /\ /\ /\ /\ /\ /\ */
+/*|__|__|__|__|__|__|__|*/
+/*|  |  |  |  |  |  |  |*/
+/*|  |  Unit Tests  |  |*/
+/*|__|__|__|__|__|__|__|*/
+/*|__|__|__|__|__|__|__|*/
+/************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ffi;
+    use crate::mock::tests::context;
+
+    #[test]
+    fn test_image_fill_read() {
+        let (device, context) = context();
+        let mut status_code = CL_INVALID_VALUE;
+        let format = cl_image_format {
+            image_channel_order: CL_RGBA,
+            image_channel_data_type: CL_UNORM_INT8,
+        };
+        let desc = cl_image_desc {
+            image_type: CL_MEM_OBJECT_IMAGE2D,
+            image_width: 4,
+            image_height: 2,
+            ..Default::default()
+        };
+        let color = [1.0f32, 0.0, 0.5, 1.0];
+        let mut pixels = [0u8; 4 * 4 * 2];
+        unsafe {
+            let queue = ffi::clCreateCommandQueue(context, device, 0, &mut status_code);
+            let image = ffi::clCreateImage(
+                context,
+                CL_MEM_READ_WRITE,
+                &format,
+                &desc,
+                ptr::null_mut(),
+                &mut status_code,
+            );
+            assert_eq!(CL_SUCCESS, status_code);
+            let (origin, region) = ([1usize, 1, 0], [3usize, 1, 1]);
+            assert_eq!(
+                CL_SUCCESS,
+                ffi::clEnqueueFillImage(
+                    queue,
+                    image,
+                    color.as_ptr() as *const c_void,
+                    origin.as_ptr(),
+                    region.as_ptr(),
+                    0,
+                    ptr::null(),
+                    ptr::null_mut(),
+                )
+            );
+            assert_eq!(
+                CL_SUCCESS,
+                ffi::clEnqueueReadImage(
+                    queue,
+                    image,
+                    CL_BLOCKING,
+                    [0usize, 0, 0].as_ptr(),
+                    [4usize, 2, 1].as_ptr(),
+                    0,
+                    0,
+                    pixels.as_mut_ptr() as *mut c_void,
+                    0,
+                    ptr::null(),
+                    ptr::null_mut(),
+                )
+            );
+            ffi::clReleaseMemObject(image);
+            ffi::clReleaseCommandQueue(queue);
+            ffi::clReleaseContext(context);
+        }
+        assert_eq!([0u8; 20], pixels[..20]);
+        assert_eq!([255, 0, 128, 255], pixels[20..24]);
+        assert_eq!([255, 0, 128, 255], pixels[28..]);
+    }
+
+    #[test]
+    fn test_sub_buffer_overlap() {
+        let (device, context) = context();
+        let mut status_code = CL_INVALID_VALUE;
+        unsafe {
+            let queue = ffi::clCreateCommandQueue(context, device, 0, &mut status_code);
+            let buffer = ffi::clCreateBuffer(
+                context,
+                CL_MEM_READ_WRITE,
+                512,
+                ptr::null_mut(),
+                &mut status_code,
+            );
+            let region = cl_buffer_region {
+                origin: 128,
+                size: 256,
+            };
+            let sub_buffer = ffi::clCreateSubBuffer(
+                buffer,
+                0,
+                CL_BUFFER_CREATE_TYPE_REGION,
+                &region as *const cl_buffer_region as *const c_void,
+                &mut status_code,
+            );
+            assert_eq!(CL_SUCCESS, status_code);
+            let copy = |src, dst, src_offset, dst_offset| {
+                ffi::clEnqueueCopyBuffer(
+                    queue,
+                    src,
+                    dst,
+                    src_offset,
+                    dst_offset,
+                    64,
+                    0,
+                    ptr::null(),
+                    ptr::null_mut(),
+                )
+            };
+            assert_eq!(CL_MEM_COPY_OVERLAP, copy(buffer, sub_buffer, 160, 0));
+            assert_eq!(CL_SUCCESS, copy(buffer, sub_buffer, 0, 0));
+            ffi::clReleaseMemObject(sub_buffer);
+            ffi::clReleaseMemObject(buffer);
+            ffi::clReleaseCommandQueue(queue);
+            ffi::clReleaseContext(context);
+        }
+    }
+}
@@ -0,0 +1,295 @@
+/*
+ * probe.rs - Diagnostics of the installed ICD vendors.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+//!
+//! With the `probe` feature, the vendor libraries an ICD loader would load
+//! can be listed and checked without loading them as vendors, whichever way
+//! `ffi` is linked. Discovery follows the Khronos loader, as the `icd`
+//! feature does:
+//!
+//! * `OCL_ICD_FILENAMES`, a list of vendor libraries separated by `:` (`;` on
+//!   windows), loaded first.
+//! * `OCL_ICD_VENDORS`, a directory of `.icd` files or a single `.icd` file,
+//!   replacing the default `/etc/OpenCL/vendors` directory.
+//!
+//! Checking a library opens it, which runs its initialization code, and
+//! leaves it loaded for the lifetime of the process.
+//!
+use crate::library::Library;
+use libc::{c_char, c_void};
+use std::path::{Path, PathBuf};
+
+pub use crate::library::LoadError;
+
+/// Environment variable listing vendor libraries to load.
+pub const FILENAMES_ENV: &str = "OCL_ICD_FILENAMES";
+/// Environment variable overriding the vendors directory.
+pub const VENDORS_ENV: &str = "OCL_ICD_VENDORS";
+
+#[cfg(not(target_os = "windows"))]
+const VENDORS_DIR: Option<&str> = Some("/etc/OpenCL/vendors");
+#[cfg(target_os = "windows")]
+const VENDORS_DIR: Option<&str> = None;
+
+#[cfg(not(target_os = "windows"))]
+const FILENAMES_SEPARATOR: char = ':';
+#[cfg(target_os = "windows")]
+const FILENAMES_SEPARATOR: char = ';';
+
+/// Where the path of a vendor library was found.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    /// The `OCL_ICD_FILENAMES` list.
+    Filenames,
+    /// An `.icd` file.
+    IcdFile(PathBuf),
+}
+
+/// A vendor library to load, in load order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub source: Source,
+    /// Path of the library, or why its `.icd` file does not give one.
+    pub library: Result<PathBuf, String>,
+}
+
+/// Outcome of checking a vendor library.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Status {
+    /// The library opens and exposes the entry points of a vendor.
+    Usable,
+    /// The library opens but lacks these entry points.
+    MissingSymbols(Vec<&'static str>),
+    /// The library does not open, `LoadError::LibraryNotFound` gives the
+    /// error of the system loader.
+    LoadFailed(LoadError),
+}
+
+/// The `.icd` file or directory of `.icd` files vendors are read from,
+/// None on platforms without a default one.
+pub fn vendors_location() -> Option<PathBuf> {
+    location(std::env::var(VENDORS_ENV).ok())
+}
+
+fn location(vendors: Option<String>) -> Option<PathBuf> {
+    vendors
+        .or_else(|| VENDORS_DIR.map(String::from))
+        .map(PathBuf::from)
+}
+
+/// Vendor libraries found from the environment and the vendors directory,
+/// including the `.icd` files that do not name one.
+pub fn entries() -> Vec<Entry> {
+    entries_from(
+        std::env::var(FILENAMES_ENV).ok(),
+        std::env::var(VENDORS_ENV).ok(),
+    )
+}
+
+pub(crate) fn entries_from(filenames: Option<String>, vendors: Option<String>) -> Vec<Entry> {
+    let mut entries: Vec<Entry> = filenames
+        .iter()
+        .flat_map(|x| x.split(FILENAMES_SEPARATOR))
+        .filter(|x| !x.is_empty())
+        .map(|x| Entry {
+            source: Source::Filenames,
+            library: Ok(PathBuf::from(x)),
+        })
+        .collect();
+
+    let vendors = match location(vendors) {
+        Some(x) if x.exists() => x,
+        _ => return entries,
+    };
+    let mut icd_files = if vendors.is_dir() {
+        match std::fs::read_dir(&vendors) {
+            Ok(entries) => entries
+                .filter_map(|x| x.ok())
+                .map(|x| x.path())
+                .filter(|x| x.extension().is_some_and(|x| x == "icd"))
+                .collect(),
+            Err(_) => Vec::new(),
+        }
+    } else {
+        vec![vendors]
+    };
+    // Read in name order so that the platform order is stable.
+    icd_files.sort();
+    for icd_file in icd_files {
+        let library = match std::fs::read_to_string(&icd_file) {
+            Ok(content) if content.trim().is_empty() => Err(String::from("empty .icd file")),
+            Ok(content) => Ok(PathBuf::from(content.trim())),
+            Err(e) => Err(e.to_string()),
+        };
+        entries.push(Entry {
+            source: Source::IcdFile(icd_file),
+            library,
+        });
+    }
+    entries
+}
+
+/// Opens the library at path and looks for the entry points every vendor
+/// exports, `clIcdGetPlatformIDsKHR` and `clGetExtensionFunctionAddress`.
+pub fn check<P: AsRef<Path>>(path: P) -> Status {
+    let library = match Library::open(&path.as_ref().to_string_lossy()) {
+        Ok(x) => x,
+        Err(e) => return Status::LoadFailed(e),
+    };
+    let mut missing = Vec::new();
+    let get_address = library.symbol("clGetExtensionFunctionAddress\0");
+    if get_address.is_none() {
+        missing.push("clGetExtensionFunctionAddress");
+    }
+    let get_platform_ids = library.symbol("clIcdGetPlatformIDsKHR\0").or_else(|| {
+        // Older vendors only expose it as an extension function.
+        let get_address = unsafe {
+            std::mem::transmute::<
+                *mut c_void,
+                unsafe extern "system" fn(*const c_char) -> *mut c_void,
+            >(get_address?)
+        };
+        let address = unsafe { get_address("clIcdGetPlatformIDsKHR\0".as_ptr() as *const c_char) };
+        if address.is_null() {
+            None
+        } else {
+            Some(address)
+        }
+    });
+    if get_platform_ids.is_none() {
+        missing.push("clIcdGetPlatformIDsKHR");
+    }
+    if missing.is_empty() {
+        Status::Usable
+    } else {
+        Status::MissingSymbols(missing)
+    }
+}
+
+/************************/
+/* /\ /\ /\ /\ /\ /\ /\ */
+/*|__|__|__|__|__|__|__|*/
+/*|  |  |  |  |  |  |  |*/
+/*|  |  Unit Tests  |  |*/
+/*|__|__|__|__|__|__|__|*/
+/*|__|__|__|__|__|__|__|*/
+/************************/
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("opencl-heads-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn compile(dir: &Path, name: &str, source: &str) -> PathBuf {
+        let source_path = dir.join(format!("{}.c", name));
+        let library = dir.join(format!("lib{}.so", name));
+        std::fs::write(&source_path, source).unwrap();
+        let status = Command::new("cc")
+            .args(["-shared", "-fPIC", "-o"])
+            .arg(&library)
+            .arg(&source_path)
+            .status()
+            .unwrap();
+        assert!(status.success());
+        library
+    }
+
+    #[test]
+    fn test_entries() {
+        let dir = temp_dir("probe-vendors");
+        std::fs::write(dir.join("b.icd"), "/opt/b/libB.so\n").unwrap();
+        std::fs::write(dir.join("a.icd"), "/opt/a/libA.so").unwrap();
+        std::fs::write(dir.join("empty.icd"), "\n").unwrap();
+        std::fs::write(dir.join("readme.txt"), "/opt/c/libC.so").unwrap();
+
+        let dir_name = dir.to_string_lossy().into_owned();
+        let entries = entries_from(Some(String::from("/x/libX.so:")), Some(dir_name));
+        let expected = vec![
+            Entry {
+                source: Source::Filenames,
+                library: Ok(PathBuf::from("/x/libX.so")),
+            },
+            Entry {
+                source: Source::IcdFile(dir.join("a.icd")),
+                library: Ok(PathBuf::from("/opt/a/libA.so")),
+            },
+            Entry {
+                source: Source::IcdFile(dir.join("b.icd")),
+                library: Ok(PathBuf::from("/opt/b/libB.so")),
+            },
+            Entry {
+                source: Source::IcdFile(dir.join("empty.icd")),
+                library: Err(String::from("empty .icd file")),
+            },
+        ];
+        assert_eq!(expected, entries);
+
+        let missing = dir.join("missing").to_string_lossy().into_owned();
+        assert_eq!(
+            Some(PathBuf::from(&missing)),
+            location(Some(missing.clone()))
+        );
+        assert!(entries_from(None, Some(missing)).is_empty());
+    }
+
+    #[test]
+    fn test_check() {
+        let dir = temp_dir("probe-libraries");
+        let vendor = compile(
+            &dir,
+            "Vendor",
+            "int clIcdGetPlatformIDsKHR(void) { return 0; }
+             void* clGetExtensionFunctionAddress(const char* name) { return 0; }",
+        );
+        assert_eq!(Status::Usable, check(&vendor));
+
+        // clIcdGetPlatformIDsKHR is also found as an extension function.
+        let old_vendor = compile(
+            &dir,
+            "OldVendor",
+            "static int get_platform_ids(void) { return 0; }
+             void* clGetExtensionFunctionAddress(const char* name) {
+                 return (void*) get_platform_ids;
+             }",
+        );
+        assert_eq!(Status::Usable, check(&old_vendor));
+
+        let unrelated = compile(&dir, "NotAVendor", "int unrelated(void) { return 0; }");
+        assert_eq!(
+            Status::MissingSymbols(vec![
+                "clGetExtensionFunctionAddress",
+                "clIcdGetPlatformIDsKHR"
+            ]),
+            check(&unrelated)
+        );
+
+        match check(dir.join("libMissing.so")) {
+            Status::LoadFailed(LoadError::LibraryNotFound { path, reason }) => {
+                assert!(path.ends_with("libMissing.so"));
+                assert!(!reason.is_empty());
+            }
+            x => panic!("unexpected {:?}", x),
+        }
+    }
+}
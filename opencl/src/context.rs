@@ -0,0 +1,108 @@
+/*
+ * context.rs - Contexts.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+use crate::macros::{gen_handle, gen_object};
+use crate::platform::Device;
+use crate::Result;
+use opencl_api::api::context::{create_context, release_context, retain_context};
+use opencl_api::api::image::get_supported_image_formats;
+use opencl_api::objects::bitfields::MemFlags;
+use opencl_api::objects::wrappers::WrappedMutablePointer;
+use opencl_heads::types::*;
+
+gen_object! {
+    /// OpenCL context, holding the objects shared by a set of devices.
+    Context: cl_context, retain_context, release_context, Send, Sync
+}
+
+gen_handle!(Context => Context);
+
+impl Context {
+    /// Creates a context for devices, of the same platform.
+    pub fn new(devices: &[Device]) -> Result<Context> {
+        let devices = devices.iter().map(Device::as_raw).collect();
+        let context = create_context(&None, devices, None, WrappedMutablePointer::null())?;
+        Ok(unsafe { Context::from_raw(context.unwrap()) })
+    }
+
+    /// Returns the image formats the context supports for images of
+    /// image_type created with flags. The formats are kept raw as drivers
+    /// may list formats of extensions unknown to `ImageFormat`.
+    pub fn supported_image_formats(
+        &self,
+        flags: MemFlags,
+        image_type: cl_mem_object_type,
+    ) -> Result<Vec<cl_image_format>> {
+        Ok(get_supported_image_formats(&self.ptr(), flags, image_type)?)
+    }
+}
+
+/************************/
+/* /‾‾\ /‾‾\ /‾‾\ /‾‾\  */
+/*|  |  |  |  |  |  |  |*/
+/*|  |  Unit Tests  |  |*/
+/*|__|__|__|__|__|__|__|*/
+/*|__|__|__|__|__|__|__|*/
+/************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::Platform;
+    use opencl_api::api::context::get_context_info;
+    use opencl_api::objects::bitfields::DeviceType;
+    use opencl_api::objects::structs::{ContextInfo, MemObjectType};
+    use opencl_api::objects::traits::GetSetGo;
+
+    fn reference_count(context: &Context) -> Option<cl_uint> {
+        get_context_info(&context.ptr(), ContextInfo::REFERENCE_COUNT)
+            .unwrap()
+            .unwrap_uint()
+    }
+
+    #[test]
+    fn test_clone_and_drop() {
+        let platform = Platform::list().unwrap()[0];
+        let device_type = DeviceType::new(DeviceType::DEFAULT).unwrap();
+        let devices = platform.devices(device_type).unwrap();
+        let context = Context::new(&devices).unwrap();
+        assert_eq!(reference_count(&context), Some(1));
+
+        let other = context.clone();
+        assert_eq!(other, context);
+        assert_eq!(reference_count(&context), Some(2));
+        drop(other);
+        assert_eq!(reference_count(&context), Some(1));
+
+        // Going through the raw handle keeps the reference.
+        let context = unsafe { Context::from_raw(context.into_raw()) };
+        assert_eq!(reference_count(&context), Some(1));
+    }
+
+    #[test]
+    fn test_supported_image_formats() {
+        let platform = Platform::list().unwrap()[0];
+        let device_type = DeviceType::new(DeviceType::DEFAULT).unwrap();
+        let devices = platform.devices(device_type).unwrap();
+        let context = Context::new(&devices).unwrap();
+        let flags = MemFlags::new(MemFlags::READ_WRITE).unwrap();
+        let formats = context
+            .supported_image_formats(flags, MemObjectType::IMAGE2D)
+            .unwrap();
+        assert!(!formats.is_empty());
+    }
+}
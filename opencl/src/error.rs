@@ -0,0 +1,41 @@
+/*
+ * error.rs - Errors of the opencl crate.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+use opencl_api::errors::OpenCLAPIError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum Error {
+    #[error(transparent)]
+    Api(#[from] OpenCLAPIError),
+    #[error("length mismatch, expected {expected} elements but found {found}")]
+    LengthMismatch { expected: usize, found: usize },
+    #[error("range {start}..{end} out of bounds of {len} elements")]
+    OutOfRange {
+        start: usize,
+        end: usize,
+        len: usize,
+    },
+    #[error("{len} elements of {size} bytes overflow the address space")]
+    SizeOverflow { len: usize, size: usize },
+    #[error("fill patterns are 1, 2, 4, ..., 128 bytes long, not {size}")]
+    PatternSize { size: usize },
+    #[error("image elements are {expected} bytes long, not {found}")]
+    ElementSize { expected: usize, found: usize },
+    #[error("mapped memory is not aligned to {align} bytes")]
+    Misaligned { align: usize },
+}
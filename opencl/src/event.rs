@@ -0,0 +1,90 @@
+/*
+ * event.rs - Events.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+use crate::context::Context;
+use crate::macros::gen_object;
+use crate::Result;
+use opencl_api::api::event::{
+    create_user_event, get_event_profiling_info, release_event, retain_event, wait_for_events,
+};
+use opencl_api::errors::{RuntimeError, ToLibraryError};
+use opencl_api::objects::enums::ParamValue;
+use opencl_api::objects::structs::ProfilingInfo;
+use opencl_heads::types::*;
+
+gen_object! {
+    /// OpenCL event, the status of a command.
+    Event: cl_event, retain_event, release_event, Send, Sync
+}
+
+impl Event {
+    /// Creates an event whose status is set by the host.
+    pub fn user(context: &Context) -> Result<Event> {
+        let event = create_user_event(&context.ptr())?;
+        Ok(unsafe { Event::from_raw(event.unwrap()) })
+    }
+
+    /// Waits on the host thread for the command of the event to complete.
+    pub fn wait(&self) -> Result<()> {
+        wait_for_events(&vec![self.raw])?;
+        Ok(())
+    }
+
+    /// Returns when the command of the event was queued, submitted, started
+    /// and ended.
+    ///
+    /// Only queues created with `CommandQueue::with_profiling` record the
+    /// times, which are known once the command completed.
+    pub fn profile(&self) -> Result<Profile> {
+        let time = |param_name| -> Result<cl_ulong> {
+            match get_event_profiling_info(&self.ptr(), param_name)? {
+                ParamValue::ULong(x) => Ok(x),
+                _ => {
+                    let error = RuntimeError::UnexpectedParamValue("clGetEventProfilingInfo");
+                    Err(error.to_error().into())
+                }
+            }
+        };
+        Ok(Profile {
+            queued: time(ProfilingInfo::QUEUED)?,
+            submit: time(ProfilingInfo::SUBMIT)?,
+            start: time(ProfilingInfo::START)?,
+            end: time(ProfilingInfo::END)?,
+        })
+    }
+}
+
+/// Device timer values in nanoseconds of the steps of a command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Profile {
+    pub queued: cl_ulong,
+    pub submit: cl_ulong,
+    pub start: cl_ulong,
+    pub end: cl_ulong,
+}
+
+impl Profile {
+    /// Nanoseconds the command ran for.
+    pub fn duration(&self) -> cl_ulong {
+        self.end.saturating_sub(self.start)
+    }
+
+    /// Nanoseconds from queueing the command to its start.
+    pub fn latency(&self) -> cl_ulong {
+        self.start.saturating_sub(self.queued)
+    }
+}
@@ -1,7 +1,52 @@
-#[cfg(test)]
-mod tests {
-    #[test]
-    fn it_works() {
-        assert_eq!(2 + 2, 4);
-    }
-}
+/*
+ * lib.rs - Safe Rust interfaces for the OpenCL API.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+//!
+//! Owned types for the OpenCL objects, built on opencl-api.
+//!
+//! Every object but `Platform` holds a reference to its OpenCL object:
+//! cloning retains the object and dropping releases it, so there is no
+//! `release_*` call to pair by hand. `Kernel`, whose arguments clones would
+//! share, is copied with `Kernel::try_clone` instead. `from_raw` and `into_raw` move the
+//! reference from and to the raw handles of opencl-api and opencl-heads.
+//!
+mod macros;
+
+pub mod context;
+mod error;
+pub mod event;
+pub mod map;
+pub mod memory;
+pub mod platform;
+pub mod program;
+pub mod queue;
+pub mod sampler;
+pub mod snapshot;
+
+pub use context::Context;
+pub use error::Error;
+pub use event::{Event, Profile};
+pub use map::{MappedImage, MappedImageMut, MappedSlice, MappedSliceMut};
+pub use memory::{Buffer, Image, Pipe, Transfer};
+pub use opencl_api::objects::traits::Pod;
+pub use platform::{Device, Platform};
+pub use program::{Kernel, Program};
+pub use queue::{CommandQueue, Scope};
+pub use sampler::Sampler;
+pub use snapshot::SystemSnapshot;
+
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,101 @@
+/*
+ * macros.rs - Macros generating the owned OpenCL objects.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+pub(crate) use opencl_api::api::info;
+pub(crate) use opencl_api::objects::types::NullMutPtr;
+pub(crate) use opencl_heads::types::intptr_t;
+
+/// Declares $name, owning a reference to an OpenCL object of type $raw.
+///
+/// Cloning retains the object with $retain and dropping releases it with
+/// $release. $retain given as `_` leaves $name without `Clone`, for objects
+/// whose clones must not share a handle. The traits listed last, e.g.
+/// `Send, Sync`, are implemented as the OpenCL specification allows for the
+/// object.
+macro_rules! gen_object {
+    ($(#[$attr:meta])* $name:ident: $raw:ty, _, $release:expr $(, $marker:ident)*) => {
+        $(#[$attr])*
+        #[derive(Debug, PartialEq, Eq, Hash)]
+        pub struct $name {
+            raw: $raw,
+        }
+
+        impl $name {
+            /// Takes ownership of a reference to raw, released on drop.
+            ///
+            /// # Safety
+            ///
+            /// raw must be a valid handle whose reference is handed over.
+            pub unsafe fn from_raw(raw: $raw) -> Self {
+                $name { raw }
+            }
+
+            /// Returns the handle, keeping the reference.
+            pub fn as_raw(&self) -> $raw {
+                self.raw
+            }
+
+            /// Gives up the reference without releasing it.
+            pub fn into_raw(self) -> $raw {
+                let raw = self.raw;
+                std::mem::forget(self);
+                raw
+            }
+
+            /// Returns the handle as taken by opencl-api.
+            pub(crate) fn ptr(&self) -> $crate::macros::NullMutPtr {
+                unsafe { $crate::macros::NullMutPtr::from_raw(self.raw as $crate::macros::intptr_t) }
+            }
+        }
+
+        impl Drop for $name {
+            fn drop(&mut self) {
+                let _ = ($release)(self.ptr());
+            }
+        }
+
+        $(unsafe impl $marker for $name {})*
+    };
+    ($(#[$attr:meta])* $name:ident: $raw:ty, $retain:expr, $release:expr $(, $marker:ident)*) => {
+        $crate::macros::gen_object!($(#[$attr])* $name: $raw, _, $release $(, $marker)*);
+
+        impl Clone for $name {
+            fn clone(&self) -> Self {
+                ($retain)(&self.ptr())
+                    .expect(concat!("failed to retain ", stringify!($name)));
+                $name { raw: self.raw }
+            }
+        }
+    };
+}
+
+pub(crate) use gen_object;
+
+/// Converts a $name to the info `Handle` of $kind, so that it is only
+/// queried for the parameters of its kind.
+macro_rules! gen_handle {
+    ($name:ident => $kind:ident) => {
+        impl From<&$name> for $crate::macros::info::Handle<$crate::macros::info::$kind> {
+            fn from(object: &$name) -> Self {
+                // A $name is an object of kind $kind.
+                unsafe { $crate::macros::info::Handle::new(&object.ptr()) }
+            }
+        }
+    };
+}
+
+pub(crate) use gen_handle;
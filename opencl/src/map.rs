@@ -0,0 +1,345 @@
+/*
+ * map.rs - Guards of the memory mapped on the host.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+//!
+//! Memory objects mapped into host memory.
+//!
+//! The guards borrow the mapped object and the queue, and enqueue unmapping
+//! the memory when dropped, waiting for it to complete. They keep the event
+//! of the map command, and `unmap` returns the event of the unmapping, e.g.
+//! to profile both.
+//!
+//! `MappedSlice` and `MappedImage` only read the memory and share the
+//! object, `MappedSliceMut` and `MappedImageMut` also write it and borrow
+//! the object mutably. The borrow only covers the handle mapped: clones of
+//! the object, the commands enqueued on it and the kernels using it still
+//! reach the memory, which is why mapping is unsafe and leaves it to the
+//! caller to keep them off the mapped region while a guard lives.
+//!
+use crate::event::Event;
+use crate::queue::CommandQueue;
+use crate::{Error, Result};
+use opencl_api::api::memory::enqueue_unmap_mem_object;
+use opencl_api::objects::traits::Pod;
+use opencl_api::objects::types::MemPtr;
+use opencl_api::objects::wrappers::{WrappedMutablePointer, WrappedPointer};
+use opencl_heads::types::*;
+use std::ffi::c_void;
+use std::marker::PhantomData;
+use std::mem::{align_of, ManuallyDrop};
+use std::ops::{Deref, DerefMut};
+use std::{ptr, slice};
+
+/// Fails unless ptr and the pitches are aligned for T.
+fn check_align<T>(ptr: *mut c_void, pitches: &[usize]) -> Result<()> {
+    let align = align_of::<T>();
+    if !(ptr as usize).is_multiple_of(align) || pitches.iter().any(|x| !x.is_multiple_of(align)) {
+        return Err(Error::Misaligned { align });
+    }
+    Ok(())
+}
+
+/// Unmaps ptr of mem, waiting for the unmapping to complete.
+fn unmap(queue: &CommandQueue, mem: &MemPtr, ptr: *mut c_void) -> Result<Event> {
+    let ptr = unsafe { WrappedMutablePointer::from_raw(ptr as intptr_t) };
+    let event = enqueue_unmap_mem_object(&queue.ptr(), mem, ptr, 0, WrappedPointer::null())?;
+    let event = unsafe { Event::from_raw(event.unwrap()) };
+    event.wait()?;
+    Ok(event)
+}
+
+/// Elements of a buffer mapped into host memory for reading.
+#[derive(Debug)]
+pub struct MappedSlice<'a, T: Pod> {
+    queue: &'a CommandQueue,
+    mem: MemPtr,
+    event: Event,
+    ptr: *mut T,
+    len: usize,
+    marker: PhantomData<&'a [T]>,
+}
+
+impl<'a, T: Pod> MappedSlice<'a, T> {
+    /// Takes over the len elements at ptr mapped from mem by event.
+    pub(crate) fn new(
+        queue: &'a CommandQueue,
+        mem: MemPtr,
+        event: Event,
+        ptr: *mut c_void,
+        len: usize,
+    ) -> Result<Self> {
+        let slice = MappedSlice {
+            queue,
+            mem,
+            event,
+            ptr: ptr as *mut T,
+            len,
+            marker: PhantomData,
+        };
+        // Dropping slice unmaps the memory on failure.
+        check_align::<T>(ptr, &[])?;
+        Ok(slice)
+    }
+
+    /// Event of the map command.
+    pub fn event(&self) -> &Event {
+        &self.event
+    }
+
+    /// Unmaps the elements, reporting a failure that dropping ignores, and
+    /// returns the event of the unmapping.
+    pub fn unmap(self) -> Result<Event> {
+        let result = unmap(self.queue, &self.mem, self.ptr as *mut c_void);
+        // Only the map event needs dropping, not the guard.
+        let this = ManuallyDrop::new(self);
+        drop(unsafe { ptr::read(&this.event) });
+        result
+    }
+}
+
+impl<T: Pod> Deref for MappedSlice<'_, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.ptr, self.len) }
+    }
+}
+
+impl<T: Pod> Drop for MappedSlice<'_, T> {
+    fn drop(&mut self) {
+        let _ = unmap(self.queue, &self.mem, self.ptr as *mut c_void);
+    }
+}
+
+/// Elements of a buffer mapped into host memory for reading and writing.
+#[derive(Debug)]
+pub struct MappedSliceMut<'a, T: Pod> {
+    slice: MappedSlice<'a, T>,
+    marker: PhantomData<&'a mut [T]>,
+}
+
+impl<'a, T: Pod> MappedSliceMut<'a, T> {
+    /// Takes over the elements of slice, which were mapped for writing from
+    /// a mutably borrowed buffer.
+    pub(crate) fn new(slice: MappedSlice<'a, T>) -> Self {
+        MappedSliceMut {
+            slice,
+            marker: PhantomData,
+        }
+    }
+
+    /// Event of the map command.
+    pub fn event(&self) -> &Event {
+        self.slice.event()
+    }
+
+    /// Unmaps the elements, reporting a failure that dropping ignores, and
+    /// returns the event of the unmapping.
+    pub fn unmap(self) -> Result<Event> {
+        self.slice.unmap()
+    }
+}
+
+impl<T: Pod> Deref for MappedSliceMut<'_, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.slice
+    }
+}
+
+impl<T: Pod> DerefMut for MappedSliceMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.slice.ptr, self.slice.len) }
+    }
+}
+
+/// Region of an image mapped into host memory for reading, a strided view
+/// of its pixels of type T.
+///
+/// Pixel (x, y, z) of the region lies y rows and z slices from its first
+/// pixel, a row being `row_pitch` and a slice `slice_pitch` bytes long.
+#[derive(Debug)]
+pub struct MappedImage<'a, T: Pod> {
+    queue: &'a CommandQueue,
+    mem: MemPtr,
+    event: Event,
+    ptr: *mut u8,
+    region: [usize; 3],
+    row_pitch: usize,
+    slice_pitch: usize,
+    marker: PhantomData<&'a [T]>,
+}
+
+impl<'a, T: Pod> MappedImage<'a, T> {
+    /// Takes over region at ptr mapped from mem by event.
+    pub(crate) fn new(
+        queue: &'a CommandQueue,
+        mem: MemPtr,
+        event: Event,
+        ptr: *mut c_void,
+        region: [usize; 3],
+        pitches: [usize; 2],
+    ) -> Result<Self> {
+        let image = MappedImage {
+            queue,
+            mem,
+            event,
+            ptr: ptr as *mut u8,
+            region,
+            row_pitch: pitches[0],
+            slice_pitch: pitches[1],
+            marker: PhantomData,
+        };
+        check_align::<T>(ptr, &pitches)?;
+        Ok(image)
+    }
+
+    /// Width, height and depth of the region in pixels.
+    pub fn region(&self) -> [usize; 3] {
+        self.region
+    }
+
+    /// Length in bytes of a row of the region.
+    pub fn row_pitch(&self) -> usize {
+        self.row_pitch
+    }
+
+    /// Length in bytes of a slice of the region, 0 for 1D and 2D images.
+    pub fn slice_pitch(&self) -> usize {
+        self.slice_pitch
+    }
+
+    /// Event of the map command.
+    pub fn event(&self) -> &Event {
+        &self.event
+    }
+
+    /// Returns the pixels of row y of slice z.
+    ///
+    /// # Panics
+    ///
+    /// Panics if y or z is out of the region.
+    pub fn row(&self, y: usize, z: usize) -> &[T] {
+        unsafe { slice::from_raw_parts(self.row_ptr(y, z), self.region[0]) }
+    }
+
+    /// Returns pixel (x, y, z), or None if out of the region.
+    pub fn get(&self, x: usize, y: usize, z: usize) -> Option<&T> {
+        if y < self.region[1] && z < self.region[2] {
+            self.row(y, z).get(x)
+        } else {
+            None
+        }
+    }
+
+    /// Unmaps the region, reporting a failure that dropping ignores, and
+    /// returns the event of the unmapping.
+    pub fn unmap(self) -> Result<Event> {
+        let result = unmap(self.queue, &self.mem, self.ptr as *mut c_void);
+        let this = ManuallyDrop::new(self);
+        drop(unsafe { ptr::read(&this.event) });
+        result
+    }
+
+    fn row_ptr(&self, y: usize, z: usize) -> *mut T {
+        assert!(
+            y < self.region[1] && z < self.region[2],
+            "row {} of slice {} out of region {:?}",
+            y,
+            z,
+            self.region
+        );
+        let offset = y * self.row_pitch + z * self.slice_pitch;
+        unsafe { self.ptr.add(offset) as *mut T }
+    }
+}
+
+impl<T: Pod> Drop for MappedImage<'_, T> {
+    fn drop(&mut self) {
+        let _ = unmap(self.queue, &self.mem, self.ptr as *mut c_void);
+    }
+}
+
+/// Region of an image mapped into host memory for reading and writing, see
+/// `MappedImage`.
+#[derive(Debug)]
+pub struct MappedImageMut<'a, T: Pod> {
+    image: MappedImage<'a, T>,
+    marker: PhantomData<&'a mut [T]>,
+}
+
+impl<'a, T: Pod> MappedImageMut<'a, T> {
+    /// Takes over the region of image, which was mapped for writing from a
+    /// mutably borrowed image.
+    pub(crate) fn new(image: MappedImage<'a, T>) -> Self {
+        MappedImageMut {
+            image,
+            marker: PhantomData,
+        }
+    }
+
+    /// Width, height and depth of the region in pixels.
+    pub fn region(&self) -> [usize; 3] {
+        self.image.region()
+    }
+
+    /// Length in bytes of a row of the region.
+    pub fn row_pitch(&self) -> usize {
+        self.image.row_pitch()
+    }
+
+    /// Length in bytes of a slice of the region, 0 for 1D and 2D images.
+    pub fn slice_pitch(&self) -> usize {
+        self.image.slice_pitch()
+    }
+
+    /// Event of the map command.
+    pub fn event(&self) -> &Event {
+        self.image.event()
+    }
+
+    /// Returns the pixels of row y of slice z.
+    ///
+    /// # Panics
+    ///
+    /// Panics if y or z is out of the region.
+    pub fn row(&self, y: usize, z: usize) -> &[T] {
+        self.image.row(y, z)
+    }
+
+    /// Returns the pixels of row y of slice z for writing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if y or z is out of the region.
+    pub fn row_mut(&mut self, y: usize, z: usize) -> &mut [T] {
+        let len = self.image.region[0];
+        unsafe { slice::from_raw_parts_mut(self.image.row_ptr(y, z), len) }
+    }
+
+    /// Returns pixel (x, y, z), or None if out of the region.
+    pub fn get(&self, x: usize, y: usize, z: usize) -> Option<&T> {
+        self.image.get(x, y, z)
+    }
+
+    /// Unmaps the region, reporting a failure that dropping ignores, and
+    /// returns the event of the unmapping.
+    pub fn unmap(self) -> Result<Event> {
+        self.image.unmap()
+    }
+}
@@ -0,0 +1,743 @@
+/*
+ * memory.rs - Memory objects: buffers, images and pipes.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+use crate::context::Context;
+use crate::event::Event;
+use crate::macros::{gen_handle, gen_object};
+use crate::map::{MappedImage, MappedImageMut, MappedSlice, MappedSliceMut};
+use crate::queue::CommandQueue;
+use crate::{Error, Result};
+use opencl_api::api::buffer::{
+    create_buffer, enqueue_copy_buffer, enqueue_fill_buffer, enqueue_map_buffer,
+    enqueue_read_buffer, enqueue_write_buffer,
+};
+use opencl_api::api::image::{create_image_from_desc, enqueue_map_image, get_image_info};
+use opencl_api::api::info::{self, Handle};
+use opencl_api::api::memory::{release_mem_object, retain_mem_object};
+use opencl_api::errors::{RuntimeError, ToLibraryError};
+use opencl_api::objects::bitfields::{MapFlags, MemFlags};
+use opencl_api::objects::enums::ParamValue;
+use opencl_api::objects::image::{ImageDesc, ImageFormat};
+use opencl_api::objects::structs::ImageInfo;
+use opencl_api::objects::traits::GetSetGo;
+use opencl_api::objects::traits::Pod;
+use opencl_api::objects::types::MemPtr;
+use opencl_api::objects::wrappers::{WrappedMutablePointer, WrappedPointer};
+use opencl_heads::consts::{CL_FALSE, CL_TRUE};
+use opencl_heads::types::*;
+use std::marker::PhantomData;
+use std::mem::size_of;
+use std::ops::Range;
+
+/// OpenCL buffer of len elements of type T.
+///
+/// The transfers check the lengths and ranges they are given against the
+/// buffer, failing with `Error::LengthMismatch` or `Error::OutOfRange`
+/// before any OpenCL call.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct Buffer<T: Pod> {
+    raw: cl_mem,
+    len: usize,
+    marker: PhantomData<T>,
+}
+
+unsafe impl<T: Pod> Send for Buffer<T> {}
+unsafe impl<T: Pod> Sync for Buffer<T> {}
+
+impl<T: Pod> From<&Buffer<T>> for Handle<info::Mem> {
+    fn from(buffer: &Buffer<T>) -> Self {
+        // A buffer is an object of kind Mem.
+        unsafe { Handle::new(&buffer.ptr()) }
+    }
+}
+
+impl<T: Pod> Buffer<T> {
+    /// Creates a buffer of len elements in context.
+    pub fn new(context: &Context, flags: MemFlags, len: usize) -> Result<Buffer<T>> {
+        let size = bytes::<T>(len)?;
+        let buffer = create_buffer(&context.ptr(), flags, size, WrappedMutablePointer::null())?;
+        Ok(unsafe { Buffer::from_raw(buffer.unwrap(), len) })
+    }
+
+    /// Takes ownership of a reference to raw, released on drop.
+    ///
+    /// # Safety
+    ///
+    /// raw must be a valid buffer of at least len elements whose reference
+    /// is handed over.
+    pub unsafe fn from_raw(raw: cl_mem, len: usize) -> Self {
+        Buffer {
+            raw,
+            len,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the handle, keeping the reference.
+    pub fn as_raw(&self) -> cl_mem {
+        self.raw
+    }
+
+    /// Gives up the reference without releasing it.
+    pub fn into_raw(self) -> cl_mem {
+        let raw = self.raw;
+        std::mem::forget(self);
+        raw
+    }
+
+    /// Number of elements of the buffer.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Reads the whole buffer into data, waiting for the read to complete.
+    pub fn read(&self, queue: &CommandQueue, data: &mut [T]) -> Result<()> {
+        self.read_range(queue, 0..self.len, data)?;
+        Ok(())
+    }
+
+    /// Reads the elements of range into data, waiting for the read to
+    /// complete, and returns the event of the completed read, e.g. to
+    /// profile it.
+    pub fn read_range(
+        &self,
+        queue: &CommandQueue,
+        range: Range<usize>,
+        data: &mut [T],
+    ) -> Result<Event> {
+        self.enqueue_read(queue, range, data, CL_TRUE)
+    }
+
+    /// Enqueues reading the elements of range into data, which stays
+    /// borrowed until the returned transfer completes. `CommandQueue::scope`
+    /// does the same without unsafe code.
+    ///
+    /// # Safety
+    ///
+    /// The transfer must be waited on or dropped, not leaked with e.g.
+    /// `std::mem::forget`, as the borrow of data would end while OpenCL
+    /// still writes to it.
+    pub unsafe fn read_async<'a>(
+        &self,
+        queue: &CommandQueue,
+        range: Range<usize>,
+        data: &'a mut [T],
+    ) -> Result<Transfer<'a>> {
+        let event = self.enqueue_read(queue, range, data, CL_FALSE)?;
+        Ok(Transfer::new(event))
+    }
+
+    /// Writes data to the whole buffer, waiting for the write to complete.
+    pub fn write(&self, queue: &CommandQueue, data: &[T]) -> Result<()> {
+        self.write_range(queue, 0..self.len, data)?;
+        Ok(())
+    }
+
+    /// Writes data to the elements of range, waiting for the write to
+    /// complete, and returns the event of the completed write, e.g. to
+    /// profile it.
+    pub fn write_range(
+        &self,
+        queue: &CommandQueue,
+        range: Range<usize>,
+        data: &[T],
+    ) -> Result<Event> {
+        self.enqueue_write(queue, range, data, CL_TRUE)
+    }
+
+    /// Enqueues writing data to the elements of range, data staying
+    /// borrowed until the returned transfer completes. `CommandQueue::scope`
+    /// does the same without unsafe code.
+    ///
+    /// # Safety
+    ///
+    /// The transfer must be waited on or dropped, not leaked with e.g.
+    /// `std::mem::forget`, as the borrow of data would end while OpenCL
+    /// still reads from it.
+    pub unsafe fn write_async<'a>(
+        &self,
+        queue: &CommandQueue,
+        range: Range<usize>,
+        data: &'a [T],
+    ) -> Result<Transfer<'a>> {
+        let event = self.enqueue_write(queue, range, data, CL_FALSE)?;
+        Ok(Transfer::new(event))
+    }
+
+    /// Enqueues setting every element of the buffer to value.
+    ///
+    /// OpenCL only fills with patterns of 1, 2, 4, ..., 128 bytes, the size
+    /// of T, failing with `Error::PatternSize` otherwise.
+    pub fn fill(&self, queue: &CommandQueue, value: T) -> Result<Event> {
+        let pattern_size = size_of::<T>();
+        if !pattern_size.is_power_of_two() || pattern_size > 128 {
+            return Err(Error::PatternSize { size: pattern_size });
+        }
+        let size = bytes::<T>(self.len)?;
+        // The pattern is copied by the call, it needs not outlive it.
+        let pattern = unsafe { WrappedPointer::from_raw(&value as *const T as intptr_t) };
+        let event = enqueue_fill_buffer(
+            &queue.ptr(),
+            &self.ptr(),
+            pattern,
+            pattern_size,
+            0,
+            size,
+            0,
+            WrappedPointer::null(),
+        )?;
+        Ok(unsafe { Event::from_raw(event.unwrap()) })
+    }
+
+    /// Enqueues copying the buffer to dst, of the same length.
+    pub fn copy_to(&self, queue: &CommandQueue, dst: &Buffer<T>) -> Result<Event> {
+        if dst.len != self.len {
+            return Err(Error::LengthMismatch {
+                expected: self.len,
+                found: dst.len,
+            });
+        }
+        let size = bytes::<T>(self.len)?;
+        let event = enqueue_copy_buffer(
+            &queue.ptr(),
+            &self.ptr(),
+            &dst.ptr(),
+            0,
+            0,
+            size,
+            0,
+            WrappedPointer::null(),
+        )?;
+        Ok(unsafe { Event::from_raw(event.unwrap()) })
+    }
+
+    pub(crate) fn enqueue_read(
+        &self,
+        queue: &CommandQueue,
+        range: Range<usize>,
+        data: &mut [T],
+        blocking: cl_bool,
+    ) -> Result<Event> {
+        let (offset, size) = self.check_range(&range, data.len())?;
+        let ptr = unsafe { WrappedMutablePointer::from_raw(data.as_mut_ptr() as intptr_t) };
+        let event = enqueue_read_buffer(
+            &queue.ptr(),
+            &self.ptr(),
+            blocking,
+            offset,
+            size,
+            ptr,
+            0,
+            WrappedPointer::null(),
+        )?;
+        Ok(unsafe { Event::from_raw(event.unwrap()) })
+    }
+
+    pub(crate) fn enqueue_write(
+        &self,
+        queue: &CommandQueue,
+        range: Range<usize>,
+        data: &[T],
+        blocking: cl_bool,
+    ) -> Result<Event> {
+        let (offset, size) = self.check_range(&range, data.len())?;
+        let ptr = unsafe { WrappedPointer::from_raw(data.as_ptr() as intptr_t) };
+        let event = enqueue_write_buffer(
+            &queue.ptr(),
+            &self.ptr(),
+            blocking,
+            offset,
+            size,
+            ptr,
+            0,
+            WrappedPointer::null(),
+        )?;
+        Ok(unsafe { Event::from_raw(event.unwrap()) })
+    }
+
+    /// Maps the elements of range into host memory for reading, waiting for
+    /// the map to complete.
+    ///
+    /// # Safety
+    ///
+    /// The mapped elements must not be written while the guard lives: not
+    /// through a clone of the buffer or a `map_mut` of it, nor by a write,
+    /// fill or copy into the buffer, nor by a kernel it is an argument of.
+    pub unsafe fn map<'a>(
+        &'a self,
+        queue: &'a CommandQueue,
+        range: Range<usize>,
+    ) -> Result<MappedSlice<'a, T>> {
+        self.enqueue_map(queue, MapFlags::READ, range)
+    }
+
+    /// Maps the elements of range into host memory for reading and writing,
+    /// waiting for the map to complete.
+    ///
+    /// The buffer stays mutably borrowed while mapped, so its elements can
+    /// not be mapped twice through it:
+    ///
+    /// ```compile_fail
+    /// # fn run(queue: &opencl::CommandQueue, buffer: &mut opencl::Buffer<u8>) {
+    /// let mut first = unsafe { buffer.map_mut(queue, 0..2) }.unwrap();
+    /// let second = unsafe { buffer.map_mut(queue, 0..2) }.unwrap();
+    /// first[0] = second[0];
+    /// # }
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// The mapped elements must not be accessed but through the guard while
+    /// it lives: not mapped through a clone of the buffer, nor read, written,
+    /// filled or copied by commands, nor used by a kernel it is an argument
+    /// of.
+    pub unsafe fn map_mut<'a>(
+        &'a mut self,
+        queue: &'a CommandQueue,
+        range: Range<usize>,
+    ) -> Result<MappedSliceMut<'a, T>> {
+        let slice = self.enqueue_map(queue, MapFlags::READ | MapFlags::WRITE, range)?;
+        Ok(MappedSliceMut::new(slice))
+    }
+
+    fn enqueue_map<'a>(
+        &'a self,
+        queue: &'a CommandQueue,
+        flags: cl_map_flags,
+        range: Range<usize>,
+    ) -> Result<MappedSlice<'a, T>> {
+        let (offset, size) = self.check_range(&range, range.len())?;
+        let flags = MapFlags::new(flags).map_err(ToLibraryError::to_error)?;
+        let (ptr, event) = enqueue_map_buffer(
+            &queue.ptr(),
+            &self.ptr(),
+            CL_TRUE,
+            flags,
+            offset,
+            size,
+            0,
+            WrappedPointer::null(),
+        )?;
+        let event = unsafe { Event::from_raw(event.unwrap()) };
+        MappedSlice::new(queue, self.ptr(), event, ptr.unwrap(), range.len())
+    }
+
+    /// Checks that range lies in the buffer and holds len elements, returns
+    /// its offset and size in bytes.
+    fn check_range(&self, range: &Range<usize>, len: usize) -> Result<(usize, usize)> {
+        if range.start > range.end || range.end > self.len {
+            return Err(Error::OutOfRange {
+                start: range.start,
+                end: range.end,
+                len: self.len,
+            });
+        }
+        if range.len() != len {
+            return Err(Error::LengthMismatch {
+                expected: range.len(),
+                found: len,
+            });
+        }
+        Ok((bytes::<T>(range.start)?, bytes::<T>(range.len())?))
+    }
+
+    pub(crate) fn ptr(&self) -> MemPtr {
+        unsafe { MemPtr::from_raw(self.raw as intptr_t) }
+    }
+}
+
+/// Size in bytes of len elements of type T.
+fn bytes<T>(len: usize) -> Result<usize> {
+    len.checked_mul(size_of::<T>()).ok_or(Error::SizeOverflow {
+        len,
+        size: size_of::<T>(),
+    })
+}
+
+impl<T: Pod> Clone for Buffer<T> {
+    fn clone(&self) -> Self {
+        retain_mem_object(&self.ptr()).expect("failed to retain Buffer");
+        Buffer {
+            raw: self.raw,
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Pod> Drop for Buffer<T> {
+    fn drop(&mut self) {
+        let _ = release_mem_object(self.ptr());
+    }
+}
+
+/// Non-blocking transfer between a buffer and host memory borrowed for 'a.
+///
+/// Dropping the transfer waits for it to complete, so the host memory is
+/// never released or reused while OpenCL may still access it.
+#[derive(Debug)]
+#[must_use = "dropping a transfer waits for it to complete"]
+pub struct Transfer<'a> {
+    event: Option<Event>,
+    borrow: PhantomData<&'a mut [u8]>,
+}
+
+impl<'a> Transfer<'a> {
+    fn new(event: Event) -> Self {
+        Transfer {
+            event: Some(event),
+            borrow: PhantomData,
+        }
+    }
+
+    /// Event of the transfer, e.g. to query its status.
+    pub fn event(&self) -> &Event {
+        self.event.as_ref().unwrap()
+    }
+
+    /// Waits for the transfer to complete, releasing the host memory.
+    pub fn wait(mut self) -> Result<()> {
+        self.event.take().unwrap().wait()
+    }
+}
+
+impl Drop for Transfer<'_> {
+    fn drop(&mut self) {
+        if let Some(event) = self.event.take() {
+            // Waiting fails when the transfer was aborted, which also ends
+            // the access to the host memory.
+            let _ = event.wait();
+        }
+    }
+}
+
+gen_object! {
+    /// OpenCL image.
+    Image: cl_mem, retain_mem_object, release_mem_object, Send, Sync
+}
+
+gen_handle!(Image => Image);
+
+impl Image {
+    /// Creates the image of format and desc in context, see
+    /// `create_image_from_desc` for the checks done before creating it.
+    pub fn new(
+        context: &Context,
+        flags: MemFlags,
+        format: &ImageFormat,
+        desc: &ImageDesc,
+    ) -> Result<Image> {
+        let image = create_image_from_desc(
+            &context.ptr(),
+            flags,
+            format,
+            desc,
+            WrappedMutablePointer::null(),
+        )?;
+        Ok(unsafe { Image::from_raw(image.unwrap()) })
+    }
+
+    /// Maps region of the image at origin into host memory for reading,
+    /// waiting for the map to complete.
+    ///
+    /// The pixels are of type T, of the element size of the image.
+    ///
+    /// # Safety
+    ///
+    /// The mapped pixels must not be written while the guard lives: not
+    /// through a clone of the image or a `map_mut` of it, nor by commands or
+    /// kernels.
+    pub unsafe fn map<'a, T: Pod>(
+        &'a self,
+        queue: &'a CommandQueue,
+        origin: [usize; 3],
+        region: [usize; 3],
+    ) -> Result<MappedImage<'a, T>> {
+        self.enqueue_map(queue, MapFlags::READ, origin, region)
+    }
+
+    /// Maps region of the image at origin into host memory for reading and
+    /// writing, waiting for the map to complete. The image stays mutably
+    /// borrowed while mapped.
+    ///
+    /// The pixels are of type T, of the element size of the image.
+    ///
+    /// # Safety
+    ///
+    /// The mapped pixels must not be accessed but through the guard while it
+    /// lives: not mapped through a clone of the image, nor by commands or
+    /// kernels.
+    pub unsafe fn map_mut<'a, T: Pod>(
+        &'a mut self,
+        queue: &'a CommandQueue,
+        origin: [usize; 3],
+        region: [usize; 3],
+    ) -> Result<MappedImageMut<'a, T>> {
+        let flags = MapFlags::READ | MapFlags::WRITE;
+        let image = self.enqueue_map(queue, flags, origin, region)?;
+        Ok(MappedImageMut::new(image))
+    }
+
+    fn enqueue_map<'a, T: Pod>(
+        &'a self,
+        queue: &'a CommandQueue,
+        flags: cl_map_flags,
+        origin: [usize; 3],
+        region: [usize; 3],
+    ) -> Result<MappedImage<'a, T>> {
+        let element_size = match get_image_info(&self.ptr(), ImageInfo::ELEMENT_SIZE)? {
+            ParamValue::CSize(x) => x,
+            _ => {
+                let error = RuntimeError::UnexpectedParamValue("clGetImageInfo");
+                return Err(error.to_error().into());
+            }
+        };
+        if element_size != size_of::<T>() {
+            return Err(Error::ElementSize {
+                expected: element_size,
+                found: size_of::<T>(),
+            });
+        }
+        let flags = MapFlags::new(flags).map_err(ToLibraryError::to_error)?;
+        let (mut row_pitch, mut slice_pitch) = (0, 0);
+        let (ptr, event) = enqueue_map_image(
+            &queue.ptr(),
+            &self.ptr(),
+            CL_TRUE,
+            flags,
+            WrappedPointer::from(&origin),
+            WrappedPointer::from(&region),
+            WrappedMutablePointer::from(&mut row_pitch),
+            WrappedMutablePointer::from(&mut slice_pitch),
+            0,
+            WrappedPointer::null(),
+        )?;
+        let event = unsafe { Event::from_raw(event.unwrap()) };
+        let pitches = [row_pitch, slice_pitch];
+        MappedImage::new(queue, self.ptr(), event, ptr.unwrap(), region, pitches)
+    }
+}
+
+gen_object! {
+    /// OpenCL pipe, a FIFO of packets between kernels.
+    Pipe: cl_mem, retain_mem_object, release_mem_object, Send, Sync
+}
+
+gen_handle!(Pipe => Pipe);
+
+/************************/
+/* /‾‾\ /‾‾\ /‾‾\ /‾‾\  */
+/*|  |  |  |  |  |  |  |*/
+/*|  |  Unit Tests  |  |*/
+/*|__|__|__|__|__|__|__|*/
+/*|__|__|__|__|__|__|__|*/
+/************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::Platform;
+    use opencl_api::objects::bitfields::DeviceType;
+
+    fn setup() -> (Context, CommandQueue) {
+        let platform = Platform::list().unwrap()[0];
+        let device_type = DeviceType::new(DeviceType::DEFAULT).unwrap();
+        let devices = platform.devices(device_type).unwrap();
+        let context = Context::new(&devices).unwrap();
+        let queue = CommandQueue::new(&context, &devices[0]).unwrap();
+        (context, queue)
+    }
+
+    #[test]
+    fn test_read_write() {
+        let (context, queue) = setup();
+        let flags = MemFlags::new(MemFlags::READ_WRITE).unwrap();
+        let buffer = Buffer::<u32>::new(&context, flags, 8).unwrap();
+        assert_eq!(buffer.len(), 8);
+        let size = info::query::<info::mem::Size>(&Handle::from(&buffer));
+        assert_eq!(size.unwrap(), 8 * 4);
+        let data: Vec<u32> = (0..8).collect();
+        buffer.write(&queue, &data).unwrap();
+        // The event of a blocking write has already completed.
+        let event = buffer.write_range(&queue, 6..8, &[60, 70]).unwrap();
+        event.wait().unwrap();
+
+        let mut read = [0u32; 8];
+        buffer.read(&queue, &mut read).unwrap();
+        assert_eq!(read, [0, 1, 2, 3, 4, 5, 60, 70]);
+        let mut read = [0u32; 3];
+        buffer.read_range(&queue, 2..5, &mut read).unwrap();
+        assert_eq!(read, [2, 3, 4]);
+
+        assert_eq!(
+            buffer.read(&queue, &mut [0; 4]),
+            Err(Error::LengthMismatch {
+                expected: 8,
+                found: 4
+            })
+        );
+        assert_eq!(
+            buffer.write_range(&queue, 6..9, &[0; 3]),
+            Err(Error::OutOfRange {
+                start: 6,
+                end: 9,
+                len: 8
+            })
+        );
+    }
+
+    #[test]
+    fn test_fill_and_copy() {
+        let (context, queue) = setup();
+        let flags = MemFlags::new(MemFlags::READ_WRITE).unwrap();
+        let src = Buffer::<[f32; 2]>::new(&context, flags.clone(), 4).unwrap();
+        let dst = Buffer::<[f32; 2]>::new(&context, flags.clone(), 4).unwrap();
+        src.fill(&queue, [1.5, -2.0]).unwrap();
+        src.copy_to(&queue, &dst).unwrap();
+        let mut read = [[0.0; 2]; 4];
+        dst.read(&queue, &mut read).unwrap();
+        assert_eq!(read, [[1.5, -2.0]; 4]);
+
+        let short = Buffer::<[f32; 2]>::new(&context, flags.clone(), 2).unwrap();
+        assert_eq!(
+            src.copy_to(&queue, &short).unwrap_err(),
+            Error::LengthMismatch {
+                expected: 4,
+                found: 2
+            }
+        );
+        let odd = Buffer::<[u8; 3]>::new(&context, flags.clone(), 4).unwrap();
+        assert_eq!(
+            odd.fill(&queue, [1, 2, 3]).unwrap_err(),
+            Error::PatternSize { size: 3 }
+        );
+        assert_eq!(
+            Buffer::<u64>::new(&context, flags, usize::MAX).unwrap_err(),
+            Error::SizeOverflow {
+                len: usize::MAX,
+                size: 8
+            }
+        );
+    }
+
+    #[test]
+    fn test_async_transfers() {
+        let (context, queue) = setup();
+        let flags = MemFlags::new(MemFlags::READ_WRITE).unwrap();
+        let buffer = Buffer::<i64>::new(&context, flags, 4).unwrap();
+        let data = vec![-1, 2, -3, 4];
+        unsafe { buffer.write_async(&queue, 0..4, &data) }
+            .unwrap()
+            .wait()
+            .unwrap();
+        drop(data);
+
+        let mut read = vec![0; 2];
+        {
+            // Dropped at the end of the scope, waiting for the read.
+            let _transfer = unsafe { buffer.read_async(&queue, 1..3, &mut read) }.unwrap();
+        }
+        assert_eq!(read, [2, -3]);
+
+        assert_eq!(
+            unsafe { buffer.read_async(&queue, 3..5, &mut read) }.unwrap_err(),
+            Error::OutOfRange {
+                start: 3,
+                end: 5,
+                len: 4
+            }
+        );
+    }
+
+    #[test]
+    fn test_scoped_transfers() {
+        let (context, queue) = setup();
+        let flags = MemFlags::new(MemFlags::READ_WRITE).unwrap();
+        let buffer = Buffer::<u32>::new(&context, flags, 4).unwrap();
+        let data = [1, 2, 3, 4];
+        let (mut read, mut past_end) = ([0; 2], [0]);
+        let result = queue.scope(|s| {
+            s.write_async(&buffer, 0..4, &data)?;
+            let event = s.read_async(&buffer, 2..4, &mut read)?;
+            assert_eq!(
+                s.read_async(&buffer, 4..5, &mut past_end).unwrap_err(),
+                Error::OutOfRange {
+                    start: 4,
+                    end: 5,
+                    len: 4
+                }
+            );
+            Ok::<_, Error>(event)
+        });
+        // The scope waited for the read before returning.
+        result.unwrap().wait().unwrap();
+        assert_eq!(read, [3, 4]);
+    }
+
+    #[test]
+    fn test_map_buffer() {
+        let (context, queue) = setup();
+        let flags = MemFlags::new(MemFlags::READ_WRITE).unwrap();
+        let mut buffer = Buffer::<u16>::new(&context, flags, 6).unwrap();
+        buffer.write(&queue, &[0, 1, 2, 3, 4, 5]).unwrap();
+        {
+            let mut mapped = unsafe { buffer.map_mut(&queue, 2..5) }.unwrap();
+            assert_eq!(*mapped, [2, 3, 4]);
+            mapped[1] = 30;
+        }
+        let mapped = unsafe { buffer.map(&queue, 0..6) }.unwrap();
+        // Reading maps may overlap.
+        let tail = unsafe { buffer.map(&queue, 4..6) }.unwrap();
+        assert_eq!(*mapped, [0, 1, 2, 30, 4, 5]);
+        assert_eq!(*tail, [4, 5]);
+        mapped.unmap().unwrap();
+    }
+
+    #[test]
+    fn test_map_image() {
+        use opencl_api::objects::structs::{ChannelOrder, ChannelType};
+
+        let (context, queue) = setup();
+        let format = ImageFormat::new(ChannelOrder::RGBA, ChannelType::UNSIGNED_INT8).unwrap();
+        let flags = MemFlags::new(MemFlags::READ_WRITE).unwrap();
+        let mut image = Image::new(&context, flags, &format, &ImageDesc::image2d(4, 3)).unwrap();
+
+        let mut mapped = unsafe { image.map_mut::<[u8; 4]>(&queue, [1, 1, 0], [2, 2, 1]) }.unwrap();
+        assert_eq!(mapped.region(), [2, 2, 1]);
+        assert!(mapped.row_pitch() >= 2 * 4);
+        mapped.row_mut(1, 0)[0] = [1, 2, 3, 4];
+        mapped.unmap().unwrap();
+
+        let mapped = unsafe { image.map::<[u8; 4]>(&queue, [0, 0, 0], [4, 3, 1]) }.unwrap();
+        assert_eq!(mapped.get(1, 2, 0), Some(&[1, 2, 3, 4]));
+        assert_eq!(mapped.get(2, 2, 0), Some(&[0; 4]));
+        assert_eq!(mapped.get(0, 3, 0), None);
+        drop(mapped);
+
+        assert_eq!(
+            unsafe { image.map::<u8>(&queue, [0; 3], [1; 3]) }.unwrap_err(),
+            Error::ElementSize {
+                expected: 4,
+                found: 1
+            }
+        );
+    }
+}
@@ -0,0 +1,132 @@
+/*
+ * platform.rs - Platforms and their devices.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+use crate::macros::{gen_handle, gen_object};
+use crate::Result;
+use opencl_api::api::device::{get_device_ids, release_device, retain_device, DeviceDetails};
+use opencl_api::api::info::Handle;
+use opencl_api::api::platform::{get_platform_ids, PlatformDetails};
+use opencl_api::objects::bitfields::DeviceType;
+use opencl_api::objects::types::PlatformPtr;
+use opencl_heads::types::*;
+
+/// OpenCL platform, i.e. an installed implementation.
+///
+/// Platforms live as long as the process and are not reference counted, so
+/// they are plain copies of their handle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Platform {
+    raw: cl_platform_id,
+}
+
+unsafe impl Send for Platform {}
+unsafe impl Sync for Platform {}
+
+gen_handle!(Platform => Platform);
+
+impl Platform {
+    /// Returns the available platforms.
+    pub fn list() -> Result<Vec<Platform>> {
+        Ok(get_platform_ids()?
+            .into_iter()
+            .map(|raw| Platform { raw })
+            .collect())
+    }
+
+    /// Returns the devices of the platform of the given type.
+    pub fn devices(&self, device_type: DeviceType) -> Result<Vec<Device>> {
+        Ok(get_device_ids(&self.ptr(), device_type)?
+            .into_iter()
+            .map(|raw| Device { raw })
+            .collect())
+    }
+
+    /// Wraps raw.
+    ///
+    /// # Safety
+    ///
+    /// raw must be a valid platform.
+    pub unsafe fn from_raw(raw: cl_platform_id) -> Self {
+        Platform { raw }
+    }
+
+    /// Queries every platform info of the platform.
+    pub fn details(&self) -> PlatformDetails {
+        PlatformDetails::query(&Handle::from(self))
+    }
+
+    pub fn as_raw(&self) -> cl_platform_id {
+        self.raw
+    }
+
+    pub fn into_raw(self) -> cl_platform_id {
+        self.raw
+    }
+
+    pub(crate) fn ptr(&self) -> PlatformPtr {
+        unsafe { PlatformPtr::from_raw(self.raw as intptr_t) }
+    }
+}
+
+gen_object! {
+    /// OpenCL device. Retaining and releasing a device only counts for the
+    /// sub devices, the devices of a platform are never freed.
+    Device: cl_device_id, retain_device, release_device, Send, Sync
+}
+
+gen_handle!(Device => Device);
+
+impl Device {
+    /// Queries every device info of the device.
+    pub fn details(&self) -> DeviceDetails {
+        DeviceDetails::query(&Handle::from(self))
+    }
+}
+
+/************************/
+/* /‾‾\ /‾‾\ /‾‾\ /‾‾\  */
+/*|  |  |  |  |  |  |  |*/
+/*|  |  Unit Tests  |  |*/
+/*|__|__|__|__|__|__|__|*/
+/*|__|__|__|__|__|__|__|*/
+/************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opencl_api::objects::traits::GetSetGo;
+
+    #[test]
+    fn test_platform_devices() {
+        let platforms = Platform::list().unwrap();
+        assert!(!platforms.is_empty());
+        assert_ne!(platforms[0].details().name.unwrap(), "");
+        let device_type = DeviceType::new(DeviceType::ALL).unwrap();
+        let devices = platforms[0].devices(device_type).unwrap();
+        assert!(!devices.is_empty());
+
+        let details = devices[0].details();
+        assert_eq!(details.platform.unwrap(), platforms[0].as_raw());
+
+        let raw = devices[0].as_raw();
+        let device = devices[0].clone();
+        assert_eq!(device.as_raw(), raw);
+        drop(devices);
+        let device = unsafe { Device::from_raw(device.into_raw()) };
+        assert_eq!(device.as_raw(), raw);
+    }
+}
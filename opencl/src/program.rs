@@ -0,0 +1,192 @@
+/*
+ * program.rs - Programs and their kernels.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+use crate::context::Context;
+use crate::event::Event;
+use crate::macros::gen_object;
+use crate::memory::Buffer;
+use crate::platform::Device;
+use crate::queue::CommandQueue;
+use crate::Result;
+use opencl_api::api::kernel::{
+    clone_kernel, create_kernel, release_kernel, set_kernel_arg, set_kernel_arg_mem,
+};
+use opencl_api::api::program::{
+    build_program, create_program_with_source, release_program, retain_program,
+};
+use opencl_api::api::queue::enqueue_nd_range_kernel;
+use opencl_api::objects::traits::Pod;
+use opencl_api::objects::wrappers::{WrappedMutablePointer, WrappedPointer};
+use opencl_heads::types::*;
+
+gen_object! {
+    /// OpenCL program, built for the devices of its context.
+    Program: cl_program, retain_program, release_program, Send, Sync
+}
+
+// Setting the arguments of a kernel is not thread safe, so kernels are not
+// Sync, and not Clone either as clones would share the arguments: `try_clone`
+// copies them into a new kernel object.
+gen_object! {
+    /// OpenCL kernel, a function of a program with its arguments.
+    Kernel: cl_kernel, _, release_kernel, Send
+}
+
+impl Program {
+    /// Creates a program from OpenCL C sources.
+    pub fn with_source(context: &Context, sources: &[&str]) -> Result<Program> {
+        let program = create_program_with_source(&context.ptr(), sources)?;
+        Ok(unsafe { Program::from_raw(program.unwrap()) })
+    }
+
+    /// Builds the program for devices, or all the devices of its context if
+    /// empty.
+    pub fn build(&self, devices: &[Device], options: Option<&str>) -> Result<()> {
+        let devices = devices.iter().map(Device::as_raw).collect();
+        build_program(
+            &self.ptr(),
+            devices,
+            options,
+            None,
+            WrappedMutablePointer::null(),
+        )?;
+        Ok(())
+    }
+}
+
+impl Kernel {
+    /// Creates the kernel named name of a built program.
+    pub fn new(program: &Program, name: &str) -> Result<Kernel> {
+        let kernel = create_kernel(&program.ptr(), name)?;
+        Ok(unsafe { Kernel::from_raw(kernel.unwrap()) })
+    }
+
+    /// Creates a kernel of the same function with a copy of the arguments,
+    /// set apart from those of self from then on. Requires OpenCL 2.1.
+    pub fn try_clone(&self) -> Result<Kernel> {
+        let kernel = clone_kernel(&self.ptr())?;
+        Ok(unsafe { Kernel::from_raw(kernel.unwrap()) })
+    }
+
+    /// Sets the argument at index to value, a scalar or vector declared by
+    /// the kernel.
+    pub fn set_arg<T: Pod>(&mut self, index: cl_uint, value: &T) -> Result<()> {
+        set_kernel_arg(&self.ptr(), index, value)?;
+        Ok(())
+    }
+
+    /// Sets the argument at index to buffer, a `__global` or `__constant`
+    /// pointer declared by the kernel.
+    pub fn set_arg_buffer<T: Pod>(&mut self, index: cl_uint, buffer: &Buffer<T>) -> Result<()> {
+        set_kernel_arg_mem(&self.ptr(), index, &buffer.ptr())?;
+        Ok(())
+    }
+
+    /// Enqueues running the kernel with its current arguments over the
+    /// work-items of global, grouped by local or as OpenCL chooses.
+    ///
+    /// global and local hold a size per dimension, from one to three.
+    pub fn enqueue(
+        &self,
+        queue: &CommandQueue,
+        global: &[usize],
+        local: Option<&[usize]>,
+    ) -> Result<Event> {
+        let event = enqueue_nd_range_kernel(
+            &queue.ptr(),
+            &self.ptr(),
+            global.len() as cl_uint,
+            None,
+            global,
+            local,
+            0,
+            WrappedPointer::null(),
+        )?;
+        Ok(unsafe { Event::from_raw(event.unwrap()) })
+    }
+}
+
+/************************/
+/* /‾‾\ /‾‾\ /‾‾\ /‾‾\  */
+/*|  |  |  |  |  |  |  |*/
+/*|  |  Unit Tests  |  |*/
+/*|__|__|__|__|__|__|__|*/
+/*|__|__|__|__|__|__|__|*/
+/************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::Platform;
+    use opencl_api::objects::bitfields::DeviceType;
+    use opencl_api::objects::traits::GetSetGo;
+
+    const SOURCE: &str = "__kernel void scale(__global float* a, const float b) {
+        a[get_global_id(0)] *= b;
+    }";
+
+    #[test]
+    fn test_build_and_create_kernel() {
+        let platform = Platform::list().unwrap()[0];
+        let device_type = DeviceType::new(DeviceType::DEFAULT).unwrap();
+        let devices = platform.devices(device_type).unwrap();
+        let context = Context::new(&devices).unwrap();
+        let program = Program::with_source(&context, &[SOURCE]).unwrap();
+        program.build(&devices, None).unwrap();
+
+        // The kernel keeps its program alive.
+        let kernel = Kernel::new(&program, "scale").unwrap();
+        drop(program);
+        let mut other = kernel.try_clone().unwrap();
+        assert_ne!(other.as_raw(), kernel.as_raw());
+        other.set_arg(1, &2.0f32).unwrap();
+        assert!(Kernel::new(&Program::with_source(&context, &[SOURCE]).unwrap(), "scale").is_err());
+    }
+
+    #[test]
+    fn test_enqueue_kernel() {
+        use crate::memory::Buffer;
+        use crate::queue::CommandQueue;
+        use opencl_api::objects::bitfields::MemFlags;
+
+        let platform = Platform::list().unwrap()[0];
+        let device_type = DeviceType::new(DeviceType::DEFAULT).unwrap();
+        let devices = platform.devices(device_type).unwrap();
+        let context = Context::new(&devices).unwrap();
+        let program = Program::with_source(&context, &[SOURCE]).unwrap();
+        program.build(&devices, None).unwrap();
+        let mut kernel = Kernel::new(&program, "scale").unwrap();
+
+        let flags = MemFlags::new(MemFlags::READ_WRITE).unwrap();
+        let buffer = Buffer::<f32>::new(&context, flags, 4).unwrap();
+        kernel.set_arg_buffer(0, &buffer).unwrap();
+        kernel.set_arg(1, &2.0f32).unwrap();
+        let queue = CommandQueue::with_profiling(&context, &devices[0]).unwrap();
+        let event = kernel.enqueue(&queue, &[4], None).unwrap();
+        queue.finish().unwrap();
+        let profile = event.profile().unwrap();
+        assert!(profile.queued <= profile.submit && profile.submit <= profile.start);
+        assert_eq!(profile.end - profile.start, profile.duration());
+        assert!(kernel.enqueue(&queue, &[], None).is_err());
+
+        // Queues without profiling do not record the times.
+        let queue = CommandQueue::new(&context, &devices[0]).unwrap();
+        let event = kernel.enqueue(&queue, &[4], Some(&[2])).unwrap();
+        event.wait().unwrap();
+        assert!(event.profile().is_err());
+    }
+}
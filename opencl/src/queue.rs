@@ -0,0 +1,155 @@
+/*
+ * queue.rs - Command queues.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+use crate::context::Context;
+use crate::event::Event;
+use crate::macros::{gen_handle, gen_object};
+use crate::memory::Buffer;
+use crate::platform::Device;
+use crate::Result;
+use opencl_api::api::queue::{
+    create_command_queue_with_properties, finish, release_command_queue, retain_command_queue,
+};
+use opencl_api::objects::traits::Pod;
+use opencl_heads::consts::{CL_FALSE, CL_QUEUE_PROFILING_ENABLE, CL_QUEUE_PROPERTIES};
+use opencl_heads::types::*;
+use std::marker::PhantomData;
+use std::ops::Range;
+use std::sync::Mutex;
+
+gen_object! {
+    /// OpenCL command queue, running commands on a device in order.
+    CommandQueue: cl_command_queue, retain_command_queue, release_command_queue, Send, Sync
+}
+
+gen_handle!(CommandQueue => CommandQueue);
+
+impl CommandQueue {
+    /// Creates a queue of commands for device in context.
+    pub fn new(context: &Context, device: &Device) -> Result<CommandQueue> {
+        let queue = create_command_queue_with_properties(&context.ptr(), &device.ptr(), &None)?;
+        Ok(unsafe { CommandQueue::from_raw(queue.unwrap()) })
+    }
+
+    /// Creates a queue for device in context recording when its commands
+    /// run, see `Event::profile`.
+    pub fn with_profiling(context: &Context, device: &Device) -> Result<CommandQueue> {
+        let properties = vec![
+            CL_QUEUE_PROPERTIES as cl_properties,
+            CL_QUEUE_PROFILING_ENABLE,
+            0,
+        ];
+        let queue =
+            create_command_queue_with_properties(&context.ptr(), &device.ptr(), &Some(properties))?;
+        Ok(unsafe { CommandQueue::from_raw(queue.unwrap()) })
+    }
+
+    /// Waits on the host thread for every command of the queue to complete.
+    pub fn finish(&self) -> Result<()> {
+        finish(&self.ptr())?;
+        Ok(())
+    }
+
+    /// Runs f with a scope to enqueue transfers in, waiting for all of them
+    /// to complete before returning, as `std::thread::scope` does for
+    /// threads.
+    ///
+    /// The host memory of the transfers is borrowed from outside f, so it
+    /// can not be released or reused while OpenCL may still access it.
+    ///
+    /// ```compile_fail
+    /// # fn run(queue: &opencl::CommandQueue, buffer: &opencl::Buffer<u8>) {
+    /// queue.scope(|s| {
+    ///     let mut data = vec![0; 4];
+    ///     // data is dropped before the scope waits for the read.
+    ///     s.read_async(buffer, 0..4, &mut data).unwrap();
+    /// });
+    /// # }
+    /// ```
+    pub fn scope<'env, F, R>(&'env self, f: F) -> R
+    where
+        F: for<'scope> FnOnce(&'scope Scope<'scope, 'env>) -> R,
+    {
+        // Dropped after the scope, waiting for the transfers also when f
+        // panics.
+        let pending = Pending::default();
+        let scope = Scope {
+            queue: self,
+            pending: &pending,
+            scope: PhantomData,
+            env: PhantomData,
+        };
+        f(&scope)
+    }
+}
+
+/// Transfers of a queue whose host memory is borrowed for 'scope, see
+/// `CommandQueue::scope`.
+#[derive(Debug)]
+pub struct Scope<'scope, 'env: 'scope> {
+    queue: &'env CommandQueue,
+    pending: &'scope Pending,
+    scope: PhantomData<&'scope mut &'scope ()>,
+    env: PhantomData<&'env mut &'env ()>,
+}
+
+impl<'scope> Scope<'scope, '_> {
+    /// Enqueues reading the elements of range of buffer into data, returns
+    /// the event of the read.
+    pub fn read_async<T: Pod>(
+        &'scope self,
+        buffer: &Buffer<T>,
+        range: Range<usize>,
+        data: &'scope mut [T],
+    ) -> Result<Event> {
+        let event = buffer.enqueue_read(self.queue, range, data, CL_FALSE)?;
+        Ok(self.push(event))
+    }
+
+    /// Enqueues writing data to the elements of range of buffer, returns the
+    /// event of the write.
+    pub fn write_async<T: Pod>(
+        &'scope self,
+        buffer: &Buffer<T>,
+        range: Range<usize>,
+        data: &'scope [T],
+    ) -> Result<Event> {
+        let event = buffer.enqueue_write(self.queue, range, data, CL_FALSE)?;
+        Ok(self.push(event))
+    }
+
+    fn push(&self, event: Event) -> Event {
+        let mut events = self.pending.0.lock().unwrap_or_else(|x| x.into_inner());
+        events.push(event.clone());
+        event
+    }
+}
+
+/// Events of the transfers of a scope, waited on when dropped.
+#[derive(Debug, Default)]
+struct Pending(Mutex<Vec<Event>>);
+
+impl Drop for Pending {
+    fn drop(&mut self) {
+        let events = self.0.get_mut().unwrap_or_else(|x| x.into_inner());
+        for event in events.drain(..) {
+            // Waiting fails when the transfer was aborted, which also ends
+            // the access to the host memory.
+            let _ = event.wait();
+        }
+    }
+}
@@ -0,0 +1,49 @@
+/*
+ * sampler.rs - Samplers.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+use crate::context::Context;
+use crate::macros::{gen_handle, gen_object};
+use crate::Result;
+use opencl_api::api::sampler::{create_sampler, release_sampler, retain_sampler};
+use opencl_api::objects::types::SamplerPtr;
+use opencl_heads::types::*;
+
+gen_object! {
+    /// OpenCL sampler, describing how kernels read images.
+    Sampler: cl_sampler, retain_sampler, |x: SamplerPtr| release_sampler(&x), Send, Sync
+}
+
+gen_handle!(Sampler => Sampler);
+
+impl Sampler {
+    /// Creates a sampler, see `AddressingMode` and `FilterMode` for the
+    /// modes.
+    pub fn new(
+        context: &Context,
+        normalized_coords: bool,
+        addressing_mode: cl_addressing_mode,
+        filter_mode: cl_filter_mode,
+    ) -> Result<Sampler> {
+        let sampler = create_sampler(
+            &context.ptr(),
+            normalized_coords as cl_bool,
+            addressing_mode,
+            filter_mode,
+        )?;
+        Ok(unsafe { Sampler::from_raw(sampler.unwrap()) })
+    }
+}
@@ -0,0 +1,546 @@
+/*
+ * snapshot.rs - Plain data snapshots of the platforms and their devices.
+ *
+ * Copyright 2020-2021 Naman Bishnoi
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+*/
+//! Snapshots hold every info of the platforms and devices as plain data,
+//! along with the partition capabilities and the supported image formats of
+//! the devices. With the `serde` feature they serialize to the schema below,
+//! which only ever changes along with `SCHEMA_VERSION`:
+//!
+//! ```text
+//! {
+//!   "schema": 1,
+//!   "platforms": [{
+//!     "info": { "CL_PLATFORM_NAME": "...", ... },
+//!     "errors": { "CL_PLATFORM_HOST_TIMER_RESOLUTION": "CL_INVALID_VALUE" },
+//!     "devices": [{
+//!       "info": { "CL_DEVICE_TYPE": ["CL_DEVICE_TYPE_GPU"], ... },
+//!       "errors": { ... },
+//!       "partition": {
+//!         "max_sub_devices": 4,
+//!         "schemes": ["CL_DEVICE_PARTITION_EQUALLY"],
+//!         "affinity_domains": []
+//!       },
+//!       "image_formats": {
+//!         "CL_MEM_OBJECT_IMAGE2D": [
+//!           { "channel_order": "CL_RGBA", "channel_type": "CL_FLOAT" }, ...
+//!         ], ...
+//!       }
+//!     }]
+//!   }]
+//! }
+//! ```
+//!
+//! `info` maps the OpenCL name of every parameter to its `Plain` value: a
+//! boolean, an integer, a string, a list, or a `{ "name", "version" }`
+//! object. Constants are given by their OpenCL names and handles are left
+//! out. `errors` maps the parameters whose query failed to the name of the
+//! failure, and the image types whose formats could not be listed as well.
+//! `max_sub_devices` is null when its query failed.
+//!
+//! `SystemSnapshot::diff` compares two snapshots, say before and after a
+//! driver update, down to the extensions and image formats added or
+//! removed.
+
+use crate::{Context, Device, Error, Platform, Result};
+use opencl_api::api::info::Plain;
+use opencl_api::errors::{OpenCLAPIError, ValidationError};
+use opencl_api::objects::bitfields::{DeviceType, MemFlags};
+use opencl_api::objects::structs::{
+    ChannelOrder, ChannelType, DeviceInfo, MemObjectType, PlatformInfo, StatusCode,
+};
+use opencl_api::objects::traits::GetSetGo;
+use opencl_heads::types::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// Version of the snapshot schema, bumped on every incompatible change.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// The image types whose supported formats are listed, for read-write
+/// images.
+pub const IMAGE_TYPES: [cl_mem_object_type; 6] = [
+    MemObjectType::IMAGE1D,
+    MemObjectType::IMAGE1D_BUFFER,
+    MemObjectType::IMAGE1D_ARRAY,
+    MemObjectType::IMAGE2D,
+    MemObjectType::IMAGE2D_ARRAY,
+    MemObjectType::IMAGE3D,
+];
+
+/// Every platform of the process along with its devices.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SystemSnapshot {
+    pub schema: u32,
+    pub platforms: Vec<PlatformSnapshot>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PlatformSnapshot {
+    pub info: BTreeMap<String, Plain>,
+    pub errors: BTreeMap<String, String>,
+    pub devices: Vec<DeviceSnapshot>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DeviceSnapshot {
+    pub info: BTreeMap<String, Plain>,
+    pub errors: BTreeMap<String, String>,
+    pub partition: PartitionSnapshot,
+    /// Formats by the OpenCL name of their image type, empty for the
+    /// devices without image support.
+    pub image_formats: BTreeMap<String, Vec<ImageFormatSnapshot>>,
+}
+
+/// How the device may be split into sub devices.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PartitionSnapshot {
+    pub max_sub_devices: Option<cl_uint>,
+    pub schemes: Vec<String>,
+    pub affinity_domains: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ImageFormatSnapshot {
+    pub channel_order: String,
+    pub channel_type: String,
+}
+
+impl SystemSnapshot {
+    /// Snapshots every platform and every device of them. Only failing to
+    /// list the platforms fails, any other failure is kept in `errors`.
+    pub fn capture() -> Result<SystemSnapshot> {
+        let platforms = Platform::list()?;
+        Ok(SystemSnapshot {
+            schema: SCHEMA_VERSION,
+            platforms: platforms.iter().map(PlatformSnapshot::capture).collect(),
+        })
+    }
+}
+
+impl PlatformSnapshot {
+    /// Snapshots platform and its devices, none when it has no device.
+    pub fn capture(platform: &Platform) -> PlatformSnapshot {
+        let (info, errors) = split(platform.details().plain(), PlatformInfo::name_of);
+        let device_type = DeviceType::new(DeviceType::ALL).unwrap();
+        let devices = platform.devices(device_type).unwrap_or_default();
+        PlatformSnapshot {
+            info,
+            errors,
+            devices: devices.iter().map(DeviceSnapshot::capture).collect(),
+        }
+    }
+}
+
+impl DeviceSnapshot {
+    /// Snapshots device, listing its image formats in a context of its own.
+    pub fn capture(device: &Device) -> DeviceSnapshot {
+        let details = device.details();
+        let (info, mut errors) = split(details.plain(), DeviceInfo::name_of);
+
+        let partition = PartitionSnapshot {
+            max_sub_devices: details.partition_max_sub_devices.ok(),
+            schemes: names(&info, DeviceInfo::PARTITION_PROPERTIES),
+            affinity_domains: names(&info, DeviceInfo::PARTITION_AFFINITY_DOMAIN),
+        };
+
+        let mut image_formats = BTreeMap::new();
+        if let Ok(true) = details.image_support {
+            let context = Context::new(&[device.clone()]);
+            let flags = MemFlags::new(MemFlags::READ_WRITE).unwrap();
+            for &image_type in IMAGE_TYPES.iter() {
+                let key = key(MemObjectType::name_of(image_type), image_type);
+                let formats = match &context {
+                    Ok(x) => x
+                        .supported_image_formats(flags.clone(), image_type)
+                        .map_err(|e| failure(&e)),
+                    Err(e) => Err(failure(e)),
+                };
+                match formats {
+                    Ok(x) => {
+                        image_formats.insert(key, x.iter().map(image_format).collect());
+                    }
+                    Err(e) => {
+                        errors.insert(key, e);
+                    }
+                }
+            }
+        }
+
+        DeviceSnapshot {
+            info,
+            errors,
+            partition,
+            image_formats,
+        }
+    }
+}
+
+/// Where a difference between two snapshots lies.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Location {
+    pub platform: usize,
+    /// None for the platform itself.
+    pub device: Option<usize>,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.device {
+            Some(x) => write!(f, "platform {}, device {}", self.platform, x),
+            None => write!(f, "platform {}", self.platform),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Change {
+    /// A value that differs, None on the side missing it, as for a failed
+    /// query or a device that is gone.
+    Value {
+        before: Option<Plain>,
+        after: Option<Plain>,
+    },
+    /// The items only one side lists, for the extensions, the flags, the
+    /// name-version lists and the image formats.
+    Items {
+        added: Vec<String>,
+        removed: Vec<String>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Difference {
+    pub location: Location,
+    /// OpenCL name of the parameter, or of the image type for the formats.
+    pub key: String,
+    pub change: Change,
+}
+
+impl SystemSnapshot {
+    /// Lists what changed from self to other, matching the platforms and
+    /// their devices by position. A platform or device found on one side
+    /// only shows up as its name alone, and the failed queries are only
+    /// seen as missing values.
+    pub fn diff(&self, other: &SystemSnapshot) -> Vec<Difference> {
+        let mut differences = Vec::new();
+        for i in 0..self.platforms.len().max(other.platforms.len()) {
+            let (before, after) = (self.platforms.get(i), other.platforms.get(i));
+            let location = Location {
+                platform: i,
+                device: None,
+            };
+            let (before, after) = match (before, after) {
+                (Some(x), Some(y)) => (x, y),
+                _ => {
+                    let name = |x: &PlatformSnapshot| x.info.get("CL_PLATFORM_NAME").cloned();
+                    let change = Change::Value {
+                        before: before.and_then(name),
+                        after: after.and_then(name),
+                    };
+                    differences.push(difference(location, "CL_PLATFORM_NAME", change));
+                    continue;
+                }
+            };
+            diff_info(location, &before.info, &after.info, &mut differences);
+
+            for j in 0..before.devices.len().max(after.devices.len()) {
+                let location = Location {
+                    platform: i,
+                    device: Some(j),
+                };
+                match (before.devices.get(j), after.devices.get(j)) {
+                    (Some(x), Some(y)) => {
+                        diff_info(location, &x.info, &y.info, &mut differences);
+                        diff_image_formats(location, x, y, &mut differences);
+                    }
+                    (x, y) => {
+                        let name = |x: &DeviceSnapshot| x.info.get("CL_DEVICE_NAME").cloned();
+                        let change = Change::Value {
+                            before: x.and_then(name),
+                            after: y.and_then(name),
+                        };
+                        differences.push(difference(location, "CL_DEVICE_NAME", change));
+                    }
+                }
+            }
+        }
+        differences
+    }
+}
+
+fn difference(location: Location, key: &str, change: Change) -> Difference {
+    Difference {
+        location,
+        key: key.to_owned(),
+        change,
+    }
+}
+
+fn diff_info(
+    location: Location,
+    before: &BTreeMap<String, Plain>,
+    after: &BTreeMap<String, Plain>,
+    differences: &mut Vec<Difference>,
+) {
+    let keys: BTreeSet<_> = before.keys().chain(after.keys()).collect();
+    for key in keys {
+        let (x, y) = (before.get(key), after.get(key));
+        if x == y {
+            continue;
+        }
+        let change = match (x.and_then(|x| items(key, x)), y.and_then(|y| items(key, y))) {
+            (Some(x), Some(y)) => match items_change(&x, &y) {
+                Some(change) => change,
+                None => continue,
+            },
+            _ => Change::Value {
+                before: x.cloned(),
+                after: y.cloned(),
+            },
+        };
+        differences.push(difference(location, key, change));
+    }
+}
+
+fn diff_image_formats(
+    location: Location,
+    before: &DeviceSnapshot,
+    after: &DeviceSnapshot,
+    differences: &mut Vec<Difference>,
+) {
+    let formats = |x: Option<&Vec<ImageFormatSnapshot>>| -> Vec<String> {
+        x.into_iter()
+            .flatten()
+            .map(|x| format!("{} {}", x.channel_order, x.channel_type))
+            .collect()
+    };
+    let (before, after) = (&before.image_formats, &after.image_formats);
+    let keys: BTreeSet<_> = before.keys().chain(after.keys()).collect();
+    for key in keys {
+        let (x, y) = (formats(before.get(key)), formats(after.get(key)));
+        if let Some(change) = items_change(&x, &y) {
+            differences.push(difference(location, key, change));
+        }
+    }
+}
+
+/// The items of the values compared as sets: the space separated
+/// extensions, and the lists of flags or of versioned names.
+fn items(key: &str, value: &Plain) -> Option<Vec<String>> {
+    match value {
+        Plain::Text(x) if key.ends_with("_EXTENSIONS") => {
+            Some(x.split_whitespace().map(str::to_owned).collect())
+        }
+        Plain::List(x) => x
+            .iter()
+            .map(|x| match x {
+                Plain::Text(x) => Some(x.clone()),
+                Plain::NameVersion { name, version } => Some(format!("{} {}", name, version)),
+                _ => None,
+            })
+            .collect(),
+        _ => None,
+    }
+}
+
+/// None when both sides list the same items, in whatever order.
+fn items_change(before: &[String], after: &[String]) -> Option<Change> {
+    let added: Vec<_> = after
+        .iter()
+        .filter(|x| !before.contains(x))
+        .cloned()
+        .collect();
+    let removed: Vec<_> = before
+        .iter()
+        .filter(|x| !after.contains(x))
+        .cloned()
+        .collect();
+    if added.is_empty() && removed.is_empty() {
+        None
+    } else {
+        Some(Change::Items { added, removed })
+    }
+}
+
+type Fields<'a> = Vec<(cl_uint, std::result::Result<Plain, &'a OpenCLAPIError>)>;
+
+/// Splits the plain fields of a details struct into the values and the
+/// failures, by the OpenCL names of their parameters.
+fn split(
+    fields: Fields,
+    name_of: fn(cl_uint) -> Option<&'static str>,
+) -> (BTreeMap<String, Plain>, BTreeMap<String, String>) {
+    let mut info = BTreeMap::new();
+    let mut errors = BTreeMap::new();
+    for (name, value) in fields {
+        match value {
+            Ok(x) => {
+                info.insert(key(name_of(name), name), x);
+            }
+            Err(e) => {
+                errors.insert(key(name_of(name), name), describe(e));
+            }
+        }
+    }
+    (info, errors)
+}
+
+/// The OpenCL name of a parameter, its value in hex when it has none.
+fn key(name: Option<&str>, value: cl_uint) -> String {
+    name.map_or_else(|| format!("{:#x}", value), str::to_owned)
+}
+
+/// Names a failed query by its status, CL_INVALID_VALUE being what drivers
+/// answer to the parameters they do not support, or by the OpenCL version
+/// the parameter requires when the object is older.
+pub fn describe(error: &OpenCLAPIError) -> String {
+    match error {
+        OpenCLAPIError::ObjectError(ValidationError::UnsupportedParam(..)) => {
+            "CL_INVALID_VALUE".to_owned()
+        }
+        OpenCLAPIError::ObjectError(ValidationError::UnsupportedVersion(_, _, major, minor)) => {
+            format!("requires OpenCL {}.{}", major, minor)
+        }
+        OpenCLAPIError::StatusCodeError { int_code, .. } => StatusCode::name_of(*int_code)
+            .map(str::to_owned)
+            .unwrap_or_else(|| int_code.to_string()),
+        _ => error.to_string(),
+    }
+}
+
+fn failure(error: &Error) -> String {
+    match error {
+        Error::Api(e) => describe(e),
+        _ => error.to_string(),
+    }
+}
+
+/// The constants listed by the info param, as its plain form holds them.
+fn names(info: &BTreeMap<String, Plain>, param: cl_uint) -> Vec<String> {
+    let key = key(DeviceInfo::name_of(param), param);
+    match info.get(&key) {
+        Some(Plain::List(x)) => x
+            .iter()
+            .filter_map(|x| match x {
+                Plain::Text(x) => Some(x.clone()),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn image_format(format: &cl_image_format) -> ImageFormatSnapshot {
+    let order = format.image_channel_order;
+    let data_type = format.image_channel_data_type;
+    ImageFormatSnapshot {
+        channel_order: ChannelOrder::name_of(order)
+            .map_or_else(|| format!("{:#x}", order), str::to_owned),
+        channel_type: ChannelType::name_of(data_type)
+            .map_or_else(|| format!("{:#x}", data_type), str::to_owned),
+    }
+}
+
+/************************/
+/* /‾‾\ /‾‾\ /‾‾\ /‾‾\  */
+/*|  |  |  |  |  |  |  |*/
+/*|  |  Unit Tests  |  |*/
+/*|__|__|__|__|__|__|__|*/
+/*|__|__|__|__|__|__|__|*/
+/************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture() {
+        let snapshot = SystemSnapshot::capture().unwrap();
+        assert_eq!(snapshot.schema, SCHEMA_VERSION);
+        let platform = &snapshot.platforms[0];
+        let name = &platform.info["CL_PLATFORM_NAME"];
+        assert!(matches!(name, Plain::Text(x) if !x.is_empty()));
+
+        let device = &platform.devices[0];
+        assert!(!device.info.contains_key("CL_DEVICE_PLATFORM"));
+        assert!(matches!(device.info["CL_DEVICE_TYPE"], Plain::List(_)));
+        assert!(matches!(
+            device.info["CL_DEVICE_AVAILABLE"],
+            Plain::Bool(true)
+        ));
+        assert!(device.partition.max_sub_devices.is_some());
+        if let Plain::Bool(true) = device.info["CL_DEVICE_IMAGE_SUPPORT"] {
+            let formats = &device.image_formats["CL_MEM_OBJECT_IMAGE2D"];
+            assert!(formats.iter().any(|x| x.channel_order == "CL_RGBA"));
+        }
+    }
+
+    #[test]
+    fn test_diff() {
+        let before = SystemSnapshot::capture().unwrap();
+        assert!(before.diff(&before).is_empty());
+
+        let mut after = before.clone();
+        let device = &mut after.platforms[0].devices[0];
+        let compute_units = "CL_DEVICE_MAX_COMPUTE_UNITS".to_owned();
+        device
+            .info
+            .insert(compute_units.clone(), Plain::Integer(1024));
+        let extensions = device.info.get_mut("CL_DEVICE_EXTENSIONS").unwrap();
+        if let Plain::Text(x) = extensions {
+            x.push_str(" cl_khr_fp16");
+        }
+        let location = Location {
+            platform: 0,
+            device: Some(0),
+        };
+        let differences = before.diff(&after);
+        assert_eq!(differences.len(), 2);
+        assert_eq!(differences[0].location, location);
+        assert_eq!(differences[0].key, "CL_DEVICE_EXTENSIONS");
+        assert_eq!(
+            differences[0].change,
+            Change::Items {
+                added: vec!["cl_khr_fp16".to_owned()],
+                removed: Vec::new(),
+            }
+        );
+        assert_eq!(differences[1].key, compute_units);
+        assert!(matches!(
+            differences[1].change,
+            Change::Value {
+                after: Some(Plain::Integer(1024)),
+                ..
+            }
+        ));
+
+        after.platforms[0]
+            .devices
+            .push(before.platforms[0].devices[0].clone());
+        let differences = before.diff(&after);
+        let added = differences.last().unwrap();
+        assert_eq!(added.location.device, Some(1));
+        assert!(matches!(added.change, Change::Value { before: None, .. }));
+    }
+}